@@ -1,5 +1,7 @@
 use rnode::{VisitMut, VisitMutWith};
-use stc_ts_types::{Array, Conditional, FnParam, Intersection, KeywordTypeMetadata, Type, TypeOrSpread, TypeParam, Union, Valid};
+use stc_ts_types::{
+    Array, Conditional, FnParam, Intersection, KeywordTypeMetadata, RestType, Tuple, Type, TypeOrSpread, TypeParam, Union, Valid,
+};
 use swc_common::TypeEq;
 
 pub trait Fix: Sized {
@@ -128,6 +130,32 @@ impl VisitMut<Intersection> for Fixer {
     }
 }
 
+impl VisitMut<Tuple> for Fixer {
+    fn visit_mut(&mut self, tuple: &mut Tuple) {
+        tuple.visit_mut_children_with(self);
+
+        // Flatten `[A, ...[B, C]]` into `[A, B, C]`.
+        if !tuple
+            .elems
+            .iter()
+            .any(|elem| matches!(elem.ty.normalize(), Type::Rest(RestType { ty, .. }) if ty.normalize().is_tuple()))
+        {
+            return;
+        }
+
+        let mut elems = Vec::with_capacity(tuple.elems.len());
+        for elem in tuple.elems.drain(..) {
+            match elem.ty.normalize() {
+                Type::Rest(RestType { ty, .. }) if ty.normalize().is_tuple() => {
+                    elems.extend(ty.normalize().as_tuple().unwrap().elems.iter().cloned());
+                }
+                _ => elems.push(elem),
+            }
+        }
+        tuple.elems = elems;
+    }
+}
+
 impl Fixer {
     fn fix_type(&mut self, ty: &mut Type) {
         if matches!(ty, Type::Arc(..)) {
@@ -125,19 +125,28 @@ pub fn builtin(_: proc_macro::TokenStream) -> proc_macro::TokenStream {
                     attrs: vec![],
                     arms: files
                         .iter()
-                        .map(|(_, f)| {
+                        .flat_map(|(_, f)| {
                             let name = syn::Ident::new(&name_for(f), call_site());
-
-                            q().quote_with(smart_quote!(
-                                Vars {
-                                    name: &name,
-                                    s: &f.replace(".d.ts","")
-                                },
-                                {
-                                    s => Ok(Lib::name)
-                                }
-                            ))
-                            .parse()
+                            let lib = f.replace(".d.ts", "");
+
+                            // `dom` of tsc is `dom.generated` in our lib directory.
+                            let alias = lib
+                                .strip_suffix(".generated")
+                                .filter(|alias| !files.iter().any(|(_, f)| *f == format!("{}.d.ts", alias)))
+                                .map(String::from);
+
+                            std::iter::once(lib).chain(alias).map(move |s| {
+                                q().quote_with(smart_quote!(
+                                    Vars {
+                                        name: &name,
+                                        s: &s
+                                    },
+                                    {
+                                        s => Ok(Lib::name)
+                                    }
+                                ))
+                                .parse()
+                            })
                         })
                         .chain(std::iter::once(
                             q().quote_with(smart_quote!(
@@ -180,7 +189,7 @@ pub fn builtin(_: proc_macro::TokenStream) -> proc_macro::TokenStream {
         };
         tokens = tokens.quote_with(smart_quote!(Vars { match_expr }, {
             impl Lib {
-                fn content(self) -> &'static str {
+                pub fn content(self) -> &'static str {
                     match_expr
                 }
             }
@@ -1,3 +1,5 @@
+/// <reference lib="decorators" />
+
 /////////////////////////////
 /// ECMAScript APIs
 /////////////////////////////
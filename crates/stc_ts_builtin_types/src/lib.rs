@@ -21,8 +21,10 @@ use swc_ecma_visit::{span_remover, FoldWith};
 builtin!();
 
 impl Lib {
+    /// Loads a lib and its dependencies. Like tsc, names are case-insensitive,
+    /// so `DOM.Iterable` is same as `dom.iterable`.
     pub fn load(lib_str: &str) -> Vec<Self> {
-        let lib: Self = match lib_str.parse() {
+        let lib: Self = match lib_str.to_lowercase().parse() {
             Ok(lib) => lib,
             Err(..) => return vec![],
         };
@@ -30,6 +32,20 @@ impl Lib {
         lib.load_deps()
     }
 
+    /// Libraries loaded if `lib` is not specified.
+    pub fn defaults_for(target: EsVersion) -> Vec<Self> {
+        match target {
+            EsVersion::Es3 | EsVersion::Es5 => vec![Lib::Es5, Lib::Dom],
+            EsVersion::Es2015 => Lib::load("es2015.full"),
+            EsVersion::Es2016 => Lib::load("es2016.full"),
+            EsVersion::Es2017 => Lib::load("es2017.full"),
+            EsVersion::Es2018 => Lib::load("es2018.full"),
+            EsVersion::Es2019 => Lib::load("es2019.full"),
+            EsVersion::Es2020 => Lib::load("es2020.full"),
+            EsVersion::Es2021 | EsVersion::Es2022 => Lib::load("esnext.full"),
+        }
+    }
+
     fn body(self) -> &'static TsNamespaceDecl {
         static CACHE: Lazy<RwLock<FxHashMap<Lib, &'static TsNamespaceDecl>>> = Lazy::new(Default::default);
 
@@ -56,6 +72,7 @@ impl Lib {
 
     fn prec(&self) -> usize {
         match self {
+            Self::Decorators => 0,
             Self::Es5 => 1,
             Self::Es2015Core => 2,
             Self::Es2015Collection => 3,
@@ -101,8 +118,8 @@ impl Lib {
             Self::Dom => 43,
             Self::WebworkerImportscripts => 44,
             Self::Scripthost => 45,
-            Self::DomIterable => 46,
-            Self::DomIterableGenerated => 47,
+            Self::DomIterableGenerated => 46,
+            Self::DomIterable => 47,
             Self::Header => 48,
             Self::WebworkerGenerated => 49,
             Self::WebworkerIterableGenerated => 50,
@@ -131,12 +148,46 @@ impl Ord for Lib {
     }
 }
 
+/// Returns the oldest `lib` which declares a global named `name`, if it's not
+/// declared by `es5`.
+///
+/// This is used to suggest changing `lib` when `name` is not found.
+pub fn lib_declaring(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "Map" | "Set" | "WeakMap" | "WeakSet" | "ReadonlyMap" | "ReadonlySet" | "Promise" | "PromiseConstructor" | "Symbol"
+        | "SymbolConstructor" | "Proxy" | "ProxyHandler" | "Reflect" | "Iterator" | "Iterable" | "IterableIterator" | "Generator"
+        | "GeneratorFunction" => "es2015",
+        "Atomics" | "SharedArrayBuffer" => "es2017",
+        "AsyncIterable" | "AsyncIterator" | "AsyncIterableIterator" | "AsyncGenerator" | "AsyncGeneratorFunction" => "es2018",
+        "BigInt" | "BigInt64Array" | "BigUint64Array" => "es2020",
+        "WeakRef" | "FinalizationRegistry" | "AggregateError" => "es2021",
+        _ => return None,
+    })
+}
+
 /// Merge definitions
 pub fn load(libs: &[Lib]) -> Vec<&'static TsNamespaceDecl> {
     libs.into_par_iter().map(|lib| lib.body()).collect()
 }
 
 fn parse(content: &str) -> TsNamespaceDecl {
+    let body = parse_declarations(content).expect("failed to parse module");
+
+    TsNamespaceDecl {
+        span: Default::default(),
+        declare: true,
+        global: true,
+        id: Ident::new(js_word!(""), Default::default()),
+        body: Box::new(TsNamespaceBody::TsModuleBlock(TsModuleBlock {
+            span: Default::default(),
+            body: body.into_iter().map(ModuleItem::Stmt).collect(),
+        })),
+    }
+}
+
+/// Parses `content` as a script of global declarations, like the lib files of
+/// typescript. Spans are removed.
+pub fn parse_declarations(content: &str) -> Result<Vec<Stmt>, swc_ecma_parser::error::Error> {
     let cm = Arc::new(SourceMap::new(FilePathMapping::empty()));
 
     let fm = cm.new_source_file(FileName::Anon, content.to_string());
@@ -153,23 +204,31 @@ fn parse(content: &str) -> TsNamespaceDecl {
     let mut parser = Parser::new_from(lexer);
 
     // We cannot use parse_module because of `eval`
-    let script = parser.parse_script().expect("failed to parse module");
+    let script = parser.parse_script()?;
 
-    TsNamespaceDecl {
-        span: Default::default(),
-        declare: true,
-        global: true,
-        id: Ident::new(js_word!(""), Default::default()),
-        body: Box::new(TsNamespaceBody::TsModuleBlock(TsModuleBlock {
-            span: Default::default(),
-            body: script
-                .body
-                .fold_with(&mut span_remover())
-                .into_iter()
-                .map(ModuleItem::Stmt)
-                .collect(),
-        })),
-    }
+    Ok(script.body.fold_with(&mut span_remover()))
+}
+
+#[test]
+fn test_defaults() {
+    assert_eq!(Lib::defaults_for(EsVersion::Es5), vec![Lib::Es5, Lib::Dom]);
+    assert!(Lib::defaults_for(EsVersion::Es2015).contains(&Lib::Es2015Promise));
+    assert!(!Lib::defaults_for(EsVersion::Es2015).contains(&Lib::Es2016ArrayInclude));
+
+    assert_eq!(lib_declaring("Promise"), Some("es2015"));
+    assert_eq!(lib_declaring("Array"), None);
+}
+
+#[test]
+fn test_dom() {
+    assert_eq!(Lib::load("dom"), vec![Lib::Dom]);
+    assert_eq!(
+        Lib::load("DOM.Iterable"),
+        vec![Lib::Dom, Lib::DomIterableGenerated, Lib::DomIterable]
+    );
+    assert_eq!(Lib::load("webworker"), vec![Lib::WebworkerGenerated]);
+    assert!(!Lib::load("es2015").contains(&Lib::Dom));
+    assert!(Lib::load("es2015.full").contains(&Lib::DomIterableGenerated));
 }
 
 #[test]
@@ -0,0 +1,53 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use anyhow::{Context, Error};
+use serde::{Deserialize, Serialize};
+
+/// Written after checking a project in build mode, so that the project is not
+/// checked again if its inputs are not modified.
+///
+/// This is not compatible with the `.tsbuildinfo` of `tsc`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    pub version: String,
+    /// Hashes of the contents of the inputs, keyed by path.
+    pub inputs: BTreeMap<String, String>,
+    /// Number of errors reported while checking the project.
+    pub errors: usize,
+}
+
+impl BuildInfo {
+    pub fn new(inputs: &[impl AsRef<Path>], errors: usize) -> Result<Self, Error> {
+        let mut hashes = BTreeMap::new();
+        for path in inputs {
+            let path = path.as_ref();
+            let content = fs::read(path).with_context(|| format!("failed to read `{}`", path.display()))?;
+            hashes.insert(path.display().to_string(), format!("{:016x}", fxhash::hash64(&content)));
+        }
+
+        Ok(BuildInfo {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            inputs: hashes,
+            errors,
+        })
+    }
+
+    /// Returns [None] if the file does not exist or is invalid.
+    pub fn read(path: &Path) -> Option<Self> {
+        let s = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&s).ok()
+    }
+
+    pub fn write(&self, path: &Path) -> Result<(), Error> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?).with_context(|| format!("failed to write `{}`", path.display()))
+    }
+
+    /// Returns `true` if the project had no error and its inputs are same as
+    /// `current`.
+    pub fn is_up_to_date(&self, current: &BuildInfo) -> bool {
+        self.errors == 0 && self.version == current.version && self.inputs == current.inputs
+    }
+}
@@ -0,0 +1,138 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Error};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use walkdir::WalkDir;
+
+/// Patterns of `files`, `include` or `exclude`, with the directory of the
+/// config which declares them.
+#[derive(Debug, Clone)]
+pub(crate) struct Patterns {
+    pub base: PathBuf,
+    pub patterns: Vec<String>,
+}
+
+const DEFAULT_EXCLUDE: &[&str] = &["**/node_modules", "**/bower_components", "**/jspm_packages"];
+
+/// Expands `include`, `exclude` and `files` like tsc.
+///
+/// If the last component of a pattern does not contain a wildcard or an
+/// extension, it's treated as a directory.
+pub(crate) fn expand(
+    dir: &Path,
+    files: Option<&Patterns>,
+    include: Option<&Patterns>,
+    exclude: Option<&Patterns>,
+    out_dir: Option<&Path>,
+    allow_js: bool,
+) -> Result<Vec<PathBuf>, Error> {
+    let mut result = vec![];
+
+    // `files` is not affected by `exclude`.
+    if let Some(files) = files {
+        for file in &files.patterns {
+            let path = files.base.join(file);
+            if !path.is_file() {
+                anyhow::bail!("file `{}` specified in `files` does not exist", path.display());
+            }
+            result.push(path);
+        }
+    }
+
+    let include = match include {
+        Some(include) => include.clone(),
+        // If `files` is specified, nothing is included by default.
+        None if files.is_some() => return Ok(result),
+        None => Patterns {
+            base: dir.to_path_buf(),
+            patterns: vec!["**/*".into()],
+        },
+    };
+
+    let include_set = build_set(&include, false)?;
+    let exclude_set = match exclude {
+        Some(exclude) => build_set(exclude, true)?,
+        None => {
+            let mut patterns = DEFAULT_EXCLUDE.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+            if let Some(out_dir) = out_dir {
+                patterns.push(out_dir.to_string_lossy().into_owned());
+            }
+            build_set(
+                &Patterns {
+                    base: dir.to_path_buf(),
+                    patterns,
+                },
+                true,
+            )?
+        }
+    };
+
+    let mut walked = vec![];
+    for entry in WalkDir::new(&include.base)
+        .sort_by(|a, b| a.file_name().cmp(b.file_name()))
+        .into_iter()
+        .filter_entry(|e| !exclude_set.is_match(e.path()))
+    {
+        let entry = entry.context("failed to read a directory")?;
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        let path = entry.path();
+        if is_supported(path, allow_js) && include_set.is_match(path) {
+            walked.push(path.to_path_buf());
+        }
+    }
+
+    for path in walked {
+        if !result.contains(&path) {
+            result.push(path);
+        }
+    }
+
+    Ok(result)
+}
+
+fn is_supported(path: &Path, allow_js: bool) -> bool {
+    let name = path.to_string_lossy();
+    if name.ends_with(".ts") || name.ends_with(".tsx") {
+        return true;
+    }
+
+    allow_js && (name.ends_with(".js") || name.ends_with(".jsx"))
+}
+
+/// If `match_dirs` is true, directory patterns also match the directory
+/// itself, so excluded directories are not walked.
+pub(crate) fn build_set(patterns: &Patterns, match_dirs: bool) -> Result<GlobSet, Error> {
+    let mut builder = GlobSetBuilder::new();
+
+    for pattern in &patterns.patterns {
+        let pattern = pattern.trim_start_matches("./");
+        let path = patterns.base.join(pattern);
+        let path = path.to_string_lossy();
+
+        let last = pattern.rsplit('/').next().unwrap_or_default();
+        let is_dir = !last.contains(['*', '?', '.']);
+
+        let mut add = |pattern: &str| -> Result<(), Error> {
+            let glob = GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .with_context(|| format!("invalid pattern `{}`", pattern))?;
+            builder.add(glob);
+            Ok(())
+        };
+
+        if is_dir {
+            add(&format!("{}/**/*", path))?;
+            if match_dirs {
+                add(&path)?;
+            }
+        } else {
+            add(&path)?;
+        }
+    }
+
+    Ok(builder.build()?)
+}
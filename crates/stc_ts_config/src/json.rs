@@ -0,0 +1,77 @@
+//! `tsconfig.json` allows comments and trailing commas.
+
+/// Replaces comments and trailing commas with whitespaces, so that the result
+/// can be parsed by `serde_json`. Line breaks are preserved, so the positions
+/// in parse errors are still correct.
+pub(crate) fn strip_comments(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                buf.push(c);
+                while let Some(c) = chars.next() {
+                    buf.push(c);
+                    match c {
+                        '\\' => {
+                            if let Some(c) = chars.next() {
+                                buf.push(c);
+                            }
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while let Some(&c) = chars.peek() {
+                    if c == '\n' {
+                        break;
+                    }
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        buf.push('\n');
+                    }
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                buf.push(' ');
+            }
+            _ => buf.push(c),
+        }
+    }
+
+    remove_trailing_commas(buf)
+}
+
+fn remove_trailing_commas(s: String) -> String {
+    let mut bytes = s.into_bytes();
+    let mut in_str = false;
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        match bytes[idx] {
+            b'\\' if in_str => idx += 1,
+            b'"' => in_str = !in_str,
+            b',' if !in_str => {
+                let next = bytes[idx + 1..].iter().find(|b| !b.is_ascii_whitespace());
+                if matches!(next, Some(b']') | Some(b'}')) {
+                    bytes[idx] = b' ';
+                }
+            }
+            _ => {}
+        }
+        idx += 1;
+    }
+
+    String::from_utf8(bytes).expect("only ascii bytes are replaced")
+}
@@ -0,0 +1,530 @@
+//! Loader of `tsconfig.json`.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{bail, Context, Error};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+pub use self::{build_info::BuildInfo, lib_dir::LibDir, lib_override::LibOverride, options::CompilerOptions, project::ProjectGraph};
+use self::{
+    files::Patterns,
+    lib_override::RawLibOverride,
+    options::{PATH_LIST_OPTIONS, PATH_OPTIONS},
+};
+
+mod build_info;
+mod files;
+mod json;
+mod lib_dir;
+mod lib_override;
+mod options;
+mod project;
+
+/// A `tsconfig.json`, with the configs it extends merged.
+#[derive(Debug, Clone)]
+pub struct ProjectConfig {
+    /// Path of the `tsconfig.json`.
+    pub path: PathBuf,
+    pub compiler_options: CompilerOptions,
+    /// Paths of the configs of referenced projects.
+    pub references: Vec<PathBuf>,
+    /// From `stc.libOverrides`. The first matching one is used for a file.
+    pub lib_overrides: Vec<LibOverride>,
+    /// From `stc.libDir`. See [LibDir].
+    pub lib_dir: Option<PathBuf>,
+    files: Option<Patterns>,
+    include: Option<Patterns>,
+    exclude: Option<Patterns>,
+    /// Directory of the config which declares `paths`.
+    paths_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Extends {
+    One(String),
+    Many(Vec<String>),
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawConfig {
+    extends: Option<Extends>,
+    #[serde(default)]
+    compiler_options: Map<String, Value>,
+    files: Option<Vec<String>>,
+    include: Option<Vec<String>>,
+    exclude: Option<Vec<String>>,
+    references: Option<Vec<Reference>>,
+    /// Options only for stc.
+    stc: Option<StcOptions>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StcOptions {
+    lib_overrides: Option<Vec<RawLibOverride>>,
+    lib_dir: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Reference {
+    path: String,
+}
+
+/// Merged, but not deserialized yet.
+#[derive(Debug, Default)]
+struct Merged {
+    compiler_options: Map<String, Value>,
+    files: Option<Patterns>,
+    include: Option<Patterns>,
+    exclude: Option<Patterns>,
+    paths_dir: Option<PathBuf>,
+    lib_overrides: Option<Vec<LibOverride>>,
+    lib_dir: Option<PathBuf>,
+    /// Not inherited from the extended configs.
+    references: Vec<PathBuf>,
+}
+
+impl ProjectConfig {
+    /// `path` may be a directory containing `tsconfig.json`.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let path = if path.is_dir() {
+            path.join("tsconfig.json")
+        } else {
+            path.to_path_buf()
+        };
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("failed to find `{}`", path.display()))?;
+
+        let mut merged = Merged::default();
+        load_into(&path, &mut merged, &mut vec![])?;
+
+        let compiler_options = serde_json::from_value(Value::Object(merged.compiler_options))
+            .with_context(|| format!("invalid `compilerOptions` in `{}`", path.display()))?;
+
+        let references = merged
+            .references
+            .into_iter()
+            .map(|path| if path.is_dir() { path.join("tsconfig.json") } else { path })
+            .collect();
+
+        Ok(ProjectConfig {
+            path,
+            compiler_options,
+            references,
+            lib_overrides: merged.lib_overrides.unwrap_or_default(),
+            lib_dir: merged.lib_dir,
+            files: merged.files,
+            include: merged.include,
+            exclude: merged.exclude,
+            paths_dir: merged.paths_dir,
+        })
+    }
+
+    /// Paths in `paths` are relative to `baseUrl` if it's specified, and
+    /// relative to the config declaring `paths` otherwise.
+    pub fn paths_base(&self) -> Option<&Path> {
+        self.compiler_options.base_url.as_deref().or(self.paths_dir.as_deref())
+    }
+
+    /// Directory of the `tsconfig.json`.
+    pub fn dir(&self) -> &Path {
+        self.path.parent().expect("config path should have a parent")
+    }
+
+    /// `tsc` rejects composite projects which disable declaration emit.
+    pub fn validate_composite(&self) -> Result<(), Error> {
+        if self.compiler_options.composite == Some(true) && self.compiler_options.declaration == Some(false) {
+            bail!(
+                "TS6304: Composite projects may not disable declaration emit. (in `{}`)",
+                self.path.display()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Path of the build info, which is `tsBuildInfoFile`, or
+    /// `<name of config>.tsbuildinfo` in `outDir` or the directory of the
+    /// config.
+    pub fn build_info_path(&self) -> PathBuf {
+        if let Some(path) = &self.compiler_options.ts_build_info_file {
+            return path.clone();
+        }
+
+        let name = self.path.file_stem().unwrap_or_default().to_string_lossy();
+        let dir = self.compiler_options.out_dir.as_deref().unwrap_or_else(|| self.dir());
+        dir.join(format!("{}.tsbuildinfo", name))
+    }
+
+    /// Loads the lib files of `stc.libDir` instead of the bundled ones, if it's
+    /// specified.
+    ///
+    /// This is the only way to check against the lib of a version other than
+    /// the bundled one.
+    pub fn load_lib_dir(&self) -> Result<Option<LibDir>, Error> {
+        let dir = match &self.lib_dir {
+            Some(dir) => dir,
+            None => return Ok(None),
+        };
+        let options = &self.compiler_options;
+
+        Ok(Some(match &options.lib {
+            _ if options.no_lib.unwrap_or(false) => LibDir::load(dir, &[])?,
+            Some(libs) => LibDir::load(dir, libs)?,
+            None => LibDir::load_defaults(dir, options.target()?)?,
+        }))
+    }
+
+    /// Files to check, from `files`, `include` and `exclude`.
+    pub fn root_files(&self) -> Result<Vec<PathBuf>, Error> {
+        files::expand(
+            self.dir(),
+            self.files.as_ref(),
+            self.include.as_ref(),
+            self.exclude.as_ref(),
+            self.compiler_options.out_dir.as_deref(),
+            self.compiler_options.allow_js.unwrap_or(false),
+        )
+    }
+}
+
+/// Loads `path` into `merged`. The configs extended by `path` are loaded
+/// first, so `path` overrides them.
+///
+/// `stack` is used to detect cycles.
+fn load_into(path: &Path, merged: &mut Merged, stack: &mut Vec<PathBuf>) -> Result<(), Error> {
+    if stack.iter().any(|p| p == path) {
+        bail!("circular `extends` found in `{}`", path.display());
+    }
+
+    let raw = read(path)?;
+    let dir = path.parent().expect("config path should have a parent");
+
+    stack.push(path.to_path_buf());
+    let bases = match raw.extends {
+        Some(Extends::One(base)) => vec![base],
+        Some(Extends::Many(bases)) => bases,
+        None => vec![],
+    };
+    for base in bases {
+        let base_path =
+            resolve_extends(dir, &base).with_context(|| format!("failed to resolve `{}` extended by `{}`", base, path.display()))?;
+        load_into(&base_path, merged, stack)?;
+    }
+    stack.pop();
+
+    merged.references = raw.references.unwrap_or_default().into_iter().map(|r| dir.join(r.path)).collect();
+
+    if raw.compiler_options.contains_key("paths") {
+        merged.paths_dir = Some(dir.to_path_buf());
+    }
+
+    for (name, mut value) in raw.compiler_options {
+        if PATH_OPTIONS.contains(&&*name) {
+            if let Value::String(s) = &value {
+                value = Value::String(dir.join(s).to_string_lossy().into_owned());
+            }
+        } else if PATH_LIST_OPTIONS.contains(&&*name) {
+            if let Value::Array(items) = &mut value {
+                for item in items {
+                    if let Value::String(s) = item {
+                        *s = dir.join(&*s).to_string_lossy().into_owned();
+                    }
+                }
+            }
+        }
+
+        merged.compiler_options.insert(name, value);
+    }
+
+    let patterns = |patterns: Vec<String>| Patterns {
+        base: dir.to_path_buf(),
+        patterns,
+    };
+    if let Some(files) = raw.files {
+        merged.files = Some(patterns(files));
+    }
+    if let Some(include) = raw.include {
+        merged.include = Some(patterns(include));
+    }
+    if let Some(exclude) = raw.exclude {
+        merged.exclude = Some(patterns(exclude));
+    }
+    if let Some(stc) = raw.stc {
+        if let Some(overrides) = stc.lib_overrides {
+            merged.lib_overrides = Some(
+                overrides
+                    .into_iter()
+                    .map(|raw| LibOverride::new(dir, raw))
+                    .collect::<Result<_, _>>()
+                    .with_context(|| format!("invalid `stc.libOverrides` in `{}`", path.display()))?,
+            );
+        }
+        if let Some(lib_dir) = stc.lib_dir {
+            merged.lib_dir = Some(dir.join(lib_dir));
+        }
+    }
+
+    Ok(())
+}
+
+fn read(path: &Path) -> Result<RawConfig, Error> {
+    let s = fs::read_to_string(path).with_context(|| format!("failed to read `{}`", path.display()))?;
+
+    parse(&s).with_context(|| format!("failed to parse `{}`", path.display()))
+}
+
+fn parse(s: &str) -> Result<RawConfig, Error> {
+    Ok(serde_json::from_str(&json::strip_comments(s))?)
+}
+
+/// Resolves a relative path, or a config in a package like
+/// `@tsconfig/node16/tsconfig.json`.
+fn resolve_extends(dir: &Path, base: &str) -> Result<PathBuf, Error> {
+    let candidates = |path: PathBuf| {
+        let with_ext = PathBuf::from(format!("{}.json", path.display()));
+        [path.clone(), with_ext, path.join("tsconfig.json")]
+    };
+
+    if base.starts_with("./") || base.starts_with("../") || Path::new(base).is_absolute() {
+        for path in candidates(dir.join(base)) {
+            if path.is_file() {
+                return Ok(path.canonicalize()?);
+            }
+        }
+        bail!("not found");
+    }
+
+    for dir in dir.ancestors() {
+        for path in candidates(dir.join("node_modules").join(base)) {
+            if path.is_file() {
+                return Ok(path.canonicalize()?);
+            }
+        }
+    }
+
+    bail!("not found in `node_modules`")
+}
+
+#[cfg(test)]
+mod tests {
+    use stc_ts_builtin_types::Lib;
+    use stc_ts_env::{JsxEntity, JsxMode, ModuleConfig, ModuleDetection};
+    use swc_ecma_ast::EsVersion;
+
+    use super::*;
+
+    #[test]
+    fn comments_and_trailing_commas() {
+        let raw = parse(
+            r#"{
+                // A comment
+                "compilerOptions": {
+                    /* A block comment */
+                    "strict": true,
+                    "outDir": "// not a comment",
+                },
+                "include": ["src",],
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(raw.compiler_options["strict"], Value::Bool(true));
+        assert_eq!(raw.compiler_options["outDir"], Value::String("// not a comment".into()));
+        assert_eq!(raw.include, Some(vec!["src".to_string()]));
+    }
+
+    #[test]
+    fn compiler_options() {
+        let options: CompilerOptions = serde_json::from_str(
+            r#"{
+                "strict": true,
+                "strictNullChecks": false,
+                "esModuleInterop": true,
+                "target": "ES2017",
+                "module": "CommonJS",
+                "moduleDetection": "Force",
+                "jsx": "react-jsx",
+                "jsxImportSource": "preact"
+            }"#,
+        )
+        .unwrap();
+
+        let rule = options.rule();
+        assert!(rule.no_implicit_any);
+        assert!(!rule.strict_null_checks);
+        assert!(rule.allow_synthetic_default_imports);
+        assert_eq!(options.target().unwrap(), EsVersion::Es2017);
+        assert!(matches!(options.module().unwrap(), ModuleConfig::CommonJs));
+        assert_eq!(rule.module_detection, ModuleDetection::Force);
+
+        let jsx = options.jsx().unwrap();
+        assert_eq!(jsx.mode, Some(JsxMode::ReactJsx));
+        assert_eq!(&*jsx.runtime_module(), "preact/jsx-runtime");
+        assert_eq!(
+            jsx.element_factory(),
+            JsxEntity::Import {
+                src: "preact/jsx-runtime".into(),
+                name: "jsx".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn extends_and_include() {
+        let dir = std::env::temp_dir().join("stc_ts_config_extends_and_include");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src/nested")).unwrap();
+        fs::write(
+            dir.join("base.json"),
+            r#"{ "compilerOptions": { "strict": true, "outDir": "out" } }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("tsconfig.json"),
+            r#"{ "extends": "./base", "compilerOptions": { "noImplicitAny": false }, "include": ["src"] }"#,
+        )
+        .unwrap();
+        fs::write(dir.join("src/a.ts"), "").unwrap();
+        fs::write(dir.join("src/nested/b.d.ts"), "").unwrap();
+        fs::write(dir.join("src/c.js"), "").unwrap();
+
+        let config = ProjectConfig::load(&dir).unwrap();
+        let rule = config.compiler_options.rule();
+        assert!(rule.strict_null_checks);
+        assert!(!rule.no_implicit_any);
+        assert_eq!(config.compiler_options.out_dir, Some(config.dir().join("out")));
+
+        let files = config.root_files().unwrap();
+        assert_eq!(files, vec![config.dir().join("src/a.ts"), config.dir().join("src/nested/b.d.ts")]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn lib_overrides() {
+        let dir = std::env::temp_dir().join("stc_ts_config_lib_overrides");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src/worker")).unwrap();
+        fs::write(
+            dir.join("tsconfig.json"),
+            r#"{
+                "compilerOptions": { "lib": ["es2020", "DOM"] },
+                "stc": { "libOverrides": [{ "files": ["src/worker/**/*.ts"], "lib": ["es2020", "WebWorker"] }] }
+            }"#,
+        )
+        .unwrap();
+
+        let config = ProjectConfig::load(&dir).unwrap();
+        assert!(config.compiler_options.libs().unwrap().contains(&Lib::Dom));
+
+        let worker = &config.lib_overrides[0];
+        assert!(worker.matches(&config.dir().join("src/worker/index.ts")));
+        assert!(!worker.matches(&config.dir().join("src/index.ts")));
+        assert!(worker.libs().contains(&Lib::WebworkerGenerated));
+        assert!(!worker.libs().contains(&Lib::Dom));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn lib_dir() {
+        let dir = std::env::temp_dir().join("stc_ts_config_lib_dir");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("typescript/lib")).unwrap();
+        fs::write(
+            dir.join("typescript/lib/lib.es5.d.ts"),
+            "/*! license */\n/// <reference no-default-lib=\"true\"/>\n/// <reference lib=\"decorators\" />\ninterface Array<T> {}",
+        )
+        .unwrap();
+        fs::write(dir.join("typescript/lib/lib.decorators.d.ts"), "interface ClassDecoratorContext {}").unwrap();
+        fs::write(
+            dir.join("typescript/lib/lib.es2015.d.ts"),
+            "/// <reference lib=\"es5\" />\n/// <reference lib=\"es2015\" />",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("tsconfig.json"),
+            r#"{ "compilerOptions": { "lib": ["ES6"] }, "stc": { "libDir": "typescript/lib" } }"#,
+        )
+        .unwrap();
+
+        let config = ProjectConfig::load(&dir).unwrap();
+        let lib_dir = config.load_lib_dir().unwrap().unwrap();
+        assert_eq!(lib_dir.files(), &["lib.decorators.d.ts", "lib.es5.d.ts", "lib.es2015.d.ts"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn lib_dir_defaults() {
+        let dir = std::env::temp_dir().join("stc_ts_config_lib_dir_defaults");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("typescript/lib")).unwrap();
+        fs::write(dir.join("typescript/lib/lib.es2022.d.ts"), "interface Array<T> {}").unwrap();
+        fs::write(dir.join("typescript/lib/lib.es2022.full.d.ts"), "/// <reference lib=\"es2022\" />").unwrap();
+        fs::write(
+            dir.join("tsconfig.json"),
+            r#"{ "compilerOptions": { "target": "ES2022" }, "stc": { "libDir": "typescript/lib" } }"#,
+        )
+        .unwrap();
+
+        let config = ProjectConfig::load(&dir).unwrap();
+        let lib_dir = config.load_lib_dir().unwrap().unwrap();
+        assert_eq!(lib_dir.files(), &["lib.es2022.d.ts", "lib.es2022.full.d.ts"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn project_references() {
+        let dir = std::env::temp_dir().join("stc_ts_config_project_references");
+        let _ = fs::remove_dir_all(&dir);
+        for name in ["core", "util", "app"] {
+            fs::create_dir_all(dir.join(name)).unwrap();
+            fs::write(dir.join(name).join("index.ts"), "").unwrap();
+        }
+        fs::write(dir.join("core/tsconfig.json"), r#"{ "compilerOptions": { "composite": true } }"#).unwrap();
+        fs::write(
+            dir.join("util/tsconfig.json"),
+            r#"{ "compilerOptions": { "composite": true }, "references": [{ "path": "../core" }] }"#,
+        )
+        .unwrap();
+        fs::write(
+            dir.join("app/tsconfig.json"),
+            r#"{ "references": [{ "path": "../util" }, { "path": "../core/tsconfig.json" }] }"#,
+        )
+        .unwrap();
+
+        let graph = ProjectGraph::load(&dir.join("app")).unwrap();
+        let names = graph
+            .projects()
+            .iter()
+            .map(|p| p.dir().file_name().unwrap().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["core", "util", "app"]);
+        assert_eq!(graph.deps_of(2), &[1, 0]);
+        assert_eq!(graph.inputs_of(1).unwrap().len(), 4);
+
+        fs::write(dir.join("core/tsconfig.json"), r#"{ "references": [{ "path": "../app" }] }"#).unwrap();
+        let err = ProjectGraph::load(&dir.join("app")).unwrap_err().to_string();
+        assert!(err.starts_with("TS6306"), "{}", err);
+
+        fs::write(
+            dir.join("core/tsconfig.json"),
+            r#"{ "compilerOptions": { "composite": true }, "references": [{ "path": "../util" }] }"#,
+        )
+        .unwrap();
+        let err = ProjectGraph::load(&dir.join("app")).unwrap_err().to_string();
+        assert!(err.starts_with("TS6202"), "{}", err);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,105 @@
+use std::{
+    borrow::Cow,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Error};
+use stc_ts_env::BuiltInProvider;
+use swc_ecma_ast::EsVersion;
+
+/// Lib files of an installation of typescript, like
+/// `node_modules/typescript/lib`, which are used instead of the bundled ones
+/// to check against the lib of a specific version of typescript.
+///
+/// Only the libs of the bundled version are shipped, so other versions are
+/// selected by pointing `stc.libDir` to an installation of the version.
+#[derive(Debug, Clone)]
+pub struct LibDir {
+    dir: PathBuf,
+    files: Vec<String>,
+    sources: Vec<String>,
+}
+
+impl LibDir {
+    /// Loads the files of `libs`, which are named like `lib` of
+    /// `compilerOptions`, and the libs they reference.
+    pub fn load(dir: &Path, libs: &[String]) -> Result<Self, Error> {
+        let mut lib_dir = LibDir::new(dir);
+        for lib in libs {
+            let name = lib.to_lowercase().replace("es6", "es2015").replace("es7", "es2016");
+            lib_dir.add(&format!("lib.{}.d.ts", name), &mut vec![])?;
+        }
+
+        Ok(lib_dir)
+    }
+
+    /// Same as [LibDir::load], but loads the default lib file of `target`,
+    /// like `lib.es2017.full.d.ts`.
+    pub fn load_defaults(dir: &Path, target: EsVersion) -> Result<Self, Error> {
+        let file = match target {
+            EsVersion::Es3 | EsVersion::Es5 => "lib.d.ts",
+            EsVersion::Es2015 => "lib.es6.d.ts",
+            EsVersion::Es2016 => "lib.es2016.full.d.ts",
+            EsVersion::Es2017 => "lib.es2017.full.d.ts",
+            EsVersion::Es2018 => "lib.es2018.full.d.ts",
+            EsVersion::Es2019 => "lib.es2019.full.d.ts",
+            EsVersion::Es2020 => "lib.es2020.full.d.ts",
+            EsVersion::Es2021 => "lib.es2021.full.d.ts",
+            EsVersion::Es2022 => "lib.es2022.full.d.ts",
+        };
+
+        let mut lib_dir = LibDir::new(dir);
+        lib_dir.add(file, &mut vec![])?;
+
+        Ok(lib_dir)
+    }
+
+    fn new(dir: &Path) -> Self {
+        LibDir {
+            dir: dir.to_path_buf(),
+            files: vec![],
+            sources: vec![],
+        }
+    }
+
+    /// Adds `file` after the libs referenced by it.
+    ///
+    /// `stack` is used to ignore circular references.
+    fn add(&mut self, file: &str, stack: &mut Vec<String>) -> Result<(), Error> {
+        if self.files.iter().chain(stack.iter()).any(|f| f == file) {
+            return Ok(());
+        }
+
+        let path = self.dir.join(file);
+        let src = fs::read_to_string(&path).with_context(|| format!("failed to read lib file `{}`", path.display()))?;
+
+        stack.push(file.to_string());
+        for line in src.lines().filter(|line| line.starts_with("///")) {
+            if let Some((name, _)) = line.split_once("<reference lib=\"").and_then(|(_, rest)| rest.split_once('"')) {
+                self.add(&format!("lib.{}.d.ts", name.to_lowercase()), stack)?;
+            }
+        }
+        stack.pop();
+
+        self.files.push(file.to_string());
+        self.sources.push(src);
+
+        Ok(())
+    }
+
+    /// Names of the loaded files, in the order of dependencies.
+    pub fn files(&self) -> &[String] {
+        &self.files
+    }
+}
+
+impl BuiltInProvider for LibDir {
+    fn id(&self) -> Cow<'_, str> {
+        Cow::Owned(format!("{}:{}", self.dir.display(), self.files.join(",")))
+    }
+
+    fn sources(&self) -> Vec<Cow<'static, str>> {
+        self.sources.iter().cloned().map(Cow::Owned).collect()
+    }
+}
@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use anyhow::Error;
+use globset::GlobSet;
+use serde::Deserialize;
+use stc_ts_builtin_types::Lib;
+
+use crate::{
+    files::{build_set, Patterns},
+    options::load_libs,
+};
+
+/// An entry of `stc.libOverrides`, which checks some files against other
+/// libs, like worker entries against `webworker` instead of `dom`.
+///
+/// ```json
+/// "stc": {
+///     "libOverrides": [{ "files": ["src/worker/**/*.ts"], "lib": ["es2020", "webworker"] }]
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LibOverride {
+    files: GlobSet,
+    pub lib: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct RawLibOverride {
+    files: Vec<String>,
+    lib: Vec<String>,
+}
+
+impl LibOverride {
+    /// Patterns of `raw` are relative to `dir`.
+    pub(crate) fn new(dir: &Path, raw: RawLibOverride) -> Result<Self, Error> {
+        let files = build_set(
+            &Patterns {
+                base: dir.to_path_buf(),
+                patterns: raw.files,
+            },
+            false,
+        )?;
+
+        Ok(LibOverride { files, lib: raw.lib })
+    }
+
+    pub fn matches(&self, path: &Path) -> bool {
+        self.files.is_match(path)
+    }
+
+    /// Libraries to load for the matched files, including dependencies of
+    /// them.
+    pub fn libs(&self) -> Vec<Lib> {
+        load_libs(&self.lib)
+    }
+}
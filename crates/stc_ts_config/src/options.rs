@@ -0,0 +1,220 @@
+use std::{collections::BTreeMap, path::PathBuf};
+
+use anyhow::{bail, Error};
+use serde::Deserialize;
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{JsxConfig, ModuleConfig, ModuleDetection, Rule};
+use swc_ecma_ast::EsVersion;
+
+/// `compilerOptions` of a `tsconfig.json`, after merging the options of the
+/// configs it extends.
+///
+/// Options which don't affect type checking are ignored. Paths are resolved
+/// relative to the config which declares them.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompilerOptions {
+    pub target: Option<String>,
+    pub module: Option<String>,
+    pub lib: Option<Vec<String>>,
+    pub no_lib: Option<bool>,
+    pub types: Option<Vec<String>>,
+    pub type_roots: Option<Vec<PathBuf>>,
+    pub base_url: Option<PathBuf>,
+    /// Resolved relative to [crate::ProjectConfig::paths_base].
+    pub paths: Option<BTreeMap<String, Vec<String>>>,
+    pub out_dir: Option<PathBuf>,
+    pub allow_js: Option<bool>,
+    pub composite: Option<bool>,
+    pub declaration: Option<bool>,
+    pub incremental: Option<bool>,
+    pub ts_build_info_file: Option<PathBuf>,
+    pub jsx: Option<String>,
+    pub jsx_factory: Option<String>,
+    pub jsx_fragment_factory: Option<String>,
+    pub jsx_import_source: Option<String>,
+
+    pub strict: Option<bool>,
+    pub no_implicit_any: Option<bool>,
+    pub no_implicit_this: Option<bool>,
+    pub always_strict: Option<bool>,
+    pub strict_null_checks: Option<bool>,
+    pub strict_function_types: Option<bool>,
+    pub strict_property_initialization: Option<bool>,
+
+    pub allow_unreachable_code: Option<bool>,
+    pub allow_unused_labels: Option<bool>,
+    pub no_fallthrough_cases_in_switch: Option<bool>,
+    pub no_implicit_returns: Option<bool>,
+    pub suppress_excess_property_errors: Option<bool>,
+    pub suppress_implicit_any_index_errors: Option<bool>,
+    pub no_strict_generic_checks: Option<bool>,
+    pub no_unused_locals: Option<bool>,
+    pub no_unused_parameters: Option<bool>,
+    pub use_define_for_class_fields: Option<bool>,
+    pub experimental_decorators: Option<bool>,
+    pub no_implicit_override: Option<bool>,
+    pub exact_optional_property_types: Option<bool>,
+    pub isolated_modules: Option<bool>,
+    pub es_module_interop: Option<bool>,
+    pub allow_synthetic_default_imports: Option<bool>,
+    pub check_js: Option<bool>,
+    pub downlevel_iteration: Option<bool>,
+    pub skip_lib_check: Option<bool>,
+    pub skip_default_lib_check: Option<bool>,
+    pub module_detection: Option<String>,
+}
+
+/// Names of path-valued options, which are resolved while loading.
+pub(crate) const PATH_OPTIONS: &[&str] = &["baseUrl", "outDir", "tsBuildInfoFile"];
+
+/// Same as [PATH_OPTIONS], but for lists of paths.
+pub(crate) const PATH_LIST_OPTIONS: &[&str] = &["typeRoots"];
+
+impl CompilerOptions {
+    pub fn rule(&self) -> Rule {
+        let strict = self.strict.unwrap_or(false);
+        let es_module_interop = self.es_module_interop.unwrap_or(false);
+
+        Rule {
+            no_implicit_any: self.no_implicit_any.unwrap_or(strict),
+            no_implicit_this: self.no_implicit_this.unwrap_or(strict),
+            always_strict: self.always_strict.unwrap_or(strict),
+            strict_null_checks: self.strict_null_checks.unwrap_or(strict),
+            strict_function_types: self.strict_function_types.unwrap_or(strict),
+            strict_property_initialization: self.strict_property_initialization.unwrap_or(strict),
+
+            allow_unreachable_code: self.allow_unreachable_code.unwrap_or(false),
+            allow_unused_labels: self.allow_unused_labels.unwrap_or(false),
+            no_fallthrough_cases_in_switch: self.no_fallthrough_cases_in_switch.unwrap_or(false),
+            no_implicit_returns: self.no_implicit_returns.unwrap_or(false),
+            suppress_excess_property_errors: self.suppress_excess_property_errors.unwrap_or(false),
+            suppress_implicit_any_index_errors: self.suppress_implicit_any_index_errors.unwrap_or(false),
+            no_strict_generic_checks: self.no_strict_generic_checks.unwrap_or(false),
+            no_unused_locals: self.no_unused_locals.unwrap_or(false),
+            no_unused_parameters: self.no_unused_parameters.unwrap_or(false),
+            use_define_property_for_class_fields: self.use_define_for_class_fields.unwrap_or(false),
+            experimental_decorators: self.experimental_decorators.unwrap_or(false),
+            no_implicit_override: self.no_implicit_override.unwrap_or(false),
+            exact_optional_property_types: self.exact_optional_property_types.unwrap_or(false),
+            isolated_modules: self.isolated_modules.unwrap_or(false),
+            allow_synthetic_default_imports: self.allow_synthetic_default_imports.unwrap_or(es_module_interop),
+            check_js: self.check_js.unwrap_or(false),
+            downlevel_iteration: self.downlevel_iteration.unwrap_or(false),
+            skip_lib_check: self.skip_lib_check.unwrap_or(false),
+            skip_default_lib_check: self.skip_default_lib_check.unwrap_or(false),
+            module_detection: self.module_detection(),
+        }
+    }
+
+    /// Unknown values are ignored with a warning, like unknown libraries.
+    fn module_detection(&self) -> ModuleDetection {
+        let name = match &self.module_detection {
+            Some(name) => name.to_lowercase(),
+            None => return ModuleDetection::default(),
+        };
+
+        name.parse().unwrap_or_else(|_| {
+            log::warn!("unknown moduleDetection `{}` is ignored", name);
+            ModuleDetection::default()
+        })
+    }
+
+    /// Defaults to [EsVersion::default].
+    pub fn target(&self) -> Result<EsVersion, Error> {
+        let target = match &self.target {
+            Some(target) => target.to_lowercase(),
+            None => return Ok(EsVersion::default()),
+        };
+
+        Ok(match &*target {
+            "es3" => EsVersion::Es3,
+            "es5" => EsVersion::Es5,
+            "es6" | "es2015" => EsVersion::Es2015,
+            "es2016" => EsVersion::Es2016,
+            "es2017" => EsVersion::Es2017,
+            "es2018" => EsVersion::Es2018,
+            "es2019" => EsVersion::Es2019,
+            "es2020" => EsVersion::Es2020,
+            "es2021" => EsVersion::Es2021,
+            "es2022" | "esnext" => EsVersion::latest(),
+            _ => bail!("unknown target `{}`", target),
+        })
+    }
+
+    /// Defaults to `commonjs` for `es3` and `es5`, and `es2015` otherwise.
+    pub fn module(&self) -> Result<ModuleConfig, Error> {
+        let module = match &self.module {
+            Some(module) => module.to_lowercase(),
+            None => {
+                return Ok(match self.target()? {
+                    EsVersion::Es3 | EsVersion::Es5 => ModuleConfig::CommonJs,
+                    _ => ModuleConfig::Es2015,
+                })
+            }
+        };
+
+        Ok(match &*module {
+            // TODO: Handle the resolution rules of node.
+            "es2022" | "node16" | "nodenext" => ModuleConfig::EsNext,
+            _ => match module.parse() {
+                Ok(v) => v,
+                Err(..) => bail!("unknown module `{}`", module),
+            },
+        })
+    }
+
+    pub fn jsx(&self) -> Result<JsxConfig, Error> {
+        let mode = match &self.jsx {
+            Some(mode) => match mode.to_lowercase().parse() {
+                Ok(v) => Some(v),
+                Err(..) => bail!("unknown jsx `{}`", mode),
+            },
+            None => None,
+        };
+
+        Ok(JsxConfig {
+            mode,
+            factory: self.jsx_factory.as_deref().map(From::from),
+            fragment_factory: self.jsx_fragment_factory.as_deref().map(From::from),
+            import_source: self.jsx_import_source.as_deref().map(From::from),
+        })
+    }
+
+    /// Libraries to load, including dependencies of them.
+    pub fn libs(&self) -> Result<Vec<Lib>, Error> {
+        if self.no_lib.unwrap_or(false) {
+            return Ok(vec![]);
+        }
+
+        Ok(match &self.lib {
+            Some(names) => load_libs(names),
+            None => {
+                let mut libs = Lib::defaults_for(self.target()?);
+                libs.sort();
+                libs.dedup();
+                libs
+            }
+        })
+    }
+}
+
+/// Loads libs named like `lib` of `compilerOptions`.
+///
+/// Unknown libraries are ignored with a warning, as they may be added in newer
+/// versions of typescript.
+pub(crate) fn load_libs(names: &[String]) -> Vec<Lib> {
+    let mut libs = vec![];
+    for name in names {
+        let name = name.to_lowercase().replace("es6", "es2015").replace("es7", "es2016");
+        if name.parse::<Lib>().is_err() {
+            log::warn!("unknown lib `{}` is ignored", name);
+            continue;
+        }
+        libs.extend(Lib::load(&name));
+    }
+    libs.sort();
+    libs.dedup();
+
+    libs
+}
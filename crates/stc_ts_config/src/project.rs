@@ -0,0 +1,106 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Error};
+
+use crate::ProjectConfig;
+
+/// A project and the projects it references transitively.
+#[derive(Debug)]
+pub struct ProjectGraph {
+    /// Referenced projects come before the projects referencing them, so the
+    /// last one is the root project.
+    projects: Vec<ProjectConfig>,
+    /// Indices of the projects directly referenced by each project.
+    deps: Vec<Vec<usize>>,
+}
+
+impl ProjectGraph {
+    /// Loads `path` and the projects it references.
+    ///
+    /// Like `tsc --build`, referenced projects must be composite, and
+    /// references must not form a cycle.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let mut graph = ProjectGraph {
+            projects: vec![],
+            deps: vec![],
+        };
+        graph.add(ProjectConfig::load(path)?, &mut vec![])?;
+
+        Ok(graph)
+    }
+
+    /// Returns the index of `config`.
+    fn add(&mut self, config: ProjectConfig, stack: &mut Vec<PathBuf>) -> Result<usize, Error> {
+        if let Some(idx) = self.projects.iter().position(|p| p.path == config.path) {
+            return Ok(idx);
+        }
+        if let Some(start) = stack.iter().position(|p| *p == config.path) {
+            let cycle = stack[start..]
+                .iter()
+                .chain([&config.path])
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>();
+            bail!(
+                "TS6202: Project references may not form a circular graph. Cycle detected: {}",
+                cycle.join(" -> ")
+            );
+        }
+
+        config.validate_composite()?;
+
+        stack.push(config.path.clone());
+        let mut deps = vec![];
+        for reference in &config.references {
+            let referenced = ProjectConfig::load(reference)?;
+            if referenced.compiler_options.composite != Some(true) {
+                bail!(
+                    "TS6306: Referenced project `{}` must have setting \"composite\": true. (referenced by `{}`)",
+                    referenced.path.display(),
+                    config.path.display()
+                );
+            }
+
+            deps.push(self.add(referenced, stack)?);
+        }
+        stack.pop();
+
+        self.projects.push(config);
+        self.deps.push(deps);
+
+        Ok(self.projects.len() - 1)
+    }
+
+    /// Projects in the order to check them.
+    pub fn projects(&self) -> &[ProjectConfig] {
+        &self.projects
+    }
+
+    /// Indices of the projects directly referenced by `self.projects()[idx]`.
+    pub fn deps_of(&self, idx: usize) -> &[usize] {
+        &self.deps[idx]
+    }
+
+    /// The configs and root files of `self.projects()[idx]` and the projects
+    /// it references transitively. A project should be checked again if one
+    /// of them is modified.
+    pub fn inputs_of(&self, idx: usize) -> Result<Vec<PathBuf>, Error> {
+        let mut inputs = vec![];
+        let mut visited = vec![false; self.projects.len()];
+        let mut queue = vec![idx];
+
+        while let Some(idx) = queue.pop() {
+            if visited[idx] {
+                continue;
+            }
+            visited[idx] = true;
+
+            inputs.push(self.projects[idx].path.clone());
+            inputs.extend(self.projects[idx].root_files()?);
+            queue.extend(&self.deps[idx]);
+        }
+
+        inputs.sort();
+        inputs.dedup();
+        Ok(inputs)
+    }
+}
@@ -202,6 +202,8 @@ fn run_bench(b: &mut Bencher, path: PathBuf) {
                 path: path.clone(),
                 info: Default::default(),
                 is_dts: false,
+                filter: None,
+                directives: None,
             };
 
             let mut module = module.clone();
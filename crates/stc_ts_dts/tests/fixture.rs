@@ -90,6 +90,8 @@ fn do_test(file_name: &Path) -> Result<(), StdErr> {
             path,
             info: Default::default(),
             is_dts: false,
+            filter: None,
+            directives: None,
         };
 
         let mut node_id_gen = NodeIdGenerator::default();
@@ -0,0 +1,105 @@
+use string_enum::StringEnum;
+use swc_atoms::JsWord;
+
+/// `jsx` of `compilerOptions`.
+#[derive(Clone, Copy, PartialEq, Eq, StringEnum)]
+pub enum JsxMode {
+    /// `preserve`
+    Preserve,
+    /// `react`
+    React,
+    /// `react-native`
+    ReactNative,
+    /// `react-jsx`
+    ReactJsx,
+    /// `react-jsxdev`
+    ReactJsxDev,
+}
+
+impl JsxMode {
+    /// Returns `true` for the automatic runtime, which imports factories from
+    /// `jsxImportSource` instead of using `jsxFactory`.
+    pub fn is_automatic(self) -> bool {
+        matches!(self, JsxMode::ReactJsx | JsxMode::ReactJsxDev)
+    }
+}
+
+/// JSX options of `compilerOptions`.
+#[derive(Debug, Clone, Default)]
+pub struct JsxConfig {
+    /// [None] if `jsx` is not set, which disallows JSX.
+    pub mode: Option<JsxMode>,
+    /// `jsxFactory`, like `h`. Defaults to `React.createElement`.
+    pub factory: Option<JsWord>,
+    /// `jsxFragmentFactory`, like `Fragment`. Defaults to `React.Fragment`.
+    pub fragment_factory: Option<JsWord>,
+    /// `jsxImportSource`, like `preact`. Defaults to `react`.
+    pub import_source: Option<JsWord>,
+}
+
+/// An entity used to type JSX, which is selected by [JsxConfig].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JsxEntity {
+    /// An entity name resolved from the scope of the element, like
+    /// `React.createElement`.
+    Name(JsWord),
+    /// An export of a module, like `jsx` of `react/jsx-runtime`.
+    Import { src: JsWord, name: JsWord },
+}
+
+impl JsxConfig {
+    pub fn is_automatic(&self) -> bool {
+        self.mode.map(JsxMode::is_automatic).unwrap_or(false)
+    }
+
+    /// The module which provides factories of the automatic runtime, like
+    /// `react/jsx-runtime`.
+    pub fn runtime_module(&self) -> JsWord {
+        let import_source = self.import_source.as_deref().unwrap_or("react");
+
+        match self.mode {
+            Some(JsxMode::ReactJsxDev) => format!("{}/jsx-dev-runtime", import_source).into(),
+            _ => format!("{}/jsx-runtime", import_source).into(),
+        }
+    }
+
+    /// The function which creates elements.
+    pub fn element_factory(&self) -> JsxEntity {
+        match self.mode {
+            Some(JsxMode::ReactJsx) => self.runtime_export("jsx"),
+            Some(JsxMode::ReactJsxDev) => self.runtime_export("jsxDEV"),
+            _ => JsxEntity::Name(self.factory.clone().unwrap_or_else(|| "React.createElement".into())),
+        }
+    }
+
+    /// The type of `<>...</>`.
+    pub fn fragment_factory(&self) -> JsxEntity {
+        if self.is_automatic() {
+            return self.runtime_export("Fragment");
+        }
+
+        JsxEntity::Name(self.fragment_factory.clone().unwrap_or_else(|| "React.Fragment".into()))
+    }
+
+    /// The namespace which contains `JSX`, like `React` for
+    /// `React.createElement`.
+    ///
+    /// The global `JSX` is used if the namespace does not contain it.
+    pub fn namespace(&self) -> JsxEntity {
+        if self.is_automatic() {
+            return self.runtime_export("JSX");
+        }
+
+        match &self.factory {
+            Some(factory) => JsxEntity::Name(factory.split('.').next().unwrap_or_default().into()),
+            None => JsxEntity::Name("React".into()),
+        }
+    }
+
+    fn runtime_export(&self, name: &str) -> JsxEntity {
+        JsxEntity::Import {
+            src: self.runtime_module(),
+            name: name.into(),
+        }
+    }
+}
@@ -1,8 +1,8 @@
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use derivative::Derivative;
 use parking_lot::Mutex;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use serde::{Deserialize, Serialize};
 use stc_ts_errors::{Error, ErrorKind};
 use stc_ts_type_ops::Fix;
@@ -13,9 +13,15 @@ use swc_atoms::JsWord;
 use swc_common::{Globals, Span, Spanned, DUMMY_SP};
 use swc_ecma_ast::EsVersion;
 
-pub use self::marks::{MarkExt, Marks};
+use self::perf::PerfRecorder;
+pub use self::{
+    jsx::{JsxConfig, JsxEntity, JsxMode},
+    marks::{MarkExt, Marks},
+};
 
+mod jsx;
 mod marks;
+pub mod perf;
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct BuiltIn {
@@ -27,6 +33,29 @@ impl BuiltIn {
     pub fn new(vars: FxHashMap<JsWord, Type>, types: FxHashMap<JsWord, Type>) -> Self {
         BuiltIn { vars, types }
     }
+
+    pub fn vars(&self) -> &FxHashMap<JsWord, Type> {
+        &self.vars
+    }
+
+    pub fn types(&self) -> &FxHashMap<JsWord, Type> {
+        &self.types
+    }
+}
+
+/// Provides the declarations of globals, instead of the lib files of
+/// typescript.
+///
+/// Embedders can implement this to check code for a custom runtime, or to use
+/// a trimmed set of declarations.
+pub trait BuiltInProvider: Send + Sync {
+    /// Identifies the declarations. Loaded declarations are cached using this,
+    /// so providers with same id should provide same declarations.
+    fn id(&self) -> Cow<'_, str>;
+
+    /// Contents of declaration files. Top-level statements are global
+    /// declarations, like the lib files of typescript.
+    fn sources(&self) -> Vec<Cow<'static, str>>;
 }
 
 /// Stuffs which can be changed between runs.
@@ -36,9 +65,13 @@ pub struct Env {
     rule: Rule,
     target: EsVersion,
     module: ModuleConfig,
+    jsx: JsxConfig,
     builtin: Arc<BuiltIn>,
     global_types: Arc<Mutex<FxHashMap<JsWord, Type>>>,
     global_vars: Arc<Mutex<FxHashMap<JsWord, Type>>>,
+    /// Names of libs loaded by `/// <reference lib="..." />`.
+    loaded_libs: Arc<Mutex<FxHashSet<JsWord>>>,
+    perf: Option<Arc<PerfRecorder>>,
 }
 
 impl Env {
@@ -48,12 +81,37 @@ impl Env {
             builtin,
             target,
             module,
+            jsx: Default::default(),
             global_types: Default::default(),
             global_vars: Default::default(),
+            loaded_libs: Default::default(),
             rule,
+            perf: None,
         }
     }
 
+    /// Returns an env which uses `builtin` instead of the builtin declarations
+    /// of `self`. Globals declared by files, like `declare global`, are
+    /// shared.
+    pub fn with_builtin(&self, builtin: Arc<BuiltIn>) -> Self {
+        Self { builtin, ..self.clone() }
+    }
+
+    pub fn with_jsx(mut self, jsx: JsxConfig) -> Self {
+        self.jsx = jsx;
+        self
+    }
+
+    /// Enables recording of expensive operations.
+    pub fn with_perf(mut self, perf: Arc<PerfRecorder>) -> Self {
+        self.perf = Some(perf);
+        self
+    }
+
+    pub fn perf(&self) -> Option<&PerfRecorder> {
+        self.perf.as_deref()
+    }
+
     pub const fn shared(&self) -> &StableEnv {
         &self.stable
     }
@@ -70,6 +128,10 @@ impl Env {
         self.rule
     }
 
+    pub const fn jsx(&self) -> &JsxConfig {
+        &self.jsx
+    }
+
     pub fn declare_global_var(&mut self, name: JsWord, ty: Type) {
         ty.assert_clone_cheap();
 
@@ -93,6 +155,59 @@ impl Env {
         }
     }
 
+    /// Registers a global variable, like `describe` of test frameworks, without
+    /// a declaration file. Like ambient declarations, it's merged with the
+    /// global variable with the same name.
+    pub fn with_global_var(mut self, name: impl Into<JsWord>, ty: Type) -> Self {
+        let name = name.into();
+        let ty = ty.fixed().freezed();
+        let ty = match self.get_global_var(ty.span(), &name) {
+            Ok(prev_ty) => Type::new_intersection(DUMMY_SP, vec![prev_ty, ty]).fixed().freezed(),
+            Err(_) => ty,
+        };
+
+        self.declare_global_var(name, ty);
+        self
+    }
+
+    /// Same as [Env::with_global_var], but registers a type, like an
+    /// interface.
+    pub fn with_global_type(mut self, name: impl Into<JsWord>, ty: Type) -> Self {
+        self.declare_global_type(name.into(), ty.fixed().freezed());
+        self
+    }
+
+    /// Makes the declarations of a lib referenced by `/// <reference lib="..."
+    /// />` global. Returns `false` if the lib is already loaded.
+    pub fn declare_lib(&mut self, name: JsWord, lib: &BuiltIn) -> bool {
+        if !self.loaded_libs.lock().insert(name) {
+            return false;
+        }
+
+        for (name, ty) in &lib.types {
+            if self.builtin.types.get(name) == Some(ty) {
+                continue;
+            }
+
+            if !self.global_types.lock().contains_key(name) {
+                // The lib also contains the parts declared by the default libs.
+                self.global_types.lock().insert(name.clone(), ty.clone());
+            } else {
+                self.declare_global_type(name.clone(), ty.clone());
+            }
+        }
+
+        for (name, ty) in &lib.vars {
+            if self.builtin.vars.get(name) == Some(ty) || self.global_vars.lock().contains_key(name) {
+                continue;
+            }
+
+            self.declare_global_var(name.clone(), ty.clone());
+        }
+
+        true
+    }
+
     #[cfg_attr(debug_assertions, tracing::instrument(skip_all))]
     pub fn get_global_var(&self, span: Span, name: &JsWord) -> Result<Type, Error> {
         if let Some(ty) = self.global_vars.lock().get(name) {
@@ -112,6 +227,13 @@ impl Env {
         .into())
     }
 
+    /// Names of all global variables, including builtin ones.
+    pub fn global_var_names(&self) -> Vec<JsWord> {
+        let mut names = self.global_vars.lock().keys().cloned().collect::<Vec<_>>();
+        names.extend(self.builtin.vars.keys().cloned());
+        names
+    }
+
     #[cfg_attr(debug_assertions, tracing::instrument(skip_all))]
     pub fn get_global_type(&self, span: Span, name: &JsWord) -> Result<Type, Error> {
         if let Some(ty) = self.global_types.lock().get(name) {
@@ -186,6 +308,28 @@ pub enum ModuleConfig {
     EsNext,
 }
 
+/// `moduleDetection` of `compilerOptions`, which determines whether a file is
+/// a module or a script.
+///
+/// Files with an import or an export are modules in all modes. `auto` also
+/// treats files using `import.meta` as modules, and `force` treats all files
+/// except declaration files as modules.
+#[derive(Clone, Copy, PartialEq, Eq, StringEnum)]
+pub enum ModuleDetection {
+    /// `auto`
+    Auto,
+    /// `legacy`
+    Legacy,
+    /// `force`
+    Force,
+}
+
+impl Default for ModuleDetection {
+    fn default() -> Self {
+        ModuleDetection::Auto
+    }
+}
+
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Rule {
     pub no_implicit_any: bool,
@@ -204,4 +348,19 @@ pub struct Rule {
     pub no_unused_locals: bool,
     pub no_unused_parameters: bool,
     pub use_define_property_for_class_fields: bool,
+    pub experimental_decorators: bool,
+    pub no_implicit_override: bool,
+    pub strict_property_initialization: bool,
+    pub exact_optional_property_types: bool,
+    pub isolated_modules: bool,
+    /// Also enabled by `esModuleInterop`.
+    pub allow_synthetic_default_imports: bool,
+    pub check_js: bool,
+    pub downlevel_iteration: bool,
+    /// Don't report errors in declaration files.
+    pub skip_lib_check: bool,
+    /// Don't report errors in declaration files marked with
+    /// `/// <reference no-default-lib="true"/>`.
+    pub skip_default_lib_check: bool,
+    pub module_detection: ModuleDetection,
 }
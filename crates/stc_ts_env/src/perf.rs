@@ -0,0 +1,235 @@
+//! Recording of expensive operations, used to find files and types which make
+//! type checking slow.
+
+use std::{cell::Cell, fmt::Write, sync::Arc, time::Duration};
+
+use parking_lot::Mutex;
+use swc_common::{FileName, SourceMap, Span};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OpKind {
+    /// Normalization which produced a union. The cost is the number of
+    /// members of the union.
+    NormalizeUnion,
+    /// Expansion of type parameters. The cost is the depth of nested
+    /// instantiations.
+    Instantiation,
+    /// Assignability check. The cost is the wall time in microseconds.
+    Assign,
+}
+
+impl OpKind {
+    const ALL: [OpKind; 3] = [OpKind::NormalizeUnion, OpKind::Instantiation, OpKind::Assign];
+
+    const fn idx(self) -> usize {
+        match self {
+            OpKind::NormalizeUnion => 0,
+            OpKind::Instantiation => 1,
+            OpKind::Assign => 2,
+        }
+    }
+
+    const fn title(self) -> &'static str {
+        match self {
+            OpKind::NormalizeUnion => "Largest unions normalized",
+            OpKind::Instantiation => "Deepest instantiations",
+            OpKind::Assign => "Slowest assignability checks",
+        }
+    }
+
+    const fn unit(self) -> &'static str {
+        match self {
+            OpKind::NormalizeUnion => "members",
+            OpKind::Instantiation => "levels",
+            OpKind::Assign => "us",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Op {
+    pub span: Span,
+    pub cost: u64,
+    /// Human-readable description of the involved types.
+    pub desc: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ModuleTiming {
+    pub path: Arc<FileName>,
+    pub time: Duration,
+}
+
+/// Keeps the `top_n` most expensive operations of each [OpKind] and the wall
+/// time of each module.
+///
+/// This is shared by all threads, so recording takes a lock. It should only
+/// be enabled while diagnosing performance problems.
+#[derive(Debug)]
+pub struct PerfRecorder {
+    top_n: usize,
+    modules: Mutex<Vec<ModuleTiming>>,
+    ops: [Mutex<Vec<Op>>; 3],
+}
+
+impl PerfRecorder {
+    pub fn new(top_n: usize) -> Self {
+        Self {
+            top_n,
+            modules: Default::default(),
+            ops: Default::default(),
+        }
+    }
+
+    pub fn record_module(&self, path: Arc<FileName>, time: Duration) {
+        self.modules.lock().push(ModuleTiming { path, time });
+    }
+
+    /// `desc` is only invoked if the operation is expensive enough to be
+    /// kept, because printing types is costly.
+    pub fn record_op(&self, kind: OpKind, span: Span, cost: u64, desc: impl FnOnce() -> String) {
+        if self.top_n == 0 {
+            return;
+        }
+
+        let mut ops = self.ops[kind.idx()].lock();
+        if ops.len() >= self.top_n {
+            // `ops` is sorted in descending order.
+            if ops.last().map_or(false, |min| min.cost >= cost) {
+                return;
+            }
+            ops.pop();
+        }
+
+        let idx = ops.partition_point(|op| op.cost >= cost);
+        ops.insert(idx, Op { span, cost, desc: desc() });
+    }
+
+    /// Returns the recorded operations of `kind`, most expensive first.
+    pub fn ops(&self, kind: OpKind) -> Vec<Op> {
+        self.ops[kind.idx()].lock().clone()
+    }
+
+    /// Returns the recorded modules, slowest first.
+    pub fn modules(&self) -> Vec<ModuleTiming> {
+        let mut modules = self.modules.lock().clone();
+        modules.sort_by(|a, b| b.time.cmp(&a.time));
+        modules
+    }
+
+    pub fn report(&self, cm: &SourceMap) -> String {
+        let mut s = String::new();
+
+        let _ = writeln!(s, "Slowest modules:");
+        for m in self.modules().iter().take(self.top_n) {
+            let _ = writeln!(s, "  {:>12?}  {}", m.time, m.path);
+        }
+
+        for kind in OpKind::ALL {
+            let _ = writeln!(s, "{}:", kind.title());
+            for op in self.ops(kind) {
+                let _ = writeln!(s, "  {:>8} {:<7}  {}", op.cost, kind.unit(), cm.span_to_string(op.span));
+                for line in op.desc.lines() {
+                    let _ = writeln!(s, "      {}", line);
+                }
+            }
+        }
+
+        s
+    }
+}
+
+/// Maximum depth of nested instantiations, same as `tsc`.
+pub const MAX_INSTANTIATION_DEPTH: u64 = 100;
+
+/// Maximum number of conditional types evaluated in tail positions of a
+/// conditional type, same as `tsc`.
+pub const MAX_TAIL_RECURSION_DEPTH: u64 = 1000;
+
+/// Maximum number of instantiations while checking a module item, same as
+/// `tsc`.
+pub const MAX_INSTANTIATION_COUNT: u64 = 5_000_000;
+
+thread_local! {
+    static INSTANTIATION_DEPTH: Cell<u64> = Cell::new(0);
+    static INSTANTIATION_COUNT: Cell<u64> = Cell::new(0);
+}
+
+/// Tracks the depth of nested instantiations on the current thread.
+pub struct InstantiationGuard {
+    depth: u64,
+}
+
+impl InstantiationGuard {
+    pub fn enter() -> Self {
+        let depth = INSTANTIATION_DEPTH.with(|d| {
+            let depth = d.get() + 1;
+            d.set(depth);
+            depth
+        });
+        INSTANTIATION_COUNT.with(|c| c.set(c.get() + 1));
+
+        Self { depth }
+    }
+
+    pub fn depth(&self) -> u64 {
+        self.depth
+    }
+
+    /// Returns true if the instantiation is likely to be infinite.
+    pub fn is_excessive(&self) -> bool {
+        self.depth >= MAX_INSTANTIATION_DEPTH || INSTANTIATION_COUNT.with(|c| c.get()) >= MAX_INSTANTIATION_COUNT
+    }
+}
+
+impl Drop for InstantiationGuard {
+    fn drop(&mut self) {
+        INSTANTIATION_DEPTH.with(|d| d.set(self.depth - 1));
+    }
+}
+
+/// Returns the depth of instantiations in progress on the current thread.
+pub fn instantiation_depth() -> u64 {
+    INSTANTIATION_DEPTH.with(|d| d.get())
+}
+
+/// Resets the number of instantiations. Should be called for each module
+/// item.
+pub fn reset_instantiation_count() {
+    INSTANTIATION_COUNT.with(|c| c.set(0));
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_common::DUMMY_SP;
+
+    use super::*;
+
+    #[test]
+    fn keeps_top_n() {
+        let recorder = PerfRecorder::new(2);
+
+        for cost in [3, 1, 5, 4, 2] {
+            recorder.record_op(OpKind::NormalizeUnion, DUMMY_SP, cost, || cost.to_string());
+        }
+
+        let costs = recorder
+            .ops(OpKind::NormalizeUnion)
+            .into_iter()
+            .map(|op| op.cost)
+            .collect::<Vec<_>>();
+        assert_eq!(costs, vec![5, 4]);
+        assert!(recorder.ops(OpKind::Assign).is_empty());
+    }
+
+    #[test]
+    fn guard_restores_depth() {
+        let outer = InstantiationGuard::enter();
+        {
+            let inner = InstantiationGuard::enter();
+            assert_eq!(inner.depth(), outer.depth() + 1);
+            assert_eq!(instantiation_depth(), inner.depth());
+        }
+        assert_eq!(instantiation_depth(), outer.depth());
+    }
+}
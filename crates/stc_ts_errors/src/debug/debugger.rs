@@ -1,4 +1,8 @@
-use std::sync::Arc;
+use std::{
+    fmt::Write,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
 
 use rnode::RNode;
 use stc_ts_ast_rnode::RTsType;
@@ -10,10 +14,54 @@ use swc_ecma_codegen::{text_writer::JsWriter, Emitter, Node};
 pub struct Debugger {
     pub cm: Arc<SourceMap>,
     pub handler: Arc<Handler>,
+    /// If set, types are recorded here instead of being emitted to `handler`.
+    pub records: Option<Arc<Mutex<Vec<TypeRecord>>>>,
+}
+
+/// Type of an expression or a declaration, recorded by [Debugger].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeRecord {
+    pub span: Span,
+    pub kind: TypeRecordKind,
+    pub ty: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeRecordKind {
+    Expr,
+    /// A variable declaration.
+    Decl,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeDumpFormat {
+    /// `file:line:col <expr|decl> <source>: <type>`
+    Text,
+    Json,
+}
+
+impl FromStr for TypeDumpFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(TypeDumpFormat::Text),
+            "json" => Ok(TypeDumpFormat::Json),
+            _ => Err(format!("unknown format `{}`; expected `text` or `json`", s)),
+        }
+    }
 }
 
-#[cfg(debug_assertions)]
 impl Debugger {
+    /// Creates a debugger which records types instead of emitting them.
+    pub fn recording(cm: Arc<SourceMap>, handler: Arc<Handler>) -> Self {
+        Debugger {
+            cm,
+            handler,
+            records: Some(Default::default()),
+        }
+    }
+
     fn dump(&self, ty: &Type) -> String {
         let ty = RTsType::from(ty.clone());
         let ty = ty.into_orig();
@@ -36,13 +84,109 @@ impl Debugger {
         String::from_utf8_lossy(&buf).to_string()
     }
 
+    fn record(&self, span: Span, kind: TypeRecordKind, ty: &Type) -> bool {
+        match &self.records {
+            Some(records) => {
+                let ty = self.dump(ty);
+                records.lock().unwrap().push(TypeRecord { span, kind, ty });
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn dump_type(&self, span: Span, ty: &Type) {
-        let ty_str = self.dump(ty);
-        self.handler.struct_span_err(span, "Type").note(&ty_str).emit();
+        if self.record(span, TypeRecordKind::Expr, ty) {
+            return;
+        }
+
+        if cfg!(debug_assertions) {
+            let ty_str = self.dump(ty);
+            self.handler.struct_span_err(span, "Type").note(&ty_str).emit();
+        }
+    }
+
+    /// Types of declarations are only recorded.
+    pub fn dump_decl_type(&self, span: Span, ty: &Type) {
+        self.record(span, TypeRecordKind::Decl, ty);
+    }
+
+    /// Returns the recorded types, sorted by their spans.
+    pub fn take_records(&self) -> Vec<TypeRecord> {
+        let mut records = match &self.records {
+            Some(records) => std::mem::take(&mut *records.lock().unwrap()),
+            None => return vec![],
+        };
+        records.sort_by_key(|r| (r.span.lo, r.span.hi));
+        records.dedup();
+        records
     }
 }
 
-#[cfg(not(debug_assertions))]
-impl Debugger {
-    pub fn dump_type(&self, _: Span, _: &Type) {}
+/// Formats the types recorded by [Debugger].
+pub fn format_type_dump(cm: &SourceMap, records: &[TypeRecord], format: TypeDumpFormat) -> String {
+    let mut buf = String::new();
+
+    if format == TypeDumpFormat::Json {
+        buf.push('[');
+    }
+
+    for (idx, record) in records.iter().enumerate() {
+        let loc = cm.lookup_char_pos(record.span.lo);
+        let file = loc.file.name.to_string();
+        let src = cm.span_to_snippet(record.span).unwrap_or_default();
+        let kind = match record.kind {
+            TypeRecordKind::Expr => "expr",
+            TypeRecordKind::Decl => "decl",
+        };
+
+        match format {
+            TypeDumpFormat::Text => {
+                let src = src.lines().next().unwrap_or_default();
+                let ty = record.ty.split_whitespace().collect::<Vec<_>>().join(" ");
+                let _ = writeln!(buf, "{}:{}:{} {} {}: {}", file, loc.line, loc.col.0 + 1, kind, src, ty);
+            }
+            TypeDumpFormat::Json => {
+                if idx != 0 {
+                    buf.push(',');
+                }
+                let _ = write!(
+                    buf,
+                    "\n  {{\"file\":{},\"line\":{},\"column\":{},\"kind\":\"{}\",\"source\":{},\"type\":{}}}",
+                    json_str(&file),
+                    loc.line,
+                    loc.col.0 + 1,
+                    kind,
+                    json_str(&src),
+                    json_str(&record.ty)
+                );
+            }
+        }
+    }
+
+    if format == TypeDumpFormat::Json {
+        buf.push_str("\n]\n");
+    }
+
+    buf
+}
+
+fn json_str(s: &str) -> String {
+    let mut buf = String::with_capacity(s.len() + 2);
+    buf.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => buf.push_str("\\\""),
+            '\\' => buf.push_str("\\\\"),
+            '\n' => buf.push_str("\\n"),
+            '\r' => buf.push_str("\\r"),
+            '\t' => buf.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(buf, "\\u{:04x}", c as u32);
+            }
+            c => buf.push(c),
+        }
+    }
+    buf.push('"');
+    buf
 }
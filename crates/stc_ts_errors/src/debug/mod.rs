@@ -153,6 +153,45 @@ pub fn force_dump_type_as_string(t: &Type) -> String {
     s.to_string()
 }
 
+/// Prints only the type itself on a single line, even in release builds.
+pub fn dump_type_name(t: &Type) -> String {
+    let mut buf = vec![];
+    {
+        let mut emitter = Emitter {
+            cfg: swc_ecma_codegen::Config {
+                minify: false,
+                ..Default::default()
+            },
+            cm: Lrc::new(FakeSourceMap),
+            comments: None,
+            wr: box JsWriter::new(Lrc::new(SourceMap::default()), "\n", &mut buf, None),
+        };
+
+        let mut body = vec![ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: box Expr::TsAs(TsAsExpr {
+                span: DUMMY_SP,
+                expr: box Expr::Ident(Ident::new("TYPE".into(), DUMMY_SP)),
+                type_ann: box RTsType::from(ALLOW_DEEP_CLONE.set(&(), || t.clone().fold_with(&mut Visualizer::default()))).into_orig(),
+            }),
+        }))];
+
+        body.visit_mut_with(&mut DropSpan { preserve_ctxt: true });
+
+        emitter
+            .emit_module(&Module {
+                span: DUMMY_SP,
+                body,
+                shebang: None,
+            })
+            .unwrap();
+    }
+    let s = String::from_utf8_lossy(&buf);
+    let s = s.trim().trim_start_matches("TYPE as").trim_end_matches(';');
+
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
 pub fn print_type(name: &str, t: &Type) {
     let s = dump_type_as_string(t);
     info!("===== ===== ===== Type ({}) ===== ===== =====\n{}", name, s);
@@ -0,0 +1,77 @@
+use std::cell::Cell;
+
+use swc_common::{Span, Spanned};
+
+use crate::{Error, ErrorKind};
+
+/// Depth of the elaboration emitted by default, which is similar to tsc.
+pub const DEFAULT_ELABORATION_DEPTH: usize = 3;
+
+/// Selects errors which are emitted with the full elaboration tree.
+///
+/// Other errors are trimmed to [DEFAULT_ELABORATION_DEPTH].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExplainOpts {
+    /// Error code to explain, like `2322`.
+    pub code: Option<usize>,
+
+    /// Errors contained in this span are explained.
+    pub span: Option<Span>,
+}
+
+impl ExplainOpts {
+    /// Reads the error code to explain from the `STC_EXPLAIN` environment
+    /// variable, like `STC_EXPLAIN=TS2322`.
+    pub fn from_env() -> Self {
+        let code = std::env::var("STC_EXPLAIN")
+            .ok()
+            .and_then(|v| v.trim().trim_start_matches("TS").parse().ok());
+
+        ExplainOpts { code, span: None }
+    }
+
+    pub(crate) fn matches(&self, err: &Error) -> bool {
+        if let Some(code) = self.code {
+            if ErrorKind::normalize_error_code(code) == ErrorKind::normalize_error_code(err.code()) {
+                return true;
+            }
+        }
+
+        match self.span {
+            Some(span) => span.contains(err.span()),
+            None => false,
+        }
+    }
+}
+
+thread_local! {
+    /// Remaining depth of nested errors to print. [None] means unlimited.
+    static ELABORATION_DEPTH: Cell<Option<usize>> = Cell::new(None);
+}
+
+/// Invokes `op` while limiting the depth of errors printed by [Debug].
+pub(crate) fn with_elaboration_depth<R>(depth: Option<usize>, op: impl FnOnce() -> R) -> R {
+    let prev = ELABORATION_DEPTH.with(|v| v.replace(depth));
+    let ret = op();
+    ELABORATION_DEPTH.with(|v| v.set(prev));
+    ret
+}
+
+/// Enters a nested error.
+///
+/// Returns [None] if the error should not be printed, and the remaining depth
+/// otherwise.
+pub(crate) fn enter_nested_error() -> Option<Option<usize>> {
+    let depth = ELABORATION_DEPTH.with(|v| v.get());
+    match depth {
+        Some(0) => None,
+        _ => {
+            ELABORATION_DEPTH.with(|v| v.set(depth.map(|d| d - 1)));
+            Some(depth)
+        }
+    }
+}
+
+pub(crate) fn leave_nested_error(depth: Option<usize>) {
+    ELABORATION_DEPTH.with(|v| v.set(depth));
+}
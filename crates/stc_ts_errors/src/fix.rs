@@ -0,0 +1,122 @@
+//! Machine-readable fixes of errors, for code actions of editors.
+
+use swc_common::{BytePos, SourceMap, Span, Spanned};
+
+use crate::{Error, ErrorKind};
+
+/// Replaces `span` with `new_text`. An empty `span` inserts `new_text`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub span: Span,
+    pub new_text: String,
+}
+
+impl TextEdit {
+    fn insert(pos: BytePos, new_text: impl Into<String>) -> Self {
+        TextEdit {
+            span: Span::new(pos, pos, Default::default()),
+            new_text: new_text.into(),
+        }
+    }
+
+    fn replace(span: Span, new_text: impl Into<String>) -> Self {
+        TextEdit {
+            span,
+            new_text: new_text.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix {
+    /// Description of the fix, like `Add 'await'`.
+    pub title: String,
+    /// Edits which do not overlap.
+    pub edits: Vec<TextEdit>,
+}
+
+impl Error {
+    /// Returns fixes of this error. `cm` is used to read the source code.
+    pub fn fixes(&self, cm: &SourceMap) -> Vec<Fix> {
+        match &*self.inner {
+            ErrorKind::Errors { errors, .. } => errors.iter().flat_map(|err| err.fixes(cm)).collect(),
+
+            ErrorKind::NoSuchVarWithSuggestion { span, suggestion, .. } => vec![Fix {
+                title: format!("Change spelling to '{}'", suggestion),
+                edits: vec![TextEdit::replace(*span, &**suggestion)],
+            }],
+
+            ErrorKind::NoSuchPropertyWithSuggestion { prop, suggestion, .. } => vec![Fix {
+                title: format!("Change spelling to '{}'", suggestion),
+                edits: vec![TextEdit::replace(prop.span(), &**suggestion)],
+            }],
+
+            ErrorKind::NoSuchPropertyInPromise {
+                obj_span, can_await: true, ..
+            } => vec![Fix {
+                title: "Add 'await'".into(),
+                edits: vec![TextEdit::insert(obj_span.lo, "(await "), TextEdit::insert(obj_span.hi, ")")],
+            }],
+
+            ErrorKind::UnusedTsExpectError { span } => vec![Fix {
+                title: "Remove unused '@ts-expect-error' directive".into(),
+                edits: vec![TextEdit::replace(line_of_comment(cm, *span).unwrap_or(*span), "")],
+            }],
+
+            ErrorKind::MissingFields { span, fields } => {
+                let names = fields.iter().filter_map(|el| el.non_computed_key()).collect::<Vec<_>>();
+                if names.is_empty() {
+                    return vec![];
+                }
+
+                // Only object literals can be fixed.
+                let src = match cm.span_to_snippet(*span) {
+                    Ok(src) if src.starts_with('{') && src.ends_with('}') => src,
+                    _ => return vec![],
+                };
+                let body = src[1..src.len() - 1].trim_end();
+
+                let props = names
+                    .iter()
+                    .map(|name| format!("{}: undefined", name))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let new_text = if body.trim_start().is_empty() {
+                    format!(" {} ", props)
+                } else if body.ends_with(',') {
+                    format!(" {}", props)
+                } else {
+                    format!(", {}", props)
+                };
+
+                // Insert after the last property.
+                let pos = span.lo + BytePos(1 + body.len() as u32);
+
+                vec![Fix {
+                    title: "Add missing properties".into(),
+                    edits: vec![TextEdit::insert(pos, new_text)],
+                }]
+            }
+
+            _ => vec![],
+        }
+    }
+}
+
+/// Returns the span of the whole line if the comment at `span` is the only
+/// thing in the line.
+fn line_of_comment(cm: &SourceMap, span: Span) -> Option<Span> {
+    let loc = cm.lookup_char_pos(span.lo);
+    let lo = *loc.file.lines.get(loc.line - 1)?;
+    let hi = loc.file.lines.get(loc.line).copied().unwrap_or(loc.file.end_pos);
+
+    let line = Span::new(lo, hi, Default::default());
+    let src = cm.span_to_snippet(line).ok()?;
+    let comment = cm.span_to_snippet(span).ok()?;
+
+    if src.trim() == comment.trim() {
+        Some(line)
+    } else {
+        None
+    }
+}
@@ -24,12 +24,22 @@ use swc_common::{
 };
 use swc_ecma_ast::{AssignOp, BinaryOp, UpdateOp};
 
-pub use self::result_ext::DebugExt;
+pub use self::{
+    explain::{ExplainOpts, DEFAULT_ELABORATION_DEPTH},
+    fix::{Fix, TextEdit},
+    message::MessageCatalog,
+    result_ext::DebugExt,
+};
 #[cfg(debug_assertions)]
 use crate::context::with_ctx;
+use crate::explain::{enter_nested_error, leave_nested_error, with_elaboration_depth};
 
 pub mod context;
 pub mod debug;
+mod explain;
+mod fix;
+mod message;
+pub mod pretty;
 mod result_ext;
 #[cfg(debug_assertions)]
 type Contexts = Vec<String>;
@@ -43,6 +53,8 @@ pub struct Error {
     contexts: Contexts,
     #[span]
     inner: Box<ErrorKind>,
+    /// `true` if this error is downgraded to a warning.
+    is_warning: bool,
 }
 
 impl std::ops::Deref for Error {
@@ -61,6 +73,7 @@ impl From<ErrorKind> for Error {
             #[cfg(not(debug_assertions))]
             contexts: (),
             inner: Box::new(kind),
+            is_warning: false,
         }
     }
 }
@@ -72,30 +85,108 @@ impl Error {
         self
     }
 
+    /// Downgrades this error to a warning.
+    pub fn into_warning(mut self) -> Error {
+        self.is_warning = true;
+        self
+    }
+
+    pub fn is_warning(&self) -> bool {
+        self.is_warning
+    }
+
+    /// Emits this error, with the elaboration selected by `STC_EXPLAIN`.
+    ///
+    /// The elaboration is used as the message. Use [Error::emit_with_catalog]
+    /// to emit the message of a [MessageCatalog].
     #[cold]
     pub fn emit(&self, h: &Handler) {
+        self.emit_with_opts(h, &ExplainOpts::from_env(), None)
+    }
+
+    /// Emits this error with the message of `messages`, followed by the
+    /// elaboration selected by `STC_EXPLAIN`.
+    #[cold]
+    pub fn emit_with_catalog(&self, h: &Handler, messages: &MessageCatalog) {
+        self.emit_with_opts(h, &ExplainOpts::from_env(), Some(messages))
+    }
+
+    /// Emits this error. The elaboration is trimmed unless `opts` selects this
+    /// error.
+    ///
+    /// If `messages` has a message for this error, the message is emitted and
+    /// the elaboration is attached as a note. Otherwise the elaboration is used
+    /// as the message.
+    pub fn emit_with_opts(&self, h: &Handler, opts: &ExplainOpts, messages: Option<&MessageCatalog>) {
         let span = self.span();
+        let elaboration = self.elaboration(opts);
+        let (msg, note) = match messages.and_then(|messages| self.message(messages)) {
+            Some(msg) => (msg, Some(elaboration)),
+            None => (elaboration, None),
+        };
+        let code = DiagnosticId::Error(format!("TS{}", ErrorKind::normalize_error_code(self.code())));
+
+        let mut err = if self.is_warning {
+            h.struct_span_warn_with_code(span, &msg, code)
+        } else {
+            h.struct_span_err_with_code(span, &msg, code)
+        };
+
+        for (span, label) in self.labels() {
+            err.span_label(span, label);
+        }
+
+        if let Some((related, note)) = self.related_span() {
+            err.span_note(related, note);
+        }
 
-        let mut err = h.struct_span_err_with_code(
-            span,
-            &format!("{:#?}", self),
-            DiagnosticId::Error(format!("TS{}", ErrorKind::normalize_error_code(self.code()))),
-        );
+        if let Some(note) = note {
+            err.note(&note);
+        }
 
         err.emit();
     }
+
+    /// Returns the message of this error, which is trimmed unless `opts`
+    /// selects this error.
+    pub fn elaboration(&self, opts: &ExplainOpts) -> String {
+        let depth = if opts.matches(self) {
+            None
+        } else {
+            Some(DEFAULT_ELABORATION_DEPTH)
+        };
+        with_elaboration_depth(depth, || format!("{:#?}", self))
+    }
 }
 
 impl Debug for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let depth = match enter_nested_error() {
+            Some(v) => v,
+            None => return write!(f, "..."),
+        };
+
+        let res = self.fmt_trimmed(f, depth.is_some());
+        leave_nested_error(depth);
+        res
+    }
+}
+
+impl Error {
+    #[cfg_attr(not(debug_assertions), allow(unused_variables))]
+    fn fmt_trimmed(&self, f: &mut Formatter<'_>, trim: bool) -> fmt::Result {
         #[cfg(debug_assertions)]
-        for ctx in self.contexts.iter().rev() {
-            writeln!(f, "{}: {}", Yellow.paint("context"), ctx)?;
+        {
+            let max = if trim { DEFAULT_ELABORATION_DEPTH } else { usize::MAX };
+            for ctx in self.contexts.iter().rev().take(max) {
+                writeln!(f, "{}: {}", Yellow.paint("context"), ctx)?;
+            }
+            if self.contexts.len() > max {
+                writeln!(f, "{}: {} more", Yellow.paint("context"), self.contexts.len() - max)?;
+            }
         }
 
-        Debug::fmt(&self.inner, f)?;
-
-        Ok(())
+        Debug::fmt(&self.inner, f)
     }
 }
 
@@ -152,6 +243,59 @@ pub enum ErrorKind {
         cause: Box<Error>,
     },
 
+    /// TS2578
+    UnusedTsExpectError {
+        span: Span,
+    },
+
+    /// TS2717
+    SubsequentPropDeclNotCompatible {
+        span: Span,
+        prev: Box<Type>,
+        ty: Box<Type>,
+    },
+
+    /// TS1360
+    ExprDoesNotSatisfyType {
+        span: Span,
+        cause: Box<Error>,
+    },
+
+    /// TS1206
+    DecoratorNotValidHere {
+        span: Span,
+    },
+
+    /// TS1238
+    InvalidClassDecorator {
+        span: Span,
+        cause: Box<Error>,
+    },
+
+    /// TS1239
+    InvalidParamDecorator {
+        span: Span,
+        cause: Box<Error>,
+    },
+
+    /// TS1240
+    InvalidPropertyDecorator {
+        span: Span,
+        cause: Box<Error>,
+    },
+
+    /// TS1241
+    InvalidMethodDecorator {
+        span: Span,
+        cause: Box<Error>,
+    },
+
+    /// TS1270
+    InvalidDecoratorReturnType {
+        span: Span,
+        cause: Box<Error>,
+    },
+
     /// TS2795
     IntrinsicIsBuiltinOnly {
         span: Span,
@@ -276,6 +420,63 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS1343
+    InvalidModuleForImportMeta {
+        span: Span,
+    },
+
+    /// TS1375
+    TopLevelAwaitInScript {
+        span: Span,
+    },
+
+    /// TS1378
+    InvalidModuleForTopLevelAwait {
+        span: Span,
+    },
+
+    /// TS1431
+    TopLevelForAwaitInScript {
+        span: Span,
+    },
+
+    /// TS1432
+    InvalidModuleForTopLevelForAwait {
+        span: Span,
+    },
+
+    /// TS17004
+    JsxNotEnabled {
+        span: Span,
+    },
+
+    /// TS2604
+    InvalidJsxElementType {
+        span: Span,
+    },
+
+    /// TS7026
+    ImplicitAnyJsxElement {
+        span: Span,
+    },
+
+    /// TS17016
+    JsxFragmentFactoryRequired {
+        span: Span,
+    },
+
+    /// TS2879
+    JsxFragmentFactoryNotFound {
+        span: Span,
+        name: JsWord,
+    },
+
+    /// TS2875
+    JsxRuntimeNotFound {
+        span: Span,
+        src: JsWord,
+    },
+
     /// TS2767
     ReturnPropertyOfIteratorMustBeMethod {
         span: Span,
@@ -286,6 +487,12 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS2766
+    CannotDelegateIterationToValue {
+        span: Span,
+        cause: Box<Error>,
+    },
+
     /// TS2631
     CannotAssignToNamespace {
         span: Span,
@@ -371,11 +578,32 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS2307
+    ///
+    /// `src` matches `pattern` of `paths` in `tsconfig.json`, but none of the
+    /// mapped paths could be resolved.
+    UnresolvedPathMapping {
+        span: Span,
+        src: JsWord,
+        pattern: JsWord,
+    },
+
+    /// TS2726
+    NoSuchLib {
+        span: Span,
+        name: JsWord,
+    },
+
     /// TS5061
     TooManyAsterisk {
         span: Span,
     },
 
+    /// TS2590
+    UnionTooComplex {
+        span: Span,
+    },
+
     /// TS2451
     DuplicateVar {
         name: Id,
@@ -452,6 +680,31 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS4112
+    OverrideWithoutSuperClass {
+        span: Span,
+    },
+
+    /// TS4113
+    OverrideOfNonExistentMember {
+        span: Span,
+    },
+
+    /// TS4114
+    MissingOverrideModifier {
+        span: Span,
+    },
+
+    /// TS4115
+    MissingOverrideModifierOnParamProp {
+        span: Span,
+    },
+
+    /// TS4116
+    MissingOverrideModifierOnAbstractMember {
+        span: Span,
+    },
+
     /// TS18011
     CannotDeletePrivateProperty {
         span: Span,
@@ -590,6 +843,24 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// Reported once instead of errors beyond the error limit of the checker.
+    TooManyErrors {
+        span: Span,
+        limit: usize,
+        /// Number of errors which are not reported.
+        omitted: usize,
+    },
+
+    /// TS2589
+    InstantiationExcessivelyDeep {
+        span: Span,
+    },
+
+    /// TS2559
+    NoCommonPropertyWithWeakType {
+        span: Span,
+    },
+
     /// TS2420
     InvalidImplOfInterface {
         span: Span,
@@ -635,6 +906,29 @@ pub enum ErrorKind {
         name: Id,
     },
 
+    /// TS2552
+    NoSuchVarWithSuggestion {
+        span: Span,
+        name: Id,
+        suggestion: JsWord,
+    },
+
+    /// TS2551
+    NoSuchPropertyWithSuggestion {
+        span: Span,
+        prop: Box<Key>,
+        suggestion: JsWord,
+    },
+
+    /// TS2570
+    NoSuchPropertyInPromise {
+        span: Span,
+        obj_span: Span,
+        prop: Box<Key>,
+        /// `true` if `await` can be used at the position.
+        can_await: bool,
+    },
+
     DestructuringAssignInAmbientContext {
         span: Span,
     },
@@ -724,6 +1018,22 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS2748
+    AmbientConstEnumWithIsolatedModules {
+        span: Span,
+    },
+
+    /// TS1205
+    ReExportTypeWithoutTypeModifierInIsolatedModules {
+        span: Span,
+    },
+
+    /// TS1361
+    TypeOnlyImportUsedAsValue {
+        span: Span,
+        name: Id,
+    },
+
     ComputedMemberInEnumWithStrMember {
         span: Span,
     },
@@ -744,6 +1054,8 @@ pub enum ErrorKind {
         /// Span of argument.
         span: Span,
         inner: Box<Error>,
+        /// Span of the parameter.
+        param_span: Span,
     },
 
     ImportFailed {
@@ -760,6 +1072,12 @@ pub enum ErrorKind {
     ExportAllFailed {
         span: Span,
     },
+
+    /// TS2308
+    AmbiguousStarExport {
+        span: Span,
+        name: JsWord,
+    },
     NoSuchPropertyInThis {
         span: Span,
     },
@@ -776,6 +1094,21 @@ pub enum ErrorKind {
         actual: usize,
     },
 
+    /// TS2314
+    TypeArgsRequired {
+        span: Span,
+        name: Id,
+        count: usize,
+    },
+
+    /// TS2707
+    TypeArgCountNotInRange {
+        span: Span,
+        name: Id,
+        min: usize,
+        max: usize,
+    },
+
     ParameterCountMismatch {
         span: Span,
         min: usize,
@@ -794,6 +1127,12 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS2366
+    LacksEndingReturn {
+        /// Span of the return type.
+        span: Span,
+    },
+
     ConstructorRequired {
         span: Span,
         lhs: Span,
@@ -924,6 +1263,15 @@ pub enum ErrorKind {
         name: Id,
     },
 
+    /// TS2583
+    ///
+    /// `name` is declared by `lib`, which is not loaded.
+    NoSuchVarInLibs {
+        span: Span,
+        name: Id,
+        lib: &'static str,
+    },
+
     /// TS2693
     TypeUsedAsVar {
         span: Span,
@@ -1005,6 +1353,12 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS2380
+    GetterTypeNotAssignableToSetterType {
+        span: Span,
+        cause: Box<Error>,
+    },
+
     /// TS2476
     ConstEnumNonIndexAccess {
         span: Span,
@@ -1034,6 +1388,12 @@ pub enum ErrorKind {
         msg: String,
     },
 
+    /// The checker panicked while checking the statement at `span`.
+    InternalError {
+        span: Span,
+        msg: String,
+    },
+
     ResolvedFailed {
         span: Span,
         base: Box<PathBuf>,
@@ -1163,6 +1523,11 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS1061
+    EnumMemberMustHaveInit {
+        span: Span,
+    },
+
     TS1016 {
         span: Span,
     },
@@ -1208,6 +1573,11 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// TS1259
+    DefaultImportOfExportEqualsWithoutInterop {
+        span: Span,
+    },
+
     AnyTypeUsedAsCalleeWithTypeArgs {
         span: Span,
     },
@@ -1290,6 +1660,8 @@ pub enum ErrorKind {
     ClassDoesNotImplementMemeber {
         span: Span,
         key: Box<Key>,
+        /// The declaration of the abstract member.
+        declared_at: Span,
     },
 
     TS2531 {
@@ -1329,6 +1701,34 @@ pub enum ErrorKind {
         span: Span,
     },
 
+    /// `TS2736`
+    UnaryPlusOnBigInt {
+        span: Span,
+    },
+
+    /// `TS2737`
+    BigIntLiteralNotAvailable {
+        span: Span,
+    },
+
+    /// `TS18028`
+    PrivateNameNotAvailable {
+        span: Span,
+    },
+
+    /// `TS1056`
+    AccessorNotAvailable {
+        span: Span,
+    },
+
+    /// `TS2802`
+    ///
+    /// A type which is not an array nor a string is iterated while targeting
+    /// ES5 or lower without `downlevelIteration`.
+    IterationNotAvailable {
+        span: Span,
+    },
+
     /// TS2356
     InvalidNumericOperand {
         span: Span,
@@ -1602,15 +2002,38 @@ impl ErrorKind {
                 vec![Error {
                     contexts: Default::default(),
                     inner: box self,
+                    is_warning: false,
                 }]
             }
         }
     }
 
+    /// A span related to this error, with a note describing it.
+    pub fn related_span(&self) -> Option<(Span, &'static str)> {
+        match self {
+            ErrorKind::ClassDoesNotImplementMemeber { declared_at, .. } => Some((*declared_at, "the abstract member is declared here")),
+            _ => None,
+        }
+    }
+
+    /// Secondary spans of this error, with labels describing them.
+    ///
+    /// Unlike [ErrorKind::related_span], these are underlined along with the
+    /// primary span.
+    pub fn labels(&self) -> Vec<(Span, &'static str)> {
+        match self {
+            ErrorKind::WrongArgType { param_span, .. } if !param_span.is_dummy() => vec![(*param_span, "parameter is declared here")],
+            _ => vec![],
+        }
+    }
+
     /// TypeScript error code.
+    ///
+    /// Errors which do not have an equivalent in tsc use `0`.
     pub fn code(&self) -> usize {
         match self {
             ErrorKind::TS1016 { .. } => 1016,
+            ErrorKind::EnumMemberMustHaveInit { .. } => 1061,
             ErrorKind::TS1063 { .. } => 1063,
             ErrorKind::TS1094 { .. } => 1094,
             ErrorKind::TS1095 { .. } => 1095,
@@ -1620,7 +2043,10 @@ impl ErrorKind {
             ErrorKind::TS1318 { .. } => 1318,
             ErrorKind::TS1319 { .. } => 1319,
             ErrorKind::ExportEqualsMixedWithOtherExports { .. } => 2309,
+            ErrorKind::DefaultImportOfExportEqualsWithoutInterop { .. } => 1259,
             ErrorKind::AnyTypeUsedAsCalleeWithTypeArgs { .. } => 2347,
+            ErrorKind::InstantiationExcessivelyDeep { .. } => 2589,
+            ErrorKind::NoCommonPropertyWithWeakType { .. } => 2559,
             ErrorKind::InvalidLhsOfInOperator { .. } => 2360,
             ErrorKind::InvalidRhsForInOperator { .. } => 2638,
             ErrorKind::WrongTypeForLhsOfNumericOperation { .. } => 2362,
@@ -1652,7 +2078,15 @@ impl ErrorKind {
             | ErrorKind::InvalidAssignmentOfArray { .. }
             | ErrorKind::UnknownPropertyInObjectLiteralAssignment { .. }
             | ErrorKind::InvalidOpAssign { .. }
-            | ErrorKind::TupleAssignError { .. } => 2322,
+            | ErrorKind::TupleAssignError { .. }
+            | ErrorKind::AssignFailedDueToOptionalityDifference { .. }
+            | ErrorKind::PrivatePropertyIsDifferent { .. }
+            | ErrorKind::PrivateMethodIsDifferent { .. }
+            | ErrorKind::CannotAssingToThis { .. }
+            | ErrorKind::ConstructorRequired { .. }
+            | ErrorKind::TooManyTupleElements { .. }
+            | ErrorKind::NotTuple { .. }
+            | ErrorKind::UnionError { .. } => 2322,
 
             ErrorKind::NonOverlappingTypeCast { .. } => 2352,
 
@@ -1679,6 +2113,8 @@ impl ErrorKind {
             ErrorKind::SpreadMustBeTupleOrPassedToRest { .. } => 2556,
 
             ErrorKind::TypeParameterCountMismatch { .. } => 2558,
+            ErrorKind::TypeArgsRequired { .. } => 2314,
+            ErrorKind::TypeArgCountNotInRange { .. } => 2707,
 
             ErrorKind::ReferencedInInit { .. } => 2372,
 
@@ -1690,6 +2126,10 @@ impl ErrorKind {
             ErrorKind::NoSuchType { .. } => 2304,
             ErrorKind::NoSuchTypeButVarExists { .. } => 2749,
             ErrorKind::NoSuchVarButThisHasSuchProperty { .. } => 2663,
+            ErrorKind::NoSuchVarWithSuggestion { .. } => 2552,
+            ErrorKind::NoSuchVarInLibs { .. } => 2583,
+            ErrorKind::NoSuchPropertyWithSuggestion { .. } => 2551,
+            ErrorKind::NoSuchPropertyInPromise { .. } => 2570,
 
             ErrorKind::CannotAssignAbstractConstructorToNonAbstractConstructor { .. } => 2322,
             ErrorKind::CannotCreateInstanceOfAbstractClass { .. } => 2511,
@@ -1703,11 +2143,16 @@ impl ErrorKind {
             ErrorKind::InvalidLValue { .. } => 2540,
 
             ErrorKind::TS2378 { .. } => 2378,
+            ErrorKind::GetterTypeNotAssignableToSetterType { .. } => 2380,
 
             ErrorKind::ConstEnumNonIndexAccess { .. } => 2476,
 
             ErrorKind::InvalidUseOfConstEnum { .. } => 2475,
 
+            ErrorKind::AmbientConstEnumWithIsolatedModules { .. } => 2748,
+            ErrorKind::ReExportTypeWithoutTypeModifierInIsolatedModules { .. } => 1205,
+            ErrorKind::TypeOnlyImportUsedAsValue { .. } => 1361,
+
             ErrorKind::ObjectIsPossiblyNull { .. } => 2531,
             ErrorKind::ObjectIsPossiblyUndefined { .. } | ErrorKind::ObjectIsPossiblyUndefinedWithType { .. } => 2532,
             ErrorKind::ObjectIsPossiblyNullOrUndefined { .. } => 2533,
@@ -1741,6 +2186,16 @@ impl ErrorKind {
 
             ErrorKind::NumericOpToSymbol { .. } => 2469,
 
+            ErrorKind::UnaryPlusOnBigInt { .. } => 2736,
+
+            ErrorKind::BigIntLiteralNotAvailable { .. } => 2737,
+
+            ErrorKind::PrivateNameNotAvailable { .. } => 18028,
+
+            ErrorKind::AccessorNotAvailable { .. } => 1056,
+
+            ErrorKind::IterationNotAvailable { .. } => 2802,
+
             ErrorKind::InvalidNumericOperand { .. } => 2356,
 
             ErrorKind::UpdateOpToSymbol { .. } => 2469,
@@ -1777,6 +2232,8 @@ impl ErrorKind {
 
             ErrorKind::ReturnRequired { .. } => 2355,
 
+            ErrorKind::LacksEndingReturn { .. } => 2366,
+
             ErrorKind::ThisRefToModuleOrNamespace { .. } => 2331,
 
             ErrorKind::CannotReferenceThisInComputedPropName { .. } => 2465,
@@ -1851,8 +2308,22 @@ impl ErrorKind {
 
             ErrorKind::UndefinedOrNullIsNotValidOperand { .. } => 18050,
 
+            ErrorKind::OverrideWithoutSuperClass { .. } => 4112,
+            ErrorKind::OverrideOfNonExistentMember { .. } => 4113,
+            ErrorKind::MissingOverrideModifier { .. } => 4114,
+            ErrorKind::MissingOverrideModifierOnParamProp { .. } => 4115,
+            ErrorKind::MissingOverrideModifierOnAbstractMember { .. } => 4116,
+
             ErrorKind::CannotDeletePrivateProperty { .. } => 18011,
 
+            ErrorKind::DecoratorNotValidHere { .. } => 1206,
+
+            ErrorKind::InvalidClassDecorator { .. } => 1238,
+            ErrorKind::InvalidParamDecorator { .. } => 1239,
+            ErrorKind::InvalidPropertyDecorator { .. } => 1240,
+            ErrorKind::InvalidMethodDecorator { .. } => 1241,
+            ErrorKind::InvalidDecoratorReturnType { .. } => 1270,
+
             ErrorKind::CannotAccessPrivatePropertyFromOutside { .. } => 18013,
 
             ErrorKind::OptionalChainCannotContainPrivateIdentifier { .. } => 18030,
@@ -1892,7 +2363,13 @@ impl ErrorKind {
 
             ErrorKind::TooManyAsterisk { .. } => 5061,
 
-            ErrorKind::ModuleNotFound { .. } => 2307,
+            ErrorKind::UnionTooComplex { .. } => 2590,
+
+            ErrorKind::ModuleNotFound { .. } | ErrorKind::UnresolvedPathMapping { .. } => 2307,
+
+            ErrorKind::AmbiguousStarExport { .. } => 2308,
+
+            ErrorKind::NoSuchLib { .. } => 2726,
 
             ErrorKind::DuplicateConstructor { .. } => 2392,
 
@@ -1917,8 +2394,32 @@ impl ErrorKind {
 
             ErrorKind::NextOfItertorShouldReturnTypeWithPropertyValue { .. } => 2490,
 
+            ErrorKind::CannotDelegateIterationToValue { .. } => 2766,
+
             ErrorKind::InvalidUsageOfNewTarget { .. } => 17013,
 
+            ErrorKind::InvalidModuleForImportMeta { .. } => 1343,
+
+            ErrorKind::TopLevelAwaitInScript { .. } => 1375,
+
+            ErrorKind::InvalidModuleForTopLevelAwait { .. } => 1378,
+
+            ErrorKind::TopLevelForAwaitInScript { .. } => 1431,
+
+            ErrorKind::InvalidModuleForTopLevelForAwait { .. } => 1432,
+
+            ErrorKind::JsxNotEnabled { .. } => 17004,
+
+            ErrorKind::InvalidJsxElementType { .. } => 2604,
+
+            ErrorKind::ImplicitAnyJsxElement { .. } => 7026,
+
+            ErrorKind::JsxFragmentFactoryRequired { .. } => 17016,
+
+            ErrorKind::JsxFragmentFactoryNotFound { .. } => 2879,
+
+            ErrorKind::JsxRuntimeNotFound { .. } => 2875,
+
             ErrorKind::AssignFailedBecauseTupleLengthDiffers { .. } => 2322,
 
             ErrorKind::ClassMemberNotCompatibleWithStringIndexSignature { .. } => 2411,
@@ -1987,13 +2488,50 @@ impl ErrorKind {
 
             ErrorKind::VarDeclNotCompatible { .. } => 2403,
 
+            ErrorKind::SubsequentPropDeclNotCompatible { .. } => 2717,
+
+            ErrorKind::UnusedTsExpectError { .. } => 2578,
+
+            ErrorKind::ExprDoesNotSatisfyType { .. } => 1360,
+
             ErrorKind::InvalidInterfaceInheritance { .. } => 2430,
 
             ErrorKind::TargetLacksConstructSignature { .. } => 7009,
 
             ErrorKind::NotExtendableType { .. } => 2312,
 
-            _ => 0,
+            ErrorKind::UndefinedSymbol { .. } | ErrorKind::ExportFailed { .. } => 2304,
+
+            ErrorKind::ImportFailed { .. } | ErrorKind::NoSuchExport { .. } => 2305,
+
+            ErrorKind::ResolvedFailed { .. } | ErrorKind::ModuleLoadFailed { .. } | ErrorKind::ExportAllFailed { .. } => 2307,
+
+            ErrorKind::NoSuchConstructor { .. } => 2351,
+
+            ErrorKind::InvalidOperatorForLhs { .. } => 2364,
+
+            ErrorKind::RedeclaredVarWithDifferentType { .. } => 2403,
+
+            ErrorKind::InvalidEnumInit { .. } => 2474,
+
+            ErrorKind::MayBeUndefined { .. } => 2532,
+
+            ErrorKind::ParameterCountMismatch { .. } | ErrorKind::WrongParams { .. } => 2554,
+
+            ErrorKind::WrongTypeParams { .. } => 2558,
+
+            ErrorKind::ClassPropertyInitRequired { .. } => 2564,
+
+            ErrorKind::IntersectionError { error, .. } => error.code(),
+
+            ErrorKind::Errors { errors, .. } => errors.first().map_or(0, |err| err.code()),
+
+            // Errors of the checker itself, which do not exist in tsc.
+            ErrorKind::StackOverflow { .. }
+            | ErrorKind::Unimplemented { .. }
+            | ErrorKind::InternalError { .. }
+            | ErrorKind::DestructuringAssignInAmbientContext { .. }
+            | ErrorKind::TooManyErrors { .. } => 0,
         }
     }
 
@@ -2004,13 +2542,19 @@ impl ErrorKind {
                 | ErrorKind::NoSuchPropertyInClass { .. }
                 | ErrorKind::NoSuchPropertyInModule { .. }
                 | ErrorKind::NoSuchPropertyInThis { .. }
+                | ErrorKind::NoSuchPropertyWithSuggestion { .. }
+                | ErrorKind::NoSuchPropertyInPromise { .. }
         )
     }
 
     pub fn is_var_not_found(&self) -> bool {
         matches!(
             self,
-            Self::NoSuchVar { .. } | Self::NoSuchVarButThisHasSuchProperty { .. } | Self::NoSuchVarForShorthand { .. }
+            Self::NoSuchVar { .. }
+                | Self::NoSuchVarButThisHasSuchProperty { .. }
+                | Self::NoSuchVarForShorthand { .. }
+                | Self::NoSuchVarWithSuggestion { .. }
+                | Self::NoSuchVarInLibs { .. }
         )
     }
 
@@ -2115,6 +2659,21 @@ impl Errors {
     pub fn append_errors(&mut self, other: &mut Self) {
         self.append(&mut other.0)
     }
+
+    #[inline]
+    pub fn iter(&self) -> std::slice::Iter<Error> {
+        self.0.iter()
+    }
+
+    /// Removes errors matching `pred` and returns them.
+    pub fn take_if<F>(&mut self, pred: F) -> Vec<Error>
+    where
+        F: FnMut(&Error) -> bool,
+    {
+        let (taken, rest) = std::mem::take(&mut self.0).into_iter().partition(pred);
+        self.0 = rest;
+        taken
+    }
 }
 
 impl Extend<Error> for Errors {
@@ -0,0 +1,232 @@
+//! Human-readable messages of errors, keyed by error code.
+
+use std::borrow::Cow;
+
+use fxhash::FxHashMap;
+use stc_ts_types::Key;
+
+use crate::{Error, ErrorKind};
+
+/// Templates of error messages, keyed by error code.
+///
+/// `{0}`, `{1}`, ... in a template are replaced with the arguments of the
+/// error, which are listed in [ErrorKind::message_args]. Embedders can
+/// override or localize the templates using [MessageCatalog::set].
+#[derive(Debug, Clone)]
+pub struct MessageCatalog {
+    templates: FxHashMap<usize, Cow<'static, str>>,
+}
+
+/// Same as the wording of tsc, except for the types which can't be printed and
+/// the number of arguments, which is not stored in errors.
+const DEFAULT_TEMPLATES: &[(usize, &str)] = &[
+    (
+        1343,
+        "The 'import.meta' meta-property is only allowed when the '--module' option is 'es2020', 'es2022', 'esnext', 'system', 'node16', \
+         or 'nodenext'.",
+    ),
+    (
+        1375,
+        "'await' expressions are only allowed at the top level of a file when that file is a module, but this file has no imports or \
+         exports. Consider adding an empty 'export {}' to make this file a module.",
+    ),
+    (
+        1378,
+        "Top-level 'await' expressions are only allowed when the 'module' option is set to 'es2022', 'esnext', 'system', 'node16', or \
+         'nodenext', and the 'target' option is set to 'es2017' or higher.",
+    ),
+    (
+        1431,
+        "'for await' loops are only allowed at the top level of a file when that file is a module, but this file has no imports or \
+         exports. Consider adding an empty 'export {}' to make this file a module.",
+    ),
+    (
+        1432,
+        "Top-level 'for await' loops are only allowed when the 'module' option is set to 'es2022', 'esnext', 'system', 'node16', or \
+         'nodenext', and the 'target' option is set to 'es2017' or higher.",
+    ),
+    (2300, "Duplicate identifier '{0}'."),
+    (2304, "Cannot find name '{0}'."),
+    (2307, "Cannot find module '{0}' or its corresponding type declarations."),
+    (2314, "Generic type '{0}' requires {1} type argument(s)."),
+    (2322, "Type is not assignable to the target type."),
+    (2339, "Property '{0}' does not exist on the type."),
+    (2344, "Type does not satisfy the constraint."),
+    (2345, "Argument is not assignable to the parameter."),
+    (2349, "This expression is not callable."),
+    (2531, "Object is possibly 'null'."),
+    (2532, "Object is possibly 'undefined'."),
+    (2533, "Object is possibly 'null' or 'undefined'."),
+    (2551, "Property '{0}' does not exist on the type. Did you mean '{1}'?"),
+    (2552, "Cannot find name '{0}'. Did you mean '{1}'?"),
+    (2554, "Expected {0} arguments."),
+    (2555, "Expected at least {0} arguments."),
+    (2558, "Expected {0} type arguments, but got {1}."),
+    (2570, "Property '{0}' does not exist on a promise. Did you forget to use 'await'?"),
+    (2578, "Unused '@ts-expect-error' directive."),
+    (2590, "Expression produces a union type that is too complex to represent."),
+    (2604, "JSX element type does not have any construct or call signatures."),
+    (
+        2583,
+        "Cannot find name '{0}'. Do you need to change your target library? Try changing the 'lib' compiler option to '{1}' or later.",
+    ),
+    (2663, "Cannot find name '{0}'. Did you mean the instance member 'this.{0}'?"),
+    (2693, "'{0}' only refers to a type, but is being used as a value here."),
+    (2707, "Generic type '{0}' requires between {1} and {2} type arguments."),
+    (2769, "No overload matches this call."),
+    (
+        2749,
+        "'{0}' refers to a value, but is being used as a type here. Did you mean 'typeof {0}'?",
+    ),
+    (
+        7026,
+        "JSX element implicitly has type 'any' because no interface 'JSX.IntrinsicElements' exists.",
+    ),
+    (17004, "Cannot use JSX unless the '--jsx' flag is provided."),
+    (
+        17016,
+        "The 'jsxFragmentFactory' compiler option must be provided to use JSX fragments with the 'jsxFactory' compiler option.",
+    ),
+    (
+        2875,
+        "This JSX tag requires the module path '{0}' to exist, but none could be found. Make sure you have types for the appropriate \
+         package installed.",
+    ),
+    (
+        2879,
+        "Using JSX fragments requires fragment factory '{0}' to be in scope, but it could not be found.",
+    ),
+];
+
+impl Default for MessageCatalog {
+    fn default() -> Self {
+        MessageCatalog {
+            templates: DEFAULT_TEMPLATES
+                .iter()
+                .map(|&(code, template)| (code, Cow::Borrowed(template)))
+                .collect(),
+        }
+    }
+}
+
+impl MessageCatalog {
+    /// A catalog without any template.
+    pub fn empty() -> Self {
+        MessageCatalog {
+            templates: Default::default(),
+        }
+    }
+
+    /// `code` is the numeric part of a code like `TS2345`.
+    pub fn set(&mut self, code: usize, template: impl Into<Cow<'static, str>>) {
+        self.templates.insert(code, template.into());
+    }
+
+    /// Falls back to the template of the normalized code.
+    pub fn get(&self, code: usize) -> Option<&str> {
+        self.templates
+            .get(&code)
+            .or_else(|| self.templates.get(&ErrorKind::normalize_error_code(code)))
+            .map(|s| &**s)
+    }
+
+    /// Returns [None] if there's no template for the error, or if the
+    /// template refers to an argument the error does not have.
+    pub fn format(&self, err: &ErrorKind) -> Option<String> {
+        let template = self.get(err.code())?;
+        interpolate(template, &err.message_args())
+    }
+}
+
+fn interpolate(template: &str, args: &[String]) -> Option<String> {
+    let mut buf = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        buf.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let end = match rest.find('}') {
+            Some(end) => end,
+            None => break,
+        };
+        match rest[1..end].parse::<usize>() {
+            Ok(idx) => buf.push_str(args.get(idx)?),
+            Err(_) => buf.push_str(&rest[..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+
+    buf.push_str(rest);
+    Some(buf)
+}
+
+fn key_to_string(key: &Key) -> Option<String> {
+    match key {
+        Key::Normal { sym, .. } => Some(sym.to_string()),
+        Key::Num(n) => Some(n.value.to_string()),
+        _ => None,
+    }
+}
+
+impl ErrorKind {
+    /// Arguments used to format the message of this error.
+    ///
+    /// Types are not included, as they can't be printed in release builds.
+    pub fn message_args(&self) -> Vec<String> {
+        match self {
+            ErrorKind::DuplicateName { name, .. }
+            | ErrorKind::NoSuchVar { name, .. }
+            | ErrorKind::NoSuchType { name, .. }
+            | ErrorKind::UndefinedSymbol { sym: name, .. }
+            | ErrorKind::NoSuchVarButThisHasSuchProperty { name, .. }
+            | ErrorKind::TypeUsedAsVar { name, .. }
+            | ErrorKind::NoSuchTypeButVarExists { name, .. } => vec![name.sym().to_string()],
+
+            ErrorKind::UnresolvedPathMapping { src, .. }
+            | ErrorKind::ResolvedFailed { src, .. }
+            | ErrorKind::JsxRuntimeNotFound { src, .. } => vec![src.to_string()],
+
+            ErrorKind::JsxFragmentFactoryNotFound { name, .. } => vec![name.to_string()],
+
+            ErrorKind::NoSuchVarInLibs { name, lib, .. } => vec![name.sym().to_string(), lib.to_string()],
+
+            ErrorKind::NoSuchVarWithSuggestion { name, suggestion, .. } => vec![name.sym().to_string(), suggestion.to_string()],
+
+            ErrorKind::NoSuchProperty { prop: Some(prop), .. } | ErrorKind::NoSuchPropertyInPromise { prop, .. } => {
+                key_to_string(prop).into_iter().collect()
+            }
+
+            ErrorKind::NoSuchPropertyWithSuggestion { prop, suggestion, .. } => match key_to_string(prop) {
+                Some(prop) => vec![prop, suggestion.to_string()],
+                None => vec![],
+            },
+
+            ErrorKind::TypeParameterCountMismatch { min, max, actual, .. } => {
+                let expected = if min == max { min.to_string() } else { format!("{}-{}", min, max) };
+                vec![expected, actual.to_string()]
+            }
+
+            ErrorKind::ExpectedNArgsButGotM { min, max, .. } => match max {
+                Some(max) if max == min => vec![min.to_string()],
+                Some(max) => vec![format!("{}-{}", min, max)],
+                None => vec![],
+            },
+
+            ErrorKind::ExpectedAtLeastNArgsButGotM { min, .. } => vec![min.to_string()],
+
+            ErrorKind::TypeArgsRequired { name, count, .. } => vec![name.sym().to_string(), count.to_string()],
+
+            ErrorKind::TypeArgCountNotInRange { name, min, max, .. } => vec![name.sym().to_string(), min.to_string(), max.to_string()],
+
+            _ => vec![],
+        }
+    }
+}
+
+impl Error {
+    /// Formats the message of this error using `catalog`.
+    pub fn message(&self, catalog: &MessageCatalog) -> Option<String> {
+        catalog.format(self)
+    }
+}
@@ -0,0 +1,162 @@
+//! Renders errors like `tsc --pretty`.
+
+use std::fmt::Write;
+
+use ansi_term::{Color, Style};
+use swc_common::{SourceMap, Span, Spanned};
+
+use crate::{Error, ErrorKind, ExplainOpts, MessageCatalog};
+
+/// Maximum number of lines of the source code printed for an error.
+const MAX_SNIPPET_LINES: usize = 4;
+
+/// Renders `err` with the source code it points to.
+///
+/// The first line of the elaboration is used as the message if `messages`
+/// does not have a message for `err`.
+///
+/// ```text
+/// foo.ts:1:7 - error TS2322: AssignFailed {
+///
+/// 1 const a: number = "";
+///         ~
+/// ```
+pub fn render(cm: &SourceMap, err: &Error, opts: &ExplainOpts, messages: &MessageCatalog, colored: bool) -> String {
+    let paint = |style: Style, s: &str| if colored { style.paint(s).to_string() } else { s.to_string() };
+
+    let severity_color = if err.is_warning() { Color::Yellow } else { Color::Red };
+    let severity = if err.is_warning() { "warning" } else { "error" };
+    let code = format!("TS{}", ErrorKind::normalize_error_code(err.code()));
+
+    let elaboration = err.elaboration(opts);
+    let mut msg_lines = elaboration.lines();
+    let msg = match err.message(messages) {
+        Some(msg) => msg,
+        None => msg_lines.next().unwrap_or_default().to_string(),
+    };
+
+    let mut buf = String::new();
+    let span = err.span();
+
+    if span.is_dummy() {
+        let _ = writeln!(
+            buf,
+            "{} {}: {}",
+            paint(severity_color.normal(), severity),
+            paint(Color::Fixed(8).normal(), &code),
+            msg
+        );
+    } else {
+        let lo = cm.lookup_char_pos(span.lo);
+
+        let _ = writeln!(
+            buf,
+            "{}:{}:{} - {} {}: {}",
+            paint(Color::Cyan.normal(), &lo.file.name.to_string()),
+            paint(Color::Yellow.normal(), &lo.line.to_string()),
+            paint(Color::Yellow.normal(), &(lo.col.0 + 1).to_string()),
+            paint(severity_color.normal(), severity),
+            paint(Color::Fixed(8).normal(), &code),
+            msg
+        );
+        buf.push('\n');
+
+        write_snippet(&mut buf, cm, span, '~', None, severity_color, colored);
+
+        for (label_span, label) in err.labels() {
+            let loc = cm.lookup_char_pos(label_span.lo);
+            buf.push('\n');
+            if loc.file.name != lo.file.name {
+                let _ = writeln!(
+                    buf,
+                    "  {}:{}:{}",
+                    paint(Color::Cyan.normal(), &loc.file.name.to_string()),
+                    paint(Color::Yellow.normal(), &loc.line.to_string()),
+                    paint(Color::Yellow.normal(), &(loc.col.0 + 1).to_string()),
+                );
+            }
+            write_snippet(&mut buf, cm, label_span, '-', Some(label), Color::Cyan, colored);
+        }
+    }
+
+    for line in msg_lines {
+        let _ = writeln!(buf, "  {}", line);
+    }
+
+    if let Some((related, note)) = err.related_span() {
+        if !related.is_dummy() {
+            let loc = cm.lookup_char_pos(related.lo);
+            let _ = writeln!(
+                buf,
+                "\n  {}:{}:{}: {}",
+                paint(Color::Cyan.normal(), &loc.file.name.to_string()),
+                paint(Color::Yellow.normal(), &loc.line.to_string()),
+                paint(Color::Yellow.normal(), &(loc.col.0 + 1).to_string()),
+                note
+            );
+        }
+    }
+
+    buf
+}
+
+/// Writes the lines of `span`, with `span` underlined by `marker`.
+///
+/// `label` is written after the last underline.
+fn write_snippet(buf: &mut String, cm: &SourceMap, span: Span, marker: char, label: Option<&str>, color: Color, colored: bool) {
+    let paint = |style: Style, s: &str| if colored { style.paint(s).to_string() } else { s.to_string() };
+
+    let lo = cm.lookup_char_pos(span.lo);
+    let hi = cm.lookup_char_pos(span.hi);
+
+    let last_line = if lo.file.name == hi.file.name {
+        hi.line.max(lo.line)
+    } else {
+        lo.line
+    };
+    let gutter_width = last_line.to_string().len();
+
+    for line in lo.line..=last_line {
+        // Skip the middle of long spans.
+        if last_line - lo.line >= MAX_SNIPPET_LINES && line > lo.line + 1 && line < last_line {
+            if line == lo.line + 2 {
+                let _ = writeln!(buf, "{}", paint(Style::new().reverse(), &format!("{:>1$}", "…", gutter_width)));
+            }
+            continue;
+        }
+
+        let src = lo.file.get_line(line - 1).unwrap_or_default();
+        let src = src.trim_end();
+        let len = src.chars().count();
+
+        let start = if line == lo.line {
+            lo.col.0
+        } else {
+            len - src.trim_start().chars().count()
+        };
+        let end = if line == last_line && lo.file.name == hi.file.name {
+            hi.col.0
+        } else {
+            len
+        };
+        let width = end.saturating_sub(start).max(1);
+
+        let _ = writeln!(
+            buf,
+            "{} {}",
+            paint(Style::new().reverse(), &format!("{:>1$}", line, gutter_width)),
+            src
+        );
+        let suffix = match label {
+            Some(label) if line == last_line => format!(" {}", label),
+            _ => String::new(),
+        };
+        let _ = writeln!(
+            buf,
+            "{} {}{}",
+            paint(Style::new().reverse(), &" ".repeat(gutter_width)),
+            " ".repeat(start),
+            paint(color.normal(), &format!("{}{}", marker.to_string().repeat(width), suffix))
+        );
+    }
+}
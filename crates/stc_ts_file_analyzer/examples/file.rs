@@ -56,6 +56,8 @@ fn profile_file(path: &Path) {
             path: Arc::new(FileName::Real(PathBuf::from(path))),
             is_dts: false,
             info: Default::default(),
+            filter: None,
+            directives: None,
         };
 
         {
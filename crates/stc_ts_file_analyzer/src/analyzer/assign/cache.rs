@@ -0,0 +1,138 @@
+use fxhash::{FxHashMap, FxHashSet};
+use stc_ts_type_ops::metadata::TypeFinder;
+use stc_ts_types::Type;
+use swc_common::DUMMY_SP;
+
+use crate::{
+    analyzer::{assign::AssignOpts, Analyzer},
+    VResult,
+};
+
+/// The number of cached entries which triggers eviction.
+const MAX_RESULTS: usize = 16 * 1024;
+
+/// Cache for results of assignments, keyed by the identity of types.
+#[derive(Debug, Default)]
+pub(crate) struct RelationCache {
+    /// Types are stored with the results so the addresses used as keys stay
+    /// valid. `AssignOpts` are the original ones, which are used to check if
+    /// spans of a cached error are correct.
+    results: FxHashMap<RelationKey, (Type, Type, AssignOpts, VResult<()>)>,
+
+    /// Whether a type can be cached, by the address. The type is stored for
+    /// the same reason as `results`.
+    cacheable: FxHashMap<usize, (Type, bool)>,
+
+    /// Relations being checked. Used to handle recursive types.
+    in_progress: FxHashSet<RelationKey>,
+
+    /// Number of relations assumed to be related because they were in
+    /// progress.
+    assumptions: usize,
+
+    #[cfg(test)]
+    pub(super) hits: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct RelationKey {
+    left: usize,
+    right: usize,
+    opts: AssignOpts,
+}
+
+impl RelationCache {
+    fn key(&mut self, left: &Type, right: &Type, opts: AssignOpts) -> Option<RelationKey> {
+        Some(RelationKey {
+            left: self.address_of(left)?,
+            right: self.address_of(right)?,
+            // Spans do not affect the relation.
+            opts: AssignOpts {
+                span: DUMMY_SP,
+                right_ident_span: None,
+                left_ident_span: None,
+                ..opts
+            },
+        })
+    }
+
+    /// Returns the address of a frozen type, if it can be cached.
+    fn address_of(&mut self, ty: &Type) -> Option<usize> {
+        let addr = match ty {
+            Type::Arc(frozen) => frozen.as_ptr() as usize,
+            _ => return None,
+        };
+
+        let (_, cacheable) = self.cacheable.entry(addr).or_insert_with(|| {
+            // Types depending on the scope cannot be identified by the address.
+            // References are fine, as their ids are unique in a module.
+            let cacheable = !TypeFinder::find(ty, |ty| matches!(ty, Type::Query(..) | Type::This(..) | Type::StaticThis(..)));
+
+            (ty.clone(), cacheable)
+        });
+
+        if *cacheable {
+            Some(addr)
+        } else {
+            None
+        }
+    }
+}
+
+impl Analyzer<'_, '_> {
+    /// Calls `op` unless the result of assigning `right` to `left` is cached.
+    ///
+    /// If the same relation is being checked, we assume that types are related,
+    /// like `tsc` does for recursive types. Results depending on such
+    /// assumptions are cached only by the outermost relation.
+    pub(super) fn with_relation_cache<F>(&mut self, left: &Type, right: &Type, opts: AssignOpts, op: F) -> VResult<()>
+    where
+        F: FnOnce(&mut Self) -> VResult<()>,
+    {
+        let cache = &mut self.data.relation_cache;
+        let key = match cache.key(left, right, opts) {
+            Some(key) => key,
+            None => return op(self),
+        };
+
+        if let Some((_, _, cached_opts, res)) = cache.results.get(&key) {
+            // Errors are reported at spans of the cached call, so they are reused
+            // only for the same spans.
+            if res.is_err() && *cached_opts != opts {
+                return op(self);
+            }
+
+            let res = res.clone();
+            #[cfg(test)]
+            {
+                cache.hits += 1;
+            }
+            return res;
+        }
+        if cache.in_progress.contains(&key) {
+            cache.assumptions += 1;
+            return Ok(());
+        }
+
+        let is_outermost = cache.in_progress.is_empty();
+        let assumptions = cache.assumptions;
+        cache.in_progress.insert(key);
+
+        let res = op(self);
+
+        let cache = &mut self.data.relation_cache;
+        cache.in_progress.remove(&key);
+        if is_outermost || cache.assumptions == assumptions {
+            if cache.results.len() >= MAX_RESULTS || cache.cacheable.len() >= MAX_RESULTS {
+                cache.results.clear();
+                cache.cacheable.clear();
+            }
+            cache.results.insert(key, (left.clone(), right.clone(), opts, res.clone()));
+        }
+        if is_outermost {
+            cache.assumptions = 0;
+        }
+
+        res
+    }
+}
@@ -27,9 +27,8 @@ impl Analyzer<'_, '_> {
             return true;
         }
 
-        // TODO(kdy1): Maybe make this check optional, but only if tsc reports error for
-        // number + bigint
-        if ty.is_kwd(TsKeywordTypeKind::TsBigIntKeyword) {
+        // Mixing `number` and `bigint` is reported by the caller.
+        if ty.is_bigint() {
             return true;
         }
 
@@ -283,7 +283,10 @@ impl Analyzer<'_, '_> {
                                     rm.type_params.as_ref(),
                                     &rm.params,
                                     Some(&rm.ret_ty),
-                                    opts,
+                                    AssignOpts {
+                                        is_params_of_method_definition: true,
+                                        ..opts
+                                    },
                                 )?;
 
                                 return Ok(());
@@ -0,0 +1,214 @@
+use std::borrow::Cow;
+
+use stc_ts_ast_rnode::RTsLit;
+use stc_ts_types::{Interface, KeywordType, LitType, PropertySignature, Type, TypeElement, TypeParam};
+use stc_utils::cache::Freeze;
+use swc_common::{Span, TypeEq};
+use swc_ecma_ast::TsKeywordTypeKind;
+
+use crate::{
+    analyzer::{assign::AssignOpts, expr::CastableOpts, types::NormalizeTypeOpts, Analyzer},
+    VResult,
+};
+
+impl Analyzer<'_, '_> {
+    /// Returns true if `source` is comparable to `target`.
+    ///
+    /// This is the relation used by type assertions and `case` clauses. Unlike
+    /// assignability, a union source only needs one comparable constituent and
+    /// optionality of properties is ignored.
+    pub(crate) fn is_type_comparable_to(&mut self, span: Span, source: &Type, target: &Type) -> VResult<bool> {
+        self.is_type_comparable_to_with_opts(span, source, target, Default::default())
+    }
+
+    pub(crate) fn is_type_comparable_to_with_opts(
+        &mut self,
+        span: Span,
+        source: &Type,
+        target: &Type,
+        opts: CastableOpts,
+    ) -> VResult<bool> {
+        let normalize_opts = NormalizeTypeOpts {
+            preserve_intersection: true,
+            preserve_union: true,
+            preserve_global_this: true,
+            ..Default::default()
+        };
+        let source = self.normalize(Some(span), Cow::Borrowed(source), normalize_opts)?.freezed();
+        let target = self.normalize(Some(span), Cow::Borrowed(target), normalize_opts)?.freezed();
+        let source = source.normalize();
+        let target = target.normalize();
+
+        if source.type_eq(target) {
+            return Ok(true);
+        }
+
+        if source.is_any() || source.is_never() || source.is_null_or_undefined() {
+            return Ok(true);
+        }
+
+        if target.is_any() || target.is_unknown() {
+            return Ok(true);
+        }
+
+        match source {
+            Type::Union(source) => {
+                for source in &source.types {
+                    if self.is_type_comparable_to_with_opts(span, source, target, opts)? {
+                        return Ok(true);
+                    }
+                }
+                return Ok(false);
+            }
+            Type::Intersection(source) => {
+                for source in &source.types {
+                    if self.is_type_comparable_to_with_opts(span, source, target, opts)? {
+                        return Ok(true);
+                    }
+                }
+                return Ok(false);
+            }
+            // A type parameter is compared using its constraint.
+            Type::Param(TypeParam { constraint, .. }) => {
+                return match constraint {
+                    Some(constraint) => self.is_type_comparable_to_with_opts(span, constraint, target, opts),
+                    None => Ok(true),
+                };
+            }
+            _ => {}
+        }
+
+        match target {
+            Type::Union(target) => {
+                for target in &target.types {
+                    if self.is_type_comparable_to_with_opts(span, source, target, opts)? {
+                        return Ok(true);
+                    }
+                }
+                return Ok(false);
+            }
+            Type::Intersection(target) => {
+                for target in &target.types {
+                    if !self.is_type_comparable_to_with_opts(span, source, target, opts)? {
+                        return Ok(false);
+                    }
+                }
+                return Ok(true);
+            }
+            _ => {}
+        }
+
+        if (source.is_str() || source.is_tpl()) && target.is_tpl() {
+            return Ok(true);
+        }
+
+        match (source, target) {
+            (Type::Lit(LitType { lit: s, .. }), Type::Keyword(KeywordType { kind, .. })) => {
+                if matches!(
+                    (s, kind),
+                    (RTsLit::Number(..), TsKeywordTypeKind::TsNumberKeyword)
+                        | (RTsLit::Str(..), TsKeywordTypeKind::TsStringKeyword)
+                        | (RTsLit::Bool(..), TsKeywordTypeKind::TsBooleanKeyword)
+                        | (RTsLit::BigInt(..), TsKeywordTypeKind::TsBigIntKeyword)
+                ) {
+                    return Ok(true);
+                }
+            }
+            // Literals of the same kind are comparable only if they are identical.
+            (Type::Lit(..), Type::Lit(..)) => return Ok(false),
+
+            (Type::Function(..), Type::Interface(Interface { name, .. })) if name == "Function" => return Ok(true),
+            _ => {}
+        }
+
+        if source.is_num() && self.can_be_casted_to_number_in_rhs(span, target) {
+            return Ok(true);
+        }
+
+        if is_object_type(source) && is_object_type(target) {
+            if let Some(result) = self.are_properties_comparable(span, source, target, opts)? {
+                return Ok(result);
+            }
+        }
+
+        Ok(self
+            .assign_with_opts(
+                &mut Default::default(),
+                target,
+                source,
+                AssignOpts {
+                    span,
+                    disallow_different_classes: opts.disallow_different_classes,
+                    allow_assignment_to_param_constraint: opts.allow_assignment_to_param_constraint,
+                    disallow_special_assignment_to_empty_class: opts.disallow_special_assignment_to_empty_class,
+                    for_castablity: true,
+                    ..Default::default()
+                },
+            )
+            .is_ok())
+    }
+
+    /// Compares properties of two object types.
+    ///
+    /// Returns [None] if `target` has members other than properties and
+    /// methods, which should be checked by the assignment.
+    fn are_properties_comparable(&mut self, span: Span, source: &Type, target: &Type, opts: CastableOpts) -> VResult<Option<bool>> {
+        let source = match self.convert_type_to_type_lit(span, Cow::Borrowed(source))? {
+            Some(v) => v.into_owned(),
+            None => return Ok(None),
+        };
+        let target = match self.convert_type_to_type_lit(span, Cow::Borrowed(target))? {
+            Some(v) => v.into_owned(),
+            None => return Ok(None),
+        };
+
+        if target.members.is_empty() {
+            return Ok(Some(true));
+        }
+
+        let mut only_props = true;
+
+        for target_el in &target.members {
+            let (key, optional, target_ty) = match target_el {
+                TypeElement::Property(PropertySignature {
+                    key, optional, type_ann, ..
+                }) => (key, *optional, type_ann.as_deref()),
+                TypeElement::Method(m) => (&m.key, m.optional, None),
+                _ => {
+                    only_props = false;
+                    continue;
+                }
+            };
+
+            let source_el = source.members.iter().find(|el| el.key().map_or(false, |k| k.type_eq(key)));
+
+            match source_el {
+                Some(TypeElement::Property(PropertySignature {
+                    type_ann: Some(source_ty), ..
+                })) => {
+                    if let Some(target_ty) = target_ty {
+                        if !self.is_type_comparable_to_with_opts(span, source_ty, target_ty, opts)? {
+                            return Ok(Some(false));
+                        }
+                    }
+                }
+                Some(..) => {}
+                None => {
+                    if !optional {
+                        return Ok(Some(false));
+                    }
+                }
+            }
+        }
+
+        if only_props {
+            Ok(Some(true))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+fn is_object_type(ty: &Type) -> bool {
+    matches!(ty.normalize(), Type::TypeLit(..) | Type::Interface(..))
+}
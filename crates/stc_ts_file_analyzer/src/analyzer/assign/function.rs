@@ -333,7 +333,14 @@ impl Analyzer<'_, '_> {
         };
 
         // TypeScript functions are bivariant if strict_function_types is false.
+        //
+        // Parameters of methods are always bivariant, but the bivariance does not
+        // apply to function types nested in them.
         if !self.env.rule().strict_function_types || opts.is_params_of_method_definition {
+            let opts = AssignOpts {
+                is_params_of_method_definition: false,
+                ..opts
+            };
             if self.assign_params(data, r_params, l_params, opts).is_ok() {
                 return Ok(());
             }
@@ -0,0 +1,227 @@
+use std::borrow::Cow;
+
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::{Array, Function, PropertySignature, Type, TypeElement};
+use stc_utils::cache::Freeze;
+use swc_common::{Span, TypeEq};
+
+use crate::{
+    analyzer::{assign::AssignOpts, types::NormalizeTypeOpts, Analyzer},
+    VResult,
+};
+
+impl Analyzer<'_, '_> {
+    /// Returns true if `a` and `b` are identical.
+    ///
+    /// This is the relation used to check declarations which must have the
+    /// same type, like redeclared variables and properties of merged
+    /// interfaces.
+    pub(crate) fn is_type_identical_to(&mut self, span: Span, a: &Type, b: &Type) -> VResult<bool> {
+        let opts = NormalizeTypeOpts {
+            preserve_intersection: true,
+            preserve_union: true,
+            preserve_global_this: true,
+            ..Default::default()
+        };
+        let a = self.normalize(Some(span), Cow::Borrowed(a), opts)?.freezed();
+        let b = self.normalize(Some(span), Cow::Borrowed(b), opts)?.freezed();
+        let a = a.normalize();
+        let b = b.normalize();
+
+        if a.type_eq(b) {
+            return Ok(true);
+        }
+
+        match (a, b) {
+            (Type::Union(a), Type::Union(b)) => {
+                return Ok(self.are_types_identical_as_set(span, &a.types, &b.types)?
+                    && self.are_types_identical_as_set(span, &b.types, &a.types)?)
+            }
+            (Type::Intersection(a), Type::Intersection(b)) => {
+                return Ok(self.are_types_identical_as_set(span, &a.types, &b.types)?
+                    && self.are_types_identical_as_set(span, &b.types, &a.types)?)
+            }
+            (Type::Union(..) | Type::Intersection(..), _) | (_, Type::Union(..) | Type::Intersection(..)) => return Ok(false),
+
+            (Type::Keyword(a), Type::Keyword(b)) => return Ok(a.kind == b.kind),
+            (Type::Lit(..), _) | (_, Type::Lit(..)) | (Type::Keyword(..), _) | (_, Type::Keyword(..)) => return Ok(false),
+
+            (Type::Optional(a), Type::Optional(b)) => return self.is_type_identical_to(span, &a.ty, &b.ty),
+            (Type::Rest(a), Type::Rest(b)) => return self.is_type_identical_to(span, &a.ty, &b.ty),
+
+            (Type::Array(Array { elem_type: a, .. }), Type::Array(Array { elem_type: b, .. })) => {
+                return self.is_type_identical_to(span, a, b)
+            }
+
+            (Type::Tuple(a), Type::Tuple(b)) => {
+                if a.elems.len() != b.elems.len() {
+                    return Ok(false);
+                }
+                for (a, b) in a.elems.iter().zip(b.elems.iter()) {
+                    if !self.is_type_identical_to(span, &a.ty, &b.ty)? {
+                        return Ok(false);
+                    }
+                }
+                return Ok(true);
+            }
+
+            (Type::Function(a), Type::Function(b)) => return self.are_fns_identical(span, a, b),
+
+            (Type::TypeLit(..) | Type::Interface(..), Type::TypeLit(..) | Type::Interface(..)) => {
+                if let Some(result) = self.are_properties_identical(span, a, b)? {
+                    return Ok(result);
+                }
+            }
+            _ => {}
+        }
+
+        // Types we can't compare structurally are identical if they are assignable to
+        // each other.
+        let opts = AssignOpts {
+            span,
+            disallow_assignment_to_unknown: true,
+            ..Default::default()
+        };
+        Ok(self.assign_with_opts(&mut Default::default(), a, b, opts).is_ok()
+            && self.assign_with_opts(&mut Default::default(), b, a, opts).is_ok())
+    }
+
+    /// Returns true if all types in `a` have an identical type in `b`.
+    fn are_types_identical_as_set(&mut self, span: Span, a: &[Type], b: &[Type]) -> VResult<bool> {
+        'outer: for a in a {
+            for b in b {
+                if self.is_type_identical_to(span, a, b)? {
+                    continue 'outer;
+                }
+            }
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    fn are_fns_identical(&mut self, span: Span, a: &Function, b: &Function) -> VResult<bool> {
+        if a.params.len() != b.params.len() {
+            return Ok(false);
+        }
+
+        match (&a.type_params, &b.type_params) {
+            (Some(a), Some(b)) => {
+                if a.params.len() != b.params.len() {
+                    return Ok(false);
+                }
+
+                for (a, b) in a.params.iter().zip(b.params.iter()) {
+                    match (&a.constraint, &b.constraint) {
+                        (Some(a), Some(b)) => {
+                            if !self.is_type_identical_to(span, a, b)? {
+                                return Ok(false);
+                            }
+                        }
+                        (None, None) => {}
+                        _ => return Ok(false),
+                    }
+                }
+            }
+            (None, None) => {}
+            _ => return Ok(false),
+        }
+
+        for (a, b) in a.params.iter().zip(b.params.iter()) {
+            if a.required != b.required || !self.is_type_identical_to(span, &a.ty, &b.ty)? {
+                return Ok(false);
+            }
+        }
+
+        self.is_type_identical_to(span, &a.ret_ty, &b.ret_ty)
+    }
+
+    /// Returns [None] if one of types has members other than properties, which
+    /// should be compared by the assignment.
+    fn are_properties_identical(&mut self, span: Span, a: &Type, b: &Type) -> VResult<Option<bool>> {
+        let a = match self.convert_type_to_type_lit(span, Cow::Borrowed(a))? {
+            Some(v) => v.into_owned(),
+            None => return Ok(None),
+        };
+        let b = match self.convert_type_to_type_lit(span, Cow::Borrowed(b))? {
+            Some(v) => v.into_owned(),
+            None => return Ok(None),
+        };
+
+        if !a.members.iter().chain(b.members.iter()).all(|el| el.is_property()) {
+            return Ok(None);
+        }
+
+        if a.members.len() != b.members.len() {
+            return Ok(Some(false));
+        }
+
+        for a_el in &a.members {
+            let a_prop = match a_el {
+                TypeElement::Property(p) => p,
+                _ => unreachable!(),
+            };
+
+            let b_prop = b.members.iter().find_map(|el| match el {
+                TypeElement::Property(p) if p.key.type_eq(&a_prop.key) => Some(p),
+                _ => None,
+            });
+            let b_prop = match b_prop {
+                Some(v) => v,
+                None => return Ok(Some(false)),
+            };
+
+            if a_prop.optional != b_prop.optional || a_prop.readonly != b_prop.readonly {
+                return Ok(Some(false));
+            }
+
+            match (&a_prop.type_ann, &b_prop.type_ann) {
+                (Some(a), Some(b)) => {
+                    if !self.is_type_identical_to(span, a, b)? {
+                        return Ok(Some(false));
+                    }
+                }
+                (None, None) => {}
+                _ => return Ok(Some(false)),
+            }
+        }
+
+        Ok(Some(true))
+    }
+
+    /// Reports properties of `new` which are declared in `orig` with a
+    /// different type.
+    pub(crate) fn report_incompatible_subsequent_props(&mut self, span: Span, orig: &[TypeElement], new: &[TypeElement]) -> VResult<()> {
+        for new in new {
+            let new = match new {
+                TypeElement::Property(p) => p,
+                _ => continue,
+            };
+
+            let orig = orig.iter().find_map(|el| match el {
+                TypeElement::Property(p) if p.key.type_eq(&new.key) => Some(p),
+                _ => None,
+            });
+
+            if let Some(PropertySignature {
+                type_ann: Some(orig_ty), ..
+            }) = orig
+            {
+                if let Some(new_ty) = &new.type_ann {
+                    if !self.is_type_identical_to(span, orig_ty, new_ty)? {
+                        self.storage.report(
+                            ErrorKind::SubsequentPropDeclNotCompatible {
+                                span: new.span,
+                                prev: orig_ty.clone(),
+                                ty: new_ty.clone(),
+                            }
+                            .into(),
+                        );
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,16 +1,17 @@
-use std::{borrow::Cow, collections::HashMap};
+use std::{borrow::Cow, collections::HashMap, time::Instant};
 
 use stc_ts_ast_rnode::{RBool, RExpr, RIdent, RLit, RStr, RTsEntityName, RTsEnumMemberId, RTsLit};
+use stc_ts_env::perf::OpKind;
 use stc_ts_errors::{
     ctx,
-    debug::{dump_type_as_string, force_dump_type_as_string},
+    debug::{dump_type_as_string, dump_type_name, force_dump_type_as_string},
     DebugExt, ErrorKind,
 };
 use stc_ts_file_analyzer_macros::context;
 use stc_ts_types::{
     Array, Conditional, EnumVariant, Instance, Interface, Intersection, Intrinsic, IntrinsicKind, Key, KeywordType, KeywordTypeMetadata,
-    LitType, Mapped, Operator, PropertySignature, QueryExpr, QueryType, Ref, RestType, ThisType, Tuple, Type, TypeElement, TypeLit,
-    TypeParam,
+    LitType, Mapped, Operator, PropertySignature, QueryExpr, QueryType, Ref, RestType, ThisType, Tuple, TupleElement, Type, TypeElement,
+    TypeLit, TypeParam,
 };
 use stc_utils::{cache::Freeze, debug_ctx, stack};
 use swc_atoms::js_word;
@@ -18,25 +19,33 @@ use swc_common::{EqIgnoreSpan, Span, Spanned, TypeEq, DUMMY_SP};
 use swc_ecma_ast::*;
 use tracing::{debug, error, info, span, Level};
 
+use self::type_el::is_fresh_object_literal;
 use crate::{
-    analyzer::{types::NormalizeTypeOpts, util::is_lit_eq_ignore_span, Analyzer},
+    analyzer::{
+        types::{is_generic_indexed_access, NormalizeTypeOpts},
+        util::is_lit_eq_ignore_span,
+        Analyzer,
+    },
     ty::TypeExt,
     VResult,
 };
 
 mod builtin;
+pub(super) mod cache;
 mod cast;
 mod class;
+mod comparable;
 mod function;
+mod identity;
 mod query;
 #[cfg(test)]
 mod tests;
-mod tpl;
+pub(super) mod tpl;
 mod type_el;
 mod unions;
 
 /// Context used for `=` assignments.
-#[derive(Debug, Clone, Copy, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub(crate) struct AssignOpts {
     /// This field should be overrided by caller.
     pub span: Span,
@@ -440,7 +449,20 @@ impl Analyzer<'_, '_> {
 
         // self.verify_before_assign("lhs", left);
         // self.verify_before_assign("rhs", right);
-        let res = self.assign_inner(data, left, right, opts);
+        let start = self.env.perf().map(|_| Instant::now());
+
+        // Results depending on `data` cannot be cached.
+        let res = if data.dejavu.is_empty() {
+            self.with_relation_cache(left, right, opts, |a| a.assign_inner(data, left, right, opts))
+        } else {
+            self.assign_inner(data, left, right, opts)
+        };
+
+        if let (Some(perf), Some(start)) = (self.env.perf(), start) {
+            perf.record_op(OpKind::Assign, opts.span, start.elapsed().as_micros() as u64, || {
+                format!("{} = {}", dump_type_name(left), dump_type_name(right))
+            });
+        }
 
         match res.as_ref().map_err(|e| &**e) {
             Err(ErrorKind::Errors { errors, .. }) if errors.is_empty() => return Ok(()),
@@ -1480,11 +1502,37 @@ impl Analyzer<'_, '_> {
                 }
             }
 
+            // Reading from `T[K]`
+            Type::IndexedAccessType(r) if is_generic_indexed_access(r) => {
+                if let Type::IndexedAccessType(l) = to {
+                    if l.obj_type.type_eq(&r.obj_type) && l.index_type.type_eq(&r.index_type) {
+                        return Ok(());
+                    }
+                }
+
+                if let Some(constraint) = self.base_constraint_of_indexed_access(span, r)? {
+                    return self
+                        .assign_inner(data, to, &constraint, opts)
+                        .context("tried to assign the base constraint of an indexed access type");
+                }
+            }
+
             _ => {}
         }
 
         match to {
             Type::Mapped(to) => return self.assign_to_mapped(data, to, rhs, opts),
+
+            // Writing to `T[K]`
+            Type::IndexedAccessType(l) if is_generic_indexed_access(l) => {
+                if let Some(write_ty) = self.write_type_of_indexed_access(span, l)? {
+                    return self
+                        .assign_inner(data, &write_ty, rhs, opts)
+                        .context("tried to assign to the write type of an indexed access type");
+                }
+
+                fail!()
+            }
             Type::Param(TypeParam {
                 constraint: Some(ref c), ..
             }) => {
@@ -1656,6 +1704,32 @@ impl Analyzer<'_, '_> {
                     }
                 }
 
+                // Excess properties of a fresh object literal are checked only against the
+                // constituents matched by the discriminant.
+                if !opts.allow_unknown_rhs.unwrap_or_default() && is_fresh_object_literal(rhs) {
+                    if let Some(matched) = self.find_union_members_by_discriminant(span, &lu.types, rhs) {
+                        let mut errors = vec![];
+                        for to in matched {
+                            match self.assign_with_opts(data, to, rhs, opts) {
+                                Ok(()) => return Ok(()),
+                                Err(err) => errors.push(err),
+                            }
+                        }
+
+                        if errors.len() == 1 {
+                            return Err(errors.remove(0));
+                        }
+                        return Err(ErrorKind::AssignFailed {
+                            span,
+                            cause: errors,
+                            left: box to.clone(),
+                            right: box rhs.clone(),
+                            right_ident: opts.right_ident_span,
+                        }
+                        .into());
+                    }
+                }
+
                 let results = lu
                     .types
                     .iter()
@@ -1861,11 +1935,20 @@ impl Analyzer<'_, '_> {
                                 kind: TsKeywordTypeKind::TsUndefinedKeyword,
                                 ..
                             })
+                            | Type::Keyword(KeywordType {
+                                kind: TsKeywordTypeKind::TsSymbolKeyword,
+                                ..
+                            })
+                            | Type::Symbol(..)
+                            | Type::Tpl(..)
+                            | Type::EnumVariant(..)
                             | Type::Lit(..)
                             | Type::Param(..) => {
                                 fail!()
                             }
 
+                            _ if rhs.is_unique_symbol() => fail!(),
+
                             // let a: object = {};
                             Type::Function(..)
                             | Type::Constructor(..)
@@ -2144,6 +2227,12 @@ impl Analyzer<'_, '_> {
                             fail!()
                         }
 
+                        if let Some(rest_idx) = elems.iter().position(|elem| elem.ty.is_rest()) {
+                            if rest_idx + 1 != elems.len() {
+                                return self.assign_to_variadic_tuple(data, elems, rest_idx, rhs_elems, opts);
+                            }
+                        }
+
                         if elems.len() < rhs_elems.len() {
                             if elems.iter().any(|elem| elem.ty.is_rest()) {
                                 // Type::Rest eats many elements
@@ -2334,18 +2423,23 @@ impl Analyzer<'_, '_> {
                 ty,
                 ..
             }) if ty.is_type_param() => {
-                return self
-                    .assign_with_opts(
-                        data,
-                        &Type::Keyword(KeywordType {
-                            span: DUMMY_SP,
-                            kind: TsKeywordTypeKind::TsStringKeyword,
-                            metadata: Default::default(),
-                        }),
-                        rhs,
-                        opts,
-                    )
-                    .context("tried to assign a type to a `keyof TypeParam`")
+                // A literal key of the constraint is a key of any type argument.
+                if let (
+                    Type::Param(TypeParam {
+                        constraint: Some(constraint),
+                        ..
+                    }),
+                    Type::Lit(..),
+                ) = (ty.normalize(), rhs.normalize())
+                {
+                    let keys = self
+                        .keyof(span, constraint)
+                        .context("tried to get keys of the constraint to assign a literal to `keyof TypeParam`")?;
+
+                    return self
+                        .assign_with_opts(data, &keys, rhs, opts)
+                        .context("tried to assign a literal to `keyof TypeParam`");
+                }
             }
 
             _ => {}
@@ -2392,6 +2486,59 @@ impl Analyzer<'_, '_> {
         Ok(())
     }
 
+    /// Assigns to a tuple with a rest element in the middle, like
+    /// `[A, ...B[], C]`.
+    fn assign_to_variadic_tuple(
+        &mut self,
+        data: &mut AssignData,
+        elems: &[TupleElement],
+        rest_idx: usize,
+        rhs_elems: &[TupleElement],
+        opts: AssignOpts,
+    ) -> VResult<()> {
+        let span = opts.span;
+        let suffix_len = elems.len() - rest_idx - 1;
+
+        if rhs_elems.len() < rest_idx + suffix_len {
+            return Err(ErrorKind::AssignFailedBecauseTupleLengthDiffers { span }.into());
+        }
+        let middle_end = rhs_elems.len() - suffix_len;
+
+        let opts = AssignOpts {
+            allow_unknown_rhs: Some(true),
+            ..opts
+        };
+
+        let mut errors = vec![];
+        for (l, r) in elems[..rest_idx].iter().zip(&rhs_elems[..rest_idx]) {
+            errors.extend(self.assign_inner(data, &l.ty, &r.ty, opts).err());
+        }
+        for (l, r) in elems[rest_idx + 1..].iter().zip(&rhs_elems[middle_end..]) {
+            errors.extend(self.assign_inner(data, &l.ty, &r.ty, opts).err());
+        }
+
+        let rest_ty = match elems[rest_idx].ty.normalize() {
+            Type::Rest(rest) => &rest.ty,
+            _ => unreachable!(),
+        };
+        for r in &rhs_elems[rest_idx..middle_end] {
+            let res = match r.ty.normalize() {
+                Type::Rest(..) => self.assign_inner(data, &elems[rest_idx].ty, &r.ty, opts),
+                _ => match rest_ty.normalize() {
+                    Type::Array(arr) => self.assign_inner(data, &arr.elem_type, &r.ty, opts),
+                    _ => self.assign_inner(data, rest_ty, &r.ty, opts),
+                },
+            };
+            errors.extend(res.err());
+        }
+
+        if !errors.is_empty() {
+            return Err(ErrorKind::TupleAssignError { span, errors }.into());
+        }
+
+        Ok(())
+    }
+
     /// Should be called only if `to` is not expandable.
     fn assign_to_intrinsic(&mut self, data: &mut AssignData, to: &Intrinsic, r: &Type, opts: AssignOpts) -> VResult<()> {
         match to.kind {
@@ -1,6 +1,11 @@
-use swc_common::Spanned;
+use stc_ts_types::Type;
+use stc_utils::cache::Freeze;
+use swc_common::{Span, Spanned};
 
-use crate::analyzer::{assign::AssignOpts, tests::test_two};
+use crate::{
+    analyzer::{assign::AssignOpts, tests::test_two, Analyzer},
+    VResult,
+};
 
 fn test_assign(l: &str, r: &str, should_success: bool, opts: AssignOpts) {
     test_two(l, r, |analyzer, l, r| {
@@ -58,3 +63,49 @@ fn array_filter_2() {
         Default::default(),
     );
 }
+
+fn assign_with_span(analyzer: &mut Analyzer, l: &Type, r: &Type, span: Span) -> VResult<()> {
+    analyzer.assign_with_opts(
+        &mut Default::default(),
+        l,
+        r,
+        AssignOpts {
+            span,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn relation_cache_ignores_spans() {
+    test_two("{ a: string }", "{ a: 'foo' }", |analyzer, l, r| {
+        let (l, r) = (l.freezed(), r.freezed());
+
+        let res = assign_with_span(analyzer, &l, &r, l.span());
+        assert!(res.is_ok(), "{:?}", res);
+
+        let hits = analyzer.data.relation_cache.hits;
+        let res = assign_with_span(analyzer, &l, &r, r.span());
+        assert!(res.is_ok(), "{:?}", res);
+        assert_eq!(analyzer.data.relation_cache.hits, hits + 1);
+    });
+}
+
+#[test]
+fn relation_cache_does_not_reuse_errors_for_other_spans() {
+    test_two("{ a: string }", "{ a: number }", |analyzer, l, r| {
+        let (l, r) = (l.freezed(), r.freezed());
+
+        let res = assign_with_span(analyzer, &l, &r, l.span());
+        assert!(res.is_err(), "{:?}", res);
+
+        let hits = analyzer.data.relation_cache.hits;
+        let res = assign_with_span(analyzer, &l, &r, l.span());
+        assert!(res.is_err(), "{:?}", res);
+        assert_eq!(analyzer.data.relation_cache.hits, hits + 1);
+
+        let res = assign_with_span(analyzer, &l, &r, r.span());
+        assert!(res.is_err(), "{:?}", res);
+        assert_eq!(analyzer.data.relation_cache.hits, hits + 1);
+    });
+}
@@ -1,8 +1,12 @@
 #![allow(clippy::if_same_then_else)]
 
-use stc_ts_ast_rnode::RTsLit;
+use std::borrow::Cow;
+
+use stc_ts_ast_rnode::{RStr, RTsLit};
 use stc_ts_errors::{ctx, debug::dump_type_as_string, ErrorKind};
-use stc_ts_types::{LitType, TplType, Type};
+use stc_ts_types::{Intrinsic, KeywordType, LitType, TplType, Type, TypeParam, TypeParamInstantiation};
+use swc_common::Span;
+use swc_ecma_ast::TsKeywordTypeKind;
 
 use crate::{
     analyzer::{
@@ -112,17 +116,14 @@ impl Analyzer<'_, '_> {
                 Ok(())
             }
             Type::Lit(LitType { lit: RTsLit::Str(r), .. }) => {
-                let mut start = 0;
-                let mut positions = vec![];
-
-                for item in &l.quasis {
-                    let q = &item.cooked.as_ref().unwrap();
-                    match r.value[start..].find(&***q) {
-                        Some(pos) => {
-                            positions.push(pos);
-                            start += pos + 1;
-                        }
-                        None => return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.into()),
+                let parts = match match_tpl_parts(l, &r.value) {
+                    Some(parts) => parts,
+                    None => return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.context("quasis do not match")),
+                };
+
+                for (ty, part) in l.types.iter().zip(parts) {
+                    if !self.is_valid_tpl_part(span, ty, part)? {
+                        return Err(ErrorKind::SimpleAssignFailed { span, cause: None }.context("invalid part of a template literal"));
                     }
                 }
 
@@ -132,4 +133,133 @@ impl Analyzer<'_, '_> {
             _ => Err(ErrorKind::SimpleAssignFailed { span, cause: None }.into()),
         }
     }
+
+    /// Returns true if `part`, a substring of a string literal, can be
+    /// assigned to a placeholder of type `ty`.
+    fn is_valid_tpl_part(&mut self, span: Span, ty: &Type, part: &str) -> VResult<bool> {
+        let ty = self.normalize(Some(span), Cow::Borrowed(ty), Default::default())?;
+
+        Ok(match ty.normalize() {
+            Type::Keyword(KeywordType { kind, .. }) => match kind {
+                TsKeywordTypeKind::TsAnyKeyword | TsKeywordTypeKind::TsStringKeyword | TsKeywordTypeKind::TsUnknownKeyword => true,
+                TsKeywordTypeKind::TsNumberKeyword => is_numeric_literal_name(part),
+                TsKeywordTypeKind::TsBigIntKeyword => part.parse::<i128>().is_ok(),
+                TsKeywordTypeKind::TsBooleanKeyword => part == "true" || part == "false",
+                TsKeywordTypeKind::TsNullKeyword => part == "null",
+                TsKeywordTypeKind::TsUndefinedKeyword => part == "undefined",
+                _ => false,
+            },
+            Type::Lit(LitType { lit, .. }) => match lit {
+                RTsLit::Str(s) => *s.value == *part,
+                RTsLit::Number(n) => is_numeric_literal_name(part) && part.parse::<f64>().ok() == Some(n.value),
+                RTsLit::BigInt(b) => part.parse::<i128>().ok().map(|v| v.to_string()) == Some(b.value.to_string()),
+                RTsLit::Bool(b) => part == if b.value { "true" } else { "false" },
+                RTsLit::Tpl(..) => false,
+            },
+            Type::Union(u) => {
+                for ty in &u.types {
+                    if self.is_valid_tpl_part(span, ty, part)? {
+                        return Ok(true);
+                    }
+                }
+                false
+            }
+            Type::Intersection(i) => {
+                for ty in &i.types {
+                    if !self.is_valid_tpl_part(span, ty, part)? {
+                        return Ok(false);
+                    }
+                }
+                true
+            }
+            Type::Tpl(tpl) => match match_tpl_parts(tpl, part) {
+                Some(parts) => {
+                    for (ty, part) in tpl.types.iter().zip(parts) {
+                        if !self.is_valid_tpl_part(span, ty, part)? {
+                            return Ok(false);
+                        }
+                    }
+                    true
+                }
+                None => false,
+            },
+            Type::Param(TypeParam { constraint, .. }) => match constraint {
+                Some(constraint) => self.is_valid_tpl_part(span, constraint, part)?,
+                None => true,
+            },
+            // Unresolved intrinsics, like `Uppercase<string>`.
+            Type::Intrinsic(i) => {
+                let ty = self.expand_intrinsic_types(
+                    span,
+                    &Intrinsic {
+                        type_args: TypeParamInstantiation {
+                            span: i.type_args.span,
+                            params: vec![Type::Lit(LitType {
+                                span,
+                                lit: RTsLit::Str(RStr {
+                                    span,
+                                    value: part.into(),
+                                    raw: None,
+                                }),
+                                metadata: Default::default(),
+                            })],
+                        },
+                        ..i.clone()
+                    },
+                )?;
+
+                match ty.normalize() {
+                    Type::Lit(LitType { lit: RTsLit::Str(s), .. }) => *s.value == *part,
+                    _ => false,
+                }
+            }
+            _ => false,
+        })
+    }
+}
+
+/// Splits `s` into the parts matched by the placeholders of `tpl`.
+///
+/// Each placeholder except the last one matches the shortest possible text,
+/// as `tsc` does.
+pub(crate) fn match_tpl_parts<'a>(tpl: &TplType, s: &'a str) -> Option<Vec<&'a str>> {
+    let quasis = tpl
+        .quasis
+        .iter()
+        .map(|q| q.cooked.as_deref().unwrap_or(&*q.raw))
+        .collect::<Vec<_>>();
+
+    let prefix = quasis.first()?;
+    let suffix = quasis.last()?;
+    if s.len() < prefix.len() + suffix.len() || !s.starts_with(prefix) || !s.ends_with(suffix) {
+        return None;
+    }
+    if quasis.len() == 1 {
+        return if s.len() == prefix.len() { Some(vec![]) } else { None };
+    }
+
+    let text = &s[..s.len() - suffix.len()];
+    let mut parts = Vec::with_capacity(tpl.types.len());
+    let mut pos = prefix.len();
+
+    for delim in &quasis[1..quasis.len() - 1] {
+        let end = if delim.is_empty() {
+            // Consume a single character.
+            pos + text[pos..].chars().next()?.len_utf8()
+        } else {
+            pos + text[pos..].find(delim)?
+        };
+
+        parts.push(&text[pos..end]);
+        pos = end + delim.len();
+    }
+    parts.push(&text[pos..]);
+
+    Some(parts)
+}
+
+/// Returns true if `s` is a valid text of a numeric literal.
+fn is_numeric_literal_name(s: &str) -> bool {
+    let s = s.trim();
+    !s.is_empty() && s.parse::<f64>().map_or(false, |n| n.is_finite())
 }
@@ -25,6 +25,66 @@ use crate::{
 };
 
 impl Analyzer<'_, '_> {
+    /// Rejects `rhs` if `lhs` is a weak type, which has only optional
+    /// properties, and `rhs` has no property in common with it.
+    ///
+    /// ```ts
+    /// interface Options {
+    ///     color?: string;
+    /// }
+    ///
+    /// const opts: Options = { colour: "red" } as { colour: string }; // TS2559
+    /// ```
+    fn check_weak_type_assignment(&mut self, span: Span, lhs: &[TypeElement], rhs: &Type) -> VResult<()> {
+        let is_weak = !lhs.is_empty()
+            && lhs.iter().all(|el| match el {
+                TypeElement::Property(p) => p.optional,
+                TypeElement::Method(m) => m.optional,
+                _ => false,
+            });
+        if !is_weak {
+            return Ok(());
+        }
+
+        match rhs.normalize() {
+            // Fresh object literals are checked for excess properties instead.
+            Type::TypeLit(..) if is_fresh_object_literal(rhs) => return Ok(()),
+            Type::TypeLit(..) | Type::Interface(..) | Type::Class(..) => {}
+            _ => return Ok(()),
+        }
+        let rhs = match self.convert_type_to_type_lit(span, Cow::Borrowed(rhs))? {
+            Some(rhs) => rhs,
+            None => return Ok(()),
+        };
+
+        let rhs_keys = rhs
+            .members
+            .iter()
+            .filter_map(|el| match el {
+                TypeElement::Property(p) => Some(&p.key),
+                TypeElement::Method(m) => Some(&m.key),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let has_signatures = rhs
+            .members
+            .iter()
+            .any(|el| matches!(el, TypeElement::Call(..) | TypeElement::Constructor(..)));
+        if rhs_keys.is_empty() && !has_signatures {
+            return Ok(());
+        }
+
+        let has_common = lhs.iter().any(|el| match el.key() {
+            Some(key) => rhs_keys.iter().any(|rhs_key| key.type_eq(rhs_key)),
+            None => false,
+        });
+        if has_common {
+            return Ok(());
+        }
+
+        Err(ErrorKind::NoCommonPropertyWithWeakType { span }.into())
+    }
+
     /// This method is called when lhs of assignment is interface or type
     /// literal.
     ///
@@ -49,6 +109,8 @@ impl Analyzer<'_, '_> {
         let mut errors = vec![];
         let mut missing_fields = vec![];
 
+        self.check_weak_type_assignment(span, lhs, rhs)?;
+
         let numeric_keyed_ty = lhs
             .iter()
             .filter_map(|e| match e {
@@ -1111,12 +1173,36 @@ impl Analyzer<'_, '_> {
                                                 }
                                             }
 
-                                            self.assign_inner(
-                                                data,
-                                                lp.type_ann.as_deref().unwrap_or(&Type::any(span, Default::default())),
-                                                rp.type_ann.as_deref().unwrap_or(&Type::any(span, Default::default())),
-                                                opts,
-                                            )
+                                            let r_ty = rp
+                                                .type_ann
+                                                .as_deref()
+                                                .cloned()
+                                                .unwrap_or_else(|| Type::any(span, Default::default()));
+                                            let l_ty = lp
+                                                .type_ann
+                                                .as_deref()
+                                                .cloned()
+                                                .unwrap_or_else(|| Type::any(span, Default::default()));
+                                            // `undefined` is implicitly added to the type of an optional property,
+                                            // unless `exactOptionalPropertyTypes` is enabled.
+                                            let l_ty =
+                                                if lp.optional && !self.rule().exact_optional_property_types && r_ty.contains_undefined() {
+                                                    Type::new_union(span, vec![l_ty, Type::undefined(span, Default::default())]).freezed()
+                                                } else {
+                                                    l_ty
+                                                };
+
+                                            // Nested object literals are checked for excess properties only if
+                                            // they are fresh.
+                                            let opts = AssignOpts {
+                                                allow_unknown_rhs: match opts.allow_unknown_rhs {
+                                                    Some(false) => Some(!is_fresh_object_literal(&r_ty)),
+                                                    v => v,
+                                                },
+                                                ..opts
+                                            };
+
+                                            self.assign_inner(data, &l_ty, &r_ty, opts)
                                         })()?;
 
                                         if let Some(pos) = unhandled_rhs.iter().position(|span| *span == rm.span()) {
@@ -1218,7 +1304,10 @@ impl Analyzer<'_, '_> {
                                                     rf.type_params.as_ref(),
                                                     &rf.params,
                                                     Some(&rf.ret_ty),
-                                                    opts,
+                                                    AssignOpts {
+                                                        is_params_of_method_definition: true,
+                                                        ..opts
+                                                    },
                                                 )
                                                 .context("tried to assign a property with callable type to a method property")?;
                                             }
@@ -1476,3 +1565,12 @@ impl Analyzer<'_, '_> {
         Ok(())
     }
 }
+
+/// Returns true if `ty` is the type of an object literal, which is not widened
+/// by a variable or a type annotation.
+pub(super) fn is_fresh_object_literal(ty: &Type) -> bool {
+    match ty.normalize() {
+        Type::TypeLit(TypeLit { metadata, .. }) => !metadata.specified && !metadata.inexact && !metadata.common.resolved_from_var,
+        _ => false,
+    }
+}
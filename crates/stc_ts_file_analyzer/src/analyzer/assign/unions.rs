@@ -8,7 +8,7 @@ use stc_ts_types::{
     KeywordType, LitType, LitTypeMetadata, PropertySignature, Tuple, TupleElement, Type, TypeElement, TypeLit, Union, UnionMetadata,
 };
 use stc_utils::cache::{Freeze, ALLOW_DEEP_CLONE};
-use swc_common::{Span, DUMMY_SP};
+use swc_common::{Span, TypeEq, DUMMY_SP};
 use swc_ecma_ast::TsKeywordTypeKind;
 
 use crate::{
@@ -46,6 +46,62 @@ impl Analyzer<'_, '_> {
         }
     }
 
+    /// Returns the constituents of `types` matched by a discriminant property
+    /// of `rhs`.
+    ///
+    /// A property is a discriminant if every constituent has a property with
+    /// the same key and a literal type. Returns [None] if there's no such
+    /// property or the discriminant does not narrow `types`.
+    ///
+    /// ```ts
+    /// type Shape = { kind: "circle"; radius: number } | { kind: "square"; size: number };
+    ///
+    /// // Only `{ kind: "circle"; radius: number }` is used to check excess properties.
+    /// const s: Shape = { kind: "circle", radius: 1, size: 1 };
+    /// ```
+    pub(super) fn find_union_members_by_discriminant<'a>(&mut self, span: Span, types: &'a [Type], rhs: &Type) -> Option<Vec<&'a Type>> {
+        let rhs_members = match rhs.normalize() {
+            Type::TypeLit(rhs) => &rhs.members,
+            _ => return None,
+        };
+
+        let lhs = types
+            .iter()
+            .map(|ty| self.convert_type_to_type_lit(span, Cow::Borrowed(ty)).ok().flatten())
+            .collect::<Option<Vec<_>>>()?;
+
+        'keys: for rm in rhs_members {
+            let (key, r_ty) = match rm {
+                TypeElement::Property(PropertySignature {
+                    key, type_ann: Some(r_ty), ..
+                }) if r_ty.is_lit() => (key, r_ty),
+                _ => continue,
+            };
+
+            let mut matched = vec![];
+            for (ty, lit) in types.iter().zip(&lhs) {
+                let l_ty = lit.members.iter().find_map(|el| match el {
+                    TypeElement::Property(p) if p.key.type_eq(key) => p.type_ann.as_deref(),
+                    _ => None,
+                });
+                let l_ty = match l_ty {
+                    Some(l_ty) if l_ty.iter_union().all(|ty| ty.is_lit()) => l_ty,
+                    _ => continue 'keys,
+                };
+
+                if self.assign(span, &mut Default::default(), l_ty, r_ty).is_ok() {
+                    matched.push(ty);
+                }
+            }
+
+            if !matched.is_empty() && matched.len() < types.len() {
+                return Some(matched);
+            }
+        }
+
+        None
+    }
+
     fn flatten_unions_for_assignment(&mut self, span: Span, ty: Cow<Type>) -> VResult<Type> {
         let ty = self.normalize(Some(span), ty, Default::default())?;
 
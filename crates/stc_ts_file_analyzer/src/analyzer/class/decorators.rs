@@ -0,0 +1,445 @@
+use rnode::NodeId;
+use stc_ts_ast_rnode::{
+    RBindingIdent, RClass, RClassMember, RDecorator, RIdent, RNumber, RParamOrTsParamProp, RPat, RTsEntityName, RTsLit,
+};
+use stc_ts_errors::{DebugExt, ErrorKind};
+use stc_ts_types::{
+    Class, ClassDef, ClassMember, ClassProperty, FnParam, Function, Key, KeywordType, LitType, Method, Ref, Type, TypeParamInstantiation,
+};
+use stc_utils::cache::Freeze;
+use swc_common::{Span, TypeEq, DUMMY_SP};
+use swc_ecma_ast::{MethodKind, TsKeywordTypeKind};
+
+use crate::{
+    analyzer::{assign::AssignOpts, util::ResultExt, Analyzer},
+    validator::ValidateWith,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DecoratorKind {
+    Class,
+    Method,
+    Property,
+    Param,
+}
+
+/// The implicit arguments of a decorator and the type the decorator should
+/// return.
+struct DecoratorCall {
+    kind: DecoratorKind,
+    args: Vec<Type>,
+    expected_ret_ty: Type,
+}
+
+impl Analyzer<'_, '_> {
+    /// Validates decorators of a class. This should be called from the scope
+    /// of the class declaration, as decorators of a class cannot reference the
+    /// type parameters of the class.
+    pub(super) fn validate_class_decorators(&mut self, decorators: &[RDecorator]) -> Vec<(Span, Type)> {
+        decorators
+            .iter()
+            .filter_map(|d| {
+                let ty = d.expr.validate_with_default(self).report(&mut self.storage)?;
+                Some((d.span, ty.freezed()))
+            })
+            .collect()
+    }
+
+    /// Checks if decorators of `c` and its members can be called with the
+    /// implicit arguments, using the semantics of `experimentalDecorators` if
+    /// it's enabled and the semantics of ES decorators otherwise.
+    ///
+    /// `members` should contain the members of the class before getters and
+    /// setters are merged, along with the index of the member in `c.body`.
+    pub(super) fn check_decorators_of_class(
+        &mut self,
+        c: &RClass,
+        class_decorators: Vec<(Span, Type)>,
+        members: &[(usize, ClassMember)],
+        class: &ClassDef,
+    ) {
+        let legacy = self.rule().experimental_decorators;
+
+        let ctor_ty = Type::ClassDef(class.clone()).freezed();
+        let instance_ty = Type::Class(Class {
+            span: class.span,
+            def: box class.clone(),
+            metadata: Default::default(),
+        })
+        .freezed();
+
+        for (span, callee) in class_decorators {
+            let call = if legacy {
+                DecoratorCall {
+                    kind: DecoratorKind::Class,
+                    args: vec![ctor_ty.clone()],
+                    expected_ret_ty: ctor_ty.clone(),
+                }
+            } else {
+                DecoratorCall {
+                    kind: DecoratorKind::Class,
+                    args: vec![
+                        ctor_ty.clone(),
+                        decorator_context(span, "ClassDecoratorContext", vec![ctor_ty.clone()]),
+                    ],
+                    expected_ret_ty: ctor_ty.clone(),
+                }
+            };
+
+            self.check_decorator_call(span, &callee, call);
+        }
+
+        for (idx, member) in c.body.iter().enumerate() {
+            let member_ty = |pred: &dyn Fn(&ClassMember) -> bool| members.iter().find(|(i, m)| *i == idx && pred(m)).map(|v| &v.1);
+
+            match member {
+                RClassMember::Constructor(ctor) => {
+                    for (param_idx, param) in ctor.params.iter().enumerate() {
+                        let decorators = match param {
+                            RParamOrTsParamProp::TsParamProp(p) => &p.decorators,
+                            RParamOrTsParamProp::Param(p) => &p.decorators,
+                        };
+
+                        self.check_param_decorators(decorators, &ctor_ty, Type::undefined(ctor.span, Default::default()), param_idx);
+                    }
+                }
+
+                RClassMember::Method(m) => {
+                    let this = if m.is_static { &ctor_ty } else { &instance_ty };
+                    let ty = match m.kind {
+                        MethodKind::Method => member_ty(&|m| matches!(m, ClassMember::Method(..))),
+                        MethodKind::Getter => member_ty(&|m| matches!(m, ClassMember::Property(p) if p.accessor.getter)),
+                        MethodKind::Setter => member_ty(&|m| matches!(m, ClassMember::Property(p) if p.accessor.setter)),
+                    };
+                    let ty = match ty {
+                        Some(ty) => ty.clone(),
+                        None => continue,
+                    };
+
+                    self.check_member_decorators(&m.function.decorators, m.kind, this, &ty, members, false);
+
+                    let key_ty = ty.key().map(|key| key_type(&key));
+                    if let Some(key_ty) = key_ty {
+                        for (param_idx, param) in m.function.params.iter().enumerate() {
+                            self.check_param_decorators(&param.decorators, this, key_ty.clone(), param_idx);
+                        }
+                    }
+                }
+
+                RClassMember::PrivateMethod(m) => {
+                    let this = if m.is_static { &ctor_ty } else { &instance_ty };
+                    let ty = match m.kind {
+                        MethodKind::Method => member_ty(&|m| matches!(m, ClassMember::Method(..))),
+                        MethodKind::Getter => member_ty(&|m| matches!(m, ClassMember::Property(p) if p.accessor.getter)),
+                        MethodKind::Setter => member_ty(&|m| matches!(m, ClassMember::Property(p) if p.accessor.setter)),
+                    };
+                    let ty = match ty {
+                        Some(ty) => ty.clone(),
+                        None => continue,
+                    };
+
+                    self.check_member_decorators(&m.function.decorators, m.kind, this, &ty, members, true);
+                }
+
+                RClassMember::ClassProp(p) => {
+                    let this = if p.is_static { &ctor_ty } else { &instance_ty };
+                    if let Some(ty) = member_ty(&|m| matches!(m, ClassMember::Property(..))).cloned() {
+                        self.check_member_decorators(&p.decorators, MethodKind::Method, this, &ty, members, false);
+                    }
+                }
+
+                RClassMember::PrivateProp(p) => {
+                    let this = if p.is_static { &ctor_ty } else { &instance_ty };
+                    if let Some(ty) = member_ty(&|m| matches!(m, ClassMember::Property(..))).cloned() {
+                        self.check_member_decorators(&p.decorators, MethodKind::Method, this, &ty, members, true);
+                    }
+                }
+
+                RClassMember::TsIndexSignature(..) | RClassMember::StaticBlock(..) | RClassMember::Empty(..) => {}
+            }
+        }
+    }
+
+    /// `kind` is ignored if `member` is not an accessor.
+    fn check_member_decorators(
+        &mut self,
+        decorators: &[RDecorator],
+        kind: MethodKind,
+        this: &Type,
+        member: &ClassMember,
+        members: &[(usize, ClassMember)],
+        is_private: bool,
+    ) {
+        if decorators.is_empty() {
+            return;
+        }
+
+        let legacy = self.rule().experimental_decorators;
+
+        for d in decorators {
+            if legacy && is_private {
+                self.storage.report(ErrorKind::DecoratorNotValidHere { span: d.span }.into());
+                continue;
+            }
+
+            let callee = match d.expr.validate_with_default(self).report(&mut self.storage) {
+                Some(ty) => ty.freezed(),
+                None => continue,
+            };
+            let span = d.span;
+
+            let call = match member {
+                ClassMember::Method(m) => {
+                    let value = method_type(m);
+
+                    if legacy {
+                        let descriptor = property_descriptor(span, value);
+                        DecoratorCall {
+                            kind: DecoratorKind::Method,
+                            args: vec![this.clone(), key_type(&m.key), descriptor.clone()],
+                            expected_ret_ty: descriptor,
+                        }
+                    } else {
+                        DecoratorCall {
+                            kind: DecoratorKind::Method,
+                            args: vec![
+                                value.clone(),
+                                decorator_context(span, "ClassMethodDecoratorContext", vec![this.clone(), value.clone()]),
+                            ],
+                            expected_ret_ty: value,
+                        }
+                    }
+                }
+
+                ClassMember::Property(p) if p.accessor.getter || p.accessor.setter => {
+                    let value = accessor_value_type(span, p, kind, members);
+
+                    if legacy {
+                        let descriptor = property_descriptor(span, value);
+                        DecoratorCall {
+                            kind: DecoratorKind::Method,
+                            args: vec![this.clone(), key_type(&p.key), descriptor.clone()],
+                            expected_ret_ty: descriptor,
+                        }
+                    } else if kind == MethodKind::Setter {
+                        let setter = fn_type(span, vec![value.clone()], Type::void(span, Default::default()));
+                        DecoratorCall {
+                            kind: DecoratorKind::Method,
+                            args: vec![
+                                setter.clone(),
+                                decorator_context(span, "ClassSetterDecoratorContext", vec![this.clone(), value]),
+                            ],
+                            expected_ret_ty: setter,
+                        }
+                    } else {
+                        let getter = fn_type(span, vec![], value.clone());
+                        DecoratorCall {
+                            kind: DecoratorKind::Method,
+                            args: vec![
+                                getter.clone(),
+                                decorator_context(span, "ClassGetterDecoratorContext", vec![this.clone(), value]),
+                            ],
+                            expected_ret_ty: getter,
+                        }
+                    }
+                }
+
+                ClassMember::Property(p) => {
+                    let value = p.value.as_deref().cloned().unwrap_or_else(|| Type::any(span, Default::default()));
+
+                    if legacy {
+                        DecoratorCall {
+                            kind: DecoratorKind::Property,
+                            args: vec![this.clone(), key_type(&p.key)],
+                            expected_ret_ty: Type::void(span, Default::default()),
+                        }
+                    } else {
+                        DecoratorCall {
+                            kind: DecoratorKind::Property,
+                            args: vec![
+                                Type::undefined(span, Default::default()),
+                                decorator_context(span, "ClassFieldDecoratorContext", vec![this.clone(), value.clone()]),
+                            ],
+                            expected_ret_ty: fn_type(span, vec![value.clone()], value),
+                        }
+                    }
+                }
+
+                _ => continue,
+            };
+
+            self.check_decorator_call(span, &callee, call);
+        }
+    }
+
+    fn check_param_decorators(&mut self, decorators: &[RDecorator], target: &Type, key: Type, idx: usize) {
+        for d in decorators {
+            if !self.rule().experimental_decorators {
+                self.storage.report(ErrorKind::DecoratorNotValidHere { span: d.span }.into());
+                continue;
+            }
+
+            let callee = match d.expr.validate_with_default(self).report(&mut self.storage) {
+                Some(ty) => ty.freezed(),
+                None => continue,
+            };
+
+            let index = Type::Lit(LitType {
+                span: d.span,
+                lit: RTsLit::Number(RNumber {
+                    span: d.span,
+                    value: idx as f64,
+                    raw: None,
+                }),
+                metadata: Default::default(),
+            });
+
+            self.check_decorator_call(
+                d.span,
+                &callee,
+                DecoratorCall {
+                    kind: DecoratorKind::Param,
+                    args: vec![target.clone(), key.clone(), index],
+                    expected_ret_ty: Type::void(d.span, Default::default()),
+                },
+            );
+        }
+    }
+
+    fn check_decorator_call(&mut self, span: Span, callee: &Type, call: DecoratorCall) {
+        let ret_ty = match self
+            .call_with_arg_types(span, callee, call.args)
+            .context("tried to call a decorator with implicit arguments")
+        {
+            Ok(ty) => ty,
+            Err(err) => {
+                let cause = box err;
+                let err = match call.kind {
+                    DecoratorKind::Class => ErrorKind::InvalidClassDecorator { span, cause },
+                    DecoratorKind::Method => ErrorKind::InvalidMethodDecorator { span, cause },
+                    DecoratorKind::Property => ErrorKind::InvalidPropertyDecorator { span, cause },
+                    DecoratorKind::Param => ErrorKind::InvalidParamDecorator { span, cause },
+                };
+                self.storage.report(err.into());
+                return;
+            }
+        };
+
+        // A decorator may return nothing to keep the original value.
+        let expected = Type::new_union(span, vec![call.expected_ret_ty, Type::void(span, Default::default())]);
+
+        self.assign_with_opts(
+            &mut Default::default(),
+            &expected,
+            &ret_ty,
+            AssignOpts {
+                span,
+                ..Default::default()
+            },
+        )
+        .context("tried to assign the return type of a decorator to the expected type")
+        .convert_err(|err| ErrorKind::InvalidDecoratorReturnType {
+            span,
+            cause: box err.into(),
+        })
+        .report(&mut self.storage);
+    }
+}
+
+pub(super) fn has_decorators(member: &RClassMember) -> bool {
+    match member {
+        RClassMember::Constructor(c) => c.params.iter().any(|p| match p {
+            RParamOrTsParamProp::TsParamProp(p) => !p.decorators.is_empty(),
+            RParamOrTsParamProp::Param(p) => !p.decorators.is_empty(),
+        }),
+        RClassMember::Method(m) => !m.function.decorators.is_empty() || m.function.params.iter().any(|p| !p.decorators.is_empty()),
+        RClassMember::PrivateMethod(m) => !m.function.decorators.is_empty(),
+        RClassMember::ClassProp(p) => !p.decorators.is_empty(),
+        RClassMember::PrivateProp(p) => !p.decorators.is_empty(),
+        RClassMember::TsIndexSignature(..) | RClassMember::StaticBlock(..) | RClassMember::Empty(..) => false,
+    }
+}
+
+fn key_type(key: &Key) -> Type {
+    match key {
+        Key::Private(p) => Type::any(p.span, Default::default()),
+        _ => key.ty().into_owned(),
+    }
+}
+
+fn method_type(m: &Method) -> Type {
+    Type::Function(Function {
+        span: m.span,
+        type_params: m.type_params.clone(),
+        params: m.params.clone(),
+        ret_ty: m.ret_ty.clone(),
+        metadata: Default::default(),
+    })
+}
+
+/// The type of the property accessed by a getter or a setter.
+///
+/// The property type of a setter without a type annotation is taken from the
+/// getter with the same key, if there's one.
+fn accessor_value_type(span: Span, p: &ClassProperty, kind: MethodKind, members: &[(usize, ClassMember)]) -> Type {
+    if let Some(value) = &p.value {
+        let is_implicit_any = matches!(
+            value.normalize(),
+            Type::Keyword(KeywordType {
+                kind: TsKeywordTypeKind::TsAnyKeyword,
+                metadata,
+                ..
+            }) if metadata.common.implicit
+        );
+
+        if kind != MethodKind::Setter || !is_implicit_any {
+            return (**value).clone();
+        }
+    }
+
+    members
+        .iter()
+        .find_map(|(_, m)| match m {
+            ClassMember::Property(getter) if getter.accessor.getter && getter.is_static == p.is_static && getter.key.type_eq(&p.key) => {
+                getter.value.as_deref().cloned()
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| Type::any(span, Default::default()))
+}
+
+fn fn_type(span: Span, params: Vec<Type>, ret_ty: Type) -> Type {
+    Type::Function(Function {
+        span,
+        type_params: None,
+        params: params
+            .into_iter()
+            .map(|ty| FnParam {
+                span,
+                required: true,
+                pat: RPat::Ident(RBindingIdent {
+                    node_id: NodeId::invalid(),
+                    id: RIdent::new("value".into(), DUMMY_SP),
+                    type_ann: None,
+                }),
+                ty: box ty,
+            })
+            .collect(),
+        ret_ty: box ret_ty,
+        metadata: Default::default(),
+    })
+}
+
+fn property_descriptor(span: Span, value: Type) -> Type {
+    decorator_context(span, "TypedPropertyDescriptor", vec![value])
+}
+
+/// Creates a reference to a global interface used by decorators.
+fn decorator_context(span: Span, name: &str, type_args: Vec<Type>) -> Type {
+    Type::Ref(Ref {
+        span,
+        type_name: RTsEntityName::Ident(RIdent::new(name.into(), DUMMY_SP)),
+        type_args: Some(box TypeParamInstantiation { span, params: type_args }),
+        metadata: Default::default(),
+    })
+}
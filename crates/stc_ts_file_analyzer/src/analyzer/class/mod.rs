@@ -21,7 +21,7 @@ use swc_common::{iter::IdentifyLast, EqIgnoreSpan, Span, Spanned, SyntaxContext,
 use swc_ecma_ast::*;
 use swc_ecma_utils::private_ident;
 
-use self::type_param::StaticTypeParamValidator;
+use self::{decorators::has_decorators, type_param::StaticTypeParamValidator};
 use crate::{
     analyzer::{
         assign::AssignOpts,
@@ -37,7 +37,10 @@ use crate::{
     VResult,
 };
 
+mod decorators;
 mod order;
+mod overrides;
+mod prop_init;
 mod type_param;
 
 #[derive(Debug, Default)]
@@ -67,34 +70,6 @@ impl Analyzer<'_, '_> {
         };
 
         if !self.is_builtin {
-            // Disabled because of false positives when the constructor initializes the
-            // field.
-            #[allow(clippy::overly_complex_bool_expr)]
-            if false && self.rule().strict_null_checks {
-                if value.is_none() {
-                    if let Some(ty) = &ty {
-                        if self
-                            .assign_with_opts(
-                                &mut Default::default(),
-                                ty,
-                                &Type::Keyword(KeywordType {
-                                    span,
-                                    kind: TsKeywordTypeKind::TsUndefinedKeyword,
-                                    metadata: Default::default(),
-                                }),
-                                AssignOpts {
-                                    span,
-                                    ..Default::default()
-                                },
-                            )
-                            .is_err()
-                        {
-                            self.storage.report(ErrorKind::ClassPropNotInitialized { span }.into())
-                        }
-                    }
-                }
-            }
-
             // Report error if type is not found.
             if let Some(ty) = &ty {
                 self.normalize(Some(span), Cow::Borrowed(ty), Default::default())
@@ -188,6 +163,7 @@ impl Analyzer<'_, '_> {
             is_optional: p.is_optional,
             readonly: p.readonly,
             definite: p.definite,
+            write_ty: None,
             accessor: Default::default(),
         })
     }
@@ -200,7 +176,7 @@ impl Analyzer<'_, '_> {
             self.storage.report(ErrorKind::ConstructorIsKeyword { span: p.key.id.span }.into());
         }
 
-        let key = Key::Private(p.key.clone().into());
+        let key = p.key.validate_with(self).map(Key::Private)?;
 
         let value = self
             .validate_type_of_class_property(p.span, p.readonly, p.is_static, &p.type_ann, &p.value)?
@@ -223,6 +199,7 @@ impl Analyzer<'_, '_> {
             is_optional: p.is_optional,
             readonly: p.readonly,
             definite: p.definite,
+            write_ty: None,
             accessor: Default::default(),
         })
     }
@@ -476,6 +453,10 @@ impl Analyzer<'_, '_> {
         let key = c.key.validate_with(self).map(Key::Private)?;
         let key_span = key.span();
 
+        if c.kind == MethodKind::Getter || c.kind == MethodKind::Setter {
+            self.report_error_for_accessor_below_es5(key_span);
+        }
+
         let (type_params, params, ret_ty) = self.with_child(
             ScopeKind::Method { is_static: c.is_static },
             Default::default(),
@@ -536,6 +517,7 @@ impl Analyzer<'_, '_> {
                 is_optional: c.is_optional,
                 readonly: false,
                 definite: false,
+                write_ty: None,
                 accessor: Accessor {
                     getter: true,
                     setter: false,
@@ -544,13 +526,17 @@ impl Analyzer<'_, '_> {
             MethodKind::Setter => Ok(ClassMember::Property(ClassProperty {
                 span: c.span,
                 key,
-                value: Some(ret_ty),
+                value: match params.get(0) {
+                    Some(p) => Some(p.ty.clone()),
+                    None => Some(box Type::any(key_span, Default::default())),
+                },
                 is_static: c.is_static,
                 accessibility: c.accessibility,
                 is_abstract: c.is_abstract,
                 is_optional: c.is_optional,
                 readonly: false,
                 definite: false,
+                write_ty: None,
                 accessor: Accessor {
                     getter: false,
                     setter: true,
@@ -572,6 +558,10 @@ impl Analyzer<'_, '_> {
         let c_span = c.span();
         let key_span = c.key.span();
 
+        if c.kind == MethodKind::Getter || c.kind == MethodKind::Setter {
+            self.report_error_for_accessor_below_es5(key_span);
+        }
+
         let (params, type_params, declared_ret_ty, inferred_ret_ty) = self.with_child(
             ScopeKind::Method { is_static: c.is_static },
             Default::default(),
@@ -722,6 +712,7 @@ impl Analyzer<'_, '_> {
                 is_optional: c.is_optional,
                 readonly: false,
                 definite: false,
+                write_ty: None,
                 accessor: Accessor {
                     getter: true,
                     setter: false,
@@ -742,6 +733,7 @@ impl Analyzer<'_, '_> {
                 is_optional: c.is_optional,
                 readonly: false,
                 definite: false,
+                write_ty: None,
                 accessor: Accessor {
                     getter: false,
                     setter: true,
@@ -1381,7 +1373,13 @@ impl Analyzer<'_, '_> {
                                 }
                             }
 
-                            continue 'outer;
+                            if !super_property.is_abstract {
+                                new_members.push(sm.clone());
+                                continue 'outer;
+                            }
+                            if super_property.is_optional {
+                                continue 'outer;
+                            }
                         }
                         ClassMember::Method(super_method) => {
                             if !super_method.is_abstract {
@@ -1418,6 +1416,7 @@ impl Analyzer<'_, '_> {
                         errors.push(
                             ErrorKind::ClassDoesNotImplementMemeber {
                                 span,
+                                declared_at: key.span(),
                                 key: box key.into_owned(),
                             }
                             .into(),
@@ -1461,7 +1460,9 @@ impl Analyzer<'_, '_> {
             has_body: !self.ctx.in_declare,
         };
 
-        c.decorators.visit_with(self);
+        let class_decorators = self.validate_class_decorators(&c.decorators);
+        let is_decorated = !class_decorators.is_empty() || c.body.iter().any(has_decorators);
+
         let name = self.scope.this_class_name.take();
         if let Some(i) = &name {
             match &**i.sym() {
@@ -1805,6 +1806,7 @@ impl Analyzer<'_, '_> {
                                         is_optional: false,
                                         readonly: p.readonly,
                                         definite: false,
+                                        write_ty: None,
                                         accessor: Default::default(),
                                     }),
                                 ));
@@ -1891,6 +1893,10 @@ impl Analyzer<'_, '_> {
                 take(&mut child.scope.this_class_members)
             };
 
+            // Decorators should be checked against getters and setters before they are
+            // merged.
+            let decorated_members = if is_decorated { body.clone() } else { vec![] };
+
             let body = child.combine_class_properties(body);
 
             if !additional_members.is_empty() {
@@ -1923,9 +1929,15 @@ impl Analyzer<'_, '_> {
                 .report(&mut child.storage);
 
             child.validate_inherited_members_from_super_class(None, &class);
+            child.report_errors_for_override_modifiers(c, &class);
+            child.report_errors_for_uninitialized_props(c, &class);
             child.report_errors_for_wrong_impls_of_class(None, &class);
             child.report_errors_for_confliicting_interfaces(&class.implements);
 
+            if is_decorated {
+                child.check_decorators_of_class(c, class_decorators, &decorated_members, &class);
+            }
+
             Ok(class)
         })?;
 
@@ -2006,6 +2018,10 @@ impl Analyzer<'_, '_> {
 impl Analyzer<'_, '_> {
     /// This method combines setters and getters, and merge it just like a
     /// normal property.
+    ///
+    /// If the parameter type of a setter is different from the type of the
+    /// getter, it's stored as `write_ty` and the type of the getter should be
+    /// assignable to it.
     fn combine_class_properties(&mut self, body: Vec<(usize, ClassMember)>) -> Vec<(usize, ClassMember)> {
         let mut getters = vec![];
         let mut setters = vec![];
@@ -2024,20 +2040,23 @@ impl Analyzer<'_, '_> {
 
             if let ClassMember::Property(ClassProperty {
                 key,
+                is_static,
+                value,
                 accessor: Accessor { setter: true, .. },
                 ..
             }) = body
             {
-                setters.push(key.clone());
+                setters.push((key.clone(), *is_static, value.clone()));
             }
 
             if let ClassMember::Property(ClassProperty {
                 key,
+                is_static,
                 accessor: Accessor { getter: true, .. },
                 ..
             }) = body
             {
-                getters.push(key.clone());
+                getters.push((key.clone(), *is_static));
             }
         }
 
@@ -2052,7 +2071,11 @@ impl Analyzer<'_, '_> {
 
                 match member {
                     ClassMember::Property(ClassProperty {
+                        span,
                         ref key,
+                        is_static,
+                        ref value,
+                        ref mut write_ty,
                         accessor:
                             Accessor {
                                 getter: true,
@@ -2060,18 +2083,58 @@ impl Analyzer<'_, '_> {
                             },
                         ..
                     }) => {
-                        if setters.iter().any(|setter_key| setter_key.type_eq(key)) {
+                        if let Some((_, _, setter_ty)) = setters
+                            .iter()
+                            .find(|(setter_key, setter_is_static, _)| *setter_is_static == is_static && setter_key.type_eq(key))
+                        {
                             *setter = true;
+
+                            if let (Some(getter_ty), Some(setter_ty)) = (value, setter_ty) {
+                                // The type of a setter without a type annotation is inferred from the
+                                // getter.
+                                let is_implicit = matches!(
+                                    setter_ty.normalize(),
+                                    Type::Keyword(KeywordType {
+                                        kind: TsKeywordTypeKind::TsAnyKeyword,
+                                        metadata,
+                                        ..
+                                    }) if metadata.common.implicit
+                                );
+
+                                if !is_implicit && !getter_ty.type_eq(setter_ty) {
+                                    self.assign_with_opts(
+                                        &mut Default::default(),
+                                        setter_ty,
+                                        getter_ty,
+                                        AssignOpts {
+                                            span,
+                                            ..Default::default()
+                                        },
+                                    )
+                                    .context("tried to assign the type of a getter to the type of the setter")
+                                    .convert_err(|err| ErrorKind::GetterTypeNotAssignableToSetterType {
+                                        span,
+                                        cause: box err.into(),
+                                    })
+                                    .report(&mut self.storage);
+
+                                    *write_ty = Some(setter_ty.clone());
+                                }
+                            }
                         }
 
                         Some((idx, member))
                     }
                     ClassMember::Property(ClassProperty {
                         ref key,
+                        is_static,
                         accessor: Accessor { setter: true, .. },
                         ..
                     }) => {
-                        if getters.iter().any(|getter_key| getter_key.type_eq(key)) {
+                        if getters
+                            .iter()
+                            .any(|(getter_key, getter_is_static)| *getter_is_static == is_static && getter_key.type_eq(key))
+                        {
                             return None;
                         }
 
@@ -2083,51 +2146,92 @@ impl Analyzer<'_, '_> {
             .collect()
     }
 
-    /// If a class have an index signature, properties should be compatible with
-    /// it.
+    /// Members of a class should be compatible with the index signatures of
+    /// the same side (static or instance).
     fn report_errors_for_class_member_incompatible_with_index_signature(&mut self, class: &ClassDef) -> VResult<()> {
-        let index = match self
-            .get_index_signature_from_class(class.span, class)
-            .context("tried to get index signature from a class")?
-        {
-            Some(v) => v,
-            None => return Ok(()),
-        };
-        let index_ret_ty = match &index.type_ann {
-            Some(v) => v,
-            // It's `any`, so we don't have to verify.
-            None => return Ok(()),
-        };
-
-        for member in &class.body {
-            if let ClassMember::Property(ClassProperty {
-                key, value: Some(value), ..
-            }) = member
-            {
-                let span = key.span();
+        for is_static in [false, true] {
+            let mut indexes = class
+                .body
+                .iter()
+                .filter_map(|member| match member {
+                    ClassMember::IndexSignature(i) if i.is_static == is_static => Some(i.clone()),
+                    _ => None,
+                })
+                .collect_vec();
+
+            if indexes.is_empty() && !is_static {
+                if let Some(super_class) = &class.super_class {
+                    indexes.extend(
+                        self.get_index_signature(class.span, super_class)
+                            .context("tried to get index signature from a super class")?,
+                    );
+                }
+            }
 
-                if !index.params[0].ty.is_kwd(TsKeywordTypeKind::TsStringKeyword)
-                    && self.assign(span, &mut Default::default(), &index.params[0].ty, &key.ty()).is_err()
-                {
+            for index in &indexes {
+                if index.params.len() != 1 {
                     continue;
                 }
+                let index_ret_ty = match &index.type_ann {
+                    Some(v) => v,
+                    // It's `any`, so we don't have to verify.
+                    None => continue,
+                };
+                let index_key_ty = &index.params[0].ty;
+
+                for member in &class.body {
+                    let (key, value) = match member {
+                        ClassMember::Property(ClassProperty {
+                            key,
+                            value: Some(value),
+                            is_static: member_is_static,
+                            ..
+                        }) if *member_is_static == is_static => (key, Cow::Borrowed(&**value)),
+
+                        ClassMember::Method(m) if m.is_static == is_static => (
+                            &m.key,
+                            Cow::Owned(Type::Function(stc_ts_types::Function {
+                                span: m.span,
+                                type_params: m.type_params.clone(),
+                                params: m.params.clone(),
+                                ret_ty: m.ret_ty.clone(),
+                                metadata: Default::default(),
+                            })),
+                        ),
+
+                        _ => continue,
+                    };
 
-                self.assign_with_opts(
-                    &mut Default::default(),
-                    index_ret_ty,
-                    value,
-                    AssignOpts {
-                        span,
-                        ..Default::default()
-                    },
-                )
-                .convert_err(|_err| {
-                    if index.params[0].ty.is_kwd(TsKeywordTypeKind::TsNumberKeyword) {
-                        ErrorKind::ClassMemberNotCompatibleWithNumericIndexSignature { span }
-                    } else {
-                        ErrorKind::ClassMemberNotCompatibleWithStringIndexSignature { span }
+                    if key.is_private() {
+                        continue;
                     }
-                })?;
+
+                    let span = key.span();
+
+                    if !index_key_ty.is_kwd(TsKeywordTypeKind::TsStringKeyword)
+                        && self.assign(span, &mut Default::default(), index_key_ty, &key.ty()).is_err()
+                    {
+                        continue;
+                    }
+
+                    self.assign_with_opts(
+                        &mut Default::default(),
+                        index_ret_ty,
+                        &value,
+                        AssignOpts {
+                            span,
+                            ..Default::default()
+                        },
+                    )
+                    .convert_err(|_err| {
+                        if index_key_ty.is_kwd(TsKeywordTypeKind::TsNumberKeyword) {
+                            ErrorKind::ClassMemberNotCompatibleWithNumericIndexSignature { span }
+                        } else {
+                            ErrorKind::ClassMemberNotCompatibleWithStringIndexSignature { span }
+                        }
+                    })
+                    .report(&mut self.storage);
+                }
             }
         }
 
@@ -0,0 +1,160 @@
+use std::borrow::Cow;
+
+use stc_ts_ast_rnode::{RClass, RClassMember, RParamOrTsParamProp, RPat, RPropName, RTsParamPropParam};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::{ClassDef, ClassMember, Key, Type};
+use swc_common::{Span, Spanned, TypeEq};
+
+use crate::analyzer::Analyzer;
+
+/// A member of a super class with the same name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BaseMember {
+    Concrete,
+    Abstract,
+    Absent,
+    /// The super class is not a class declaration, so we can't know.
+    Unknown,
+}
+
+impl Analyzer<'_, '_> {
+    /// Checks `override` modifiers of members of `c`, and reports members
+    /// overriding a member of the super class without it if
+    /// `noImplicitOverride` is enabled.
+    pub(super) fn report_errors_for_override_modifiers(&mut self, c: &RClass, class: &ClassDef) {
+        if self.is_builtin {
+            return;
+        }
+
+        let require_override = self.rule().no_implicit_override && !self.ctx.in_declare;
+
+        for member in &c.body {
+            match member {
+                RClassMember::Method(m) => {
+                    if let Some(key) = key_of_prop_name(&m.key) {
+                        self.check_override_modifier(class, key, m.is_static, m.is_abstract, m.is_override, require_override, false);
+                    }
+                }
+
+                RClassMember::ClassProp(p) => {
+                    if let Some(key) = key_of_prop_name(&p.key) {
+                        self.check_override_modifier(class, key, p.is_static, p.is_abstract, p.is_override, require_override, false);
+                    }
+                }
+
+                RClassMember::Constructor(ctor) => {
+                    for param in &ctor.params {
+                        if let RParamOrTsParamProp::TsParamProp(p) = param {
+                            let id = match &p.param {
+                                RTsParamPropParam::Ident(i) => &i.id,
+                                RTsParamPropParam::Assign(p) => match &*p.left {
+                                    RPat::Ident(i) => &i.id,
+                                    _ => continue,
+                                },
+                            };
+                            let key = Key::Normal {
+                                span: id.span,
+                                sym: id.sym.clone(),
+                            };
+
+                            self.check_override_modifier(class, key, false, false, p.is_override, require_override, true);
+                        }
+                    }
+                }
+
+                _ => {}
+            }
+        }
+    }
+
+    fn check_override_modifier(
+        &mut self,
+        class: &ClassDef,
+        key: Key,
+        is_static: bool,
+        is_abstract: bool,
+        is_override: bool,
+        require_override: bool,
+        is_param: bool,
+    ) {
+        let span = key.span();
+
+        let super_class = match &class.super_class {
+            Some(v) => v,
+            None => {
+                if is_override {
+                    self.storage.report(ErrorKind::OverrideWithoutSuperClass { span }.into());
+                }
+                return;
+            }
+        };
+
+        if !is_override && !require_override {
+            return;
+        }
+
+        match self.find_base_member(span, super_class, &key, is_static) {
+            BaseMember::Concrete if !is_override => {
+                if is_param {
+                    self.storage.report(ErrorKind::MissingOverrideModifierOnParamProp { span }.into());
+                } else {
+                    self.storage.report(ErrorKind::MissingOverrideModifier { span }.into());
+                }
+            }
+            // Like tsc, a concrete implementation of an abstract member does not need it.
+            BaseMember::Abstract if !is_override && is_abstract => {
+                self.storage
+                    .report(ErrorKind::MissingOverrideModifierOnAbstractMember { span }.into());
+            }
+            BaseMember::Absent if is_override => {
+                self.storage.report(ErrorKind::OverrideOfNonExistentMember { span }.into());
+            }
+            _ => {}
+        }
+    }
+
+    /// Finds a member named `key` from `super_class` and its super classes.
+    fn find_base_member(&mut self, span: Span, super_class: &Type, key: &Key, is_static: bool) -> BaseMember {
+        let super_class = match self.normalize(Some(span), Cow::Borrowed(super_class), Default::default()) {
+            Ok(v) => v,
+            Err(..) => return BaseMember::Unknown,
+        };
+
+        let def = match super_class.normalize() {
+            Type::ClassDef(def) => def,
+            _ => return BaseMember::Unknown,
+        };
+
+        for member in &def.body {
+            let (member_key, member_is_static, is_abstract) = match member {
+                ClassMember::Method(m) => (&m.key, m.is_static, m.is_abstract),
+                ClassMember::Property(p) => (&p.key, p.is_static, p.is_abstract),
+                _ => continue,
+            };
+
+            if member_is_static == is_static && member_key.type_eq(key) {
+                return if is_abstract { BaseMember::Abstract } else { BaseMember::Concrete };
+            }
+        }
+
+        match &def.super_class {
+            Some(super_class) => self.find_base_member(span, super_class, key, is_static),
+            None => BaseMember::Absent,
+        }
+    }
+}
+
+pub(super) fn key_of_prop_name(name: &RPropName) -> Option<Key> {
+    match name {
+        RPropName::Ident(i) => Some(Key::Normal {
+            span: i.span,
+            sym: i.sym.clone(),
+        }),
+        RPropName::Str(s) => Some(Key::Normal {
+            span: s.span,
+            sym: s.value.clone(),
+        }),
+        RPropName::Num(n) => Some(Key::Num(n.clone())),
+        RPropName::BigInt(..) | RPropName::Computed(..) => None,
+    }
+}
@@ -0,0 +1,294 @@
+use rnode::{Visit, VisitWith};
+use stc_ts_ast_rnode::{
+    RArrowExpr, RAssignExpr, RBinExpr, RBlockStmt, RClass, RClassMember, RCondExpr, RExpr, RFunction, RLit, RMemberExpr, RMemberProp, RPat,
+    RPatOrExpr, RStmt,
+};
+use stc_ts_errors::ErrorKind;
+use stc_ts_types::{ClassDef, ClassMember, Key, KeywordType, Type};
+use stc_utils::AHashSet;
+use swc_atoms::JsWord;
+use swc_common::{Span, Spanned, TypeEq};
+use swc_ecma_ast::{BinaryOp, TsKeywordTypeKind};
+
+use super::overrides::key_of_prop_name;
+use crate::analyzer::{assign::AssignOpts, Analyzer};
+
+/// `(is_private, name)` of a property assigned using `this`.
+type PropName = (bool, JsWord);
+
+impl Analyzer<'_, '_> {
+    /// Reports instance properties which are not initialized by an initializer
+    /// nor definitely assigned in the constructor, if
+    /// `strictPropertyInitialization` is enabled.
+    pub(super) fn report_errors_for_uninitialized_props(&mut self, c: &RClass, class: &ClassDef) {
+        if self.is_builtin || self.ctx.in_declare {
+            return;
+        }
+
+        let rule = self.rule();
+        if !rule.strict_property_initialization || !rule.strict_null_checks {
+            return;
+        }
+
+        let assigned = c
+            .body
+            .iter()
+            .find_map(|member| match member {
+                RClassMember::Constructor(ctor) => ctor.body.as_ref(),
+                _ => None,
+            })
+            .map(|body| assigned_in_block(body).assigned)
+            .unwrap_or_default();
+
+        for member in &c.body {
+            let (span, key, name) = match member {
+                RClassMember::ClassProp(p) => {
+                    if p.is_static || p.value.is_some() || p.type_ann.is_none() || p.definite || p.declare || p.is_abstract || p.is_optional
+                    {
+                        continue;
+                    }
+
+                    let key = match key_of_prop_name(&p.key) {
+                        Some(v) => v,
+                        None => continue,
+                    };
+                    let name = match &key {
+                        Key::Normal { sym, .. } => (false, sym.clone()),
+                        _ => continue,
+                    };
+                    (key.span(), key, name)
+                }
+
+                RClassMember::PrivateProp(p) => {
+                    if p.is_static || p.value.is_some() || p.type_ann.is_none() || p.definite || p.is_optional {
+                        continue;
+                    }
+
+                    (p.key.span, Key::Private(p.key.clone().into()), (true, p.key.id.sym.clone()))
+                }
+
+                _ => continue,
+            };
+
+            if assigned.contains(&name) {
+                continue;
+            }
+
+            let ty = class.body.iter().find_map(|member| match member {
+                ClassMember::Property(p) if !p.is_static && p.key.type_eq(&key) => p.value.clone(),
+                _ => None,
+            });
+            let ty = match ty {
+                Some(v) => v,
+                None => continue,
+            };
+
+            if self.accepts_undefined(span, &ty) {
+                continue;
+            }
+
+            self.storage.report(ErrorKind::ClassPropNotInitialized { span }.into());
+        }
+    }
+
+    fn accepts_undefined(&mut self, span: Span, ty: &Type) -> bool {
+        self.assign_with_opts(
+            &mut Default::default(),
+            ty,
+            &Type::Keyword(KeywordType {
+                span,
+                kind: TsKeywordTypeKind::TsUndefinedKeyword,
+                metadata: Default::default(),
+            }),
+            AssignOpts {
+                span,
+                ..Default::default()
+            },
+        )
+        .is_ok()
+    }
+}
+
+/// Properties definitely assigned by a list of statements.
+#[derive(Debug, Default)]
+struct Flow {
+    assigned: AHashSet<PropName>,
+    /// `true` if the end of the statements is unreachable, so any property can
+    /// be treated as assigned.
+    diverges: bool,
+}
+
+impl Flow {
+    fn merge(self, other: Flow) -> Flow {
+        match (self.diverges, other.diverges) {
+            (true, _) => other,
+            (_, true) => self,
+            _ => Flow {
+                assigned: self.assigned.intersection(&other.assigned).cloned().collect(),
+                diverges: false,
+            },
+        }
+    }
+}
+
+fn assigned_in_block(b: &RBlockStmt) -> Flow {
+    assigned_in_stmts(&b.stmts)
+}
+
+fn assigned_in_stmts(stmts: &[RStmt]) -> Flow {
+    let mut flow = Flow::default();
+
+    for stmt in stmts {
+        let Flow { assigned, diverges } = assigned_in_stmt(stmt);
+        flow.assigned.extend(assigned);
+        if diverges {
+            flow.diverges = true;
+            break;
+        }
+    }
+
+    flow
+}
+
+fn assigned_in_stmt(s: &RStmt) -> Flow {
+    match s {
+        RStmt::Block(b) => assigned_in_block(b),
+
+        RStmt::If(s) => {
+            let mut test = assigned_in_expr(&s.test);
+            let cons = assigned_in_stmt(&s.cons);
+            let alt = s.alt.as_deref().map(assigned_in_stmt).unwrap_or_default();
+            let branches = cons.merge(alt);
+
+            test.assigned.extend(branches.assigned);
+            test.diverges = branches.diverges;
+            test
+        }
+
+        RStmt::Try(s) => {
+            let mut flow = match &s.handler {
+                Some(handler) => assigned_in_block(&s.block).merge(assigned_in_block(&handler.body)),
+                None => assigned_in_block(&s.block),
+            };
+
+            if let Some(finalizer) = &s.finalizer {
+                let finalizer = assigned_in_block(finalizer);
+                flow.assigned.extend(finalizer.assigned);
+                flow.diverges |= finalizer.diverges;
+            }
+
+            flow
+        }
+
+        // The body of a `do-while` loop is executed at least once.
+        RStmt::DoWhile(s) => {
+            let mut flow = assigned_in_stmt(&s.body);
+            flow.diverges = false;
+            flow
+        }
+
+        RStmt::Return(s) => {
+            let mut flow = s.arg.as_deref().map(assigned_in_expr).unwrap_or_default();
+            flow.diverges = true;
+            flow
+        }
+
+        RStmt::Throw(s) => {
+            let mut flow = assigned_in_expr(&s.arg);
+            flow.diverges = true;
+            flow
+        }
+
+        RStmt::Expr(s) => assigned_in_expr(&s.expr),
+
+        RStmt::Decl(..) => {
+            let mut v = ThisAssignFinder::default();
+            s.visit_with(&mut v);
+            Flow {
+                assigned: v.assigned,
+                diverges: false,
+            }
+        }
+
+        // Loops and `switch` may not execute their bodies.
+        _ => Default::default(),
+    }
+}
+
+fn assigned_in_expr(e: &RExpr) -> Flow {
+    let mut v = ThisAssignFinder::default();
+    e.visit_with(&mut v);
+    Flow {
+        assigned: v.assigned,
+        diverges: false,
+    }
+}
+
+/// Finds `this.foo = value` which is evaluated unconditionally.
+#[derive(Default)]
+struct ThisAssignFinder {
+    assigned: AHashSet<PropName>,
+}
+
+impl Visit<RAssignExpr> for ThisAssignFinder {
+    fn visit(&mut self, e: &RAssignExpr) {
+        e.right.visit_with(self);
+
+        let left = match &e.left {
+            RPatOrExpr::Expr(e) => &**e,
+            RPatOrExpr::Pat(p) => match &**p {
+                RPat::Expr(e) => &**e,
+                _ => return,
+            },
+        };
+
+        if let RExpr::Member(RMemberExpr { obj, prop, .. }) = left {
+            if let RExpr::This(..) = &**obj {
+                match prop {
+                    RMemberProp::Ident(i) => {
+                        self.assigned.insert((false, i.sym.clone()));
+                    }
+                    RMemberProp::PrivateName(p) => {
+                        self.assigned.insert((true, p.id.sym.clone()));
+                    }
+                    RMemberProp::Computed(c) => {
+                        if let RExpr::Lit(RLit::Str(s)) = &*c.expr {
+                            self.assigned.insert((false, s.value.clone()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Only the left operand of logical operators is evaluated unconditionally.
+impl Visit<RBinExpr> for ThisAssignFinder {
+    fn visit(&mut self, e: &RBinExpr) {
+        e.left.visit_with(self);
+
+        if !matches!(e.op, BinaryOp::LogicalAnd | BinaryOp::LogicalOr | BinaryOp::NullishCoalescing) {
+            e.right.visit_with(self);
+        }
+    }
+}
+
+impl Visit<RCondExpr> for ThisAssignFinder {
+    fn visit(&mut self, e: &RCondExpr) {
+        e.test.visit_with(self);
+    }
+}
+
+/// Callbacks may not be called.
+impl Visit<RFunction> for ThisAssignFinder {
+    fn visit(&mut self, _: &RFunction) {}
+}
+
+impl Visit<RArrowExpr> for ThisAssignFinder {
+    fn visit(&mut self, _: &RArrowExpr) {}
+}
+
+/// `this` of nested classes is different.
+impl Visit<RClass> for ThisAssignFinder {
+    fn visit(&mut self, _: &RClass) {}
+}
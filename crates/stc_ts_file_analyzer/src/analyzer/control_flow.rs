@@ -13,7 +13,9 @@ use stc_ts_ast_rnode::{
 };
 use stc_ts_errors::{debug::dump_type_as_string, DebugExt, ErrorKind};
 use stc_ts_type_ops::Fix;
-use stc_ts_types::{name::Name, Array, ArrayMetadata, Id, Key, KeywordType, KeywordTypeMetadata, Union};
+use stc_ts_types::{
+    name::Name, Array, ArrayMetadata, Id, Key, KeywordType, KeywordTypeMetadata, PropertySignature, TypeElement, TypeLit, TypeParam, Union,
+};
 use stc_ts_utils::MapWithMut;
 use stc_utils::{
     cache::Freeze,
@@ -42,6 +44,16 @@ use crate::{
     VResult,
 };
 
+/// Whether a type has a property, used to narrow types with `in`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PropertyPresence {
+    Required,
+    Optional,
+    Absent,
+    /// The type does not declare its properties, like `object`.
+    Unknown,
+}
+
 /// Conditional facts
 #[derive(Debug, Clone, Default, PartialEq)]
 pub(crate) struct CondFacts {
@@ -114,6 +126,38 @@ impl CondFacts {
         }
     }
 
+    /// Merges facts of two code paths which reach the same code, like
+    /// `case 'a': case 'b':`. Only facts known for both paths are kept.
+    pub(crate) fn merge_alternatives(mut self, mut other: Self) -> Self {
+        let facts = self
+            .facts
+            .drain()
+            .filter_map(|(k, v)| Some((k.clone(), v & other.facts.remove(&k)?)))
+            .collect();
+
+        let vars = self
+            .vars
+            .drain()
+            .filter_map(|(k, v)| {
+                let r = other.vars.remove(&k)?;
+                Some((k, Type::new_union(v.span(), vec![v, r]).freezed()))
+            })
+            .collect();
+
+        Self {
+            facts,
+            vars,
+            ..Default::default()
+        }
+    }
+
+    /// Removes facts about references which do not satisfy `f`.
+    pub(crate) fn retain_names(&mut self, mut f: impl FnMut(&Name) -> bool) {
+        self.facts.retain(|name, _| f(name));
+        self.vars.retain(|name, _| f(name));
+        self.excludes.retain(|name, _| f(name));
+    }
+
     pub fn take(&mut self) -> Self {
         Self {
             facts: take(&mut self.facts),
@@ -377,6 +421,10 @@ impl Analyzer<'_, '_> {
 
         let ends_with_ret = stmt.cons.ends_with_ret();
 
+        let flow_of_test = self.data.flow.cur();
+        let end_label = self.data.flow.new_label();
+        self.data.flow.add_cond(flow_of_test, &true_facts);
+
         self.cur_facts = prev_facts.clone();
         self.with_child(ScopeKind::Flow, true_facts, |child: &mut Analyzer| {
             stmt.cons.visit_with(child);
@@ -387,6 +435,9 @@ impl Analyzer<'_, '_> {
         })
         .report(&mut self.storage);
 
+        self.data.flow.add_antecedent(end_label);
+        self.data.flow.add_cond(flow_of_test, &false_facts);
+
         let mut alt_ends_with_unreachable = None;
 
         if let Some(alt) = &stmt.alt {
@@ -401,6 +452,9 @@ impl Analyzer<'_, '_> {
             .report(&mut self.storage);
         }
 
+        self.data.flow.add_antecedent(end_label);
+        self.data.flow.finish_label(end_label);
+
         self.cur_facts = prev_facts;
 
         if ends_with_ret {
@@ -568,6 +622,16 @@ impl Analyzer<'_, '_> {
         let len = stmt.cases.len();
         let stmt_span = stmt.span();
 
+        // Facts for the body of the previous case, if it may fall through.
+        let mut fallthrough_facts: Option<CondFacts> = None;
+        // The default case is reached only if all previous tests failed.
+        let mut facts_for_default = CondFacts::default();
+
+        let flow_of_discriminant = self.data.flow.cur();
+        let end_label = self.data.flow.new_label();
+        self.data.flow.push_jump_target(end_label, None);
+        let mut fallthrough_flow = None;
+
         let mut errored = false;
         // Check cases *in order*
         for (i, case) in stmt.cases.iter().enumerate() {
@@ -608,16 +672,46 @@ impl Analyzer<'_, '_> {
                 }
             }
 
-            let true_facts_created_by_case = self.cur_facts.true_facts.take();
+            let mut true_facts_created_by_case = self.cur_facts.true_facts.take();
             let false_facts_created_by_case = self.cur_facts.false_facts.take();
 
+            let facts_for_case = match case.test {
+                Some(..) => true_facts_created_by_case.clone(),
+                None => self.narrow_by_excluded_discriminants(span, facts_for_default.clone()),
+            };
+
+            let case_label = self.data.flow.new_label();
+            self.data.flow.add_cond(flow_of_discriminant, &facts_for_case);
+            self.data.flow.add_antecedent(case_label);
+            if let Some(prev) = fallthrough_flow.take() {
+                self.data.flow.add_antecedents(case_label, &[prev]);
+            }
+            self.data.flow.finish_label(case_label);
+
+            let is_fallthrough_target = fallthrough_facts.is_some();
+            if let Some(prev) = fallthrough_facts.take() {
+                true_facts_created_by_case = true_facts_created_by_case.merge_alternatives(prev);
+            }
+
             let mut facts_for_body = base_true_facts.clone();
-            facts_for_body += true_facts_created_by_case;
+            if case.test.is_none() && !is_fallthrough_target {
+                facts_for_body += facts_for_case;
+            }
+            facts_for_body += true_facts_created_by_case.clone();
 
             self.with_child(ScopeKind::Flow, facts_for_body, |child| {
                 cons.visit_with(child);
                 Ok(())
             })?;
+            fallthrough_flow = Some(self.data.flow.cur());
+
+            if case.test.is_some() {
+                facts_for_default += false_facts_created_by_case.clone();
+            }
+
+            if !ends_with_ret && !self.is_switch_case_body_unconditional_termination(cons) {
+                fallthrough_facts = Some(true_facts_created_by_case);
+            }
 
             if ends_with_ret || last {
                 false_facts += false_facts_created_by_case.clone();
@@ -625,6 +719,21 @@ impl Analyzer<'_, '_> {
             }
         }
 
+        if let Some(last) = fallthrough_flow {
+            self.data.flow.add_antecedents(end_label, &[last]);
+        }
+        if stmt.cases.iter().all(|case| case.test.is_some()) {
+            let facts_for_default = self.narrow_by_excluded_discriminants(stmt_span, facts_for_default);
+
+            // An exhaustive `switch` does not fall through to the next statement.
+            if !facts_for_default.vars.values().any(|ty| ty.is_never()) {
+                self.data.flow.add_cond(flow_of_discriminant, &facts_for_default);
+                self.data.flow.add_antecedent(end_label);
+            }
+        }
+        self.data.flow.pop_jump_target();
+        self.data.flow.finish_label(end_label);
+
         if !errored {
             self.ctx.in_unreachable |= stmt
                 .cases
@@ -660,13 +769,23 @@ impl Analyzer<'_, '_> {
                 RStmt::Return(..) | RStmt::Throw(..) | RStmt::Continue(..) => return true,
                 RStmt::Break(..) => return false,
 
-                RStmt::If(s) => match &s.alt {
-                    Some(alt) => {
-                        return self.is_switch_case_body_unconditional_termination(&[&*s.cons])
-                            && self.is_switch_case_body_unconditional_termination(&[&**alt]);
+                RStmt::Block(b) => {
+                    if self.is_switch_case_body_unconditional_termination(&b.stmts) {
+                        return true;
                     }
-                    None => return self.is_switch_case_body_unconditional_termination(&[&*s.cons]),
-                },
+                    if b.stmts.ends_with_ret() {
+                        return false;
+                    }
+                }
+
+                // `if` without `else` may not be taken.
+                RStmt::If(RIfStmt { cons, alt: Some(alt), .. }) => {
+                    if self.is_switch_case_body_unconditional_termination(&[&**cons])
+                        && self.is_switch_case_body_unconditional_termination(&[&**alt])
+                    {
+                        return true;
+                    }
+                }
                 _ => {}
             }
         }
@@ -703,6 +822,8 @@ impl Analyzer<'_, '_> {
                     }
 
                     if let RExpr::Ident(left) = &**expr {
+                        self.data.flow.add_assign(Name::from(left), None);
+
                         if op == op!("??=") {
                             if let Ok(prev) = self.type_of_var(left, TypeOfMode::RValue, None) {
                                 let new_actual_ty = self.apply_type_facts_to_type(TypeFacts::NEUndefinedOrNull, prev);
@@ -794,6 +915,9 @@ impl Analyzer<'_, '_> {
             }
 
             RPat::Ident(i) => {
+                // Narrowing is reset even if the type of the variable is not updated below.
+                self.data.flow.add_assign(Name::from(&i.id), None);
+
                 // Verify using immutable references.
                 if let Some(var_info) = self.scope.get_var(&i.id.clone().into()) {
                     if let Some(mut var_ty) = var_info.ty.clone() {
@@ -869,7 +993,8 @@ impl Analyzer<'_, '_> {
                     let mut new_ty = actual_ty.unwrap_or_else(|| ty.clone());
                     new_ty.assert_valid();
                     new_ty.make_clone_cheap();
-                    var_info.actual_ty = Some(new_ty);
+                    var_info.actual_ty = Some(new_ty.clone());
+                    self.data.flow.add_assign(Name::from(&i.id), Some(new_ty));
                     return Ok(());
                 }
 
@@ -900,10 +1025,15 @@ impl Analyzer<'_, '_> {
                     return if self.ctx.allow_ref_declaring && self.scope.declaring.contains(&i.id.clone().into()) {
                         Ok(())
                     } else {
+                        let sym: Id = i.id.clone().into();
                         // undefined symbol
-                        Err(ErrorKind::UndefinedSymbol {
-                            sym: i.id.clone().into(),
-                            span: i.id.span,
+                        Err(match self.suggest_var_name(&sym) {
+                            Some(suggestion) => ErrorKind::NoSuchVarWithSuggestion {
+                                span: i.id.span,
+                                name: sym,
+                                suggestion,
+                            },
+                            None => ErrorKind::UndefinedSymbol { sym, span: i.id.span },
                         }
                         .into())
                     };
@@ -912,6 +1042,7 @@ impl Analyzer<'_, '_> {
                 // Variable is defined on parent scope.
                 //
                 // We copy varinfo with enhanced type.
+                self.data.flow.add_assign(Name::from(&i.id), var_info.actual_ty.clone());
                 self.scope.insert_var(i.id.clone().into(), var_info);
 
                 Ok(())
@@ -1099,13 +1230,13 @@ impl Analyzer<'_, '_> {
 
     /// While this type fact is in scope, the var named `sym` will be treated as
     /// `ty`.
-    pub(super) fn add_type_fact(&mut self, sym: &Id, ty: Type, exclude: Type) {
-        info!("add_type_fact({}); ty = {:?}", sym, ty);
+    pub(super) fn add_type_fact(&mut self, name: Name, ty: Type, exclude: Type) {
+        info!("add_type_fact({:?}); ty = {:?}", name, ty);
 
         ty.assert_clone_cheap();
         exclude.assert_clone_cheap();
 
-        self.cur_facts.insert_var(sym, ty, exclude, false);
+        self.cur_facts.insert_var(name, ty, exclude, false);
     }
 
     pub(super) fn add_deep_type_fact(&mut self, span: Span, name: Name, ty: Type, is_for_true: bool) {
@@ -1240,6 +1371,167 @@ impl Analyzer<'_, '_> {
         Ok(src.into_owned())
     }
 
+    /// Calculates the types of `obj` in the true branch and the false branch
+    /// of `'foo' in obj`, like `narrowTypeByInKeyword` of tsc.
+    ///
+    /// If a member of an union declares the property, members are filtered by
+    /// whether they may have the property. Otherwise, the type is intersected
+    /// with `Record<"foo", unknown>` in the true branch.
+    pub(super) fn narrow_types_with_in(&mut self, span: Span, src: &Type, property: &JsWord) -> VResult<(Type, Type)> {
+        src.assert_valid();
+
+        let src = self.normalize(
+            Some(span),
+            Cow::Borrowed(src),
+            NormalizeTypeOpts {
+                preserve_union: true,
+                preserve_global_this: true,
+                ..Default::default()
+            },
+        )?;
+
+        let members = match src.normalize() {
+            Type::Union(u) => u.types.clone(),
+            _ => vec![src.clone().into_owned()],
+        };
+        let presences = members
+            .iter()
+            .map(|ty| self.property_presence(span, ty, property))
+            .collect::<VResult<Vec<_>>>()?;
+
+        let is_known = presences
+            .iter()
+            .any(|presence| matches!(presence, PropertyPresence::Required | PropertyPresence::Optional));
+        if !is_known {
+            let src = src.into_owned();
+            let record = Type::TypeLit(TypeLit {
+                span,
+                members: vec![TypeElement::Property(PropertySignature {
+                    span,
+                    accessibility: None,
+                    readonly: false,
+                    key: Key::Normal {
+                        span,
+                        sym: property.clone(),
+                    },
+                    optional: false,
+                    params: Default::default(),
+                    type_ann: Some(box Type::unknown(span, Default::default())),
+                    type_params: Default::default(),
+                    metadata: Default::default(),
+                    accessor: Default::default(),
+                })],
+                metadata: Default::default(),
+            });
+
+            return Ok((Type::new_intersection(span, vec![src.clone(), record]), src));
+        }
+
+        let mut true_types = vec![];
+        let mut false_types = vec![];
+
+        for (ty, presence) in members.into_iter().zip(presences) {
+            match presence {
+                PropertyPresence::Required => true_types.push(ty),
+                PropertyPresence::Optional => {
+                    true_types.push(ty.clone());
+                    false_types.push(ty);
+                }
+                PropertyPresence::Absent | PropertyPresence::Unknown => false_types.push(ty),
+            }
+        }
+
+        true_types.dedup_type();
+        false_types.dedup_type();
+
+        Ok((
+            Type::new_union_without_dedup(span, true_types),
+            Type::new_union_without_dedup(span, false_types),
+        ))
+    }
+
+    fn property_presence(&mut self, span: Span, ty: &Type, property: &JsWord) -> VResult<PropertyPresence> {
+        let ty = self.normalize(Some(span), Cow::Borrowed(ty), Default::default())?;
+
+        match ty.normalize() {
+            Type::Keyword(KeywordType {
+                kind: TsKeywordTypeKind::TsAnyKeyword,
+                ..
+            }) => return Ok(PropertyPresence::Optional),
+
+            Type::Param(TypeParam {
+                constraint: Some(constraint),
+                ..
+            }) => return self.property_presence(span, constraint, property),
+
+            Type::Keyword(KeywordType {
+                kind: TsKeywordTypeKind::TsObjectKeyword | TsKeywordTypeKind::TsUnknownKeyword,
+                ..
+            })
+            | Type::Param(..) => return Ok(PropertyPresence::Unknown),
+
+            Type::TypeLit(..) | Type::Interface(..) | Type::Class(..) => {
+                if let Some(type_lit) = self.convert_type_to_type_lit(span, ty.clone())? {
+                    let mut has_index_signature = false;
+
+                    for member in &type_lit.members {
+                        match member {
+                            TypeElement::Property(p) if member.non_computed_key() == Some(property) => {
+                                return Ok(if p.optional {
+                                    PropertyPresence::Optional
+                                } else {
+                                    PropertyPresence::Required
+                                });
+                            }
+                            TypeElement::Method(m) if member.non_computed_key() == Some(property) => {
+                                return Ok(if m.optional {
+                                    PropertyPresence::Optional
+                                } else {
+                                    PropertyPresence::Required
+                                });
+                            }
+                            TypeElement::Index(..) => {
+                                has_index_signature = true;
+                            }
+                            _ => {}
+                        }
+                    }
+
+                    return Ok(if has_index_signature {
+                        PropertyPresence::Optional
+                    } else {
+                        PropertyPresence::Absent
+                    });
+                }
+            }
+
+            _ => {}
+        }
+
+        let prop_res = self.access_property(
+            ty.span().or_else(|| span),
+            &ty,
+            &Key::Normal {
+                span: DUMMY_SP,
+                sym: property.clone(),
+            },
+            TypeOfMode::RValue,
+            IdCtx::Var,
+            AccessPropertyOpts {
+                disallow_creating_indexed_type_from_ty_els: true,
+                ..Default::default()
+            },
+        );
+
+        match prop_res {
+            Ok(..) => Ok(PropertyPresence::Required),
+            Err(err) => match *err {
+                ErrorKind::NoSuchProperty { .. } | ErrorKind::NoSuchPropertyInClass { .. } => Ok(PropertyPresence::Absent),
+                _ => Ok(PropertyPresence::Optional),
+            },
+        }
+    }
+
     fn determine_type_fact_by_field_fact(&mut self, span: Span, name: &Name, ty: &Type) -> VResult<Option<(Name, Type)>> {
         ty.assert_valid();
 
@@ -1297,6 +1589,106 @@ impl Analyzer<'_, '_> {
 
         Ok(None)
     }
+
+    /// Narrows variables using literals excluded by `facts`, like `x` in the
+    /// `default` clause of `switch (x.kind)`.
+    ///
+    /// A variable is narrowed to `never` if all constituents are excluded,
+    /// which means the `switch` is exhaustive.
+    fn narrow_by_excluded_discriminants(&mut self, span: Span, mut facts: CondFacts) -> CondFacts {
+        let excludes = facts
+            .excludes
+            .iter()
+            .filter(|(name, _)| name.len() <= 2)
+            .map(|(name, types)| {
+                let types = types
+                    .iter()
+                    .filter(|ty| ty.is_str_lit() || ty.is_num_lit() || ty.is_bool_lit() || ty.is_null() || ty.is_undefined())
+                    .cloned()
+                    .collect::<Vec<_>>();
+                (name.clone(), types)
+            })
+            .filter(|(_, types)| !types.is_empty())
+            .collect::<Vec<_>>();
+
+        for (name, excluded) in excludes {
+            if let Some((name, mut ty)) = self
+                .determine_type_fact_by_excluded_field(span, &name, &excluded)
+                .report(&mut self.storage)
+                .flatten()
+            {
+                ty.make_clone_cheap();
+                facts.vars.insert(name, ty);
+            }
+        }
+
+        facts
+    }
+
+    /// Removes constituents of `obj` in `obj.prop` whose `prop` is one of
+    /// `excluded`. If `name` is a variable, constituents of the variable are
+    /// removed instead.
+    fn determine_type_fact_by_excluded_field(&mut self, span: Span, name: &Name, excluded: &[Type]) -> VResult<Option<(Name, Type)>> {
+        let ids = name.as_ids();
+        let mut id: RIdent = ids[0].clone().into();
+        id.span.lo = span.lo;
+        id.span.hi = span.hi;
+
+        let obj = self.type_of_var(&id, TypeOfMode::RValue, None)?;
+        let obj = self.normalize(
+            Some(span),
+            Cow::Owned(obj),
+            NormalizeTypeOpts {
+                preserve_global_this: true,
+                preserve_union: true,
+                ..Default::default()
+            },
+        )?;
+
+        let mut new_obj_types = vec![];
+        for obj in obj.iter_union() {
+            let discriminant = match ids.get(1) {
+                Some(prop) => {
+                    match self.access_property(
+                        obj.span(),
+                        obj,
+                        &Key::Normal {
+                            span,
+                            sym: prop.sym().clone(),
+                        },
+                        TypeOfMode::RValue,
+                        IdCtx::Var,
+                        Default::default(),
+                    ) {
+                        Ok(ty) => ty,
+                        Err(..) => {
+                            new_obj_types.push(obj.clone());
+                            continue;
+                        }
+                    }
+                }
+                None => obj.clone(),
+            };
+
+            if !excluded.iter().any(|ty| ty.type_eq(&discriminant)) {
+                new_obj_types.push(obj.clone());
+            }
+        }
+
+        if new_obj_types.len() == obj.iter_union().count() {
+            return Ok(None);
+        }
+
+        let ty = if new_obj_types.is_empty() {
+            Type::never(span, Default::default())
+        } else {
+            let mut ty = Type::union(new_obj_types);
+            ty.fix();
+            ty
+        };
+
+        Ok(Some((Name::from(ids[0].clone()), ty)))
+    }
 }
 
 #[validator]
@@ -14,16 +14,17 @@ use stc_ts_ast_rnode::{
 use stc_ts_errors::{ctx, ErrorKind};
 use stc_ts_file_analyzer_macros::extra_validator;
 use stc_ts_types::{
-    type_id::SymbolId, Accessor, Alias, AliasMetadata, Array, CallSignature, CommonTypeMetadata, ComputedKey, Conditional,
-    ConstructorSignature, FnParam, Id, IdCtx, ImportType, IndexSignature, IndexedAccessType, InferType, InferTypeMetadata, Interface,
-    Intrinsic, IntrinsicKind, Key, KeywordType, KeywordTypeMetadata, LitType, LitTypeMetadata, Mapped, MethodSignature, Operator,
-    OptionalType, Predicate, PropertySignature, QueryExpr, QueryType, Ref, RefMetadata, RestType, Symbol, ThisType, TplType, TsExpr, Tuple,
-    TupleElement, TupleMetadata, Type, TypeElement, TypeLit, TypeLitMetadata, TypeParam, TypeParamDecl, TypeParamInstantiation,
+    type_id::SymbolId, Accessor, Alias, AliasMetadata, Array, CallSignature, ClassDef, CommonTypeMetadata, ComputedKey, Conditional,
+    ConditionalMetadata, ConstructorSignature, FnParam, Id, IdCtx, ImportType, IndexSignature, IndexedAccessType, InferType,
+    InferTypeMetadata, Interface, Intrinsic, IntrinsicKind, Key, KeywordType, KeywordTypeMetadata, LitType, LitTypeMetadata, Mapped,
+    MethodSignature, Operator, OptionalType, Predicate, PropertySignature, QueryExpr, QueryType, Ref, RefMetadata, RestType, Symbol,
+    ThisType, TplType, TsExpr, Tuple, TupleElement, TupleMetadata, Type, TypeElement, TypeLit, TypeLitMetadata, TypeParam, TypeParamDecl,
+    TypeParamInstantiation,
 };
 use stc_ts_utils::{find_ids_in_pat, PatExt};
 use stc_utils::{cache::Freeze, debug_ctx, AHashSet};
 use swc_atoms::js_word;
-use swc_common::{Spanned, SyntaxContext, TypeEq, DUMMY_SP};
+use swc_common::{Span, Spanned, SyntaxContext, TypeEq, DUMMY_SP};
 use swc_ecma_ast::TsKeywordTypeKind;
 use tracing::warn;
 
@@ -557,6 +558,7 @@ impl Analyzer<'_, '_> {
         let extends_type = box t.extends_type.validate_with(self)?;
         let true_type = box t.true_type.validate_with(self)?;
         let false_type = box t.false_type.validate_with(self)?;
+        let distributive = check_type.is_type_param();
 
         Ok(Conditional {
             span: t.span,
@@ -564,7 +566,10 @@ impl Analyzer<'_, '_> {
             extends_type,
             true_type,
             false_type,
-            metadata: Default::default(),
+            metadata: ConditionalMetadata {
+                distributive,
+                ..Default::default()
+            },
         })
     }
 }
@@ -721,6 +726,7 @@ impl Analyzer<'_, '_> {
 
                 if let Some(types) = self.find_type(&i.into())? {
                     let mut found = false;
+                    let mut checked_type_args = false;
                     for ty in types {
                         found = true;
 
@@ -731,6 +737,10 @@ impl Analyzer<'_, '_> {
                         if let Type::Param(..) = ty.normalize() {
                             return Ok(ty.into_owned());
                         }
+
+                        if !self.is_builtin && !checked_type_args {
+                            checked_type_args = self.report_error_for_type_arg_count(span, i, &ty, type_args.as_deref());
+                        }
                     }
 
                     if !self.is_builtin && !found && self.ctx.in_actual_type {
@@ -1137,6 +1147,62 @@ impl Analyzer<'_, '_> {
         }
     }
 
+    /// Returns `true` if `ty` is generic.
+    fn report_error_for_type_arg_count(
+        &mut self,
+        span: Span,
+        name: &RIdent,
+        ty: &Type,
+        type_args: Option<&TypeParamInstantiation>,
+    ) -> bool {
+        let type_params = match ty.normalize() {
+            Type::Interface(Interface {
+                type_params: Some(type_params),
+                ..
+            })
+            | Type::Alias(Alias {
+                type_params: Some(type_params),
+                ..
+            })
+            | Type::ClassDef(ClassDef {
+                type_params: Some(type_params),
+                ..
+            }) => type_params,
+            _ => return false,
+        };
+
+        let max = type_params.params.len();
+        let min = type_params.params.iter().filter(|param| param.default.is_none()).count();
+        let actual = type_args.map(|type_args| type_args.params.len()).unwrap_or(0);
+
+        if min <= actual && actual <= max {
+            return true;
+        }
+
+        if min == max {
+            self.storage.report(
+                ErrorKind::TypeArgsRequired {
+                    span,
+                    name: name.into(),
+                    count: max,
+                }
+                .into(),
+            )
+        } else {
+            self.storage.report(
+                ErrorKind::TypeArgCountNotInRange {
+                    span,
+                    name: name.into(),
+                    min,
+                    max,
+                }
+                .into(),
+            )
+        }
+
+        true
+    }
+
     /// Handle implicit defaults.
     pub(crate) fn default_any_pat(&mut self, p: &RPat) {
         match p {
@@ -1,8 +1,8 @@
-use std::borrow::Cow;
+use std::{borrow::Cow, mem::discriminant};
 
 use fxhash::FxHashMap;
 use stc_ts_errors::{ctx, debug::dump_type_as_string, DebugExt};
-use stc_ts_types::{ClassDef, ClassMember, ClassProperty, Id, Interface, Method, Type, TypeElement, TypeParam};
+use stc_ts_types::{ClassDef, ClassMember, ClassProperty, Id, Interface, Method, Module, Type, TypeElement, TypeParam};
 use stc_utils::cache::Freeze;
 use swc_common::{Span, Spanned};
 use tracing::info;
@@ -24,6 +24,7 @@ impl Analyzer<'_, '_> {
                 is_optional: p.optional,
                 readonly: p.readonly,
                 definite: false,
+                write_ty: None,
                 accessor: p.accessor,
             }))),
             TypeElement::Method(m) => Ok(Some(ClassMember::Method(Method {
@@ -115,6 +116,10 @@ impl Analyzer<'_, '_> {
                 }
                 let b = self.expand_type_params(&type_params, b, Default::default())?.freezed();
 
+                if let Type::Interface(bi) = b.normalize() {
+                    self.report_incompatible_subsequent_props(span, &a.body, &bi.body)?;
+                }
+
                 let mut new_members = a.body.clone();
 
                 // Convert to a type literal first.
@@ -130,6 +135,23 @@ impl Analyzer<'_, '_> {
                 }
             }
 
+            (Type::Module(a), Type::Module(b)) => {
+                let mut exports = a.exports.clone();
+
+                exports.vars.extend(b.exports.vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+                exports
+                    .private_vars
+                    .extend(b.exports.private_vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+                for (k, v) in &b.exports.types {
+                    exports.types.entry(k.clone()).or_default().extend(v.iter().cloned());
+                }
+                for (k, v) in &b.exports.private_types {
+                    exports.private_types.entry(k.clone()).or_default().extend(v.iter().cloned());
+                }
+
+                return Ok(Some(Type::Module(Module { exports, ..a.clone() })));
+            }
+
             _ => {}
         }
 
@@ -137,20 +159,22 @@ impl Analyzer<'_, '_> {
     }
 
     /// Handle declaration merging.
-    fn merge_declaration_types(&mut self, span: Span, orig: Type, new: Type) -> VResult<Type> {
+    fn merge_declaration_types(&mut self, span: Span, orig: Type, new: Type) -> VResult<Option<Type>> {
         debug_assert!(orig.is_clone_cheap());
         debug_assert!(new.is_clone_cheap());
 
         if let Some(new_ty) = self.merge_from_to(span, orig.clone(), new.clone())? {
-            return Ok(new_ty);
-        }
-        if let Some(new_ty) = self.merge_from_to(span, new.clone(), orig)? {
-            return Ok(new_ty);
+            return Ok(Some(new_ty));
         }
-
-        Ok(new)
+        self.merge_from_to(span, new, orig)
     }
 
+    /// Returns the type to register and `true` if it should override the
+    /// previous declaration.
+    ///
+    /// If `new` cannot be merged into any of the previous declarations, like a
+    /// class and a namespace, this returns `(new, false)` and the caller
+    /// stores it next to the previous declarations as an intersection.
     pub(crate) fn merge_decl_with_name(&mut self, name: Id, new: Type) -> VResult<(Type, bool)> {
         let orig = self.find_type(&name)?;
         let mut orig = match orig {
@@ -159,10 +183,50 @@ impl Analyzer<'_, '_> {
         };
 
         let orig = orig.next().unwrap().into_owned();
+        let span = new.span();
+
+        let mut types = match orig.normalize() {
+            Type::Intersection(i) => i.types.clone(),
+            _ => vec![orig],
+        };
 
-        let new = self.merge_declaration_types(new.span(), orig, new)?;
-        info!("Merging declaration {} with type {}", name, dump_type_as_string(&new));
+        for idx in (0..types.len()).rev() {
+            let merged = if is_same_decl(&types[idx], &new) {
+                Some(new.clone())
+            } else {
+                self.merge_declaration_types(span, types[idx].clone(), new.clone())?
+            };
 
-        Ok((new, true))
+            if let Some(merged) = merged {
+                info!("Merging declaration {} with type {}", name, dump_type_as_string(&merged));
+
+                types[idx] = merged;
+                return Ok((Type::new_intersection(span, types).freezed(), true));
+            }
+        }
+
+        Ok((new, false))
+    }
+}
+
+/// Returns `true` if `new` is the declaration `orig` registered again.
+fn is_same_decl(orig: &Type, new: &Type) -> bool {
+    let span = new.span();
+    !span.is_dummy() && orig.span() == span && discriminant(orig.normalize()) == discriminant(new.normalize())
+}
+
+/// Removes namespaces from declarations merged with them, as a namespace does
+/// not have a meaning as a type.
+pub(super) fn remove_merged_namespaces(ty: &Type) -> Option<Type> {
+    let i = ty.normalize().as_intersection()?;
+    if !i.types.iter().any(|ty| ty.normalize().is_module()) {
+        return None;
     }
+
+    let types = i.types.iter().filter(|ty| !ty.normalize().is_module()).cloned().collect::<Vec<_>>();
+    if types.is_empty() {
+        return None;
+    }
+
+    Some(Type::new_intersection(i.span, types).freezed())
 }
@@ -1,7 +1,8 @@
-use fxhash::FxHashMap;
+use fxhash::{FxHashMap, FxHashSet};
 use rnode::{NodeId, Visit, VisitWith};
 use stc_ts_ast_rnode::{
-    RBinExpr, RBindingIdent, RExpr, RIdent, RLit, RNumber, RPat, RStr, RTsEnumDecl, RTsEnumMember, RTsEnumMemberId, RTsLit,
+    RBinExpr, RBindingIdent, RComputedPropName, RExpr, RIdent, RLit, RMemberExpr, RMemberProp, RNumber, RPat, RStr, RTsEnumDecl,
+    RTsEnumMember, RTsEnumMemberId, RTsLit,
 };
 use stc_ts_errors::{ErrorKind, Errors};
 use stc_ts_types::{
@@ -43,12 +44,14 @@ impl Analyzer<'_, '_> {
             self.validate_with(|a| a.validate_enum_memeber_name(&m.id));
         }
 
-        let mut default = 0.0;
+        let mut default = Some(0.0);
         let mut values = Default::default();
+        let other_enums = self.values_of_referenced_enums(e);
 
         let mut eval = Evaluator {
             e,
             values: &mut values,
+            other_enums: &other_enums,
             errors: Default::default(),
         };
 
@@ -59,11 +62,13 @@ impl Analyzer<'_, '_> {
                 .map(|m| -> VResult<_> {
                     let id_span = m.id.span();
                     let val = eval
-                        .compute(id_span, Some(default), m.init.as_deref())
+                        .compute(id_span, default, m.init.as_deref())
                         .map(|val| {
-                            if let RTsLit::Number(n) = &val {
-                                default = n.value + 1.0;
-                            }
+                            // Only members following a numeric member can be auto-incremented.
+                            default = match &val {
+                                RTsLit::Number(n) => Some(n.value + 1.0),
+                                _ => None,
+                            };
                             eval.values.insert(
                                 match &m.id {
                                     RTsEnumMemberId::Ident(i) => i.sym.clone(),
@@ -87,7 +92,12 @@ impl Analyzer<'_, '_> {
                         .or_else(|err| match &m.init {
                             None => Err(err),
                             Some(v) => {
+                                // Members of const enums should be constant expressions.
                                 if e.is_const {
+                                    let err = match &*err {
+                                        ErrorKind::InvalidEnumInit { .. } => ErrorKind::InvalidInitInConstEnum { span: v.span() }.into(),
+                                        _ => err,
+                                    };
                                     self.storage.report(err);
                                 }
                                 Ok(*v.clone())
@@ -114,7 +124,7 @@ impl Analyzer<'_, '_> {
                 span: e.span,
                 has_num: members.iter().any(|m| matches!(*m.val, RExpr::Lit(RLit::Num(..)))),
                 has_str,
-                declare: e.declare,
+                declare: e.declare || self.ctx.in_declare,
                 is_const: e.is_const,
                 id: e.id.clone(),
                 members,
@@ -137,24 +147,6 @@ impl Analyzer<'_, '_> {
         self.declare_var(e.span, VarKind::Enum, name, Some(stored_ty), None, true, true, false)
             .report(&mut self.storage);
 
-        // Validate const enums
-        if e.is_const {
-            for m in &e.members {
-                if let Some(init) = &m.init {
-                    let mut v = LitValidator {
-                        error: false,
-                        decl: e,
-                        errors: Default::default(),
-                    };
-                    init.visit_with(&mut v);
-                    self.storage.report_all(v.errors);
-                    if v.error {
-                        self.storage.report(ErrorKind::InvalidInitInConstEnum { span: init.span() }.into())
-                    }
-                }
-            }
-        }
-
         ty
     }
 }
@@ -162,6 +154,8 @@ impl Analyzer<'_, '_> {
 struct Evaluator<'a> {
     e: &'a RTsEnumDecl,
     values: &'a mut EnumValues,
+    /// Values of other enums referenced by `e`, keyed by the name of the enum.
+    other_enums: &'a FxHashMap<JsWord, EnumValues>,
 
     #[allow(unused)]
     errors: Errors,
@@ -205,20 +199,19 @@ impl Evaluator<'_> {
                         }
                     }
 
-                    if let Some(v) = self.values.get(&id.sym) {
-                        return Ok(v.clone());
+                    return self.compute_ref(span, &id.sym);
+                }
+                // `E.A` or `E["A"]`, where `E` is the enum being declared or another enum.
+                RExpr::Member(ref m) => {
+                    if let Some(sym) = member_of_enum(self.e, m) {
+                        return self.compute_ref(span, &sym);
                     }
-                    //
-                    for m in self.e.members.iter() {
-                        match m.id {
-                            RTsEnumMemberId::Str(RStr { value: ref sym, .. }) | RTsEnumMemberId::Ident(RIdent { ref sym, .. }) => {
-                                if *sym == id.sym {
-                                    return self.compute(span, None, m.init.as_deref());
-                                }
-                            }
+
+                    if let Some((obj, sym)) = enum_member_ref(m) {
+                        if let Some(v) = self.other_enums.get(obj).and_then(|values| values.get(&sym)) {
+                            return Ok(v.clone());
                         }
                     }
-                    return Err(ErrorKind::InvalidEnumInit { span }.into());
                 }
                 RExpr::Unary(ref expr) => {
                     let v = self.compute(span, None, Some(&expr.arg))?;
@@ -229,14 +222,7 @@ impl Evaluator<'_> {
                                 value: match expr.op {
                                     op!(unary, "+") => v,
                                     op!(unary, "-") => -v,
-                                    op!("!") => {
-                                        if v == 0.0f64 {
-                                            0.0
-                                        } else {
-                                            1.0
-                                        }
-                                    }
-                                    op!("~") => (!(v as i32)) as f64,
+                                    op!("~") => (!to_int32(v)) as f64,
                                     _ => Err(ErrorKind::InvalidEnumInit { span })?,
                                 },
                                 raw: None,
@@ -249,10 +235,28 @@ impl Evaluator<'_> {
                     }
                 }
 
-                RExpr::Tpl(ref t) if t.exprs.is_empty() => {
-                    if let Some(v) = &t.quasis[0].cooked {
-                        return Ok(v.clone().into());
+                RExpr::Tpl(ref t) => {
+                    let mut value = String::new();
+                    for (idx, q) in t.quasis.iter().enumerate() {
+                        match &q.cooked {
+                            Some(cooked) => value.push_str(cooked),
+                            None => return Err(ErrorKind::InvalidEnumInit { span }.into()),
+                        }
+
+                        if let Some(expr) = t.exprs.get(idx) {
+                            match self.compute(span, None, Some(expr))? {
+                                RTsLit::Str(s) => value.push_str(&s.value),
+                                RTsLit::Number(n) => value.push_str(&n.value.to_string()),
+                                _ => return Err(ErrorKind::InvalidEnumInit { span }.into()),
+                            }
+                        }
                     }
+
+                    return Ok(RTsLit::Str(RStr {
+                        span: t.span,
+                        value: value.into(),
+                        raw: None,
+                    }));
                 }
 
                 _ => {}
@@ -265,40 +269,60 @@ impl Evaluator<'_> {
                     raw: None,
                 }));
             }
+
+            return Err(ErrorKind::EnumMemberMustHaveInit { span }.into());
         }
 
         Err(ErrorKind::InvalidEnumInit { span }.into())
     }
 
+    /// Computes the value of a reference to another member of the enum.
+    fn compute_ref(&mut self, span: Span, sym: &JsWord) -> VResult<RTsLit> {
+        if let Some(v) = self.values.get(sym) {
+            return Ok(v.clone());
+        }
+        //
+        for m in self.e.members.iter() {
+            match m.id {
+                RTsEnumMemberId::Str(RStr { value: ref name, .. }) | RTsEnumMemberId::Ident(RIdent { sym: ref name, .. }) => {
+                    if *name == *sym {
+                        if let Some(init) = &m.init {
+                            return self.compute(span, None, Some(init));
+                        }
+                    }
+                }
+            }
+        }
+        Err(ErrorKind::InvalidEnumInit { span }.into())
+    }
+
     fn compute_bin(&mut self, span: Span, expr: &RBinExpr) -> VResult<RTsLit> {
         let l = self.compute(span, None, Some(&expr.left))?;
         let r = self.compute(span, None, Some(&expr.right))?;
 
         Ok(match (l, r) {
-            (RTsLit::Number(RNumber { value: l, .. }), RTsLit::Number(RNumber { value: r, .. })) => {
-                RTsLit::Number(RNumber {
-                    span,
-                    value: match expr.op {
-                        op!(bin, "+") => l + r,
-                        op!(bin, "-") => l - r,
-                        op!("*") => l * r,
-                        op!("/") => l / r,
-
-                        // TODO
-                        op!("&") => ((l.round() as i64) & (r.round() as i64)) as _,
-                        op!("|") => ((l.round() as i64) | (r.round() as i64)) as _,
-                        op!("^") => ((l.round() as i64) ^ (r.round() as i64)) as _,
-
-                        op!("<<") => ((l.round() as i64) << (r.round() as i64)) as _,
-                        op!(">>") => ((l.round() as i64) >> (r.round() as i64)) as _,
-                        // TODO(kdy1): Verify this
-                        op!(">>>") => ((l.round() as u64) >> (r.round() as u64)) as _,
-                        _ => Err(ErrorKind::InvalidEnumInit { span })?,
-                    },
+            (RTsLit::Number(RNumber { value: l, .. }), RTsLit::Number(RNumber { value: r, .. })) => RTsLit::Number(RNumber {
+                span,
+                value: match expr.op {
+                    op!(bin, "+") => l + r,
+                    op!(bin, "-") => l - r,
+                    op!("*") => l * r,
+                    op!("/") => l / r,
+                    op!("%") => l % r,
+                    op!("**") => l.powf(r),
+
+                    op!("&") => (to_int32(l) & to_int32(r)) as _,
+                    op!("|") => (to_int32(l) | to_int32(r)) as _,
+                    op!("^") => (to_int32(l) ^ to_int32(r)) as _,
+
+                    op!("<<") => to_int32(l).wrapping_shl(to_uint32(r) & 31) as _,
+                    op!(">>") => to_int32(l).wrapping_shr(to_uint32(r) & 31) as _,
+                    op!(">>>") => to_uint32(l).wrapping_shr(to_uint32(r) & 31) as _,
+                    _ => Err(ErrorKind::InvalidEnumInit { span })?,
+                },
 
-                    raw: None,
-                })
-            }
+                raw: None,
+            }),
             (RTsLit::Str(l), RTsLit::Str(r)) if expr.op == op!(bin, "+") => RTsLit::Str(RStr {
                 span,
                 value: format!("{}{}", l.value, r.value).into(),
@@ -327,7 +351,88 @@ impl Evaluator<'_> {
     }
 }
 
+/// `ToUint32` of the ECMAScript specification.
+fn to_uint32(v: f64) -> u32 {
+    if !v.is_finite() {
+        return 0;
+    }
+
+    v.trunc().rem_euclid(4294967296.0) as u32
+}
+
+/// `ToInt32` of the ECMAScript specification.
+fn to_int32(v: f64) -> i32 {
+    to_uint32(v) as i32
+}
+
+/// Returns the name of the member if `m` is `E.A` or `E["A"]`.
+fn member_of_enum(e: &RTsEnumDecl, m: &RMemberExpr) -> Option<JsWord> {
+    match enum_member_ref(m) {
+        Some((obj, sym)) if *obj == e.id.sym => Some(sym),
+        _ => None,
+    }
+}
+
+/// Returns the name of the object and the member if `m` is `E.A` or `E["A"]`.
+fn enum_member_ref(m: &RMemberExpr) -> Option<(&JsWord, JsWord)> {
+    let obj = match &*m.obj {
+        RExpr::Ident(obj) => &obj.sym,
+        _ => return None,
+    };
+
+    match &m.prop {
+        RMemberProp::Ident(i) => Some((obj, i.sym.clone())),
+        RMemberProp::Computed(RComputedPropName {
+            expr: box RExpr::Lit(RLit::Str(s)),
+            ..
+        }) => Some((obj, s.value.clone())),
+        _ => None,
+    }
+}
+
 impl Analyzer<'_, '_> {
+    /// Returns the values of the members of other enums which are referenced by
+    /// the initializers of `e`, like `B = Other.A`.
+    fn values_of_referenced_enums(&mut self, e: &RTsEnumDecl) -> FxHashMap<JsWord, EnumValues> {
+        let mut finder = EnumRefFinder {
+            e,
+            refs: Default::default(),
+        };
+        e.members.visit_with(&mut finder);
+
+        let mut other_enums = FxHashMap::default();
+
+        for id in finder.refs {
+            let types = match self.find_type(&id) {
+                Ok(Some(types)) => types,
+                _ => continue,
+            };
+
+            for ty in types {
+                if let Type::Enum(other) = ty.normalize() {
+                    let values = other
+                        .members
+                        .iter()
+                        .filter_map(|m| {
+                            let name = match &m.id {
+                                RTsEnumMemberId::Ident(i) => i.sym.clone(),
+                                RTsEnumMemberId::Str(s) => s.value.clone(),
+                            };
+                            match &*m.val {
+                                RExpr::Lit(RLit::Num(n)) => Some((name, RTsLit::Number(n.clone()))),
+                                RExpr::Lit(RLit::Str(s)) => Some((name, RTsLit::Str(s.clone()))),
+                                _ => None,
+                            }
+                        })
+                        .collect();
+                    other_enums.insert(id.sym().clone(), values);
+                }
+            }
+        }
+
+        other_enums
+    }
+
     fn validate_enum_memeber_name(&mut self, e: &RTsEnumMemberId) -> VResult<()> {
         match e {
             RTsEnumMemberId::Ident(i) => {}
@@ -599,37 +704,19 @@ impl Analyzer<'_, '_> {
     }
 }
 
-struct LitValidator<'a> {
-    decl: &'a RTsEnumDecl,
-    error: bool,
-    errors: Errors,
+/// Finds objects of member expressions which may be other enums.
+struct EnumRefFinder<'a> {
+    e: &'a RTsEnumDecl,
+    refs: FxHashSet<Id>,
 }
 
-impl Visit<RExpr> for LitValidator<'_> {
-    fn visit(&mut self, e: &RExpr) {
-        e.visit_children_with(self);
-
-        match e {
-            RExpr::Lit(..) => {}
-            RExpr::Ident(ref i) => {
-                if i.sym == js_word!("NaN") {
-                    return;
-                }
-                if i.sym == js_word!("Infinity") {
-                    return;
-                }
-
-                let is_ref = self.decl.members.iter().any(|m| match m.id {
-                    RTsEnumMemberId::Ident(RIdent { ref sym, .. }) | RTsEnumMemberId::Str(RStr { value: ref sym, .. }) => *sym == i.sym,
-                });
-                if !is_ref {
-                    self.error = true;
-                }
-            }
-            RExpr::Unary(..) | RExpr::Bin(..) | RExpr::Paren(..) => {}
+impl Visit<RMemberExpr> for EnumRefFinder<'_> {
+    fn visit(&mut self, m: &RMemberExpr) {
+        m.visit_children_with(self);
 
-            _ => {
-                self.error = true;
+        if let RExpr::Ident(obj) = &*m.obj {
+            if obj.sym != self.e.id.sym {
+                self.refs.insert(obj.into());
             }
         }
     }
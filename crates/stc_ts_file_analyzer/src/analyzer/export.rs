@@ -1,9 +1,13 @@
+use std::{collections::hash_map::Entry, mem::take};
+
 use rnode::{NodeId, VisitWith};
+use rustc_hash::{FxHashMap, FxHashSet};
 use stc_ts_ast_rnode::{
     RBindingIdent, RDecl, RDefaultDecl, RExportAll, RExportDecl, RExportDefaultDecl, RExportDefaultExpr, RExportNamedSpecifier,
     RExportSpecifier, RExpr, RIdent, RModuleExportName, RNamedExport, RPat, RStmt, RTsExportAssignment, RTsModuleName, RTsTypeAnn,
     RVarDecl, RVarDeclarator,
 };
+use stc_ts_base_type_ops::bindings::BindingKind;
 use stc_ts_errors::{ctx, ErrorKind};
 use stc_ts_file_analyzer_macros::extra_validator;
 use stc_ts_types::{Id, IdCtx, ModuleId};
@@ -21,6 +25,9 @@ use crate::{
     VResult,
 };
 
+/// Name of the export created by `export =`, as it's not a valid identifier.
+pub(super) const EXPORT_EQUALS: &str = "export=";
+
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, export: &RExportDecl) {
@@ -234,14 +241,14 @@ impl Analyzer<'_, '_> {
     }
 
     /// Exports a variable.
-    fn export_expr(&mut self, name: Id, item_node_id: NodeId, e: &RExpr) -> VResult<()> {
+    fn export_expr(&mut self, name: Id, item_node_id: NodeId, e: &RExpr) -> VResult<Type> {
         self.report_errors_for_duplicated_exports_of_var(e.span(), name.sym().clone());
 
         let ty = e.validate_with_default(self)?.freezed();
 
         if *name.sym() == js_word!("default") {
             if let RExpr::Ident(..) = e {
-                return Ok(());
+                return Ok(ty);
             }
             let var = RVarDeclarator {
                 node_id: NodeId::invalid(),
@@ -275,11 +282,9 @@ impl Analyzer<'_, '_> {
                 m.for_export_defaults.entry(item_node_id).or_default().replace_with =
                     Some(box RExpr::Ident(RIdent::new("_default".into(), DUMMY_SP)));
             }
-
-            return Ok(());
         }
 
-        Ok(())
+        Ok(ty)
     }
 }
 
@@ -287,9 +292,32 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RTsExportAssignment) {
+        let span = node.span;
+        let module_id = self.ctx.module_id;
+        let name = JsWord::from(EXPORT_EQUALS);
+
+        // Types and namespaces named by the expression are exported too.
+        let mut has_type = false;
+        if let RExpr::Ident(i) = &*node.expr {
+            if let Some(types) = self.find_type(&i.into())? {
+                let types = types.into_iter().map(|ty| ty.into_owned().freezed()).collect::<Vec<_>>();
+                for ty in types {
+                    has_type = true;
+                    self.storage.reexport_type(span, module_id, name.clone(), ty);
+                }
+            }
+        }
+
         let ctx = Ctx { ..self.ctx };
-        self.with_ctx(ctx)
-            .export_expr(Id::word(js_word!("default")), node.node_id, &node.expr)?;
+        match self
+            .with_ctx(ctx)
+            .export_expr(Id::word(js_word!("default")), node.node_id, &node.expr)
+        {
+            Ok(ty) => self.storage.reexport_var(span, module_id, name, ty),
+            // `export = SomeInterface`
+            Err(..) if has_type => {}
+            Err(err) => return Err(err),
+        }
 
         Ok(())
     }
@@ -346,24 +374,10 @@ impl Analyzer<'_, '_> {
         let span = node.span;
         let ctxt = self.ctx.module_id;
 
-        let (dep, data) = self.get_imported_items(span, &node.src.value);
+        let (dep, _) = self.get_imported_items(span, &node.src.value);
 
         if ctxt != dep {
-            match data.normalize() {
-                Type::Module(data) => {
-                    for (id, ty) in data.exports.vars.iter() {
-                        self.storage.reexport_var(span, dep, id.clone(), ty.clone());
-                    }
-                    for (id, types) in data.exports.types.iter() {
-                        for ty in types {
-                            self.storage.reexport_type(span, dep, id.clone(), ty.clone());
-                        }
-                    }
-                }
-                _ => {
-                    unreachable!()
-                }
-            }
+            self.data.for_module.star_exports.push((ctxt, span, dep));
         }
         Ok(())
     }
@@ -376,24 +390,33 @@ impl Analyzer<'_, '_> {
         let base = self.ctx.module_id;
 
         // Visit export specifiers only if it's not a reexport.
-        if node.src.is_none() {
-            node.specifiers.visit_with(self);
+        if node.src.is_none() && !node.type_only {
+            for specifier in &node.specifiers {
+                match specifier {
+                    RExportSpecifier::Named(named) if named.is_type_only => {}
+                    _ => specifier.visit_with(self),
+                }
+            }
         }
 
         for specifier in &node.specifiers {
             match specifier {
-                RExportSpecifier::Namespace(_) => {
-                    // We need
-                    match &node.src {
-                        Some(src) => {
-                            let (dep, data) = self.get_imported_items(node.span, &src.value);
+                RExportSpecifier::Namespace(ns) => {
+                    if let Some(src) = &node.src {
+                        let (dep, data) = self.get_imported_items(node.span, &src.value);
+
+                        if base != dep {
+                            let name = Id::from(&ns.name);
+
+                            self.report_errors_for_duplicated_exports_of_var(ns.span, name.sym().clone());
+                            self.storage.reexport_var(span, base, name.sym().clone(), data.clone());
+                            self.storage.reexport_type(span, base, name.sym().clone(), data);
                         }
-                        None => {}
                     }
                 }
                 RExportSpecifier::Default(_) => {}
                 RExportSpecifier::Named(named) => {
-                    //
+                    let is_type_only = node.type_only || named.is_type_only;
 
                     match &node.src {
                         Some(src) => {
@@ -403,8 +426,9 @@ impl Analyzer<'_, '_> {
                                 span,
                                 base,
                                 dep,
-                                named.exported.as_ref().map(Id::from).unwrap_or_else(|| Id::from(&named.orig)),
                                 Id::from(&named.orig),
+                                named.exported.as_ref().map(Id::from).unwrap_or_else(|| Id::from(&named.orig)),
+                                is_type_only,
                             );
                         }
                         None => {
@@ -413,6 +437,7 @@ impl Analyzer<'_, '_> {
                                 base,
                                 Id::from(&named.orig),
                                 named.exported.as_ref().map(Id::from).unwrap_or_else(|| Id::from(&named.orig)),
+                                is_type_only,
                             );
                         }
                     }
@@ -425,19 +450,103 @@ impl Analyzer<'_, '_> {
 }
 
 impl Analyzer<'_, '_> {
-    fn export_named(&mut self, span: Span, ctxt: ModuleId, orig: Id, id: Id) {
-        if self.storage.get_local_var(ctxt, orig.clone()).is_some() {
+    /// If `is_type_only` is true, only types are exported unless `orig` is a
+    /// value without types.
+    fn export_named(&mut self, span: Span, ctxt: ModuleId, orig: Id, id: Id, is_type_only: bool) {
+        let has_var = self.storage.get_local_var(ctxt, orig.clone()).is_some();
+        let local_type = self.storage.get_local_type(ctxt, orig.clone());
+        let has_type = local_type.is_some();
+
+        if !is_type_only && self.rule().isolated_modules && has_type {
+            let is_import = self
+                .data
+                .bindings
+                .all
+                .get(&orig)
+                .map_or(false, |kinds| kinds.contains(&BindingKind::Import));
+
+            if is_import && (!has_var || self.data.type_only_imports.contains(&orig)) {
+                self.storage
+                    .report(ErrorKind::ReExportTypeWithoutTypeModifierInIsolatedModules { span }.into());
+            }
+
+            if is_import && local_type.as_ref().map_or(false, is_ambient_const_enum) {
+                self.storage.report(ErrorKind::AmbientConstEnumWithIsolatedModules { span }.into());
+            }
+        }
+
+        if has_var && !(is_type_only && has_type) {
             self.report_errors_for_duplicated_exports_of_var(span, id.sym().clone());
 
             self.storage.export_var(span, ctxt, id.clone(), orig.clone());
         }
 
-        if self.storage.get_local_type(ctxt, orig).is_some() {
+        if has_type {
             self.storage.export_type(span, ctxt, id);
         }
     }
 
-    fn reexport(&mut self, span: Span, ctxt: ModuleId, from: ModuleId, orig: Id, id: Id) {
+    /// Handles `export * from 'dep'`.
+    ///
+    /// `default` is not reexported, and a name provided by multiple star
+    /// exports with different declarations is reported as ambiguous.
+    fn reexport_star_exports(&mut self) {
+        let star_exports = take(&mut self.data.for_module.star_exports);
+
+        // Name to the exported var and types, per module.
+        let mut provided = FxHashMap::<(ModuleId, JsWord), (Option<Type>, Vec<Type>)>::default();
+
+        for (ctxt, span, dep) in star_exports {
+            let data = match self.imports.get(&(ctxt, dep)) {
+                Some(data) => data.clone(),
+                None => continue,
+            };
+            let data = match data.normalize() {
+                Type::Module(data) => data,
+                // `declare module "foo";`
+                _ => continue,
+            };
+
+            let names = data
+                .exports
+                .vars
+                .keys()
+                .chain(data.exports.types.keys())
+                .cloned()
+                .collect::<FxHashSet<_>>();
+
+            for name in names {
+                if name == js_word!("default") || &*name == EXPORT_EQUALS || self.storage.is_exported(ctxt, &name) {
+                    continue;
+                }
+
+                let var = data.exports.vars.get(&name).cloned();
+                let types = data.exports.types.get(&name).cloned().unwrap_or_default();
+
+                match provided.entry((ctxt, name.clone())) {
+                    Entry::Occupied(e) => {
+                        if *e.get() != (var, types) {
+                            self.storage.report(ErrorKind::AmbiguousStarExport { span, name }.into());
+                        }
+                    }
+                    Entry::Vacant(e) => {
+                        e.insert((var, types));
+                    }
+                }
+            }
+        }
+
+        for ((ctxt, name), (var, types)) in provided {
+            if let Some(var) = var {
+                self.storage.reexport_var(DUMMY_SP, ctxt, name.clone(), var);
+            }
+            for ty in types {
+                self.storage.reexport_type(DUMMY_SP, ctxt, name.clone(), ty);
+            }
+        }
+    }
+
+    fn reexport(&mut self, span: Span, ctxt: ModuleId, from: ModuleId, orig: Id, id: Id, is_type_only: bool) {
         let mut did_work = false;
 
         // Dependency module is not found.
@@ -448,7 +557,25 @@ impl Analyzer<'_, '_> {
         if let Some(data) = self.imports.get(&(ctxt, from)) {
             match data.normalize() {
                 Type::Module(data) => {
-                    if let Some(ty) = data.exports.vars.get(orig.sym()) {
+                    let ty_exists = data.exports.types.contains_key(orig.sym());
+
+                    if !is_type_only && self.rule().isolated_modules && ty_exists && !data.exports.vars.contains_key(orig.sym()) {
+                        self.storage
+                            .report(ErrorKind::ReExportTypeWithoutTypeModifierInIsolatedModules { span }.into());
+                    }
+
+                    if !is_type_only
+                        && self.rule().isolated_modules
+                        && data
+                            .exports
+                            .types
+                            .get(orig.sym())
+                            .map_or(false, |types| types.iter().any(is_ambient_const_enum))
+                    {
+                        self.storage.report(ErrorKind::AmbientConstEnumWithIsolatedModules { span }.into());
+                    }
+
+                    if let Some(ty) = data.exports.vars.get(orig.sym()).filter(|_| !(is_type_only && ty_exists)) {
                         did_work = true;
                         self.storage.reexport_var(span, ctxt, id.sym().clone(), ty.clone());
                     }
@@ -459,6 +586,12 @@ impl Analyzer<'_, '_> {
                         self.storage.reexport_type(span, ctxt, id.sym().clone(), ty);
                     }
                 }
+                // `declare module "foo";`
+                _ if data.is_any() => {
+                    did_work = true;
+                    self.storage.reexport_var(span, ctxt, id.sym().clone(), data.clone());
+                    self.storage.reexport_type(span, ctxt, id.sym().clone(), data.clone());
+                }
                 _ => {
                     unreachable!()
                 }
@@ -470,3 +603,12 @@ impl Analyzer<'_, '_> {
         }
     }
 }
+
+/// Returns `true` if `ty` is a const enum which is inlined by the compiler.
+fn is_ambient_const_enum(ty: &Type) -> bool {
+    match ty.normalize() {
+        Type::Enum(e) => e.is_const && e.declare,
+        Type::Union(u) => u.types.iter().any(is_ambient_const_enum),
+        _ => false,
+    }
+}
@@ -1,7 +1,7 @@
 use std::{borrow::Cow, time::Instant};
 
 use itertools::Itertools;
-use stc_ts_ast_rnode::{RArrayLit, RExpr, RExprOrSpread, RInvalid, RNumber, RTsLit};
+use stc_ts_ast_rnode::{RArrayLit, RBool, RExpr, RExprOrSpread, RInvalid, RNumber, RPat, RTsLit};
 use stc_ts_errors::{
     ctx,
     debug::{dump_type_as_string, force_dump_type_as_string},
@@ -18,7 +18,7 @@ use stc_utils::{
 };
 use swc_atoms::js_word;
 use swc_common::{Span, Spanned, SyntaxContext};
-use swc_ecma_ast::TsKeywordTypeKind;
+use swc_ecma_ast::{EsVersion, TsKeywordTypeKind};
 use tracing::debug;
 
 use crate::{
@@ -130,6 +130,8 @@ impl Analyzer<'_, '_> {
                             });
                         }
                         _ => {
+                            self.report_error_for_iteration_below_es2015(span, &element_type);
+
                             let elem_type = self
                                 .get_iterator_element_type(span, Cow::Owned(element_type), false, Default::default())
                                 .context("tried to calculated the element type of a iterable provided to spread")?
@@ -489,6 +491,14 @@ impl Analyzer<'_, '_> {
     }
 
     pub(crate) fn get_value_type_from_iterator_result<'a>(&mut self, span: Span, iterator_result: Cow<'a, Type>) -> VResult<Cow<'a, Type>> {
+        // `IteratorReturnResult` does not contribute to the element type.
+        let (yield_results, return_results) = self.split_iterator_result(span, &iterator_result).unwrap_or_default();
+        let iterator_result = if !yield_results.is_empty() && !return_results.is_empty() {
+            Cow::Owned(Type::union(yield_results))
+        } else {
+            iterator_result
+        };
+
         let mut elem_ty = self
             .access_property(
                 span,
@@ -524,6 +534,140 @@ impl Analyzer<'_, '_> {
         Ok(Cow::Owned(elem_ty))
     }
 
+    /// Splits the result type of `next()` of an iterator into
+    /// `IteratorYieldResult`s and `IteratorReturnResult`s, using the type of
+    /// `done`.
+    ///
+    /// Returns `(yield_results, return_results)`.
+    fn split_iterator_result(&mut self, span: Span, iterator_result: &Type) -> VResult<(Vec<Type>, Vec<Type>)> {
+        let iterator_result = self
+            .normalize(Some(span), Cow::Borrowed(iterator_result), Default::default())
+            .context("tried to normalize the result of `next()` to split it")?;
+
+        let mut yield_results = vec![];
+        let mut return_results = vec![];
+
+        for result in iterator_result.iter_union() {
+            let done = self.access_property(
+                span,
+                result,
+                &Key::Normal { span, sym: "done".into() },
+                TypeOfMode::RValue,
+                IdCtx::Var,
+                Default::default(),
+            );
+
+            match done {
+                Ok(done) if matches!(done.normalize(), Type::Lit(LitType { lit: RTsLit::Bool(RBool { value: true, .. }), .. })) => {
+                    return_results.push(result.clone());
+                }
+                _ => {
+                    yield_results.push(result.clone());
+                }
+            }
+        }
+
+        Ok((yield_results, return_results))
+    }
+
+    /// Returns `TReturn` of an iterable, which is the type of `value` of
+    /// `IteratorReturnResult`s returned from `next()`.
+    ///
+    /// This is the type of a delegating yield expression (`yield*`).
+    pub(crate) fn get_iterator_return_type(&mut self, span: Span, ty: Cow<Type>) -> VResult<Type> {
+        let iterator = self
+            .get_iterator(span, ty, Default::default())
+            .context("tried to get an iterator to calculate `TReturn`")?;
+
+        if iterator.is_str() || matches!(iterator.normalize(), Type::Array(..) | Type::Tuple(..)) {
+            return Ok(Type::any(span, Default::default()));
+        }
+
+        let next_ret_ty = self
+            .call_property(
+                span,
+                ExtractKind::Call,
+                Default::default(),
+                &iterator,
+                &iterator,
+                &Key::Normal { span, sym: "next".into() },
+                None,
+                &[],
+                &[],
+                &[],
+                None,
+                Default::default(),
+            )
+            .context("tried calling `next()` to calculate `TReturn` of an iterator")?;
+
+        let (_, return_results) = self.split_iterator_result(span, &next_ret_ty)?;
+
+        if return_results.is_empty() {
+            return Ok(Type::any(span, Default::default()));
+        }
+
+        let mut types = Vec::with_capacity(return_results.len());
+        for result in return_results {
+            let ty = self
+                .access_property(
+                    span,
+                    &result,
+                    &Key::Normal { span, sym: "value".into() },
+                    TypeOfMode::RValue,
+                    IdCtx::Var,
+                    AccessPropertyOpts {
+                        disallow_indexing_array_with_string: true,
+                        disallow_creating_indexed_type_from_ty_els: true,
+                        ..Default::default()
+                    },
+                )
+                .context("tried to get the type of property named `value` of `IteratorReturnResult`")
+                .convert_err(|err| ErrorKind::NextOfItertorShouldReturnTypeWithPropertyValue { span: err.span() })?;
+
+            types.push(ty);
+        }
+        types.dedup_type();
+
+        Ok(Type::union(types))
+    }
+
+    /// Returns the type of the value accepted by `next()` of an iterator,
+    /// which is `TNext`.
+    ///
+    /// Returns [None] if `iterator` does not have a `next` method with a
+    /// parameter.
+    pub(crate) fn get_iterator_next_arg_type(&mut self, span: Span, iterator: &Type) -> VResult<Option<Type>> {
+        let next = self
+            .access_property(
+                span,
+                iterator,
+                &Key::Normal { span, sym: "next".into() },
+                TypeOfMode::RValue,
+                IdCtx::Var,
+                Default::default(),
+            )
+            .context("tried to get the type of `next` to calculate `TNext` of an iterator")?;
+        let next = self.normalize(Some(span), Cow::Owned(next), Default::default())?;
+
+        let param = match next.normalize() {
+            Type::Function(f) => match f.params.first() {
+                Some(param) => param.clone(),
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        match param.pat {
+            // `next(...args: [] | [TNext])`
+            RPat::Rest(..) => self
+                .get_element_from_iterator(span, Cow::Owned(*param.ty), 0)
+                .map(Cow::into_owned)
+                .map(Some)
+                .context("tried to get the first element of the rest parameter of `next`"),
+            _ => Ok(Some(*param.ty)),
+        }
+    }
+
     pub(crate) fn get_rest_elements<'a>(
         &mut self,
         span: Option<Span>,
@@ -592,6 +736,31 @@ impl Analyzer<'_, '_> {
         Ok(iterator)
     }
 
+    /// Reports an error if `ty` is iterated using `[Symbol.iterator]()`, which
+    /// is not available below ES2015 without `downlevelIteration`.
+    pub(crate) fn report_error_for_iteration_below_es2015(&mut self, span: Span, ty: &Type) {
+        if self.is_builtin || self.env.target() >= EsVersion::Es2015 || self.rule().downlevel_iteration {
+            return;
+        }
+
+        let requires_iterator = ty.iter_union().any(|ty| {
+            if ty.is_any() {
+                return false;
+            }
+
+            match self.get_iterator_inner(span, Cow::Borrowed(ty), Default::default()) {
+                Ok(iterator) => {
+                    !(iterator.is_str() || iterator.is_any() || matches!(iterator.normalize(), Type::Array(..) | Type::Tuple(..)))
+                }
+                Err(..) => false,
+            }
+        });
+
+        if requires_iterator {
+            self.storage.report(ErrorKind::IterationNotAvailable { span }.into());
+        }
+    }
+
     fn get_iterator_inner<'a>(&mut self, span: Span, ty: Cow<'a, Type>, opts: GetIteratorOpts) -> VResult<Cow<'a, Type>> {
         let ty_str = force_dump_type_as_string(&ty);
         debug!("[exprs/array] get_iterator({})", ty_str);
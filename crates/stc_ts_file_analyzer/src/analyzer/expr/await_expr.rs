@@ -1,12 +1,14 @@
 use std::borrow::Cow;
 
 use stc_ts_ast_rnode::{RAwaitExpr, RIdent, RTsEntityName};
-use stc_ts_errors::DebugExt;
+use stc_ts_env::ModuleConfig;
+use stc_ts_errors::{DebugExt, ErrorKind};
 use stc_ts_file_analyzer_macros::validator;
 use stc_ts_types::{IdCtx, Key, Ref, Type, TypeParamInstantiation};
 use stc_utils::cache::Freeze;
 use swc_atoms::js_word;
 use swc_common::{Span, SyntaxContext};
+use swc_ecma_ast::EsVersion;
 
 use crate::{
     analyzer::{expr::TypeOfMode, Analyzer},
@@ -20,6 +22,8 @@ impl Analyzer<'_, '_> {
     fn validate(&mut self, e: &RAwaitExpr, type_ann: Option<&Type>) -> VResult<Type> {
         let span = e.span;
 
+        self.validate_top_level_await(span, false);
+
         let arg_type_ann = type_ann
             .map(|ty| {
                 // If type annotation is Promise<T>, we use PromiseLike<T> as the annotation.
@@ -74,6 +78,32 @@ impl Analyzer<'_, '_> {
 }
 
 impl Analyzer<'_, '_> {
+    /// Reports an error if `await` or `for await` at `span` is at the top
+    /// level of a script, or if the module config does not support it.
+    pub(crate) fn validate_top_level_await(&mut self, span: Span, is_for_await: bool) {
+        if self.ctx.is_dts || !self.scope.is_top_level() {
+            return;
+        }
+
+        if !self.ctx.is_external_module {
+            self.storage.report(if is_for_await {
+                ErrorKind::TopLevelForAwaitInScript { span }.into()
+            } else {
+                ErrorKind::TopLevelAwaitInScript { span }.into()
+            });
+            return;
+        }
+
+        match self.env.module() {
+            ModuleConfig::EsNext | ModuleConfig::System if self.env.target() >= EsVersion::Es2017 => {}
+            _ => self.storage.report(if is_for_await {
+                ErrorKind::InvalidModuleForTopLevelForAwait { span }.into()
+            } else {
+                ErrorKind::InvalidModuleForTopLevelAwait { span }.into()
+            }),
+        }
+    }
+
     pub(crate) fn get_awaited_type<'a>(&mut self, span: Span, ty: Cow<'a, Type>) -> VResult<Cow<'a, Type>> {
         if let Some(arg) = unwrap_ref_with_single_arg(&ty, "Promise") {
             return self.get_awaited_type(span, Cow::Borrowed(arg)).map(Cow::into_owned).map(Cow::Owned);
@@ -5,19 +5,20 @@ use std::{
 };
 
 use stc_ts_ast_rnode::{
-    RBinExpr, RComputedPropName, RExpr, RIdent, RLit, RMemberExpr, RMemberProp, ROptChainBase, ROptChainExpr, RPat, RPatOrExpr, RStr, RTpl,
-    RTsEntityName, RTsLit, RUnaryExpr,
+    RBinExpr, RComputedPropName, RExpr, RIdent, RLit, RMemberExpr, RMemberProp, ROptChainBase, ROptChainExpr, RPat, RPatOrExpr,
+    RPrivateName, RStr, RTpl, RTsEntityName, RTsLit, RUnaryExpr,
 };
 use stc_ts_errors::{DebugExt, ErrorKind, Errors};
 use stc_ts_file_analyzer_macros::extra_validator;
 use stc_ts_type_ops::{generalization::prevent_generalize, is_str_lit_or_union, Fix};
 use stc_ts_types::{
-    name::Name, Class, IdCtx, Intersection, Key, KeywordType, KeywordTypeMetadata, LitType, Ref, TypeElement, Union, UnionMetadata,
+    name::Name, Class, ClassMember, IdCtx, Intersection, Key, KeywordType, KeywordTypeMetadata, LitType, QueryExpr, QueryType, Ref,
+    StaticThis, ThisType, TypeElement, Union, UnionMetadata,
 };
 use stc_utils::{cache::Freeze, stack};
-use swc_atoms::js_word;
+use swc_atoms::{js_word, JsWord};
 use swc_common::{Span, Spanned, SyntaxContext, TypeEq};
-use swc_ecma_ast::{op, BinaryOp, TsKeywordTypeKind, TsTypeOperatorOp};
+use swc_ecma_ast::{op, BinaryOp, TsKeywordTypeKind, TsTypeOperatorOp, VarDeclKind};
 use swc_ecma_utils::Value::Known;
 use tracing::info;
 
@@ -26,7 +27,7 @@ use crate::{
         assign::AssignOpts,
         expr::{type_cast::CastableOpts, TypeOfMode},
         generic::ExtendsOpts,
-        scope::ExpandOpts,
+        scope::{ExpandOpts, VarKind},
         types::NormalizeTypeOpts,
         util::{Comparator, ResultExt},
         Analyzer, Ctx, ScopeKind,
@@ -75,9 +76,13 @@ impl Analyzer<'_, '_> {
             },
         );
 
-        let mut lt = {
-            let mut a = self.with_ctx(ctx);
-            left.validate_with_args(&mut *a, child_ctxt)
+        let mut lt = match &**left {
+            // `#foo` of `#foo in obj` does not have a type.
+            RExpr::PrivateName(p) if op == op!("in") => Ok(Type::any(p.span, Default::default())),
+            _ => {
+                let mut a = self.with_ctx(ctx);
+                left.validate_with_args(&mut *a, child_ctxt)
+            }
         }
         .and_then(|mut ty| {
             if ty.is_ref_type() {
@@ -350,7 +355,7 @@ impl Analyzer<'_, '_> {
                     )
                     | (RExpr::Lit(RLit::Null(..)), _) => None,
 
-                    (l, r) => Some((extract_name_for_assignment(l, op == op!("==="))?, r_ty)),
+                    (l, r) => Some((self.extract_name_for_assignment(l, op == op!("==="))?, r_ty)),
                 }) {
                     if self.ctx.in_cond {
                         let (name, mut r) = self.calc_type_facts_for_equality(l, r_ty)?;
@@ -373,11 +378,28 @@ impl Analyzer<'_, '_> {
                             }
                         }
 
+                        // `const { kind } = action; if (kind === 'a') {}`
+                        let destructured = if name.len() == 1 {
+                            self.data.destructured_props.get(&name.top()).cloned()
+                        } else {
+                            None
+                        };
+
                         if op == op!("===") || op == op!("==") {
+                            if let Some(prop) = destructured {
+                                self.cur_facts.false_facts.excludes.entry(prop.clone()).or_default().push(r.clone());
+                                self.add_deep_type_fact(span, prop, r.clone(), true);
+                            }
+
                             self.cur_facts.false_facts.excludes.entry(name.clone()).or_default().push(r.clone());
 
                             self.add_deep_type_fact(span, name, r, true);
                         } else if !is_eq {
+                            if let Some(prop) = destructured {
+                                self.cur_facts.true_facts.excludes.entry(prop.clone()).or_default().push(r.clone());
+                                self.add_deep_type_fact(span, prop, r.clone(), false);
+                            }
+
                             // Remove from union
                             self.cur_facts.true_facts.excludes.entry(name.clone()).or_default().push(r.clone());
 
@@ -499,6 +521,10 @@ impl Analyzer<'_, '_> {
                     return Err(ErrorKind::Unknown { span }.into());
                 }
 
+                if let Some(ty) = self.type_of_bigint_operation(span, op, &lt, &rt)? {
+                    return Ok(ty);
+                }
+
                 if lt.is_num() && rt.is_num() {
                     return Ok(Type::Keyword(KeywordType {
                         span,
@@ -595,6 +621,10 @@ impl Analyzer<'_, '_> {
             op!("*") | op!("/") => {
                 no_unknown!();
 
+                if let Some(ty) = self.type_of_bigint_operation(span, op, &lt, &rt)? {
+                    return Ok(ty);
+                }
+
                 Ok(Type::Keyword(KeywordType {
                     span,
                     kind: TsKeywordTypeKind::TsNumberKeyword,
@@ -605,6 +635,10 @@ impl Analyzer<'_, '_> {
             op!(bin, "-") | op!("<<") | op!(">>") | op!(">>>") | op!("%") | op!("|") | op!("&") | op!("^") | op!("**") => {
                 no_unknown!();
 
+                if let Some(ty) = self.type_of_bigint_operation(span, op, &lt, &rt)? {
+                    return Ok(ty);
+                }
+
                 if op == op!("**") {
                     let lt = lt.normalize();
                     let rt = rt.normalize();
@@ -697,7 +731,10 @@ impl Analyzer<'_, '_> {
             }
 
             op!("in") => {
-                if self.ctx.in_cond {
+                if let RExpr::PrivateName(name) = &**left {
+                    self.add_type_facts_for_private_brand_check(span, name, right)
+                        .report(&mut self.storage);
+                } else if self.ctx.in_cond {
                     let left = match &**left {
                         RExpr::Lit(RLit::Str(s)) => Some(s.value.clone()),
                         RExpr::Tpl(t) if t.quasis.len() == 1 => t.quasis[0].cooked.clone().map(|v| (&*v).into()),
@@ -710,11 +747,10 @@ impl Analyzer<'_, '_> {
 
                     if let Some(name) = name {
                         if let Some(property) = left {
-                            let new_ty = self.narrow_types_with_property(span, &rt, &property, None)?.fixed().freezed();
+                            let (true_ty, false_ty) = self.narrow_types_with_in(span, &rt, &property)?;
 
-                            self.add_deep_type_fact(span, name.clone(), new_ty.clone(), true);
-
-                            self.cur_facts.false_facts.excludes.entry(name).or_default().push(new_ty);
+                            self.add_deep_type_fact(span, name.clone(), true_ty.fixed().freezed(), true);
+                            self.add_deep_type_fact(span, name, false_ty.fixed().freezed(), false);
                         }
                     }
                 }
@@ -991,6 +1027,26 @@ impl Analyzer<'_, '_> {
             if disc_ty.is_intersection() {
                 return Ok(true);
             }
+
+            if case_ty.is_null_or_undefined() {
+                return Ok(true);
+            }
+
+            // Literals are compared as is only if both of them are literals.
+            if !disc_ty.is_lit() || !case_ty.is_lit() {
+                let disc_ty = disc_ty.clone().generalize_lit();
+                let case_ty = case_ty.clone().generalize_lit();
+
+                return self.has_overlap(
+                    span,
+                    &disc_ty,
+                    &case_ty,
+                    CastableOpts {
+                        allow_assignment_to_param_constraint: true,
+                        ..Default::default()
+                    },
+                );
+            }
         }
 
         self.has_overlap(
@@ -1161,6 +1217,67 @@ impl Analyzer<'_, '_> {
         Ok(ty.into_owned())
     }
 
+    /// `#foo in obj` narrows `obj` to the class declaring `#foo`, or to
+    /// `typeof Class` if `#foo` is static.
+    fn add_type_facts_for_private_brand_check(&mut self, span: Span, name: &RPrivateName, obj: &RExpr) -> VResult<()> {
+        let key = Key::Private(name.clone().into());
+
+        let is_static = self.scope.class_members().iter().find_map(|(_, member)| match member {
+            ClassMember::Property(p) if p.key.type_eq(&key) => Some(p.is_static),
+            ClassMember::Method(m) if m.key.type_eq(&key) => Some(m.is_static),
+            _ => None,
+        });
+        let is_static = match is_static {
+            Some(v) => v,
+            None => {
+                return Err(ErrorKind::NoSuchPropertyInClass {
+                    span: name.span,
+                    class_name: self.scope.get_this_class_name(),
+                    prop: key,
+                }
+                .into())
+            }
+        };
+
+        if !self.ctx.in_cond || self.is_builtin {
+            return Ok(());
+        }
+
+        let name = match Name::try_from(obj) {
+            Ok(v) => v,
+            Err(..) => return Ok(()),
+        };
+
+        // `this` of a subclass does not have to be the object, so the object is
+        // narrowed to the declaring class.
+        let ty = match (self.scope.get_this_class_name(), is_static) {
+            (Some(class_name), true) => Type::Query(QueryType {
+                span,
+                expr: box QueryExpr::TsEntityName(class_name.into()),
+                metadata: Default::default(),
+            }),
+            (Some(class_name), false) => Type::Ref(Ref {
+                span,
+                type_name: class_name.into(),
+                type_args: None,
+                metadata: Default::default(),
+            }),
+            // Anonymous classes can't be referenced by name.
+            (None, true) => Type::from(StaticThis {
+                span,
+                metadata: Default::default(),
+            }),
+            (None, false) => Type::from(ThisType {
+                span,
+                metadata: Default::default(),
+            }),
+        };
+
+        self.add_deep_type_fact(span, name, ty.freezed(), true);
+
+        Ok(())
+    }
+
     fn validate_relative_comparison_operands(&mut self, span: Span, op: BinaryOp, l: &Type, r: &Type) -> VResult<()> {
         let marks = self.marks();
 
@@ -1842,6 +1959,47 @@ impl Analyzer<'_, '_> {
         self.storage.report_all(errors);
     }
 
+    /// Returns the type of an arithmetic operation if an operand can be a
+    /// `bigint`.
+    ///
+    /// Mixing `bigint` and `number` is an error.
+    fn type_of_bigint_operation(&mut self, span: Span, op: BinaryOp, lt: &Type, rt: &Type) -> VResult<Option<Type>> {
+        let opts = NormalizeTypeOpts {
+            preserve_union: true,
+            ..Default::default()
+        };
+        let l = self.normalize(Some(lt.span()), Cow::Borrowed(lt), opts)?;
+        let r = self.normalize(Some(rt.span()), Cow::Borrowed(rt), opts)?;
+
+        if !is_maybe_bigint(&l) && !is_maybe_bigint(&r) {
+            return Ok(None);
+        }
+
+        let is_add = op == op!(bin, "+");
+        // `any` is treated as a `bigint` except for `+`, because `any + 1n` is `any`.
+        let is_bigint = |ty: &Type| is_bigint_like(ty) || (!is_add && ty.is_any());
+
+        if is_bigint(&l) && is_bigint(&r) && op != op!(">>>") {
+            return Ok(Some(Type::Keyword(KeywordType {
+                span,
+                kind: TsKeywordTypeKind::TsBigIntKeyword,
+                metadata: Default::default(),
+            })));
+        }
+
+        if is_add && (l.is_any() || r.is_any() || is_str_like_for_addition(&l) || is_str_like_for_addition(&r)) {
+            return Ok(None);
+        }
+
+        Err(ErrorKind::InvalidBinaryOp {
+            span,
+            op,
+            left: box lt.clone(),
+            right: box rt.clone(),
+        }
+        .into())
+    }
+
     fn is_valid_lhs_of_in(&mut self, ty: &Type) -> bool {
         let ty = ty.normalize();
 
@@ -1957,33 +2115,85 @@ impl Analyzer<'_, '_> {
     }
 }
 
-pub(super) fn extract_name_for_assignment(e: &RExpr, is_exact_eq: bool) -> Option<Name> {
-    match e {
-        RExpr::Paren(e) => extract_name_for_assignment(&e.expr, is_exact_eq),
-        RExpr::Assign(e) => match &e.left {
-            RPatOrExpr::Expr(e) => extract_name_for_assignment(e, is_exact_eq),
-            RPatOrExpr::Pat(pat) => match &**pat {
-                RPat::Ident(i) => Some(i.id.clone().into()),
-                RPat::Expr(e) => extract_name_for_assignment(e, is_exact_eq),
-                _ => None,
+impl Analyzer<'_, '_> {
+    pub(super) fn extract_name_for_assignment(&self, e: &RExpr, is_exact_eq: bool) -> Option<Name> {
+        match e {
+            RExpr::Paren(e) => self.extract_name_for_assignment(&e.expr, is_exact_eq),
+            RExpr::Assign(e) => match &e.left {
+                RPatOrExpr::Expr(e) => self.extract_name_for_assignment(e, is_exact_eq),
+                RPatOrExpr::Pat(pat) => match &**pat {
+                    RPat::Ident(i) => Some(i.id.clone().into()),
+                    RPat::Expr(e) => self.extract_name_for_assignment(e, is_exact_eq),
+                    _ => None,
+                },
             },
-        },
-        RExpr::Member(RMemberExpr { obj, prop, .. }) => {
-            let mut name = extract_name_for_assignment(obj, is_exact_eq)?;
-
-            name.push(match prop {
-                RMemberProp::Ident(i) => i.sym.clone(),
-                RMemberProp::Computed(RComputedPropName {
-                    expr: box RExpr::Lit(RLit::Str(s)),
-                    ..
-                }) => s.value.clone(),
-                _ => return None,
-            });
+            RExpr::Member(RMemberExpr { obj, prop, .. }) => {
+                let mut name = self.extract_name_for_assignment(obj, is_exact_eq)?;
+
+                name.push(self.literal_key_of_member_prop(prop)?);
+
+                Some(name)
+            }
 
-            Some(name)
+            _ => Name::try_from(e).ok(),
         }
+    }
+
+    /// Like `Name::try_from`, but element accesses with literal keys are also
+    /// converted, like `x.kind` for `x[k]`.
+    pub(super) fn name_of_member_expr(&self, e: &RMemberExpr) -> Option<Name> {
+        let mut name = match &*e.obj {
+            RExpr::Member(obj) => self.name_of_member_expr(obj)?,
+            obj => Name::try_from(obj).ok()?,
+        };
+
+        name.push(self.literal_key_of_member_prop(&e.prop)?);
+
+        Some(name)
+    }
+
+    /// Returns the key of `x.kind`, `x["kind"]` or `x[k]` where `k` is a
+    /// `const` variable of a string literal type.
+    fn literal_key_of_member_prop(&self, prop: &RMemberProp) -> Option<JsWord> {
+        match prop {
+            RMemberProp::Ident(i) => Some(i.sym.clone()),
+            RMemberProp::Computed(RComputedPropName { expr, .. }) => self.literal_key(expr),
+            RMemberProp::PrivateName(..) => None,
+        }
+    }
+
+    fn literal_key(&self, e: &RExpr) -> Option<JsWord> {
+        match e {
+            RExpr::Lit(RLit::Str(s)) => Some(s.value.clone()),
+            RExpr::Tpl(t) if t.exprs.is_empty() => t.quasis[0].cooked.clone().map(|v| (&*v).into()),
+            RExpr::Paren(e) => self.literal_key(&e.expr),
+            RExpr::Ident(i) => {
+                let var = self.find_var(&i.into())?;
+                if var.kind != VarKind::Var(VarDeclKind::Const) {
+                    return None;
+                }
 
-        _ => Name::try_from(e).ok(),
+                match var.ty.as_ref().or(var.actual_ty.as_ref())?.normalize() {
+                    Type::Lit(LitType { lit: RTsLit::Str(s), .. }) => Some(s.value.clone()),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+fn is_bigint_like(t: &Type) -> bool {
+    match t.normalize() {
+        Type::Union(Union { types, .. }) => types.iter().all(is_bigint_like),
+        _ => t.is_bigint(),
+    }
+}
+
+fn is_maybe_bigint(t: &Type) -> bool {
+    match t.normalize() {
+        Type::Union(Union { types, .. }) => types.iter().any(is_maybe_bigint),
+        _ => t.is_bigint(),
     }
 }
 
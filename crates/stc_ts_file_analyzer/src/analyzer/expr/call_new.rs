@@ -12,13 +12,13 @@ use stc_ts_ast_rnode::{
 use stc_ts_env::MarkExt;
 use stc_ts_errors::{
     debug::{dump_type_as_string, dump_type_map, force_dump_type_as_string, print_type},
-    DebugExt, ErrorKind,
+    DebugExt, ErrorKind, Errors,
 };
 use stc_ts_file_analyzer_macros::extra_validator;
 use stc_ts_generics::type_param::finder::TypeParamUsageFinder;
 use stc_ts_type_ops::{generalization::prevent_generalize, is_str_lit_or_union, Fix};
 use stc_ts_types::{
-    type_id::SymbolId, Alias, Array, Class, ClassDef, ClassMember, ClassProperty, CommonTypeMetadata, Function, Id, IdCtx,
+    name::Name, type_id::SymbolId, Alias, Array, Class, ClassDef, ClassMember, ClassProperty, CommonTypeMetadata, Function, IdCtx,
     IndexedAccessType, Instance, Interface, Intersection, Key, KeywordType, KeywordTypeMetadata, LitType, Ref, Symbol, ThisType, Union,
     UnionMetadata,
 };
@@ -33,10 +33,10 @@ use ty::TypeExt;
 use crate::{
     analyzer::{
         assign::AssignOpts,
-        expr::TypeOfMode,
+        expr::{jsx::is_context_sensitive_fn, TypeOfMode},
         generic::InferTypeOpts,
         scope::ExpandOpts,
-        types::NormalizeTypeOpts,
+        types::{is_generic_indexed_access, NormalizeTypeOpts},
         util::{make_instance_type, ResultExt},
         Analyzer, Ctx, ScopeKind,
     },
@@ -111,7 +111,13 @@ impl Analyzer<'_, '_> {
                 return Ok(Type::any(span, Default::default()));
             }
             RCallee::Expr(callee) => callee,
-            RCallee::Import(..) => todo!("dynamic import"),
+            RCallee::Import(..) => {
+                return Err(ErrorKind::Unimplemented {
+                    span,
+                    msg: "dynamic import".into(),
+                }
+                .into())
+            }
         };
 
         let is_callee_iife = is_fn_expr(callee);
@@ -253,21 +259,31 @@ impl Analyzer<'_, '_> {
 
         match *callee {
             RExpr::Ident(ref i) if i.sym == js_word!("require") => {
-                let id = args
-                    .iter()
-                    .cloned()
-                    .map(|arg| match arg {
-                        RExprOrSpread { spread: None, expr } => match *expr {
-                            RExpr::Lit(RLit::Str(RStr { span, value, .. })) => RIdent::new(value, span).into(),
-                            _ => unimplemented!("dynamic import: require()"),
-                        },
-                        _ => unimplemented!("error reporting: spread element in require()"),
-                    })
-                    .next()
-                    .unwrap();
-                if let Some(dep) = self.find_imported_var(&id)? {
-                    let dep = dep;
-                    unimplemented!("dep: {:#?}", dep);
+                let id = match args.first() {
+                    Some(RExprOrSpread { spread: None, expr }) => match &**expr {
+                        RExpr::Lit(RLit::Str(RStr { span, value, .. })) => RIdent::new(value.clone(), *span).into(),
+                        expr => {
+                            return Err(ErrorKind::Unimplemented {
+                                span: expr.span(),
+                                msg: "dynamic import: require()".into(),
+                            }
+                            .into())
+                        }
+                    },
+                    _ => {
+                        return Err(ErrorKind::Unimplemented {
+                            span,
+                            msg: "error reporting: require() without a string literal".into(),
+                        }
+                        .into())
+                    }
+                };
+                if self.find_imported_var(&id)?.is_some() {
+                    return Err(ErrorKind::Unimplemented {
+                        span,
+                        msg: "require() of an imported module".into(),
+                    }
+                    .into());
                 }
 
                 // if let Some(Type::Enum(ref e)) = self.scope.find_type(&i.into()) {
@@ -297,7 +313,8 @@ impl Analyzer<'_, '_> {
 
                 // Symbol uses special type
                 if !args.is_empty() {
-                    unimplemented!("Error reporting for calling `Symbol` with arguments is not implemented yet")
+                    // TODO: Validate types of arguments.
+                    self.validate_args(args).report(&mut self.storage);
                 }
 
                 return Ok(Type::Symbol(Symbol {
@@ -528,6 +545,46 @@ impl Analyzer<'_, '_> {
         })
     }
 
+    /// Calls `callee` with arguments which do not exist in the source code,
+    /// like the implicit arguments of a decorator.
+    pub(crate) fn call_with_arg_types(&mut self, span: Span, callee: &Type, arg_types: Vec<Type>) -> VResult<Type> {
+        let args = arg_types
+            .iter()
+            .map(|ty| RExprOrSpread {
+                spread: None,
+                expr: box RExpr::Invalid(RInvalid { span: ty.span() }),
+            })
+            .collect_vec();
+        let mut arg_types = arg_types
+            .into_iter()
+            .map(|ty| TypeOrSpread {
+                span: ty.span(),
+                spread: None,
+                ty: box ty,
+            })
+            .collect_vec();
+        arg_types.make_clone_cheap();
+
+        self.with_child(ScopeKind::Call, Default::default(), |analyzer: &mut Analyzer| {
+            let spread_arg_types = analyzer.spread_args(&arg_types).context("tried to handle spreads in arguments")?;
+
+            let ty = analyzer.extract(
+                span,
+                ReevalMode::NoReeval,
+                callee,
+                ExtractKind::Call,
+                &args,
+                &arg_types,
+                &spread_arg_types,
+                None,
+                None,
+                Default::default(),
+            )?;
+
+            Ok(ty.fixed())
+        })
+    }
+
     /// TODO(kdy1): Use Cow for `obj_type`
     ///
     /// ## Parameters
@@ -1239,6 +1296,8 @@ impl Analyzer<'_, '_> {
                         _ => {
                             self.scope.is_call_arg_count_unknown = true;
 
+                            self.report_error_for_iteration_below_es2015(arg.span(), &arg_ty);
+
                             let elem_type = self
                                 .get_iterator_element_type(arg.span(), arg_ty, false, Default::default())
                                 .context("tried to get element type of an iterator for spread syntax in arguments")?;
@@ -1953,7 +2012,20 @@ impl Analyzer<'_, '_> {
 
                 if candidates.is_empty() {
                     if let Some(sc) = &cls.super_class {
-                        candidates.extend(self.extract_callee_candidates(span, kind, sc)?);
+                        // Constructors of the super class are inherited, but they create an
+                        // instance of this class.
+                        candidates.extend(
+                            self.extract_callee_candidates(span, kind, sc)?
+                                .into_iter()
+                                .map(|candidate| CallCandidate {
+                                    ret_ty: Type::Class(Class {
+                                        span,
+                                        def: box cls.clone(),
+                                        metadata: Default::default(),
+                                    }),
+                                    ..candidate
+                                }),
+                        );
                     }
                 }
 
@@ -2111,8 +2183,10 @@ impl Analyzer<'_, '_> {
         let mut max_param = Some(params.len());
         for (index, param) in params.iter().enumerate() {
             match &param.pat {
-                RPat::Rest(..) => match param.ty.normalize_instance() {
+                RPat::Rest(rest) => match param.ty.normalize_instance() {
                     Type::Tuple(param_ty) => {
+                        // Array patterns are already counted by `count_required_pat`.
+                        let counted = matches!(&*rest.arg, RPat::Array(..));
                         for elem in &param_ty.elems {
                             match elem.ty.normalize() {
                                 Type::Rest(..) => {
@@ -2121,6 +2195,9 @@ impl Analyzer<'_, '_> {
                                 }
                                 Type::Optional(..) => {}
                                 _ => {
+                                    if !counted {
+                                        min_param += 1;
+                                    }
                                     if let Some(max) = &mut max_param {
                                         *max += 1;
                                     }
@@ -2236,9 +2313,11 @@ impl Analyzer<'_, '_> {
     ) -> VResult<Option<Type>> {
         let span = span.with_ctxt(SyntaxContext::empty());
 
-        let mut callable = candidates
+        let filtered = self.filter_candidates_by_lit_args(span, candidates, args, arg_types);
+
+        let mut callable = filtered
             .iter()
-            .map(|c| {
+            .map(|&c| {
                 let res = self.check_call_args(
                     span,
                     c.type_params.as_deref(),
@@ -2260,7 +2339,7 @@ impl Analyzer<'_, '_> {
 
         // Check if all candidates are failed.
         if !opts.skip_check_for_overloads
-            && callable.len() > 1
+            && candidates.len() > 1
             && callable
                 .iter()
                 .all(|(_, res)| matches!(res, ArgCheckResult::WrongArgCount | ArgCheckResult::ArgTypeMismatch))
@@ -2304,6 +2383,71 @@ impl Analyzer<'_, '_> {
         .map(Some)
     }
 
+    /// Filters overloads using literal arguments before the first function
+    /// expression, like `"click"` of `addEventListener("click", cb)`.
+    ///
+    /// Parameters of callbacks are implicitly typed as `any` until a signature
+    /// is selected, so they should not affect the selection.
+    ///
+    /// Returns all candidates if none of them matches.
+    fn filter_candidates_by_lit_args<'a>(
+        &mut self,
+        span: Span,
+        candidates: &'a [CallCandidate],
+        args: &[RExprOrSpread],
+        arg_types: &[TypeOrSpread],
+    ) -> Vec<&'a CallCandidate> {
+        let lit_arg_count = args.iter().take_while(|arg| arg.spread.is_none() && !is_fn_expr(&arg.expr)).count();
+        if candidates.len() <= 1 || !args[..lit_arg_count].iter().any(|arg| is_lit_expr(&arg.expr)) {
+            return candidates.iter().collect();
+        }
+
+        let filtered = candidates
+            .iter()
+            .filter(|c| {
+                // Types of parameters are not known yet.
+                if c.type_params.is_some() {
+                    return true;
+                }
+
+                let skip = match c.params.first().map(|param| &param.pat) {
+                    Some(RPat::Ident(RBindingIdent {
+                        id: RIdent { sym: js_word!("this"), .. },
+                        ..
+                    })) => 1,
+                    _ => 0,
+                };
+
+                args[..lit_arg_count].iter().enumerate().all(|(idx, arg)| {
+                    if !is_lit_expr(&arg.expr) {
+                        return true;
+                    }
+
+                    match c.params.get(skip + idx) {
+                        Some(param) if !matches!(param.pat, RPat::Rest(..)) => self
+                            .assign_with_opts(
+                                &mut Default::default(),
+                                &param.ty,
+                                &arg_types[idx].ty,
+                                AssignOpts {
+                                    span,
+                                    ..Default::default()
+                                },
+                            )
+                            .is_ok(),
+                        _ => true,
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if filtered.is_empty() {
+            candidates.iter().collect()
+        } else {
+            filtered
+        }
+    }
+
     /// Returns the return type of function. This method should be called only
     /// for final step because it emits errors instead of returning them.
     ///
@@ -2389,11 +2533,26 @@ impl Analyzer<'_, '_> {
 
         debug!("get_return_type: \ntype_params = {:?}\nret_ty = {:?}", type_params, ret_ty);
 
+        let widened_arg_types;
+        let (arg_types, spread_arg_types) = if spread_arg_types.len() == args.len()
+            && args.iter().all(|arg| arg.spread.is_none())
+            && args.iter().any(|arg| is_tpl_expr(&arg.expr))
+        {
+            widened_arg_types = self.widen_tpl_args(span, &params, args, spread_arg_types);
+            (&*widened_arg_types, &*widened_arg_types)
+        } else {
+            (arg_types, spread_arg_types)
+        };
+
         if let Some(type_params) = type_params {
             // Type parameters should default to `unknown`.
             let mut default_unknown_map = HashMap::with_capacity_and_hasher(type_params.len(), Default::default());
 
-            if type_ann.is_none() && self.ctx.reevaluating_call_or_new {
+            // Type parameters of generic function arguments are preserved if the return
+            // type is a single non-generic signature.
+            let keep_arg_type_params = matches!(ret_ty.normalize(), Type::Function(Function { type_params: None, .. }));
+
+            if type_ann.is_none() && self.ctx.reevaluating_call_or_new && !keep_arg_type_params {
                 for at in spread_arg_types {
                     if let Type::Function(Function {
                         type_params: Some(type_params),
@@ -2420,31 +2579,24 @@ impl Analyzer<'_, '_> {
                 self.register_type(param.name.clone(), Type::Param(param.clone()));
             }
 
-            let inferred_from_return_type = if self.ctx.reevaluating_call_or_new {
-                None
+            let contextual_arg_types;
+            let (arg_types, spread_arg_types) = if !self.ctx.reevaluating_call_or_new
+                && spread_arg_types.len() == args.len()
+                && args.iter().all(|arg| arg.spread.is_none())
+                && args.iter().skip(1).any(|arg| is_context_sensitive_fn(&arg.expr))
+            {
+                contextual_arg_types = self.type_callbacks_left_to_right(span, type_args, type_params, &params, args, spread_arg_types)?;
+                (&*contextual_arg_types, &*contextual_arg_types)
             } else {
-                match type_ann {
-                    Some(type_ann) => self
-                        .infer_type_with_types(span, type_params, &ret_ty, type_ann, Default::default())
-                        .map(Some)?,
-                    None => None,
-                }
+                (arg_types, spread_arg_types)
             };
 
-            let mut expanded_params;
-            let params = if let Some(map) = &inferred_from_return_type {
-                expanded_params = params
-                    .into_iter()
-                    .map(|v| -> VResult<_> {
-                        let ty = box self.expand_type_params(map, *v.ty, Default::default())?;
-
-                        Ok(FnParam { ty, ..v })
-                    })
-                    .collect::<Result<Vec<_>, _>>()?;
-                expanded_params.make_clone_cheap();
-                expanded_params
+            // Inferences from the return type have lower priority than the ones from
+            // arguments.
+            let inference_from_ret_ty = if self.ctx.reevaluating_call_or_new {
+                None
             } else {
-                params
+                type_ann.map(|type_ann| (&ret_ty, type_ann))
             };
 
             // Assert deep clone
@@ -2463,8 +2615,10 @@ impl Analyzer<'_, '_> {
                 &params,
                 spread_arg_types,
                 None,
+                inference_from_ret_ty,
                 InferTypeOpts {
                     is_type_ann: type_ann.is_some(),
+                    keep_arg_type_params,
                     ..Default::default()
                 },
             )?;
@@ -2481,107 +2635,7 @@ impl Analyzer<'_, '_> {
                 .collect::<Result<Vec<_>, _>>()?
                 .freezed();
 
-            let ctx = Ctx {
-                in_argument: true,
-                reevaluating_argument: true,
-                ..self.ctx
-            };
-            let mut new_args = vec![];
-
-            for (idx, (arg, param)) in args.iter().zip(expanded_param_types.iter()).enumerate() {
-                let arg_ty = &arg_types[idx];
-                print_type(&format!("Expanded parameter at {}", idx), &param.ty);
-                print_type(&format!("Original argument at {}", idx), &arg_ty.ty);
-
-                let (type_param_decl, actual_params) = match param.ty.normalize() {
-                    Type::Function(f) => (&f.type_params, &f.params),
-                    _ => {
-                        new_args.push(arg_ty.clone());
-                        continue;
-                    }
-                };
-
-                if let Some(type_param_decl) = type_param_decl {
-                    for param in &type_param_decl.params {
-                        self.register_type(param.name.clone(), Type::Param(param.clone()));
-                    }
-                }
-
-                // TODO: Use apply_fn_type_ann instead
-                let mut patch_arg = |idx: usize, pat: &RPat| -> VResult<()> {
-                    if actual_params.len() <= idx {
-                        return Ok(());
-                    }
-                    let actual = &actual_params[idx];
-
-                    let default_any_ty: Option<_> = try {
-                        let node_id = pat.node_id()?;
-                        self.mutations.as_ref()?.for_pats.get(&node_id)?.ty.clone()?
-                    };
-
-                    if let Some(ty) = default_any_ty {
-                        match &ty {
-                            Type::Keyword(KeywordType {
-                                span,
-                                kind: TsKeywordTypeKind::TsAnyKeyword,
-                                metadata,
-                                ..
-                            }) if metadata.common.implicit => {
-                                // let new_ty =
-                                // RTsType::from(actual.ty.clone()).validate_with(self)?;
-                                // if let Some(node_id) = pat.node_id() {
-                                //     if let Some(m) = &mut self.mutations {
-                                //         m.for_pats.entry(node_id).or_default().ty = Some(new_ty);
-                                //     }
-                                // }
-                                let new_ty = *actual.ty.clone();
-                                if let Some(node_id) = pat.node_id() {
-                                    if let Some(m) = &mut self.mutations {
-                                        m.for_pats.entry(node_id).or_default().ty = Some(new_ty);
-                                    }
-                                }
-                                return Ok(());
-                            }
-                            _ => {}
-                        }
-                    }
-                    Ok(())
-                };
-
-                let ty = match &*arg.expr {
-                    RExpr::Arrow(arrow) => {
-                        for (idx, pat) in arrow.params.iter().enumerate() {
-                            patch_arg(idx, pat)?;
-                        }
-
-                        info!("Inferring type of arrow expr with updated type");
-                        // It's okay to use default as we have patched parameters.
-                        let mut ty = box Type::Function(arrow.validate_with_default(&mut *self.with_ctx(ctx))?);
-                        self.add_required_type_params(&mut ty);
-                        ty
-                    }
-                    RExpr::Fn(fn_expr) => {
-                        for (idx, param) in fn_expr.function.params.iter().enumerate() {
-                            patch_arg(idx, &param.pat)?;
-                        }
-
-                        info!("Inferring type of function expr with updated type");
-                        let mut ty = box Type::Function(
-                            fn_expr
-                                .function
-                                .validate_with_args(&mut *self.with_ctx(ctx), fn_expr.ident.as_ref())?,
-                        );
-                        self.add_required_type_params(&mut ty);
-                        ty
-                    }
-                    _ => arg_ty.ty.clone(),
-                };
-                print_type(&format!("Mapped argument at {}", idx), &arg_ty.ty);
-
-                let new_arg = TypeOrSpread { ty, ..arg_ty.clone() };
-
-                new_args.push(new_arg);
-            }
+            let mut new_args = self.reevaluate_fn_args(args, arg_types, &expanded_param_types, false)?;
 
             if !self.ctx.reevaluating_call_or_new {
                 debug!("Reevaluating a call");
@@ -2730,12 +2784,28 @@ impl Analyzer<'_, '_> {
             ty.make_clone_cheap();
 
             if kind == ExtractKind::Call {
-                self.add_call_facts(&expanded_param_types, args, &mut ty);
+                self.add_call_facts(expr, &expanded_param_types, args, &mut ty);
             }
 
             return Ok(ty);
         }
 
+        // Arguments are validated before selecting a signature, so parameters of
+        // callbacks are implicitly typed as `any` if the callee is overloaded.
+        //
+        // Now the signature is selected using the literal arguments (like `"click"`
+        // of `addEventListener("click", cb)`), so we patch them using it.
+        let mut new_args;
+        let spread_arg_types = if args.iter().all(|arg| arg.spread.is_none()) {
+            new_args = self.reevaluate_fn_args(args, arg_types, &params, true)?;
+            new_args.extend(arg_types[new_args.len()..].iter().cloned());
+            new_args.make_clone_cheap();
+
+            &*new_args
+        } else {
+            spread_arg_types
+        };
+
         self.validate_arg_types(&params, spread_arg_types, type_params.is_some());
 
         print_type("Return", &ret_ty);
@@ -2749,12 +2819,284 @@ impl Analyzer<'_, '_> {
         ret_ty.make_clone_cheap();
 
         if kind == ExtractKind::Call {
-            self.add_call_facts(&params, args, &mut ret_ty);
+            self.add_call_facts(expr, &params, args, &mut ret_ty);
         }
 
         Ok(ret_ty)
     }
 
+    /// Widens template literal arguments to `string` unless the type of the
+    /// parameter requests a template literal type.
+    ///
+    /// ```ts
+    /// declare function f<T>(x: T): T;
+    /// declare function g<T extends string>(x: T): T;
+    ///
+    /// f(`a${"b"}`); // string
+    /// g(`a${"b"}`); // "ab"
+    /// ```
+    fn widen_tpl_args(&mut self, span: Span, params: &[FnParam], args: &[RExprOrSpread], arg_types: &[TypeOrSpread]) -> Vec<TypeOrSpread> {
+        let skip = match params.first().map(|param| &param.pat) {
+            Some(RPat::Ident(RBindingIdent {
+                id: RIdent { sym: js_word!("this"), .. },
+                ..
+            })) => 1,
+            _ => 0,
+        };
+
+        let mut arg_types = arg_types.to_vec();
+
+        for (idx, arg) in args.iter().enumerate() {
+            if !is_tpl_expr(&arg.expr) {
+                continue;
+            }
+
+            let is_literal_context = match params.get(skip + idx) {
+                Some(param) if !matches!(param.pat, RPat::Rest(..)) => self.is_tpl_lit_contextual_type(span, &param.ty),
+                _ => false,
+            };
+            if is_literal_context {
+                continue;
+            }
+
+            arg_types[idx].ty = box Type::Keyword(KeywordType {
+                span: arg_types[idx].ty.span(),
+                kind: TsKeywordTypeKind::TsStringKeyword,
+                metadata: Default::default(),
+            });
+        }
+
+        arg_types.make_clone_cheap();
+
+        arg_types
+    }
+
+    /// Validates context-sensitive callbacks again from left to right, using
+    /// type parameters inferred from the arguments before them.
+    ///
+    /// ```ts
+    /// declare function f<T, U>(items: T[], map: (item: T) => U, cb: (u: U) => void): void;
+    ///
+    /// f([1], (item) => item.toFixed(), (u) => u.toUpperCase());
+    /// ```
+    fn type_callbacks_left_to_right(
+        &mut self,
+        span: Span,
+        type_args: Option<&TypeParamInstantiation>,
+        type_params: &[TypeParam],
+        params: &[FnParam],
+        args: &[RExprOrSpread],
+        arg_types: &[TypeOrSpread],
+    ) -> VResult<Vec<TypeOrSpread>> {
+        let skip = match params.first().map(|param| &param.pat) {
+            Some(RPat::Ident(RBindingIdent {
+                id: RIdent { sym: js_word!("this"), .. },
+                ..
+            })) => 1,
+            _ => 0,
+        };
+
+        let mut arg_types = arg_types.to_vec();
+
+        for idx in 1..args.len() {
+            if !is_context_sensitive_fn(&args[idx].expr) {
+                continue;
+            }
+
+            let param = match params.get(skip + idx) {
+                Some(param) if !matches!(param.pat, RPat::Rest(..)) => param,
+                _ => break,
+            };
+
+            let inferred = self.infer_arg_types(
+                span,
+                type_args,
+                type_params,
+                params,
+                &arg_types[..idx],
+                None,
+                None,
+                Default::default(),
+            )?;
+            let param_ty = self
+                .expand_type_params(&inferred.types, *param.ty.clone(), Default::default())?
+                .freezed();
+
+            // Parameters of the callback are typed later if they are not fixed yet.
+            match param_ty.normalize() {
+                Type::Function(f) => {
+                    let mut usage_visitor = TypeParamUsageFinder::default();
+                    f.params.visit_with(&mut usage_visitor);
+                    if usage_visitor
+                        .params
+                        .iter()
+                        .any(|used| type_params.iter().any(|type_param| type_param.name == used.name))
+                    {
+                        continue;
+                    }
+                }
+                _ => continue,
+            }
+
+            let param = FnParam {
+                ty: box param_ty,
+                ..param.clone()
+            };
+            let mut new_arg = self.reevaluate_fn_args(&args[idx..=idx], &arg_types[idx..=idx], &[param], true)?;
+            arg_types[idx] = new_arg.remove(0);
+        }
+
+        arg_types.fix();
+        arg_types.make_clone_cheap();
+
+        Ok(arg_types)
+    }
+
+    /// Validates function expressions in `args` again, using types of `params`
+    /// for parameters which are implicitly typed as `any`.
+    ///
+    /// If `only_patched` is true, arguments are validated again only if a
+    /// parameter of it is patched.
+    fn reevaluate_fn_args(
+        &mut self,
+        args: &[RExprOrSpread],
+        arg_types: &[TypeOrSpread],
+        params: &[FnParam],
+        only_patched: bool,
+    ) -> VResult<Vec<TypeOrSpread>> {
+        let ctx = Ctx {
+            in_argument: true,
+            reevaluating_argument: true,
+            ..self.ctx
+        };
+        let mut new_args = vec![];
+
+        for (idx, (arg, param)) in args.iter().zip(params.iter()).enumerate() {
+            let arg_ty = &arg_types[idx];
+            print_type(&format!("Expanded parameter at {}", idx), &param.ty);
+            print_type(&format!("Original argument at {}", idx), &arg_ty.ty);
+
+            let (type_param_decl, actual_params) = match param.ty.normalize() {
+                Type::Function(f) => (&f.type_params, &f.params),
+                _ => {
+                    new_args.push(arg_ty.clone());
+                    continue;
+                }
+            };
+
+            if let Some(type_param_decl) = type_param_decl {
+                for param in &type_param_decl.params {
+                    self.register_type(param.name.clone(), Type::Param(param.clone()));
+                }
+            }
+
+            // TODO: Use apply_fn_type_ann instead
+            let mut patch_arg = |idx: usize, pat: &RPat| -> VResult<bool> {
+                if actual_params.len() <= idx {
+                    return Ok(false);
+                }
+                let actual = &actual_params[idx];
+
+                let default_any_ty: Option<_> = try {
+                    let node_id = pat.node_id()?;
+                    self.mutations.as_ref()?.for_pats.get(&node_id)?.ty.clone()?
+                };
+
+                if let Some(ty) = default_any_ty {
+                    match &ty {
+                        Type::Keyword(KeywordType {
+                            span,
+                            kind: TsKeywordTypeKind::TsAnyKeyword,
+                            metadata,
+                            ..
+                        }) if metadata.common.implicit => {
+                            // let new_ty =
+                            // RTsType::from(actual.ty.clone()).validate_with(self)?;
+                            // if let Some(node_id) = pat.node_id() {
+                            //     if let Some(m) = &mut self.mutations {
+                            //         m.for_pats.entry(node_id).or_default().ty = Some(new_ty);
+                            //     }
+                            // }
+                            let new_ty = *actual.ty.clone();
+                            if let Some(node_id) = pat.node_id() {
+                                if let Some(m) = &mut self.mutations {
+                                    m.for_pats.entry(node_id).or_default().ty = Some(new_ty);
+                                }
+                            }
+                            return Ok(true);
+                        }
+                        _ => {}
+                    }
+                }
+                Ok(false)
+            };
+
+            let ty = match &*arg.expr {
+                RExpr::Arrow(arrow) => {
+                    let mut patched = false;
+                    for (idx, pat) in arrow.params.iter().enumerate() {
+                        patched |= patch_arg(idx, pat)?;
+                    }
+                    if only_patched && !patched {
+                        new_args.push(arg_ty.clone());
+                        continue;
+                    }
+
+                    info!("Inferring type of arrow expr with updated type");
+                    // It's okay to use default as we have patched parameters.
+                    let mut ty = box Type::Function(self.without_duplicate_errors(|a| arrow.validate_with_default(&mut *a.with_ctx(ctx)))?);
+                    self.add_required_type_params(&mut ty);
+                    ty
+                }
+                RExpr::Fn(fn_expr) => {
+                    let mut patched = false;
+                    for (idx, param) in fn_expr.function.params.iter().enumerate() {
+                        patched |= patch_arg(idx, &param.pat)?;
+                    }
+                    if only_patched && !patched {
+                        new_args.push(arg_ty.clone());
+                        continue;
+                    }
+
+                    info!("Inferring type of function expr with updated type");
+                    let mut ty = box Type::Function(self.without_duplicate_errors(|a| {
+                        fn_expr.function.validate_with_args(&mut *a.with_ctx(ctx), fn_expr.ident.as_ref())
+                    })?);
+                    self.add_required_type_params(&mut ty);
+                    ty
+                }
+                _ => arg_ty.ty.clone(),
+            };
+            print_type(&format!("Mapped argument at {}", idx), &arg_ty.ty);
+
+            let new_arg = TypeOrSpread { ty, ..arg_ty.clone() };
+
+            new_args.push(new_arg);
+        }
+
+        Ok(new_args)
+    }
+
+    /// Calls `op`, which validates a callback again, and drops errors which are
+    /// already reported by a previous validation of the callback.
+    fn without_duplicate_errors<T>(&mut self, op: impl FnOnce(&mut Self) -> VResult<T>) -> VResult<T> {
+        let prev = ErrorKind::flatten(self.storage.take_errors().into());
+
+        let res = op(self);
+
+        let new_errors = ErrorKind::flatten(self.storage.take_errors().into())
+            .into_iter()
+            .filter(|err| !prev.iter().any(|prev| prev.span() == err.span() && prev.code() == err.code()))
+            .collect::<Vec<_>>();
+
+        let mut errors = Errors::default();
+        errors.extend(prev);
+        errors.extend(new_errors);
+        self.storage.report_all(errors);
+
+        res
+    }
+
     fn validate_arg_types(&mut self, params: &[FnParam], spread_arg_types: &[TypeOrSpread], is_generic: bool) {
         info!("[exprs] Validating arguments");
 
@@ -2865,6 +3207,7 @@ impl Analyzer<'_, '_> {
                                     .convert_err(|err| ErrorKind::WrongArgType {
                                         span: arg.span(),
                                         inner: box err.into(),
+                                        param_span: param.span,
                                     })
                                     .context("tried to assign to first element of a tuple type of a parameter");
 
@@ -2903,6 +3246,7 @@ impl Analyzer<'_, '_> {
                                         .convert_err(|err| ErrorKind::WrongArgType {
                                             span: arg.span(),
                                             inner: box err.into(),
+                                            param_span: param.span,
                                         })
                                         .context("tried to assign to element of a tuple type of a parameter");
 
@@ -2950,6 +3294,7 @@ impl Analyzer<'_, '_> {
                                 .convert(|err| ErrorKind::WrongArgType {
                                     span: arg.span(),
                                     inner: box err.into(),
+                                    param_span: param.span,
                                 })
                                 .context("tried assigning elem type of an array because parameter is declared as a rest pattern");
                             report_err!(err);
@@ -3002,6 +3347,7 @@ impl Analyzer<'_, '_> {
                         .convert_err(|err| ErrorKind::WrongArgType {
                             span: err.span(),
                             inner: box err.into(),
+                            param_span: param.span,
                         })
                         .context("arg is spread");
                     if let Err(err) = res {
@@ -3041,6 +3387,7 @@ impl Analyzer<'_, '_> {
                                                     ErrorKind::WrongArgType {
                                                         span: err.span(),
                                                         inner: box err.clone(),
+                                                        param_span: param.span,
                                                     }
                                                     .into()
                                                 })
@@ -3054,6 +3401,7 @@ impl Analyzer<'_, '_> {
                             ErrorKind::WrongArgType {
                                 span: arg.span(),
                                 inner: box err.into(),
+                                param_span: param.span,
                             }
                         });
 
@@ -3074,7 +3422,7 @@ impl Analyzer<'_, '_> {
     ///
     /// should make type of `subscriber` `SafeSubscriber`, not `Subscriber`.
     /// I (kdy1) don't know why.
-    fn add_call_facts(&mut self, params: &[FnParam], args: &[RExprOrSpread], ret_ty: &mut Type) {
+    fn add_call_facts(&mut self, expr: ReevalMode, params: &[FnParam], args: &[RExprOrSpread], ret_ty: &mut Type) {
         if let Type::Predicate(p) = ret_ty.normalize() {
             let ty = match &p.ty {
                 Some(v) => v.normalize(),
@@ -3082,16 +3430,31 @@ impl Analyzer<'_, '_> {
             };
 
             match &p.param_name {
-                RTsThisTypeOrIdent::TsThisType(this) => {}
+                RTsThisTypeOrIdent::TsThisType(..) => {
+                    // `a.isFoo()` narrows `a`, and `this.isFoo()` narrows `this`.
+                    if let ReevalMode::Call(RCallExpr {
+                        callee: RCallee::Expr(callee),
+                        ..
+                    }) = expr
+                    {
+                        if let RExpr::Member(RMemberExpr { obj, .. }) = callee.as_ref() {
+                            if let Ok(name) = Name::try_from(&**obj) {
+                                let ty = ty.clone().freezed();
+                                self.store_call_fact_for_var(obj.span(), name, &ty);
+                            }
+                        }
+                    }
+                }
                 RTsThisTypeOrIdent::Ident(arg_id) => {
                     for (idx, param) in params.iter().enumerate() {
                         match &param.pat {
                             RPat::Ident(i) if i.id.sym == arg_id.sym => {
                                 // TODO(kdy1): Check length of args.
                                 let arg = &args[idx];
-                                if let RExpr::Ident(var_name) = &*arg.expr {
+                                // `Array.isArray(obj.prop)` narrows `obj.prop`.
+                                if let Ok(name) = Name::try_from(&*arg.expr) {
                                     let ty = ty.clone().freezed();
-                                    self.store_call_fact_for_var(var_name.span, var_name.into(), &ty);
+                                    self.store_call_fact_for_var(arg.expr.span(), name, &ty);
                                 }
                             }
                             _ => {}
@@ -3124,6 +3487,13 @@ impl Analyzer<'_, '_> {
             false
         })();
 
+        // `any` is narrowed to the predicate type, unless it's `Object` or `Function`.
+        if orig_ty.is_any()
+            && !matches!(new_ty.normalize(), Type::Interface(i) if matches!(*i.name.sym(), js_word!("Object") | js_word!("Function")))
+        {
+            return Ok(new_ty.into_owned());
+        }
+
         if use_simple_intersection {
             return Ok(Type::Intersection(Intersection {
                 span,
@@ -3160,11 +3530,13 @@ impl Analyzer<'_, '_> {
 
                 let mut upcasted = false;
                 for ty in orig_ty.iter_union() {
-                    if let Some(true) = self.extends(span, &new_ty, ty, Default::default()) {
+                    // Constituents are preferred, so `Array.isArray` narrows `string | number[]`
+                    // to `number[]` instead of `any[]`.
+                    if let Some(true) = self.extends(span, ty, &new_ty, Default::default()) {
+                        new_types.push(ty.clone());
+                    } else if let Some(true) = self.extends(span, &new_ty, ty, Default::default()) {
                         upcasted = true;
                         new_types.push(new_ty.clone().into_owned());
-                    } else if let Some(true) = self.extends(span, ty, &new_ty, Default::default()) {
-                        new_types.push(ty.clone());
                     }
                 }
 
@@ -3194,21 +3566,27 @@ impl Analyzer<'_, '_> {
     }
 
     #[extra_validator]
-    fn store_call_fact_for_var(&mut self, span: Span, var_name: Id, new_ty: &Type) {
+    fn store_call_fact_for_var(&mut self, span: Span, name: Name, new_ty: &Type) {
         match new_ty.normalize() {
             Type::Keyword(..) | Type::Lit(..) => {}
             _ => {
-                if let Some(previous_types) = self.find_var_type(&var_name.clone(), TypeOfMode::RValue).map(Cow::into_owned) {
+                let previous_types = if name.len() == 1 {
+                    self.find_var_type(&name.top(), TypeOfMode::RValue).map(Cow::into_owned)
+                } else {
+                    self.type_of_name(span, name.as_ids(), TypeOfMode::RValue, None).ok()
+                };
+
+                if let Some(previous_types) = previous_types {
                     let narrowed_ty = self.narrow_with_predicate(span, &previous_types, new_ty.clone())?.freezed();
 
-                    self.add_type_fact(&var_name, narrowed_ty, new_ty.clone());
+                    self.add_type_fact(name, narrowed_ty, new_ty.clone());
                     return;
                 }
             }
         }
 
         let new_ty = new_ty.clone().freezed();
-        self.add_type_fact(&var_name, new_ty.clone(), new_ty);
+        self.add_type_fact(name, new_ty.clone(), new_ty);
     }
 
     pub(crate) fn validate_type_args_count(
@@ -3219,12 +3597,13 @@ impl Analyzer<'_, '_> {
     ) -> VResult<()> {
         if let Some(type_params) = type_params {
             if let Some(type_args) = type_args {
-                // TODO(kdy1): Handle defaults of the type parameter (Change to range)
-                if type_params.len() != type_args.params.len() {
+                let min = type_params.iter().filter(|param| param.default.is_none()).count();
+
+                if type_args.params.len() < min || type_params.len() < type_args.params.len() {
                     return Err(ErrorKind::TypeParameterCountMismatch {
                         span,
                         max: type_params.len(),
-                        min: type_params.len(),
+                        min,
                         actual: type_args.params.len(),
                     }
                     .into());
@@ -3533,6 +3912,29 @@ impl VisitMut<Type> for ReturnTypeSimplifier<'_, '_, '_> {
                 .fixed();
             }
 
+            // `{ a: string }["a"]` => `string`, which is created by instantiating `T[K]`.
+            Type::IndexedAccessType(iat) if !is_generic_indexed_access(iat) && !iat.obj_type.is_ref_type() => {
+                let span = iat.span;
+                let simplified = self
+                    .analyzer
+                    .normalize(Some(span), Cow::Owned(ty.clone()), Default::default())
+                    .map(Cow::into_owned)
+                    .ok();
+
+                match simplified {
+                    Some(simplified) if !simplified.is_indexed_access_type() => {
+                        *ty = simplified;
+                    }
+                    _ => {
+                        if let Type::IndexedAccessType(iat) = ty {
+                            if is_str_lit_or_union(&iat.index_type) {
+                                prevent_generalize(iat);
+                            }
+                        }
+                    }
+                }
+            }
+
             Type::IndexedAccessType(ty) if is_str_lit_or_union(&ty.index_type) => {
                 prevent_generalize(ty);
             }
@@ -3623,3 +4025,20 @@ pub(super) struct CallCandidate {
     pub params: Vec<FnParam>,
     pub ret_ty: Type,
 }
+
+fn is_lit_expr(e: &RExpr) -> bool {
+    match e {
+        RExpr::Paren(e) => is_lit_expr(&e.expr),
+        RExpr::Lit(RLit::Str(..) | RLit::Num(..) | RLit::Bool(..) | RLit::BigInt(..)) => true,
+        RExpr::Tpl(e) => e.exprs.is_empty(),
+        _ => false,
+    }
+}
+
+fn is_tpl_expr(e: &RExpr) -> bool {
+    match e {
+        RExpr::Paren(e) => is_tpl_expr(&e.expr),
+        RExpr::Tpl(e) => !e.exprs.is_empty(),
+        _ => false,
+    }
+}
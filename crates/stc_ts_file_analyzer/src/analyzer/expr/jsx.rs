@@ -1 +1,853 @@
+use std::borrow::Cow;
 
+use fxhash::FxHashMap;
+use stc_ts_ast_rnode::{
+    RBool, RExpr, RJSXAttr, RJSXAttrName, RJSXAttrOrSpread, RJSXAttrValue, RJSXElement, RJSXElementChild, RJSXElementName, RJSXExpr,
+    RJSXExprContainer, RJSXFragment, RJSXMemberExpr, RJSXObject, RJSXOpeningElement, RLit, RTsLit,
+};
+use stc_ts_env::{JsxEntity, JsxMode};
+use stc_ts_errors::{DebugExt, ErrorKind};
+use stc_ts_file_analyzer_macros::validator;
+use stc_ts_types::{
+    Alias, Array, FnParam, Id, Interface, Key, KeywordType, LitType, PropertySignature, TypeElement, TypeLit, TypeLitMetadata, TypeOrSpread,
+};
+use stc_ts_utils::PatExt;
+use stc_utils::cache::Freeze;
+use swc_atoms::JsWord;
+use swc_common::{EqIgnoreSpan, Span, Spanned, DUMMY_SP};
+use swc_ecma_ast::TsKeywordTypeKind;
+
+use crate::{
+    analyzer::{
+        assign::AssignOpts,
+        expr::{
+            call_new::{CallCandidate, ExtractKind},
+            IdCtx, TypeOfMode,
+        },
+        util::ResultExt,
+        Analyzer, ScopeKind,
+    },
+    ty::Type,
+    validator::ValidateWith,
+    VResult,
+};
+
+#[validator]
+impl Analyzer<'_, '_> {
+    fn validate(&mut self, e: &RJSXElement) -> VResult<Type> {
+        let span = e.span;
+
+        self.report_error_for_jsx_without_option(span);
+        self.report_error_for_missing_element_factory(span, e.children.iter().filter(|c| !is_ignored_jsx_child(c)).count() > 1);
+
+        let children = self.type_of_jsx_children(span, &e.children)?;
+        let props = self
+            .with_child(ScopeKind::Call, Default::default(), |analyzer: &mut Analyzer| {
+                analyzer.jsx_props_of_element(&e.opening, children.as_ref())
+            })
+            .report(&mut self.storage)
+            .flatten();
+        let attrs = self.type_of_jsx_attrs(span, &e.opening.attrs, props.as_ref(), children)?;
+
+        if let (Some(props), Some(attrs)) = (props, attrs) {
+            let has_spread = attrs.metadata.inexact;
+
+            self.assign_with_opts(
+                &mut Default::default(),
+                &props,
+                &Type::TypeLit(attrs),
+                AssignOpts {
+                    span,
+                    ..Default::default()
+                },
+            )
+            .context("tried to assign attributes of a JSX element to the props")
+            .report(&mut self.storage);
+
+            if has_spread {
+                self.report_error_for_excess_jsx_attrs(&props, &e.opening.attrs);
+            }
+        }
+
+        Ok(self.jsx_element_type(span))
+    }
+}
+
+#[validator]
+impl Analyzer<'_, '_> {
+    fn validate(&mut self, e: &RJSXFragment) -> VResult<Type> {
+        let span = e.span;
+
+        self.report_error_for_jsx_without_option(span);
+        self.report_error_for_missing_fragment_factory(span);
+
+        self.type_of_jsx_children(span, &e.children)?;
+
+        Ok(self.jsx_element_type(span))
+    }
+}
+
+impl Analyzer<'_, '_> {
+    fn report_error_for_jsx_without_option(&mut self, span: Span) {
+        if self.env.jsx().mode.is_none() {
+            self.storage.report(ErrorKind::JsxNotEnabled { span }.into())
+        }
+    }
+
+    /// The automatic runtime uses `jsxs` for elements with multiple children.
+    fn report_error_for_missing_element_factory(&mut self, span: Span, has_multiple_children: bool) {
+        let name = match self.env.jsx().element_factory() {
+            JsxEntity::Import { .. } if has_multiple_children && self.env.jsx().mode == Some(JsxMode::ReactJsx) => "jsxs".into(),
+            JsxEntity::Import { name, .. } => name,
+            // TODO: Check if the factory is in scope.
+            JsxEntity::Name(..) => return,
+        };
+
+        self.report_error_for_missing_jsx_runtime_export(span, name);
+    }
+
+    /// `<>...</>` is compiled to a call of the element factory with the
+    /// fragment factory, which should be in scope.
+    fn report_error_for_missing_fragment_factory(&mut self, span: Span) {
+        if let JsxEntity::Import { name, .. } = self.env.jsx().fragment_factory() {
+            self.report_error_for_missing_jsx_runtime_export(span, name);
+            return;
+        }
+
+        let jsx = self.env.jsx();
+        if jsx.mode != Some(JsxMode::React) {
+            return;
+        }
+
+        if jsx.factory.is_some() && jsx.fragment_factory.is_none() {
+            self.storage.report(ErrorKind::JsxFragmentFactoryRequired { span }.into());
+            return;
+        }
+
+        let name: JsWord = match jsx.fragment_factory() {
+            JsxEntity::Name(name) => name.split('.').next().unwrap_or_default().into(),
+            JsxEntity::Import { .. } => return,
+        };
+
+        if self.scope.find_var_by_sym(&name).is_some() || self.env.get_global_var(span, &name).is_ok() {
+            return;
+        }
+
+        self.storage.report(ErrorKind::JsxFragmentFactoryNotFound { span, name }.into());
+    }
+
+    /// Exports of the module of the automatic runtime, like
+    /// `react/jsx-runtime`, which is imported implicitly by files using JSX.
+    ///
+    /// Returns [None] if the module is not loaded. It's reported while loading
+    /// imports.
+    fn jsx_runtime_exports(&mut self) -> Option<Type> {
+        let src = self.env.jsx().runtime_module();
+        let ctxt = self.ctx.module_id;
+        let base = self.storage.path(ctxt);
+        let dep_id = self.loader.module_id(&base, &src)?;
+
+        self.imports.get(&(ctxt, dep_id)).cloned()
+    }
+
+    fn report_error_for_missing_jsx_runtime_export(&mut self, span: Span, name: JsWord) {
+        let exports = match self.jsx_runtime_exports() {
+            Some(exports) => exports,
+            None => return,
+        };
+
+        // `declare module "react/jsx-runtime";` exports anything.
+        if let Type::Module(m) = exports.normalize() {
+            if m.exports.vars.contains_key(&name) {
+                return;
+            }
+
+            let id = Id::word(name);
+            self.storage.report(
+                ErrorKind::ImportFailed {
+                    span,
+                    orig: id.clone(),
+                    id,
+                }
+                .into(),
+            );
+        }
+    }
+
+    /// Returns a type declared in the `JSX` namespace, like `JSX.Element`.
+    pub(crate) fn find_jsx_type(&mut self, span: Span, name: &str) -> Option<Type> {
+        let ns = self.find_jsx_namespace()?;
+
+        self.access_property(
+            span,
+            &ns,
+            &Key::Normal { span, sym: name.into() },
+            TypeOfMode::RValue,
+            IdCtx::Type,
+            Default::default(),
+        )
+        .ok()
+    }
+
+    /// Finds the `JSX` namespace of the factory, like `JSX` exported by
+    /// `preact/jsx-runtime` or `h.JSX` for `h`, or the global one.
+    fn find_jsx_namespace(&mut self) -> Option<Type> {
+        let name: JsWord = "JSX".into();
+
+        let ns = match self.env.jsx().namespace() {
+            JsxEntity::Import { .. } => self.jsx_runtime_exports(),
+            JsxEntity::Name(factory_ns) => self.find_jsx_factory_namespace(&factory_ns),
+        };
+        if let Some(ty) = ns.as_ref().and_then(|ns| jsx_namespace_in(ns, &name)) {
+            return Some(ty);
+        }
+
+        if let Some(ty) = self.scope.find_type_by_sym(&name) {
+            return Some(ty.clone());
+        }
+
+        self.env.get_global_type(DUMMY_SP, &name).ok()
+    }
+
+    /// Finds the namespace of the factory, like `React` of
+    /// `React.createElement`, which may be declared in the file or imported.
+    fn find_jsx_factory_namespace(&mut self, factory_ns: &JsWord) -> Option<Type> {
+        let id = self.data.bindings.all.keys().find(|id| id.sym() == factory_ns)?.clone();
+
+        let mut candidates = self
+            .find_type(&id)
+            .ok()
+            .flatten()
+            .map(|types| types.map(Cow::into_owned).collect::<Vec<_>>())
+            .unwrap_or_default();
+        // `import * as React from "react"`
+        candidates.extend(self.find_var_type(&id, TypeOfMode::RValue).map(Cow::into_owned));
+
+        candidates.into_iter().find(|ty| jsx_namespace_in(ty, &"JSX".into()).is_some())
+    }
+
+    /// `JSX.Element`, or `any` if it's not declared.
+    fn jsx_element_type(&mut self, span: Span) -> Type {
+        self.find_jsx_type(span, "Element")
+            .unwrap_or_else(|| Type::any(span, Default::default()))
+            .freezed()
+    }
+
+    /// Returns [None] if the props can't be checked, like for components of
+    /// type `any`.
+    fn jsx_props_of_element(&mut self, e: &RJSXOpeningElement, children: Option<&Type>) -> VResult<Option<Type>> {
+        let span = e.name.span();
+
+        if let Some(tag) = intrinsic_tag(&e.name) {
+            let intrinsics = match self.find_jsx_type(span, "IntrinsicElements") {
+                Some(intrinsics) => intrinsics,
+                None if self.rule().no_implicit_any => return Err(ErrorKind::ImplicitAnyJsxElement { span }.into()),
+                None => return Ok(None),
+            };
+
+            return self
+                .access_property(
+                    span,
+                    &intrinsics,
+                    &Key::Normal { span, sym: tag },
+                    TypeOfMode::RValue,
+                    IdCtx::Var,
+                    Default::default(),
+                )
+                .map(Some);
+        }
+
+        let component = match &e.name {
+            RJSXElementName::Ident(i) => self.type_of_var(i, TypeOfMode::RValue, None)?,
+            RJSXElementName::JSXMemberExpr(e) => self.type_of_jsx_member_expr(e)?,
+            RJSXElementName::JSXNamespacedName(..) => unreachable!("namespaced names are intrinsic"),
+        };
+
+        self.jsx_props_of_component(span, &component, e, children)
+    }
+
+    fn type_of_jsx_member_expr(&mut self, e: &RJSXMemberExpr) -> VResult<Type> {
+        let obj = match &e.obj {
+            RJSXObject::Ident(i) => self.type_of_var(i, TypeOfMode::RValue, None)?,
+            RJSXObject::JSXMemberExpr(e) => self.type_of_jsx_member_expr(e)?,
+        };
+
+        self.access_property(
+            e.prop.span,
+            &obj,
+            &Key::Normal {
+                span: e.prop.span,
+                sym: e.prop.sym.clone(),
+            },
+            TypeOfMode::RValue,
+            IdCtx::Var,
+            Default::default(),
+        )
+    }
+
+    /// Function components take props as the first parameter, and class
+    /// components have them in the property named by
+    /// `JSX.ElementAttributesProperty`.
+    fn jsx_props_of_component(
+        &mut self,
+        span: Span,
+        component: &Type,
+        e: &RJSXOpeningElement,
+        children: Option<&Type>,
+    ) -> VResult<Option<Type>> {
+        if component.is_any() {
+            return Ok(None);
+        }
+
+        if let Some(candidate) = self
+            .extract_callee_candidates(span, ExtractKind::Call, component)?
+            .into_iter()
+            .next()
+        {
+            let candidate = match self.instantiate_jsx_candidate(span, candidate, e, children)? {
+                Some(candidate) => candidate,
+                None => return Ok(None),
+            };
+
+            let props = first_param_or_empty(span, &candidate.params);
+            let props = self.jsx_managed_attrs(span, component, props)?;
+            return self.with_jsx_intrinsic_attrs(span, props, None).map(Some);
+        }
+
+        if let Some(candidate) = self
+            .extract_callee_candidates(span, ExtractKind::New, component)?
+            .into_iter()
+            .next()
+        {
+            let candidate = match self.instantiate_jsx_candidate(span, candidate, e, children)? {
+                Some(candidate) => candidate,
+                None => return Ok(None),
+            };
+
+            let props = match self.find_jsx_type(span, "ElementAttributesProperty") {
+                Some(attrs_prop) => match self.first_prop_name(span, &attrs_prop)? {
+                    Some(name) => self
+                        .access_property(
+                            span,
+                            &candidate.ret_ty,
+                            &Key::Normal { span, sym: name },
+                            TypeOfMode::RValue,
+                            IdCtx::Var,
+                            Default::default(),
+                        )
+                        .unwrap_or_else(|_| empty_object(span)),
+                    // An empty `ElementAttributesProperty` means that the instance type is the props.
+                    None => candidate.ret_ty.clone(),
+                },
+                None => first_param_or_empty(span, &candidate.params),
+            };
+
+            let props = self.jsx_managed_attrs(span, component, props)?;
+            return self.with_jsx_intrinsic_attrs(span, props, Some(&candidate.ret_ty)).map(Some);
+        }
+
+        Err(ErrorKind::InvalidJsxElementType { span }.into())
+    }
+
+    /// Instantiates a generic component with the type arguments of the element.
+    /// If they are not provided, they are inferred from the attributes like
+    /// arguments of a call.
+    ///
+    /// Returns [None] if type arguments can't be inferred because of a spread
+    /// attribute.
+    fn instantiate_jsx_candidate(
+        &mut self,
+        span: Span,
+        mut candidate: CallCandidate,
+        e: &RJSXOpeningElement,
+        children: Option<&Type>,
+    ) -> VResult<Option<CallCandidate>> {
+        let type_params = match candidate.type_params.take() {
+            Some(type_params) => type_params,
+            None => return Ok(Some(candidate)),
+        };
+
+        let type_args = match &e.type_args {
+            Some(type_args) => {
+                let mut type_args = type_args.validate_with(self)?;
+                self.prevent_expansion(&mut type_args);
+                type_args.make_clone_cheap();
+                Some(type_args)
+            }
+            None => None,
+        };
+
+        self.validate_type_args_count(span, Some(&type_params[..]), type_args.as_ref())
+            .report(&mut self.storage);
+
+        for param in &type_params {
+            self.register_type(param.name.clone(), Type::Param(param.clone()));
+        }
+
+        let args = match self.type_of_jsx_attrs_for_inference(span, &e.attrs, children)? {
+            Some(attrs) => vec![TypeOrSpread {
+                span,
+                spread: None,
+                ty: box Type::TypeLit(attrs),
+            }],
+            None if type_args.is_some() => vec![],
+            None => return Ok(None),
+        };
+
+        let inferred = self.infer_arg_types(
+            span,
+            type_args.as_ref(),
+            &type_params,
+            &candidate.params,
+            &args,
+            None,
+            None,
+            Default::default(),
+        )?;
+
+        let mut types = inferred.types;
+        for param in &type_params {
+            if inferred.errored.contains(&param.name) || !types.contains_key(&param.name) {
+                types.insert(param.name.clone(), Type::unknown(span, Default::default()));
+            }
+        }
+
+        let params = candidate
+            .params
+            .into_iter()
+            .map(|param| -> VResult<_> {
+                let ty = box self.expand_type_params(&types, *param.ty, Default::default())?;
+
+                Ok(FnParam { ty, ..param })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .freezed();
+        let ret_ty = self.expand_type_params(&types, candidate.ret_ty, Default::default())?.freezed();
+
+        Ok(Some(CallCandidate {
+            type_params: None,
+            params,
+            ret_ty,
+        }))
+    }
+
+    /// Adds `JSX.IntrinsicAttributes`, which declares attributes like `key`, to
+    /// the props of a component.
+    ///
+    /// Class components also accept `JSX.IntrinsicClassAttributes<T>`, like
+    /// `ref`, where `T` is `instance`.
+    fn with_jsx_intrinsic_attrs(&mut self, span: Span, props: Type, instance: Option<&Type>) -> VResult<Type> {
+        let mut types = vec![props];
+
+        if let Some(attrs) = self.find_jsx_type(span, "IntrinsicAttributes") {
+            types.push(attrs);
+        }
+
+        if let Some(instance) = instance {
+            if let Some(attrs) = self.find_jsx_type(span, "IntrinsicClassAttributes") {
+                if let Some(attrs) = self.instantiate_jsx_type(span, &attrs, &[instance.clone()])? {
+                    types.push(attrs);
+                }
+            }
+        }
+
+        if types.len() == 1 {
+            return Ok(types.remove(0));
+        }
+
+        Ok(Type::new_intersection(span, types).freezed())
+    }
+
+    /// Applies `JSX.LibraryManagedAttributes<C, P>` to the props of a
+    /// component, which is used to make props with `defaultProps` optional.
+    fn jsx_managed_attrs(&mut self, span: Span, component: &Type, props: Type) -> VResult<Type> {
+        let managed = match self.find_jsx_type(span, "LibraryManagedAttributes") {
+            Some(managed) => managed,
+            None => return Ok(props),
+        };
+
+        Ok(self
+            .instantiate_jsx_type(span, &managed, &[component.clone(), props.clone()])?
+            .unwrap_or(props))
+    }
+
+    /// Instantiates a generic interface or a generic type alias of the `JSX`
+    /// namespace with `args`.
+    fn instantiate_jsx_type(&mut self, span: Span, ty: &Type, args: &[Type]) -> VResult<Option<Type>> {
+        let (type_params, ty) = match ty.normalize() {
+            Type::Interface(Interface {
+                type_params: Some(type_params),
+                ..
+            }) => match self.convert_type_to_type_lit(span, Cow::Borrowed(ty))? {
+                Some(lit) => (&type_params.params, Type::TypeLit(lit.into_owned())),
+                None => return Ok(None),
+            },
+            Type::Alias(Alias {
+                type_params: Some(type_params),
+                ty,
+                ..
+            }) => (&type_params.params, *ty.clone()),
+            _ => return Ok(None),
+        };
+
+        if type_params.len() != args.len() {
+            return Ok(None);
+        }
+
+        let params = type_params
+            .iter()
+            .map(|param| param.name.clone())
+            .zip(args.iter().map(|arg| arg.clone().freezed()))
+            .collect::<FxHashMap<_, _>>();
+
+        let ty = self.expand_type_params(&params, ty, Default::default())?;
+        let ty = self.normalize(Some(span), Cow::Owned(ty), Default::default())?.into_owned();
+
+        Ok(Some(ty.freezed()))
+    }
+
+    /// The name of the property for children, which is declared by
+    /// `JSX.ElementChildrenAttribute`.
+    fn jsx_children_prop_name(&mut self, span: Span) -> VResult<Option<JsWord>> {
+        match self.find_jsx_type(span, "ElementChildrenAttribute") {
+            Some(ty) => self.first_prop_name(span, &ty),
+            None => Ok(None),
+        }
+    }
+
+    fn first_prop_name(&mut self, span: Span, ty: &Type) -> VResult<Option<JsWord>> {
+        let ty = self.convert_type_to_type_lit(span, Cow::Borrowed(ty))?;
+
+        Ok(ty.and_then(|ty| {
+            ty.members.iter().find_map(|member| match member.key()? {
+                Key::Normal { sym, .. } => Some(sym.clone()),
+                _ => None,
+            })
+        }))
+    }
+
+    /// Attributes override previous ones with the same name, including the
+    /// properties of spread attributes.
+    ///
+    /// Returns [None] if the attributes can't be checked, like for a spread
+    /// attribute of type `any`.
+    ///
+    /// `children` is added as the property named by
+    /// `JSX.ElementChildrenAttribute`.
+    fn type_of_jsx_attrs(
+        &mut self,
+        span: Span,
+        attrs: &[RJSXAttrOrSpread],
+        props: Option<&Type>,
+        children: Option<Type>,
+    ) -> VResult<Option<TypeLit>> {
+        let mut members = vec![];
+        let mut has_spread = false;
+        let mut is_unknown = false;
+
+        for attr in attrs {
+            match attr {
+                RJSXAttrOrSpread::JSXAttr(attr) => {
+                    let name = jsx_attr_name(&attr.name);
+                    let key = Key::Normal {
+                        span: attr.name.span(),
+                        sym: name.clone(),
+                    };
+
+                    let type_ann = props.and_then(|props| {
+                        self.access_property(span, props, &key, TypeOfMode::RValue, IdCtx::Var, Default::default())
+                            .ok()
+                    });
+
+                    let ty = self.type_of_jsx_attr_value(attr, type_ann.as_ref())?;
+
+                    add_jsx_attr(&mut members, jsx_attr(attr.span, name, ty));
+                }
+
+                RJSXAttrOrSpread::SpreadElement(spread) => {
+                    let ty = spread.expr.validate_with_default(self)?;
+                    has_spread = true;
+
+                    if !self.spread_jsx_attrs(span, &mut members, &ty)? {
+                        is_unknown = true;
+                    }
+                }
+            }
+        }
+
+        if is_unknown {
+            return Ok(None);
+        }
+
+        if let Some(children) = children {
+            if let Some(name) = self.jsx_children_prop_name(span)? {
+                add_jsx_attr(&mut members, jsx_attr(span, name, children));
+            }
+        }
+
+        Ok(Some(TypeLit {
+            span,
+            members,
+            metadata: TypeLitMetadata {
+                inexact: has_spread,
+                ..Default::default()
+            },
+        }))
+    }
+
+    /// Adds properties of a spread attribute to `members`.
+    ///
+    /// Returns `false` if the properties are unknown, like for `any`.
+    fn spread_jsx_attrs(&mut self, span: Span, members: &mut Vec<TypeElement>, ty: &Type) -> VResult<bool> {
+        if ty.is_any() {
+            return Ok(false);
+        }
+
+        // TODO: Handle unions and type parameters.
+        let ty = match self.convert_type_to_type_lit(span, Cow::Borrowed(ty))? {
+            Some(ty) => ty,
+            None => return Ok(false),
+        };
+
+        for member in &ty.members {
+            if let TypeElement::Property(..) | TypeElement::Method(..) = member {
+                add_jsx_attr(members, member.clone());
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Excess properties are not checked while assigning attributes with a
+    /// spread attribute, so explicit attributes are checked here.
+    fn report_error_for_excess_jsx_attrs(&mut self, props: &Type, attrs: &[RJSXAttrOrSpread]) {
+        let props = props.normalize();
+
+        for attr in attrs {
+            let attr = match attr {
+                RJSXAttrOrSpread::JSXAttr(attr) => attr,
+                RJSXAttrOrSpread::SpreadElement(..) => continue,
+            };
+            let span = attr.name.span();
+            let key = Key::Normal {
+                span,
+                sym: jsx_attr_name(&attr.name),
+            };
+
+            let types = match props {
+                Type::Union(u) => &*u.types,
+                _ => std::slice::from_ref(props),
+            };
+            let is_known = types.iter().any(|ty| {
+                self.access_property(span, ty, &key, TypeOfMode::RValue, IdCtx::Var, Default::default())
+                    .is_ok()
+            });
+
+            if !is_known {
+                self.storage
+                    .report(ErrorKind::UnknownPropertyInObjectLiteralAssignment { span }.into());
+            }
+        }
+    }
+
+    /// Same as [Analyzer::type_of_jsx_attrs], but functions with untyped
+    /// parameters are skipped because they need the instantiated props to be
+    /// typed.
+    fn type_of_jsx_attrs_for_inference(
+        &mut self,
+        span: Span,
+        attrs: &[RJSXAttrOrSpread],
+        children: Option<&Type>,
+    ) -> VResult<Option<TypeLit>> {
+        let mut members = vec![];
+
+        for attr in attrs {
+            match attr {
+                RJSXAttrOrSpread::JSXAttr(attr) => {
+                    if let Some(RJSXAttrValue::JSXExprContainer(RJSXExprContainer {
+                        expr: RJSXExpr::Expr(e), ..
+                    })) = &attr.value
+                    {
+                        if is_context_sensitive_fn(e) {
+                            continue;
+                        }
+                    }
+
+                    let ty = self.type_of_jsx_attr_value(attr, None)?;
+
+                    add_jsx_attr(&mut members, jsx_attr(attr.span, jsx_attr_name(&attr.name), ty));
+                }
+
+                RJSXAttrOrSpread::SpreadElement(spread) => {
+                    let ty = spread.expr.validate_with_default(self)?;
+
+                    if !self.spread_jsx_attrs(span, &mut members, &ty)? {
+                        return Ok(None);
+                    }
+                }
+            }
+        }
+
+        if let Some(children) = children {
+            if let Some(name) = self.jsx_children_prop_name(span)? {
+                add_jsx_attr(&mut members, jsx_attr(span, name, children.clone()));
+            }
+        }
+
+        Ok(Some(TypeLit {
+            span,
+            members,
+            metadata: Default::default(),
+        }))
+    }
+
+    fn type_of_jsx_attr_value(&mut self, attr: &RJSXAttr, type_ann: Option<&Type>) -> VResult<Type> {
+        Ok(match &attr.value {
+            None => Type::Lit(LitType {
+                span: attr.span,
+                lit: RTsLit::Bool(RBool {
+                    span: attr.span,
+                    value: true,
+                }),
+                metadata: Default::default(),
+            }),
+            Some(RJSXAttrValue::Lit(RLit::Str(s))) => Type::Lit(LitType {
+                span: s.span,
+                lit: RTsLit::Str(s.clone()),
+                metadata: Default::default(),
+            }),
+            Some(RJSXAttrValue::Lit(..)) => Type::any(attr.span, Default::default()),
+            Some(RJSXAttrValue::JSXExprContainer(RJSXExprContainer {
+                expr: RJSXExpr::Expr(e), ..
+            })) => e.validate_with_args(self, (TypeOfMode::RValue, None, type_ann))?,
+            Some(RJSXAttrValue::JSXExprContainer(..)) => Type::any(attr.span, Default::default()),
+            Some(RJSXAttrValue::JSXElement(e)) => e.validate_with(self)?,
+            Some(RJSXAttrValue::JSXFragment(e)) => e.validate_with(self)?,
+        })
+    }
+
+    /// Returns [None] if there's no child.
+    ///
+    /// Multiple children are passed as an array.
+    fn type_of_jsx_children(&mut self, span: Span, children: &[RJSXElementChild]) -> VResult<Option<Type>> {
+        let mut types = vec![];
+
+        for child in children {
+            if is_ignored_jsx_child(child) {
+                continue;
+            }
+
+            let ty = match child {
+                RJSXElementChild::JSXText(text) => Type::Keyword(KeywordType {
+                    span: text.span,
+                    kind: TsKeywordTypeKind::TsStringKeyword,
+                    metadata: Default::default(),
+                }),
+                RJSXElementChild::JSXExprContainer(RJSXExprContainer {
+                    expr: RJSXExpr::Expr(e), ..
+                }) => e.validate_with_default(self)?,
+                RJSXElementChild::JSXExprContainer(..) => continue,
+                RJSXElementChild::JSXSpreadChild(c) => c.expr.validate_with_default(self)?,
+                RJSXElementChild::JSXElement(e) => e.validate_with(self)?,
+                RJSXElementChild::JSXFragment(e) => e.validate_with(self)?,
+            };
+
+            types.push(ty);
+        }
+
+        Ok(match types.len() {
+            0 => None,
+            1 => types.pop(),
+            _ => Some(Type::Array(Array {
+                span,
+                elem_type: box Type::new_union(span, types),
+                metadata: Default::default(),
+            })),
+        })
+    }
+}
+
+/// Returns the tag name if `name` refers to an intrinsic element, like `div`.
+fn intrinsic_tag(name: &RJSXElementName) -> Option<JsWord> {
+    match name {
+        RJSXElementName::Ident(i) if i.sym.starts_with(|c: char| c.is_ascii_lowercase()) || i.sym.contains('-') => Some(i.sym.clone()),
+        RJSXElementName::JSXNamespacedName(n) => Some(format!("{}:{}", n.ns.sym, n.name.sym).into()),
+        _ => None,
+    }
+}
+
+/// `JSX` declared in a namespace or a module, which may be merged with a
+/// function like `h` of preact.
+fn jsx_namespace_in(ns: &Type, name: &JsWord) -> Option<Type> {
+    match ns.normalize() {
+        Type::Module(m) => m.exports.types.get(name).and_then(|types| types.first()).cloned(),
+        Type::Intersection(i) => i.types.iter().find_map(|ty| jsx_namespace_in(ty, name)),
+        _ => None,
+    }
+}
+
+/// Whitespaces including a line break are removed.
+fn is_ignored_jsx_child(child: &RJSXElementChild) -> bool {
+    match child {
+        RJSXElementChild::JSXText(text) => text.value.trim().is_empty() && text.value.contains('\n'),
+        RJSXElementChild::JSXExprContainer(RJSXExprContainer {
+            expr: RJSXExpr::JSXEmptyExpr(..),
+            ..
+        }) => true,
+        _ => false,
+    }
+}
+
+fn jsx_attr_name(name: &RJSXAttrName) -> JsWord {
+    match name {
+        RJSXAttrName::Ident(i) => i.sym.clone(),
+        RJSXAttrName::JSXNamespacedName(n) => format!("{}:{}", n.ns.sym, n.name.sym).into(),
+    }
+}
+
+/// Returns `true` for functions with a parameter without a type annotation,
+/// which is typed using the contextual type.
+pub(super) fn is_context_sensitive_fn(e: &RExpr) -> bool {
+    match e {
+        RExpr::Paren(e) => is_context_sensitive_fn(&e.expr),
+        RExpr::Arrow(e) => e.params.iter().any(|param| param.get_ty().is_none()),
+        RExpr::Fn(e) => e.function.params.iter().any(|param| param.pat.get_ty().is_none()),
+        _ => false,
+    }
+}
+
+/// Adds `attr`, overriding a previous attribute with the same name.
+fn add_jsx_attr(members: &mut Vec<TypeElement>, attr: TypeElement) {
+    if let Some(key) = attr.key() {
+        members.retain(|member| member.key().map_or(true, |k| !k.eq_ignore_span(key)));
+    }
+
+    members.push(attr);
+}
+
+fn jsx_attr(span: Span, name: JsWord, ty: Type) -> TypeElement {
+    TypeElement::Property(PropertySignature {
+        span,
+        accessibility: None,
+        readonly: false,
+        key: Key::Normal { span, sym: name },
+        optional: false,
+        params: Default::default(),
+        type_ann: Some(box ty),
+        type_params: Default::default(),
+        metadata: Default::default(),
+        accessor: Default::default(),
+    })
+}
+
+fn first_param_or_empty(span: Span, params: &[FnParam]) -> Type {
+    params.first().map(|param| *param.ty.clone()).unwrap_or_else(|| empty_object(span))
+}
+
+fn empty_object(span: Span) -> Type {
+    Type::TypeLit(TypeLit {
+        span,
+        members: vec![],
+        metadata: Default::default(),
+    })
+}
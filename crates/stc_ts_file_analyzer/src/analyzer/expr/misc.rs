@@ -1,11 +1,12 @@
 use stc_ts_ast_rnode::{RTsInstantiation, RTsSatisfiesExpr};
-use stc_ts_errors::DebugExt;
+use stc_ts_errors::{DebugExt, ErrorKind};
 use stc_ts_file_analyzer_macros::validator;
 use stc_ts_types::{Type, TypeParamInstantiation};
 use stc_utils::cache::Freeze;
+use swc_common::Spanned;
 
 use crate::{
-    analyzer::{expr::TypeOfMode, Analyzer},
+    analyzer::{assign::AssignOpts, expr::TypeOfMode, util::ResultExt, Analyzer},
     validator::ValidateWith,
     VResult,
 };
@@ -21,12 +22,30 @@ impl Analyzer<'_, '_> {
     ) -> VResult<Type> {
         let type_ann = e.type_ann.validate_with(self)?.freezed();
 
+        // The type annotation is used only as a contextual type, so the type of the
+        // expression is not widened to `type_ann`.
         let ty = e
             .expr
             .validate_with_args(self, (mode, type_args, Some(&type_ann)))
-            .context("tried to verify expr of ts satisfies expression")?;
+            .context("tried to verify expr of ts satisfies expression")?
+            .freezed();
 
-        // TODO: verify
+        let span = e.expr.span();
+        self.assign_with_opts(
+            &mut Default::default(),
+            &type_ann,
+            &ty,
+            AssignOpts {
+                span,
+                ..Default::default()
+            },
+        )
+        .context("tried to assign the expression to the type of a satisfies expression")
+        .convert_err(|err| ErrorKind::ExprDoesNotSatisfyType {
+            span,
+            cause: box err.into(),
+        })
+        .report(&mut self.storage);
 
         Ok(ty)
     }
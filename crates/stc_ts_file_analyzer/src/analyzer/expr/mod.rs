@@ -1,19 +1,20 @@
 use std::{
     borrow::Cow,
     collections::HashMap,
-    convert::{TryFrom, TryInto},
+    convert::TryFrom,
     mem::take,
     time::{Duration, Instant},
 };
 
 use optional_chaining::is_obj_opt_chaining;
-use rnode::{NodeId, VisitWith};
+use rnode::NodeId;
 use stc_ts_ast_rnode::{
     RAssignExpr, RBindingIdent, RClassExpr, RExpr, RIdent, RInvalid, RLit, RMemberExpr, RMemberProp, RNull, RNumber, ROptChainBase,
     ROptChainExpr, RParenExpr, RPat, RPatOrExpr, RSeqExpr, RStr, RSuper, RSuperProp, RSuperPropExpr, RThisExpr, RTpl, RTplElement,
     RTsEntityName, RTsEnumMemberId, RTsLit, RTsNonNullExpr, RUnaryExpr,
 };
 use stc_ts_base_type_ops::bindings::BindingKind;
+use stc_ts_builtin_types::lib_declaring;
 use stc_ts_errors::{
     debug::{dump_type_as_string, force_dump_type_as_string},
     DebugExt, ErrorKind, Errors,
@@ -30,16 +31,15 @@ use stc_utils::{cache::Freeze, debug_ctx, ext::TypeVecExt, stack};
 use swc_atoms::js_word;
 use swc_common::{Span, Spanned, SyntaxContext, TypeEq, DUMMY_SP};
 use swc_ecma_ast::{op, EsVersion, TruePlusMinus, TsKeywordTypeKind, TsTypeOperatorOp, VarDeclKind};
-use tracing::{debug, info, warn, Level};
+use tracing::{debug, info, span, warn, Level};
 use ty::TypeExt;
 
-use self::bin::extract_name_for_assignment;
-pub(crate) use self::{array::GetIteratorOpts, call_new::CallOpts};
+pub(crate) use self::{array::GetIteratorOpts, call_new::CallOpts, type_cast::CastableOpts};
 use crate::{
     analyzer::{
         assign::AssignOpts,
         pat::PatMode,
-        scope::{ExpandOpts, ScopeKind, VarKind},
+        scope::{ExpandOpts, Scope, ScopeKind, VarKind},
         types::NormalizeTypeOpts,
         util::ResultExt,
         Analyzer, Ctx,
@@ -110,6 +110,13 @@ impl Analyzer<'_, '_> {
         let _ctx = debug_ctx!(format!("validate\nExpr: {:?}", e));
 
         let span = e.span();
+        let _tracing = if cfg!(debug_assertions) && tracing::enabled!(Level::ERROR) {
+            let line_col = self.line_col(span);
+            Some(span!(Level::ERROR, "Expr", line_col = &*line_col).entered())
+        } else {
+            None
+        };
+
         let need_type_param_handling = match e {
             RExpr::Member(..) => true,
             RExpr::Call(..) | RExpr::New(..) if self.ctx.in_argument => false,
@@ -179,6 +186,11 @@ impl Analyzer<'_, '_> {
                             let name = Name::from(Id::word(js_word!("this")));
 
                             if !self.is_builtin {
+                                // Narrowed by `this.isFoo()`.
+                                if let Some(narrowed) = self.find_var_type(&name.top(), TypeOfMode::RValue) {
+                                    ty = narrowed.into_owned();
+                                }
+
                                 ty = self.apply_type_facts(&name, ty);
 
                                 ty.assert_valid();
@@ -215,11 +227,23 @@ impl Analyzer<'_, '_> {
                             metadata: Default::default(),
                         }));
                     }
+                    if self.data.type_only_imports.contains(&Id::from(i)) {
+                        self.storage
+                            .report(ErrorKind::TypeOnlyImportUsedAsValue { span, name: i.into() }.into());
+                    }
                     let ty = self.type_of_var(i, mode, type_args)?;
+                    if mode == TypeOfMode::RValue {
+                        self.check_use_before_init(i);
+                    }
                     if self.ctx.should_store_truthy_for_access && mode == TypeOfMode::RValue {
                         // `i` is truthy
                         self.cur_facts.true_facts.facts.insert(i.into(), TypeFacts::Truthy);
                         self.cur_facts.false_facts.facts.insert(i.into(), TypeFacts::Falsy);
+
+                        // `const ok = x !== undefined; if (ok) {}`
+                        if let Some(facts) = self.data.aliased_conds.get(&Id::from(i)) {
+                            self.cur_facts += facts.clone();
+                        }
                     }
 
                     Ok(ty)
@@ -242,11 +266,17 @@ impl Analyzer<'_, '_> {
                     lit: RTsLit::Number(v.clone()),
                     metadata: Default::default(),
                 })),
-                RExpr::Lit(RLit::BigInt(v)) => Ok(Type::Lit(LitType {
-                    span: v.span,
-                    lit: RTsLit::BigInt(v.clone()),
-                    metadata: Default::default(),
-                })),
+                RExpr::Lit(RLit::BigInt(v)) => {
+                    if !self.is_builtin && self.env.target() < EsVersion::Es2020 {
+                        self.storage.report(ErrorKind::BigIntLiteralNotAvailable { span: v.span }.into());
+                    }
+
+                    Ok(Type::Lit(LitType {
+                        span: v.span,
+                        lit: RTsLit::BigInt(v.clone()),
+                        metadata: Default::default(),
+                    }))
+                }
                 RExpr::Lit(RLit::Null(RNull { span })) => {
                     if self.ctx.in_export_default_expr {
                         // TODO(kdy1): strict mode
@@ -280,7 +310,13 @@ impl Analyzer<'_, '_> {
                 RExpr::Tpl(ref e) => e.validate_with_args(self, type_ann),
 
                 RExpr::TsNonNull(RTsNonNullExpr { span, ref expr, .. }) => {
-                    let mut ty = expr.validate_with_args(self, (mode, type_args, type_ann))?.remove_falsy();
+                    let ctx = Ctx {
+                        in_non_null_expr: matches!(**expr, RExpr::Ident(..)),
+                        ..self.ctx
+                    };
+                    let mut ty = expr
+                        .validate_with_args(&mut *self.with_ctx(ctx), (mode, type_args, type_ann))?
+                        .remove_falsy();
                     ty.reposition(*span);
                     Ok(ty)
                 }
@@ -288,10 +324,10 @@ impl Analyzer<'_, '_> {
                 RExpr::Object(e) => e.validate_with_args(self, type_ann),
 
                 // https://github.com/Microsoft/TypeScript/issues/26959
-                RExpr::Yield(..) => {
-                    e.visit_children_with(self);
-                    Ok(Type::any(span, Default::default()))
-                }
+                //
+                // The type of a normal `yield` is `any`, while `yield*` results in `TReturn` of the
+                // delegated iterator.
+                RExpr::Yield(e) => e.validate_with(self),
 
                 RExpr::Await(e) => e.validate_with_args(self, type_ann),
 
@@ -330,6 +366,10 @@ impl Analyzer<'_, '_> {
 
                 RExpr::TsInstantiation(expr) => expr.validate_with_args(self, (mode, None, type_ann)),
 
+                RExpr::JSXElement(e) => e.validate_with(self),
+
+                RExpr::JSXFragment(e) => e.validate_with(self),
+
                 _ => unimplemented!("typeof ({:?})", e),
             }
         })()?;
@@ -850,7 +890,10 @@ impl Analyzer<'_, '_> {
                             }
 
                             if let Some(ref type_ann) = p.type_ann {
-                                if p.optional {
+                                // With `exactOptionalPropertyTypes`, `undefined` can be written to an optional
+                                // property only if it's in the declared type.
+                                let exact = type_mode == TypeOfMode::LValue && self.rule().exact_optional_property_types;
+                                if p.optional && !exact {
                                     let mut types = vec![Type::undefined(span, Default::default()), *type_ann.clone()];
                                     types.dedup_type();
                                     matching_elements.push(Type::union(types));
@@ -1278,8 +1321,31 @@ impl Analyzer<'_, '_> {
                                     .context("tried to access a property of `globalThis`")
                             };
 
-                            // TODO(kdy1): Apply correct rule
                             if res.is_err() {
+                                // Top-level declarations of a script are properties of `globalThis`,
+                                // except for block-scoped ones.
+                                if !self.ctx.is_external_module {
+                                    if let Some(var) = self.scope.get_root_var(sym) {
+                                        match var.kind {
+                                            VarKind::Var(VarDeclKind::Var) | VarKind::Fn => {
+                                                if let Some(ty) = var.ty.clone().or_else(|| var.actual_ty.clone()) {
+                                                    return Ok(ty);
+                                                }
+                                            }
+                                            VarKind::Var(..) | VarKind::Class | VarKind::Enum => {
+                                                return Err(ErrorKind::NoSuchProperty {
+                                                    span,
+                                                    obj: Some(box obj.clone()),
+                                                    prop: Some(box Key::Normal { span, sym: sym.clone() }),
+                                                }
+                                                .into());
+                                            }
+                                            _ => {}
+                                        }
+                                    }
+                                }
+
+                                // TODO(kdy1): Apply correct rule
                                 return Ok(Type::any(span, Default::default()));
                             }
 
@@ -1314,6 +1380,7 @@ impl Analyzer<'_, '_> {
                         }
                     }
                 }
+                // Keys below refer to values even in types, like `(typeof globalThis)['foo']`.
                 Key::Num(v) => {
                     return self.access_property_inner(
                         span,
@@ -1323,7 +1390,7 @@ impl Analyzer<'_, '_> {
                             sym: v.value.to_string().into(),
                         },
                         type_mode,
-                        id_ctx,
+                        IdCtx::Var,
                         opts,
                     )
                 }
@@ -1345,7 +1412,7 @@ impl Analyzer<'_, '_> {
                                 sym: sym.clone(),
                             },
                             type_mode,
-                            id_ctx,
+                            IdCtx::Var,
                             opts,
                         )
                     }
@@ -1360,7 +1427,7 @@ impl Analyzer<'_, '_> {
                                 sym: v.value.to_string().into(),
                             },
                             type_mode,
-                            id_ctx,
+                            IdCtx::Var,
                             opts,
                         )
                     }
@@ -1416,6 +1483,8 @@ impl Analyzer<'_, '_> {
 
                 Type::This(this) if !self.ctx.in_computed_prop_name && self.scope.is_this_ref_to_class() => {
                     if !computed {
+                        let mut index_signature = None;
+
                         // We are currently declaring a class.
                         for (_, member) in self.scope.class_members() {
                             match member {
@@ -1435,6 +1504,28 @@ impl Analyzer<'_, '_> {
 
                                 ClassMember::Property(member @ ClassProperty { is_static: false, .. }) => {
                                     if member.key.type_eq(prop) {
+                                        // Getters and setters are not combined yet, so we use the getter for
+                                        // reads and the setter for writes.
+                                        let want_setter = type_mode == TypeOfMode::LValue;
+                                        if (member.accessor.getter || member.accessor.setter) && member.accessor.setter != want_setter {
+                                            let has_counterpart = self.scope.class_members().iter().any(|(_, m)| match m {
+                                                ClassMember::Property(p) => {
+                                                    !p.is_static
+                                                        && p.key.type_eq(prop)
+                                                        && (p.accessor.getter || p.accessor.setter)
+                                                        && p.accessor.setter == want_setter
+                                                }
+                                                _ => false,
+                                            });
+
+                                            if has_counterpart {
+                                                continue;
+                                            }
+                                            if want_setter {
+                                                return Err(ErrorKind::ReadOnly { span }.into());
+                                            }
+                                        }
+
                                         let ty = *member.value.clone().unwrap_or_else(|| box Type::any(span, Default::default()));
                                         let ty = match self.expand_top_ref(span, Cow::Borrowed(&ty), Default::default()) {
                                             Ok(new_ty) => {
@@ -1453,8 +1544,10 @@ impl Analyzer<'_, '_> {
 
                                 ClassMember::Property(..) | ClassMember::Method(..) => {}
 
-                                ClassMember::IndexSignature(_) => {
-                                    unimplemented!("class -> this.foo where an `IndexSignature` exists")
+                                ClassMember::IndexSignature(index) => {
+                                    if !index.is_static && index_signature.is_none() {
+                                        index_signature = Some(index.clone());
+                                    }
                                 }
                             }
                         }
@@ -1481,6 +1574,20 @@ impl Analyzer<'_, '_> {
                             }
                         }
 
+                        if let Some(index) = index_signature {
+                            if index.params.len() == 1 {
+                                let index_ty = &index.params[0].ty;
+                                let prop_ty = prop.ty();
+
+                                let indexed = (index_ty.is_kwd(TsKeywordTypeKind::TsStringKeyword) && prop_ty.is_num())
+                                    || self.assign(span, &mut Default::default(), index_ty, &prop_ty).is_ok();
+
+                                if indexed {
+                                    return Ok(index.type_ann.map(|v| *v).unwrap_or_else(|| Type::any(span, Default::default())));
+                                }
+                            }
+                        }
+
                         return Err(ErrorKind::NoSuchPropertyInClass {
                             span,
                             class_name: self.scope.get_this_class_name(),
@@ -1711,6 +1818,12 @@ impl Analyzer<'_, '_> {
             }
 
             Type::Enum(ref e) => {
+                // Ambient const enums are inlined by the compiler, which is impossible if files
+                // are transpiled one by one.
+                if e.is_const && e.declare && id_ctx == IdCtx::Var && self.rule().isolated_modules {
+                    self.storage.report(ErrorKind::AmbientConstEnumWithIsolatedModules { span }.into());
+                }
+
                 // TODO(kdy1): Check if variant exists.
 
                 match prop {
@@ -1846,6 +1959,10 @@ impl Analyzer<'_, '_> {
             },
 
             Type::Class(ref c) => {
+                // Properties, including ones from the super class, have higher priority than
+                // index signatures.
+                let mut index_signature_fallback = None;
+
                 for v in c.def.body.iter() {
                     match v {
                         ClassMember::Property(ref class_prop @ ClassProperty { is_static: false, .. }) => {
@@ -1863,6 +1980,16 @@ impl Analyzer<'_, '_> {
 
                             //
                             if self.key_matches(span, &class_prop.key, prop, false) {
+                                if type_mode == TypeOfMode::LValue {
+                                    if class_prop.accessor.getter && !class_prop.accessor.setter {
+                                        return Err(ErrorKind::ReadOnly { span }.into());
+                                    }
+
+                                    if let Some(write_ty) = &class_prop.write_ty {
+                                        return Ok(*write_ty.clone());
+                                    }
+                                }
+
                                 return Ok(match class_prop.value {
                                     Some(ref ty) => *ty.clone(),
                                     None => Type::any(span, Default::default()),
@@ -1906,6 +2033,10 @@ impl Analyzer<'_, '_> {
                         }
 
                         ClassMember::IndexSignature(index) => {
+                            if index.is_static || index_signature_fallback.is_some() {
+                                continue;
+                            }
+
                             if index.params.len() == 1 {
                                 // `[s: string]: boolean` can be indexed with a number.
 
@@ -1917,11 +2048,13 @@ impl Analyzer<'_, '_> {
                                     || self.assign(span, &mut Default::default(), index_ty, &prop_ty).is_ok();
 
                                 if indexed {
-                                    return Ok(index
-                                        .type_ann
-                                        .clone()
-                                        .map(|v| *v)
-                                        .unwrap_or_else(|| Type::any(span, Default::default())));
+                                    index_signature_fallback = Some(
+                                        index
+                                            .type_ann
+                                            .clone()
+                                            .map(|v| *v)
+                                            .unwrap_or_else(|| Type::any(span, Default::default())),
+                                    );
                                 }
                             }
                         }
@@ -1939,6 +2072,10 @@ impl Analyzer<'_, '_> {
                     }
                 }
 
+                if let Some(ty) = index_signature_fallback {
+                    return Ok(ty);
+                }
+
                 let has_better_default = !opts.disallow_indexing_class_with_computed
                     && prop.is_computed()
                     && match prop.ty().normalize() {
@@ -2549,6 +2686,16 @@ impl Analyzer<'_, '_> {
                             }
                             // TODO(kdy1): normalized string / ident
                             if self.key_matches(span, &p.key, prop, false) {
+                                if type_mode == TypeOfMode::LValue {
+                                    if p.accessor.getter && !p.accessor.setter {
+                                        return Err(ErrorKind::ReadOnly { span }.into());
+                                    }
+
+                                    if let Some(ref write_ty) = p.write_ty {
+                                        return Ok(*write_ty.clone());
+                                    }
+                                }
+
                                 if let Some(ref ty) = p.value {
                                     return Ok(*ty.clone());
                                 }
@@ -3111,6 +3258,73 @@ impl Analyzer<'_, '_> {
         }
     }
 
+    /// Reports a reference to a `let` or `const` variable before its
+    /// declaration, or a read of a `let` variable which may not be assigned.
+    ///
+    /// Like tsc, references in functions are assumed to be evaluated after
+    /// variables of the parent are assigned.
+    fn check_use_before_init(&mut self, i: &RIdent) {
+        if self.is_builtin {
+            return;
+        }
+
+        let id = Id::from(i);
+        let (kind, declared) = match self.find_var(&id) {
+            Some(var) => (var.kind, var.ty.clone()),
+            None => return,
+        };
+        if !matches!(kind, VarKind::Var(VarDeclKind::Let | VarDeclKind::Const)) {
+            return;
+        }
+
+        if let Some(decl_span) = self.data.block_scoped_decls.get(&id) {
+            if i.span.lo < decl_span.lo && !self.is_deferred_ref(&id) {
+                self.storage.report(ErrorKind::BlockScopedVarUsedBeforeInit { span: i.span }.into());
+                return;
+            }
+        }
+
+        if !self.rule().strict_null_checks || self.ctx.in_non_null_expr || self.data.flow.is_definitely_assigned(&Name::from(&id)) {
+            return;
+        }
+
+        // Reading an unassigned variable is fine if its type contains `undefined`.
+        let declared = match declared {
+            Some(declared) => declared,
+            None => return,
+        };
+        let declared = match self.normalize(Some(i.span), Cow::Borrowed(&declared), Default::default()) {
+            Ok(ty) => ty,
+            Err(..) => return,
+        };
+        if declared
+            .iter_union()
+            .any(|ty| ty.is_any() || ty.is_unknown() || ty.is_undefined() || ty.is_kwd(TsKeywordTypeKind::TsVoidKeyword))
+        {
+            return;
+        }
+
+        self.storage.report(ErrorKind::VarMayNotBeInitialized { span: i.span }.into());
+    }
+
+    /// Returns `true` if a function or a class exists between the current scope
+    /// and the scope declaring `id`.
+    fn is_deferred_ref(&self, id: &Id) -> bool {
+        // Variables copied to store narrowed types are not declarations.
+        let declares = |scope: &Scope| scope.vars.get(id).map_or(false, |var| !var.copied);
+
+        match self.scope.first(|scope| {
+            declares(scope)
+                || matches!(
+                    scope.kind(),
+                    ScopeKind::Method { .. } | ScopeKind::Fn | ScopeKind::ArrowFn | ScopeKind::Constructor | ScopeKind::Class
+                )
+        }) {
+            Some(scope) => !declares(scope),
+            None => true,
+        }
+    }
+
     /// Returned type reflects conditional type facts.
     #[cfg_attr(debug_assertions, tracing::instrument(skip_all))]
     pub(super) fn type_of_var(&mut self, i: &RIdent, type_mode: TypeOfMode, type_args: Option<&TypeParamInstantiation>) -> VResult<Type> {
@@ -3503,10 +3717,21 @@ impl Analyzer<'_, '_> {
                 debug_assert!(ty.is_clone_cheap());
                 ty.assert_valid();
 
-                if let Type::Module(..) = ty.normalize() {
-                    return Ok(ty.clone().into_owned());
+                match ty.normalize() {
+                    Type::Module(..) => return Ok(ty.clone().into_owned()),
+                    // An interface merged with a namespace.
+                    Type::Intersection(i) => {
+                        if let Some(module) = i.types.iter().find(|ty| ty.normalize().is_module()) {
+                            return Ok(module.clone());
+                        }
+                    }
+                    _ => {}
                 }
             }
+            if lib_declaring(&i.sym).is_some() {
+                // The value is declared by a lib which is not loaded.
+                return Err(ErrorKind::TS2585 { span }.into());
+            }
             Err(ErrorKind::TypeUsedAsVar {
                 span,
                 name: i.clone().into(),
@@ -3561,6 +3786,20 @@ impl Analyzer<'_, '_> {
                         name: i.clone().into(),
                     }
                     .into())
+                } else if let Some(lib) = lib_declaring(&i.sym) {
+                    Err(ErrorKind::NoSuchVarInLibs {
+                        span,
+                        name: i.clone().into(),
+                        lib,
+                    }
+                    .into())
+                } else if let Some(suggestion) = self.suggest_var_name(&i.into()) {
+                    Err(ErrorKind::NoSuchVarWithSuggestion {
+                        span,
+                        name: i.clone().into(),
+                        suggestion,
+                    }
+                    .into())
                 } else {
                     Err(ErrorKind::NoSuchVar {
                         span,
@@ -3577,6 +3816,34 @@ impl Analyzer<'_, '_> {
         self.type_of_ts_entity_name_inner(span, n, type_args)
     }
 
+    /// Returns the type of the value named by `n`, like `Ns.Inner.value`.
+    pub(crate) fn type_of_ts_entity_name_as_value(&mut self, span: Span, n: &RTsEntityName) -> VResult<Type> {
+        match n {
+            RTsEntityName::Ident(i) => {
+                let ctx = Ctx {
+                    allow_module_var: true,
+                    ..self.ctx
+                };
+                self.with_ctx(ctx).type_of_var(i, TypeOfMode::RValue, None)
+            }
+            RTsEntityName::TsQualifiedName(q) => {
+                let obj = self.type_of_ts_entity_name_as_value(span, &q.left)?;
+
+                self.access_property(
+                    span,
+                    &obj,
+                    &Key::Normal {
+                        span: q.right.span,
+                        sym: q.right.sym.clone(),
+                    },
+                    TypeOfMode::RValue,
+                    IdCtx::Var,
+                    Default::default(),
+                )
+            }
+        }
+    }
+
     #[cfg_attr(debug_assertions, tracing::instrument(skip_all))]
     fn type_of_ts_entity_name_inner(&mut self, span: Span, n: &RExpr, type_args: Option<&TypeParamInstantiation>) -> VResult<Type> {
         let span = span.with_ctxt(SyntaxContext::empty());
@@ -3688,6 +3955,12 @@ impl Analyzer<'_, '_> {
                                     }
                                 }
                             }
+                            // A declaration merged with a namespace.
+                            Type::Intersection(i) if i.types.iter().any(|ty| ty.normalize().is_module()) => {
+                                let mut ty = ty.into_owned();
+                                ty.respan(span);
+                                return Ok(ty);
+                            }
                             Type::Intersection(ty) => {
                                 // TODO(kdy1): Expand types
                                 if !self.is_builtin {
@@ -3785,7 +4058,7 @@ impl Analyzer<'_, '_> {
         } = *expr;
         let computed = matches!(prop, RMemberProp::Computed(_));
 
-        let name: Option<Name> = expr.try_into().ok();
+        let name = self.name_of_member_expr(expr);
 
         if let TypeOfMode::RValue = type_mode {
             if let Some(name) = &name {
@@ -3859,19 +4132,19 @@ impl Analyzer<'_, '_> {
             ..self.ctx
         };
 
-        let mut ty = self
-            .with_ctx(prop_access_ctx)
-            .access_property(
-                span,
-                &obj_ty,
-                &prop,
-                type_mode,
-                IdCtx::Var,
-                AccessPropertyOpts {
-                    check_for_undefined_or_null: true,
-                    ..Default::default()
-                },
-            )
+        let res = self.with_ctx(prop_access_ctx).access_property(
+            span,
+            &obj_ty,
+            &prop,
+            type_mode,
+            IdCtx::Var,
+            AccessPropertyOpts {
+                check_for_undefined_or_null: true,
+                ..Default::default()
+            },
+        );
+        let mut ty = res
+            .map_err(|err| self.suggest_for_missing_property(span, obj.span(), &obj_ty, err))
             .context("tried to access property of an object to calculate type of a member expression")?;
 
         if !self.is_builtin {
@@ -3887,7 +4160,7 @@ impl Analyzer<'_, '_> {
         } else {
             if self.ctx.in_cond && self.ctx.should_store_truthy_for_access {
                 // Add type facts.
-                if let Some(name) = extract_name_for_assignment(obj, false) {
+                if let Some(name) = self.extract_name_for_assignment(obj, false) {
                     let next_ty = self
                         .narrow_types_with_property(
                             span,
@@ -4133,76 +4406,170 @@ impl Analyzer<'_, '_> {
             .map(|e| e.validate_with_default(self).map(|v| v.freezed()))
             .collect::<VResult<Vec<_>>>()?;
 
-        let quasis = e.quasis.clone();
+        // Template literal types are inferred only if literals are requested, like
+        // tsc. Otherwise the type of a template expression is `string`.
+        //
+        // Arguments are widened after the callee is known.
+        let is_literal_context = self.ctx.in_const_assertion
+            || self.ctx.in_argument
+            || type_ann.map_or(false, |type_ann| self.is_tpl_lit_contextual_type(e.span, type_ann));
 
-        if types.iter().any(|ty| ty.is_str_lit()) && quasis.iter().all(|q| q.cooked.is_some()) {
-            // We have to concat string literals
-            //
-            // https://github.com/dudykr/stc/issues/334
+        if !is_literal_context || e.quasis.iter().any(|q| q.cooked.is_none()) {
+            return Ok(Type::Keyword(KeywordType {
+                span: e.span,
+                kind: TsKeywordTypeKind::TsStringKeyword,
+                metadata: Default::default(),
+            }));
+        }
 
-            let mut nq = Vec::with_capacity(quasis.len());
-            let mut nt = Vec::with_capacity(types.len());
-            let mut quasis = quasis.into_iter();
+        self.create_tpl_type(e.span, &e.quasis, types)
+    }
+}
 
-            let mut cur_str = String::new();
+impl Analyzer<'_, '_> {
+    /// Returns true if `ty` is a contextual type which requests a template
+    /// literal type.
+    fn is_tpl_lit_contextual_type(&mut self, span: Span, ty: &Type) -> bool {
+        let ty = match self.normalize(Some(span), Cow::Borrowed(ty), Default::default()) {
+            Ok(ty) => ty,
+            Err(..) => return false,
+        };
 
-            for ty in types {
-                if let Type::Lit(LitType { lit: RTsLit::Str(v), .. }) = ty.normalize() {
-                    cur_str.push_str(quasis.next().unwrap().cooked.as_ref().unwrap());
-                    cur_str.push_str(&v.value);
-                    continue;
-                }
+        match ty.normalize() {
+            Type::Lit(LitType { lit: RTsLit::Str(..), .. }) | Type::Tpl(..) => true,
+            Type::Union(u) => u.types.iter().any(|ty| self.is_tpl_lit_contextual_type(span, ty)),
+            Type::Param(TypeParam {
+                constraint: Some(constraint), ..
+            }) => constraint.is_str() || self.is_tpl_lit_contextual_type(span, constraint),
+            _ => false,
+        }
+    }
 
-                if !cur_str.is_empty() {
-                    cur_str.push_str(quasis.next().unwrap().cooked.as_ref().unwrap());
+    /// Creates a template literal type.
+    ///
+    /// Literal types are concatenated with quasis and unions are distributed,
+    /// so `` `id-${x}` `` where `x: "a" | "b"` results in `"id-a" | "id-b"`.
+    pub(crate) fn create_tpl_type(&mut self, span: Span, quasis: &[RTplElement], types: Vec<Type>) -> VResult<Type> {
+        /// Same as the limit of tsc.
+        const MAX_CROSS_PRODUCT: usize = 100_000;
+
+        let mut cross_product: Vec<Vec<Type>> = vec![vec![]];
+
+        for ty in types {
+            let ty = self.normalize(Some(span), Cow::Owned(ty), Default::default())?.freezed();
+            let variants = match ty.normalize() {
+                Type::Union(u) => u.types.clone(),
+                _ => vec![ty.into_owned()],
+            };
 
-                    nq.push(RTplElement {
-                        span: e.span,
-                        node_id: NodeId::invalid(),
-                        raw: cur_str.clone().into(),
-                        cooked: Some(take(&mut cur_str).into()),
-                        tail: false,
-                    });
-                } else {
-                    nq.push(quasis.next().unwrap());
-                }
-                nt.push(ty);
+            if cross_product.len() * variants.len() > MAX_CROSS_PRODUCT {
+                self.storage.report(ErrorKind::UnionTooComplex { span }.into());
+
+                return Ok(Type::Keyword(KeywordType {
+                    span,
+                    kind: TsKeywordTypeKind::TsStringKeyword,
+                    metadata: Default::default(),
+                }));
             }
 
-            if !cur_str.is_empty() {
-                cur_str.push_str(quasis.next().unwrap().cooked.as_ref().unwrap());
+            cross_product = cross_product
+                .into_iter()
+                .flat_map(|prev| {
+                    variants.iter().map(move |variant| {
+                        let mut types = prev.clone();
+                        types.push(variant.clone());
+                        types
+                    })
+                })
+                .collect();
+        }
+
+        let types = cross_product
+            .into_iter()
+            .map(|types| concat_tpl_parts(span, quasis, types))
+            .collect::<Vec<_>>();
 
-                nq.push(RTplElement {
-                    span: e.span,
-                    node_id: NodeId::invalid(),
-                    raw: cur_str.clone().into(),
-                    cooked: Some(take(&mut cur_str).into()),
-                    tail: false,
-                });
-            } else {
-                nq.push(quasis.next().unwrap());
-            }
+        Ok(Type::new_union(span, types))
+    }
+}
 
-            debug_assert_eq!(nq.len(), nt.len() + 1);
+/// Concatenates literal types in `types` with `quasis`.
+///
+/// Returns a string literal type if all types are literals.
+fn concat_tpl_parts(span: Span, quasis: &[RTplElement], types: Vec<Type>) -> Type {
+    debug_assert_eq!(quasis.len(), types.len() + 1);
 
-            return Ok(Type::Tpl(TplType {
-                span: e.span,
-                quasis: nq,
-                types: nt,
-                metadata: TplTypeMetadata {
-                    common: CommonTypeMetadata { ..Default::default() },
-                },
-            }));
+    let mut nq = Vec::with_capacity(quasis.len());
+    let mut nt = Vec::with_capacity(types.len());
+
+    let mut cur_str = quasis[0].cooked.as_deref().unwrap_or_default().to_string();
+
+    for (ty, quasi) in types.into_iter().zip(quasis[1..].iter()) {
+        if let Some(s) = tpl_lit_value(&ty) {
+            cur_str.push_str(&s);
+        } else {
+            nq.push(RTplElement {
+                span,
+                node_id: NodeId::invalid(),
+                raw: cur_str.clone().into(),
+                cooked: Some(take(&mut cur_str).into()),
+                tail: false,
+            });
+            nt.push(ty);
         }
+        cur_str.push_str(quasi.cooked.as_deref().unwrap_or_default());
+    }
 
-        Ok(Type::Tpl(TplType {
-            span: e.span,
-            quasis,
-            types,
-            metadata: TplTypeMetadata {
-                common: CommonTypeMetadata { ..Default::default() },
-            },
-        }))
+    if nt.is_empty() {
+        return Type::Lit(LitType {
+            span,
+            lit: RTsLit::Str(RStr {
+                span,
+                value: cur_str.into(),
+                raw: None,
+            }),
+            metadata: Default::default(),
+        });
+    }
+
+    nq.push(RTplElement {
+        span,
+        node_id: NodeId::invalid(),
+        raw: cur_str.clone().into(),
+        cooked: Some(cur_str.into()),
+        tail: true,
+    });
+
+    Type::Tpl(TplType {
+        span,
+        quasis: nq,
+        types: nt,
+        metadata: TplTypeMetadata {
+            common: CommonTypeMetadata { ..Default::default() },
+        },
+    })
+}
+
+/// Returns the string representation of `ty` if it can be inlined into a
+/// template literal type.
+fn tpl_lit_value(ty: &Type) -> Option<String> {
+    match ty.normalize() {
+        Type::Lit(LitType { lit, .. }) => match lit {
+            RTsLit::Str(s) => Some(s.value.to_string()),
+            RTsLit::Number(n) => Some(n.value.to_string()),
+            RTsLit::Bool(b) => Some(b.value.to_string()),
+            RTsLit::BigInt(b) => Some(b.value.to_string()),
+            RTsLit::Tpl(..) => None,
+        },
+        Type::Keyword(KeywordType {
+            kind: TsKeywordTypeKind::TsNullKeyword,
+            ..
+        }) => Some("null".into()),
+        Type::Keyword(KeywordType {
+            kind: TsKeywordTypeKind::TsUndefinedKeyword,
+            ..
+        }) => Some("undefined".into()),
+        _ => None,
     }
 }
 
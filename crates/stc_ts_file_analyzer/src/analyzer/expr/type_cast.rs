@@ -1,21 +1,17 @@
-use std::borrow::Cow;
-
-use stc_ts_ast_rnode::{RTsAsExpr, RTsLit, RTsTypeAssertion};
+use stc_ts_ast_rnode::{RTsAsExpr, RTsTypeAssertion};
 use stc_ts_errors::{DebugExt, ErrorKind};
-use stc_ts_types::{Interface, KeywordType, LitType, TypeElement, TypeParamInstantiation};
+use stc_ts_types::TypeParamInstantiation;
 use stc_utils::cache::Freeze;
 use swc_common::{Span, Spanned, TypeEq};
-use swc_ecma_ast::TsKeywordTypeKind;
 
 use crate::{
     analyzer::{
-        assign::AssignOpts,
         expr::TypeOfMode,
         scope::ExpandOpts,
         util::{make_instance_type, ResultExt},
-        Analyzer, NormalizeTypeOpts,
+        Analyzer,
     },
-    ty::Type,
+    ty::{Type, TypeExt},
     util::is_str_or_union,
     validator,
     validator::ValidateWith,
@@ -195,15 +191,20 @@ impl Analyzer<'_, '_> {
             return Ok(());
         }
 
-        self.castable(span, orig, casted, Default::default())
-            .and_then(|castable| {
-                if castable {
-                    Ok(())
-                } else {
-                    Err(ErrorKind::NonOverlappingTypeCast { span }.into())
-                }
-            })
-            .convert_err(|err| ErrorKind::NonOverlappingTypeCast { span })
+        // The target should be comparable to the widened type of the expression, or
+        // the expression should be comparable to the target.
+        let widened = orig.clone().generalize_lit().freezed();
+        if self
+            .is_type_comparable_to(span, casted, &widened)
+            .convert_err(|err| ErrorKind::NonOverlappingTypeCast { span })?
+            || self
+                .is_type_comparable_to(span, orig, casted)
+                .convert_err(|err| ErrorKind::NonOverlappingTypeCast { span })?
+        {
+            Ok(())
+        } else {
+            Err(ErrorKind::NonOverlappingTypeCast { span }.into())
+        }
     }
 
     pub(crate) fn has_overlap(&mut self, span: Span, l: &Type, r: &Type, opts: CastableOpts) -> VResult<bool> {
@@ -214,216 +215,6 @@ impl Analyzer<'_, '_> {
             return Ok(true);
         }
 
-        Ok(self.castable(span, l, r, opts)? || self.castable(span, r, l, opts)?)
-    }
-
-    /// # Parameters
-    ///
-    /// - `l`: from
-    /// - `r`: to
-
-    pub(crate) fn castable(&mut self, span: Span, from: &Type, to: &Type, opts: CastableOpts) -> VResult<bool> {
-        let from = self
-            .normalize(
-                Some(span),
-                Cow::Borrowed(from),
-                NormalizeTypeOpts {
-                    preserve_intersection: true,
-                    preserve_union: true,
-                    preserve_global_this: true,
-                    ..Default::default()
-                },
-            )?
-            .freezed();
-        let to = self
-            .normalize(
-                Some(span),
-                Cow::Borrowed(to),
-                NormalizeTypeOpts {
-                    preserve_intersection: true,
-                    preserve_union: true,
-                    preserve_global_this: true,
-                    ..Default::default()
-                },
-            )?
-            .freezed();
-
-        let from = from.normalize();
-        let to = to.normalize();
-
-        if from.type_eq(to) {
-            return Ok(true);
-        }
-
-        // Overlaps with all types.
-        if from.is_any() || from.is_kwd(TsKeywordTypeKind::TsNullKeyword) || from.is_kwd(TsKeywordTypeKind::TsUndefinedKeyword) {
-            return Ok(true);
-        }
-
-        if (from.is_str() || from.is_tpl()) && to.is_tpl() {
-            return Ok(true);
-        }
-
-        if let Type::TypeLit(to) = to {
-            if to.members.is_empty() {
-                return Ok(true);
-            }
-        }
-
-        if from.type_eq(to) {
-            return Ok(true);
-        }
-
-        match (from, to) {
-            (
-                Type::Lit(LitType {
-                    lit: RTsLit::Number(..), ..
-                }),
-                Type::Keyword(KeywordType {
-                    kind: TsKeywordTypeKind::TsNumberKeyword,
-                    ..
-                }),
-            ) => return Ok(true),
-            (
-                Type::Lit(LitType { lit: RTsLit::Str(..), .. }),
-                Type::Keyword(KeywordType {
-                    kind: TsKeywordTypeKind::TsStringKeyword,
-                    ..
-                }),
-            ) => return Ok(true),
-            (
-                Type::Lit(LitType { lit: RTsLit::Bool(..), .. }),
-                Type::Keyword(KeywordType {
-                    kind: TsKeywordTypeKind::TsBooleanKeyword,
-                    ..
-                }),
-            ) => return Ok(true),
-            (
-                Type::Lit(LitType {
-                    lit: RTsLit::BigInt(..), ..
-                }),
-                Type::Keyword(KeywordType {
-                    kind: TsKeywordTypeKind::TsBigIntKeyword,
-                    ..
-                }),
-            ) => return Ok(true),
-            (
-                Type::Lit(LitType {
-                    lit: RTsLit::Number(..), ..
-                }),
-                Type::Lit(LitType {
-                    lit: RTsLit::Number(..), ..
-                }),
-            )
-            | (Type::Lit(LitType { lit: RTsLit::Str(..), .. }), Type::Lit(LitType { lit: RTsLit::Str(..), .. }))
-            | (
-                Type::Lit(LitType {
-                    lit: RTsLit::BigInt(..), ..
-                }),
-                Type::Lit(LitType {
-                    lit: RTsLit::BigInt(..), ..
-                }),
-            ) => return Ok(false),
-
-            (Type::Function(..), Type::Interface(Interface { name, .. })) if name == "Function" => return Ok(true),
-            _ => {}
-        }
-
-        // TODO(kdy1): More check
-        if from.is_fn_type() && to.is_fn_type() {
-            return Ok(false);
-        }
-
-        if let (Type::TypeLit(lt), Type::TypeLit(rt)) = (from, to) {
-            // It's an error if type of the parameter of index signature is same but type
-            // annotation is different.
-            for lm in &lt.members {
-                for rm in &rt.members {
-                    if let (TypeElement::Index(lm), TypeElement::Index(rm)) = (lm, rm) {
-                        if lm.params.type_eq(&rm.params) {
-                            if let Some(lt) = &lm.type_ann {
-                                if let Some(rt) = &rm.type_ann {
-                                    if self.assign(span, &mut Default::default(), lt, rt).is_err()
-                                        && self.assign(span, &mut Default::default(), rt, lt).is_err()
-                                    {
-                                        return Ok(false);
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        // TODO(kdy1): This is wrong
-        if from.is_type_lit() && to.is_type_lit() {
-            return Ok(true);
-        }
-
-        if let Type::Union(l) = from {
-            for l in &l.types {
-                if self.castable(span, l, to, opts)? {
-                    return Ok(true);
-                }
-            }
-
-            return Ok(false);
-        }
-
-        match to {
-            Type::Union(to) => {
-                for to in &to.types {
-                    if self.castable(span, from, to, opts)? {
-                        return Ok(true);
-                    }
-                }
-
-                return Ok(false);
-            }
-
-            Type::Intersection(to) => {
-                for to in &to.types {
-                    if self.castable(span, from, to, opts)? {
-                        return Ok(true);
-                    }
-                }
-
-                return Ok(false);
-            }
-            _ => {}
-        }
-
-        if from.is_num() {
-            if self.can_be_casted_to_number_in_rhs(span, to) {
-                return Ok(true);
-            }
-        }
-
-        if from.is_class() && (to.is_interface() || to.is_type_lit()) {
-            return Ok(false);
-        }
-
-        // class A {}
-        // class B extends A {}
-        //
-        // We can cast A to B, thus from = A, to = B.
-        if let Ok(()) = self.assign_with_opts(
-            &mut Default::default(),
-            from,
-            to,
-            AssignOpts {
-                span,
-                disallow_different_classes: opts.disallow_different_classes,
-                allow_assignment_to_param_constraint: opts.allow_assignment_to_param_constraint,
-                disallow_special_assignment_to_empty_class: opts.disallow_special_assignment_to_empty_class,
-                for_castablity: true,
-                ..Default::default()
-            },
-        ) {
-            return Ok(true);
-        }
-
-        Ok(false)
+        Ok(self.is_type_comparable_to_with_opts(span, l, r, opts)? || self.is_type_comparable_to_with_opts(span, r, l, opts)?)
     }
 }
@@ -112,6 +112,39 @@ impl Analyzer<'_, '_> {
 
             op!(unary, "-") | op!(unary, "+") => {
                 if let Some(arg) = &arg_ty {
+                    if arg.is_bigint() {
+                        if *op == op!(unary, "+") {
+                            self.storage.report(ErrorKind::UnaryPlusOnBigInt { span }.into());
+
+                            return Ok(Type::Keyword(KeywordType {
+                                span,
+                                kind: TsKeywordTypeKind::TsNumberKeyword,
+                                metadata: Default::default(),
+                            }));
+                        }
+
+                        if let Type::Lit(LitType {
+                            lit: RTsLit::BigInt(v), ..
+                        }) = arg.normalize()
+                        {
+                            return Ok(Type::Lit(LitType {
+                                span: v.span,
+                                lit: RTsLit::BigInt(RBigInt {
+                                    value: box -(*v.value.clone()),
+                                    span: v.span,
+                                    raw: None,
+                                }),
+                                metadata: Default::default(),
+                            }));
+                        }
+
+                        return Ok(Type::Keyword(KeywordType {
+                            span,
+                            kind: TsKeywordTypeKind::TsBigIntKeyword,
+                            metadata: Default::default(),
+                        }));
+                    }
+
                     if let Type::Lit(LitType {
                         lit: RTsLit::Number(RNumber { span, value, .. }),
                         ..
@@ -139,6 +172,14 @@ impl Analyzer<'_, '_> {
             }
 
             op!("~") => {
+                if matches!(&arg_ty, Some(arg) if arg.is_bigint()) {
+                    return Ok(Type::Keyword(KeywordType {
+                        span,
+                        kind: TsKeywordTypeKind::TsBigIntKeyword,
+                        metadata: Default::default(),
+                    }));
+                }
+
                 return Ok(Type::Keyword(KeywordType {
                     span,
                     kind: TsKeywordTypeKind::TsNumberKeyword,
@@ -190,17 +231,17 @@ impl Analyzer<'_, '_> {
     fn validate_delete_operand(&mut self, arg: &RExpr) -> VResult<()> {
         let span = arg.span();
         match arg {
+            //
+            // delete (o4.b?.c.d);
+            // delete (o4.b?.c.d)?.e;
+            RExpr::Paren(RParenExpr { expr, .. }) => self.validate_delete_operand(expr),
+
             RExpr::Member(RMemberExpr {
-                obj: box RExpr::This(..),
                 prop: RMemberProp::PrivateName(..),
                 ..
             }) => Err(ErrorKind::CannotDeletePrivateProperty { span }.into()),
 
-            RExpr::Paren(RParenExpr {
-                expr: box RExpr::Member(expr),
-                ..
-            })
-            | RExpr::OptChain(ROptChainExpr {
+            RExpr::OptChain(ROptChainExpr {
                 base: ROptChainBase::Member(expr),
                 ..
             })
@@ -212,6 +253,12 @@ impl Analyzer<'_, '_> {
                         | ErrorKind::ObjectIsPossiblyNullOrUndefined { span, .. } => ErrorKind::DeleteOperandMustBeOptional { span },
                         _ => err,
                     })?;
+
+                    // `any`, `unknown` and `never` can always be deleted.
+                    if ty.is_any() || ty.is_unknown() || ty.is_never() {
+                        return Ok(());
+                    }
+
                     if !self.can_be_undefined(span, &ty, false)? {
                         return Err(ErrorKind::DeleteOperandMustBeOptional { span }.into());
                     }
@@ -219,13 +266,6 @@ impl Analyzer<'_, '_> {
                 Ok(())
             }
 
-            //
-            // delete (o4.b?.c.d);
-            // delete (o4.b?.c.d)?.e;
-            RExpr::Paren(RParenExpr { expr, .. }) => self.validate_delete_operand(expr),
-
-            RExpr::Await(..) => Err(ErrorKind::InvalidDeleteOperand { span }.into()),
-
             _ => Err(ErrorKind::InvalidDeleteOperand { span }.into()),
         }
     }
@@ -0,0 +1,577 @@
+//! Control flow graph used to narrow types of variables.
+//!
+//! Flow nodes are created while validating statements, in the order of
+//! execution. Each node points to its antecedents, and the narrowed type of a
+//! reference is computed by walking antecedents from the current node, like
+//! `getFlowTypeOfReference` of tsc.
+//!
+//! Facts of scopes take precedence over the graph. See
+//! `Analyzer::find_var_type` for the reason.
+
+use std::cell::{Cell, RefCell};
+
+use fxhash::{FxHashMap, FxHashSet};
+use stc_ts_ast_rnode::{RBool, RExpr, RLit};
+use stc_ts_types::name::Name;
+use stc_utils::cache::Freeze;
+use swc_common::{TypeEq, DUMMY_SP};
+
+use crate::{analyzer::control_flow::CondFacts, ty::Type};
+
+/// Index of a [FlowNode] in a [FlowGraph].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) struct FlowNodeId(u32);
+
+#[derive(Debug)]
+enum FlowNode {
+    /// The start of a file or a function.
+    Start,
+    /// After `return`, `throw`, `break` or `continue`.
+    Unreachable,
+    /// An assignment to a variable, including declarations with an
+    /// initializer.
+    ///
+    /// `ty` is [None] if the assigned type is not known, which resets the
+    /// narrowed type to the declared one.
+    Assign {
+        antecedent: FlowNodeId,
+        name: Name,
+        ty: Option<Type>,
+    },
+    /// A declaration without an initializer, like `let a: string;`.
+    Declare { antecedent: FlowNodeId, name: Name },
+    /// The path taken if a condition is true or false.
+    Cond {
+        antecedent: FlowNodeId,
+        vars: FxHashMap<Name, Type>,
+    },
+    /// A join point, like the end of an `if` statement or the header of a
+    /// loop.
+    ///
+    /// The first antecedent of a loop header is the entry of the loop, and
+    /// others are back edges.
+    Label { antecedents: Vec<FlowNodeId>, is_loop: bool },
+}
+
+/// The maximum number of iterations to compute types at a loop header.
+const MAX_LOOP_ITERATIONS: usize = 8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum JumpKind {
+    Break,
+    Continue,
+}
+
+/// A statement which can be the target of `break` or `continue`.
+#[derive(Debug)]
+struct JumpTarget {
+    break_label: FlowNodeId,
+    /// [None] for `switch` statements.
+    continue_label: Option<FlowNodeId>,
+}
+
+/// The flow of the parent of a function, which is restored by
+/// [FlowGraph::restore].
+#[derive(Debug)]
+pub(crate) struct ParentFlow {
+    cur: Option<FlowNodeId>,
+    jump_targets: Vec<JumpTarget>,
+    try_assigns: Vec<Vec<FlowNodeId>>,
+}
+
+/// A state of a [FlowGraph] which can be restored by [FlowGraph::rollback].
+#[derive(Debug)]
+pub(crate) struct FlowCheckpoint {
+    node_count: usize,
+    cur: Option<FlowNodeId>,
+    jump_target_count: usize,
+    try_assigns: Vec<Vec<FlowNodeId>>,
+    loop_depth: u32,
+    declared_without_init: FxHashSet<Name>,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct FlowGraph {
+    nodes: Vec<FlowNode>,
+    /// [None] until the first node is created.
+    cur: Option<FlowNodeId>,
+    jump_targets: Vec<JumpTarget>,
+    /// Assignments in `try` blocks, which are antecedents of `catch` and
+    /// `finally` clauses because an exception can be thrown anywhere.
+    try_assigns: Vec<Vec<FlowNodeId>>,
+    /// Narrowed types by nodes. [None] means the declared type.
+    ///
+    /// Types are cached at labels and at nodes where types are queried, so a
+    /// query walks back only to the previous query of the same reference.
+    cache: RefCell<FxHashMap<(FlowNodeId, Name), Option<Type>>>,
+    /// Nodes which have an entry in `cache` or `loop_cache`, which allows
+    /// walking linear nodes without looking up the cache for each name.
+    cached_nodes: RefCell<FxHashSet<FlowNodeId>>,
+    /// Like `cache`, but for types computed while iterating over a loop, which
+    /// depend on types at the loop header which are not final yet.
+    loop_cache: RefCell<FxHashMap<(FlowNodeId, Name), Option<Type>>>,
+    loop_depth: Cell<u32>,
+    /// Types at loop headers which are being computed.
+    loop_headers: RefCell<FxHashMap<(FlowNodeId, Name), Option<Type>>>,
+    /// Variables which have a [FlowNode::Declare].
+    declared_without_init: FxHashSet<Name>,
+}
+
+impl FlowGraph {
+    fn add_node(&mut self, node: FlowNode) -> FlowNodeId {
+        let id = FlowNodeId(self.nodes.len() as u32);
+        self.nodes.push(node);
+        id
+    }
+
+    fn node(&self, id: FlowNodeId) -> &FlowNode {
+        &self.nodes[id.0 as usize]
+    }
+
+    /// Starts the flow of a function, which does not see narrowing of the
+    /// parent.
+    pub fn start(&mut self) -> ParentFlow {
+        let start = self.add_node(FlowNode::Start);
+
+        ParentFlow {
+            cur: self.cur.replace(start),
+            jump_targets: std::mem::take(&mut self.jump_targets),
+            try_assigns: std::mem::take(&mut self.try_assigns),
+        }
+    }
+
+    pub fn restore(&mut self, parent: ParentFlow) {
+        self.cur = parent.cur;
+        self.jump_targets = parent.jump_targets;
+        self.try_assigns = parent.try_assigns;
+    }
+
+    pub fn checkpoint(&self) -> FlowCheckpoint {
+        FlowCheckpoint {
+            node_count: self.nodes.len(),
+            cur: self.cur,
+            jump_target_count: self.jump_targets.len(),
+            try_assigns: self.try_assigns.clone(),
+            loop_depth: self.loop_depth.get(),
+            declared_without_init: self.declared_without_init.clone(),
+        }
+    }
+
+    /// Removes nodes created after `checkpoint`.
+    pub fn rollback(&mut self, checkpoint: FlowCheckpoint) {
+        self.nodes.truncate(checkpoint.node_count);
+        self.cur = checkpoint.cur;
+        self.jump_targets.truncate(checkpoint.jump_target_count);
+        self.try_assigns = checkpoint.try_assigns;
+        self.loop_depth.set(checkpoint.loop_depth);
+        self.declared_without_init = checkpoint.declared_without_init;
+
+        // Cached types may refer to removed nodes.
+        self.cache.get_mut().clear();
+        self.cached_nodes.get_mut().clear();
+        self.loop_cache.get_mut().clear();
+        self.loop_headers.get_mut().clear();
+    }
+
+    pub fn cur(&mut self) -> FlowNodeId {
+        match self.cur {
+            Some(cur) => cur,
+            None => {
+                let start = self.add_node(FlowNode::Start);
+                self.cur = Some(start);
+                start
+            }
+        }
+    }
+
+    pub fn set_cur(&mut self, node: FlowNodeId) {
+        self.cur = Some(node);
+    }
+
+    pub fn is_unreachable(&self, node: FlowNodeId) -> bool {
+        matches!(self.node(node), FlowNode::Unreachable)
+    }
+
+    pub fn mark_unreachable(&mut self) {
+        let unreachable = self.add_node(FlowNode::Unreachable);
+        self.cur = Some(unreachable);
+    }
+
+    pub fn add_assign(&mut self, name: Name, ty: Option<Type>) {
+        let antecedent = self.cur();
+        if self.is_unreachable(antecedent) {
+            return;
+        }
+
+        let assign = self.add_node(FlowNode::Assign {
+            antecedent,
+            name,
+            ty: ty.map(Freeze::freezed),
+        });
+        for assigns in &mut self.try_assigns {
+            assigns.push(assign);
+        }
+        self.cur = Some(assign);
+    }
+
+    pub fn add_declare(&mut self, name: Name) {
+        let antecedent = self.cur();
+        if self.is_unreachable(antecedent) {
+            return;
+        }
+
+        self.declared_without_init.insert(name.clone());
+        let declare = self.add_node(FlowNode::Declare { antecedent, name });
+        self.cur = Some(declare);
+    }
+
+    /// Continues from `antecedent`, with the types narrowed by `facts`.
+    pub fn add_cond(&mut self, antecedent: FlowNodeId, facts: &CondFacts) {
+        if facts.vars.is_empty() || self.is_unreachable(antecedent) {
+            self.cur = Some(antecedent);
+            return;
+        }
+
+        let cond = self.add_node(FlowNode::Cond {
+            antecedent,
+            vars: facts.vars.clone(),
+        });
+        self.cur = Some(cond);
+    }
+
+    /// Creates a join point. Antecedents should be added using
+    /// [FlowGraph::add_antecedent].
+    pub fn new_label(&mut self) -> FlowNodeId {
+        self.add_node(FlowNode::Label {
+            antecedents: vec![],
+            is_loop: false,
+        })
+    }
+
+    /// Creates the header of a loop. The current node should be added as the
+    /// first antecedent.
+    pub fn new_loop_label(&mut self) -> FlowNodeId {
+        self.add_node(FlowNode::Label {
+            antecedents: vec![],
+            is_loop: true,
+        })
+    }
+
+    /// Adds the current node to the antecedents of `label`.
+    pub fn add_antecedent(&mut self, label: FlowNodeId) {
+        let cur = self.cur();
+        self.add_antecedent_node(label, cur);
+    }
+
+    fn add_antecedent_node(&mut self, label: FlowNodeId, node: FlowNodeId) {
+        if self.is_unreachable(node) {
+            return;
+        }
+
+        if let FlowNode::Label { antecedents, .. } = &mut self.nodes[label.0 as usize] {
+            if !antecedents.contains(&node) {
+                antecedents.push(node);
+                self.invalidate_cache(label);
+            }
+        }
+    }
+
+    /// Removes cached types which may be changed by a new antecedent of
+    /// `label`.
+    ///
+    /// Antecedents are added to a label only while the statement creating it
+    /// is being validated, and nodes created before the label can reach it
+    /// only through a back edge of a loop, which is added after the body. So
+    /// only the types of the label and nodes created after it are affected.
+    fn invalidate_cache(&mut self, label: FlowNodeId) {
+        self.cache.get_mut().retain(|(node, _), _| node.0 < label.0);
+        self.cached_nodes.get_mut().retain(|node| node.0 < label.0);
+    }
+
+    /// Continues from `label`, which is unreachable if all antecedents are
+    /// unreachable.
+    pub fn finish_label(&mut self, label: FlowNodeId) {
+        match self.node(label) {
+            FlowNode::Label { antecedents, .. } if antecedents.is_empty() => self.mark_unreachable(),
+            _ => self.cur = Some(label),
+        }
+    }
+
+    pub fn push_jump_target(&mut self, break_label: FlowNodeId, continue_label: Option<FlowNodeId>) {
+        self.jump_targets.push(JumpTarget {
+            break_label,
+            continue_label,
+        });
+    }
+
+    pub fn pop_jump_target(&mut self) {
+        self.jump_targets.pop();
+    }
+
+    /// `break` or `continue`.
+    ///
+    /// TODO: Find the target of a labeled jump. For now, it's added to all
+    /// enclosing targets, which is safe but less precise.
+    pub fn jump(&mut self, kind: JumpKind, is_labeled: bool) {
+        let targets = self
+            .jump_targets
+            .iter()
+            .rev()
+            .filter_map(|target| match kind {
+                JumpKind::Break => Some(target.break_label),
+                JumpKind::Continue => target.continue_label,
+            })
+            .take(if is_labeled { usize::MAX } else { 1 })
+            .collect::<Vec<_>>();
+
+        for target in targets {
+            self.add_antecedent(target);
+        }
+
+        self.mark_unreachable();
+    }
+
+    pub fn enter_try(&mut self) {
+        self.try_assigns.push(vec![]);
+    }
+
+    /// Returns assignments in the `try` block.
+    pub fn exit_try(&mut self) -> Vec<FlowNodeId> {
+        self.try_assigns.pop().unwrap_or_default()
+    }
+
+    pub fn antecedents(&self, label: FlowNodeId) -> &[FlowNodeId] {
+        match self.node(label) {
+            FlowNode::Label { antecedents, .. } => antecedents,
+            _ => &[],
+        }
+    }
+
+    /// Removes `nodes` from the antecedents of `label`, which is used to
+    /// replace edges added by the previous validation of a loop body.
+    pub fn remove_antecedents(&mut self, label: FlowNodeId, nodes: &[FlowNodeId]) {
+        if nodes.is_empty() {
+            return;
+        }
+
+        if let FlowNode::Label { antecedents, .. } = &mut self.nodes[label.0 as usize] {
+            antecedents.retain(|node| !nodes.contains(node));
+            self.invalidate_cache(label);
+        }
+    }
+
+    /// Adds `nodes` to the antecedents of `label`.
+    pub fn add_antecedents(&mut self, label: FlowNodeId, nodes: &[FlowNodeId]) {
+        for &node in nodes {
+            self.add_antecedent_node(label, node);
+        }
+    }
+
+    /// The narrowed type of `name` at the current node, or [None] if it's the
+    /// declared type.
+    pub fn narrowed_type(&self, name: &Name) -> Option<Type> {
+        self.type_at(self.cur?, name)
+    }
+
+    fn type_at(&self, start: FlowNodeId, name: &Name) -> Option<Type> {
+        let ty = self.type_at_uncached(start, name);
+        self.insert_cache((start, name.clone()), ty.clone());
+        ty
+    }
+
+    fn type_at_uncached(&self, mut node: FlowNodeId, name: &Name) -> Option<Type> {
+        // Linear nodes are handled without recursion, as they can be long.
+        loop {
+            if self.cached_nodes.borrow().contains(&node) {
+                if let Some(ty) = self.cached(&(node, name.clone())) {
+                    return ty;
+                }
+            }
+
+            match self.node(node) {
+                FlowNode::Start | FlowNode::Unreachable => return None,
+
+                FlowNode::Assign {
+                    antecedent,
+                    name: assigned,
+                    ty,
+                } => {
+                    if assigned == name {
+                        return ty.clone();
+                    }
+                    // `a = b` resets narrowing of `a.c`.
+                    if name.as_ids().starts_with(assigned.as_ids()) {
+                        return None;
+                    }
+                    node = *antecedent;
+                }
+
+                FlowNode::Declare {
+                    antecedent,
+                    name: declared,
+                } => {
+                    if declared == name {
+                        return None;
+                    }
+                    node = *antecedent;
+                }
+
+                FlowNode::Cond { antecedent, vars } => {
+                    if let Some(ty) = vars.get(name) {
+                        return Some(ty.clone());
+                    }
+                    node = *antecedent;
+                }
+
+                FlowNode::Label { antecedents, is_loop } => {
+                    let key = (node, name.clone());
+                    // Types at loop headers being computed are not in `cached_nodes`.
+                    if let Some(ty) = self.cached(&key) {
+                        return ty;
+                    }
+
+                    let ty = if *is_loop {
+                        self.type_at_loop_label(&key, antecedents, name)
+                    } else {
+                        // A placeholder for cycles, which are handled by loop headers.
+                        self.insert_cache(key.clone(), None);
+                        self.type_at_label(antecedents, name)
+                    };
+
+                    self.insert_cache(key, ty.clone());
+                    return ty;
+                }
+            }
+        }
+    }
+
+    /// Returns `false` if a declaration of `name` without an initializer can
+    /// be reached from the current node without passing an assignment, like
+    /// `isFlowDefinitelyAssigned` of tsc.
+    ///
+    /// The start of a function is treated as assigned, so references in
+    /// closures are assumed to be evaluated after variables are assigned.
+    pub fn is_definitely_assigned(&self, name: &Name) -> bool {
+        if !self.declared_without_init.contains(name) {
+            return true;
+        }
+        let cur = match self.cur {
+            Some(cur) => cur,
+            None => return true,
+        };
+
+        let mut visited = FxHashSet::default();
+        let mut stack = vec![cur];
+        while let Some(node) = stack.pop() {
+            if !visited.insert(node) {
+                continue;
+            }
+
+            match self.node(node) {
+                FlowNode::Start | FlowNode::Unreachable => {}
+                FlowNode::Assign {
+                    antecedent,
+                    name: assigned,
+                    ..
+                } => {
+                    if assigned != name {
+                        stack.push(*antecedent);
+                    }
+                }
+                FlowNode::Declare {
+                    antecedent,
+                    name: declared,
+                } => {
+                    if declared == name {
+                        return false;
+                    }
+                    stack.push(*antecedent);
+                }
+                FlowNode::Cond { antecedent, .. } => stack.push(*antecedent),
+                FlowNode::Label { antecedents, .. } => stack.extend(antecedents),
+            }
+        }
+
+        true
+    }
+
+    fn type_at_label(&self, antecedents: &[FlowNodeId], name: &Name) -> Option<Type> {
+        let types = antecedents
+            .iter()
+            .map(|&antecedent| self.type_at(antecedent, name))
+            .collect::<Option<Vec<_>>>()?;
+
+        if types.is_empty() {
+            return None;
+        }
+
+        Some(Type::new_union(DUMMY_SP, types).freezed())
+    }
+
+    /// Computes the type at a loop header by iterating until types from back
+    /// edges do not change, like `getTypeAtFlowLoopLabel` of tsc.
+    fn type_at_loop_label(&self, key: &(FlowNodeId, Name), antecedents: &[FlowNodeId], name: &Name) -> Option<Type> {
+        let (&entry, back_edges) = antecedents.split_first()?;
+
+        self.loop_headers.borrow_mut().insert(key.clone(), None);
+        let ty = self.iterate_loop_label(key, entry, back_edges, name);
+        self.loop_headers.borrow_mut().remove(key);
+
+        ty
+    }
+
+    fn iterate_loop_label(&self, key: &(FlowNodeId, Name), entry: FlowNodeId, back_edges: &[FlowNodeId], name: &Name) -> Option<Type> {
+        let mut ty = self.type_at(entry, name)?;
+
+        for _ in 0..MAX_LOOP_ITERATIONS {
+            // Types computed in the previous iteration depend on the previous type.
+            self.loop_cache.borrow_mut().clear();
+            self.loop_headers.borrow_mut().insert(key.clone(), Some(ty.clone()));
+
+            self.loop_depth.set(self.loop_depth.get() + 1);
+            let types = back_edges
+                .iter()
+                .map(|&back_edge| self.type_at(back_edge, name))
+                .collect::<Option<Vec<_>>>();
+            self.loop_depth.set(self.loop_depth.get() - 1);
+
+            let new_ty = Type::new_union(DUMMY_SP, std::iter::once(ty.clone()).chain(types?)).freezed();
+            if new_ty.type_eq(&ty) {
+                return Some(ty);
+            }
+            ty = new_ty;
+        }
+
+        None
+    }
+
+    fn cached(&self, key: &(FlowNodeId, Name)) -> Option<Option<Type>> {
+        if let Some(ty) = self.loop_headers.borrow().get(key) {
+            return Some(ty.clone());
+        }
+        if let Some(ty) = self.loop_cache.borrow().get(key) {
+            return Some(ty.clone());
+        }
+
+        self.cache.borrow().get(key).cloned()
+    }
+
+    fn insert_cache(&self, key: (FlowNodeId, Name), ty: Option<Type>) {
+        self.cached_nodes.borrow_mut().insert(key.0);
+        if self.loop_depth.get() == 0 {
+            self.cache.borrow_mut().insert(key, ty);
+        } else {
+            self.loop_cache.borrow_mut().insert(key, ty);
+        }
+    }
+}
+
+/// Returns `true` if the false branch of `test` is unreachable, like
+/// `while (true)`.
+pub(crate) fn is_always_true(test: &RExpr) -> bool {
+    match test {
+        RExpr::Lit(RLit::Bool(RBool { value: true, .. })) => true,
+        RExpr::Paren(e) => is_always_true(&e.expr),
+        _ => false,
+    }
+}
@@ -156,6 +156,11 @@ impl Analyzer<'_, '_> {
                     .as_ref()
                     .map(|body| child.visit_stmts_for_return(span, is_async, is_generator, &body.stmts)));
 
+            let end_is_reachable = {
+                let end = child.data.flow.cur();
+                !child.data.flow.is_unreachable(end)
+            };
+
             let mut inferred_return_type = match inferred_return_type {
                 Some(Some(inferred_return_type)) => {
                     let mut inferred_return_type = match inferred_return_type {
@@ -172,6 +177,15 @@ impl Analyzer<'_, '_> {
                                 .storage
                                 .report(ErrorKind::GeneratorCannotHaveVoidAsReturnType { span: declared.span() }.into())
                         }
+
+                        if end_is_reachable
+                            && !is_async
+                            && !is_generator
+                            && child.rule().strict_null_checks
+                            && !child.can_fall_off_end(declared)
+                        {
+                            errors.push(ErrorKind::LacksEndingReturn { span: declared.span() }.into());
+                        }
                     } else {
                         if child.rule().no_implicit_any {
                             if child.is_implicitly_typed(&inferred_return_type) {
@@ -255,6 +269,19 @@ impl Analyzer<'_, '_> {
 }
 
 impl Analyzer<'_, '_> {
+    /// Returns `true` if the end of a function body can be reached without
+    /// `return`, which returns `undefined`.
+    fn can_fall_off_end(&mut self, declared: &Type) -> bool {
+        let declared = match self.normalize(Some(declared.span()), Cow::Borrowed(declared), Default::default()) {
+            Ok(ty) => ty,
+            Err(..) => return true,
+        };
+
+        declared
+            .iter_union()
+            .any(|ty| ty.is_any() || ty.is_unknown() || ty.is_never() || ty.is_undefined() || ty.is_kwd(TsKeywordTypeKind::TsVoidKeyword))
+    }
+
     pub(crate) fn fn_to_type_element(&mut self, f: &Function) -> VResult<TypeElement> {
         Ok(TypeElement::Call(CallSignature {
             span: f.span.with_ctxt(SyntaxContext::empty()),
@@ -1,11 +1,13 @@
 use fxhash::FxHashMap;
+use itertools::Itertools;
 use rnode::FoldWith;
-use stc_ts_errors::debug::dump_type_as_string;
+use stc_ts_env::perf::{InstantiationGuard, OpKind};
+use stc_ts_errors::debug::{dump_type_as_string, dump_type_name};
 use stc_ts_generics::{expander::GenericExpander, ExpandGenericOpts};
 use stc_ts_type_ops::Fix;
 use stc_ts_types::{Id, Interface, KeywordType, TypeParam, TypeParamDecl, TypeParamInstantiation};
 use stc_utils::{cache::Freeze, ext::SpanExt};
-use swc_common::{Span, Spanned, TypeEq};
+use swc_common::{Span, Spanned, TypeEq, DUMMY_SP};
 use swc_ecma_ast::*;
 use tracing::debug;
 
@@ -50,8 +52,9 @@ impl Analyzer<'_, '_> {
                 params.insert(param.name.clone(), arg);
             } else {
                 if let Some(default) = &param.default {
-                    let default = default.clone().freezed();
-                    params.insert(param.name.clone(), *default.clone());
+                    // Defaults may reference type parameters declared before them.
+                    let default = self.expand_type_params(&params, *default.clone(), Default::default())?.freezed();
+                    params.insert(param.name.clone(), default);
                 } else {
                     unimplemented!(
                         "Reporting errors when type parameter count and type argument count differs\nParams={:#?}\nArgs: {:#?}",
@@ -91,6 +94,19 @@ impl Analyzer<'_, '_> {
             debug_assert!(param.is_clone_cheap());
         }
 
+        let instantiation = InstantiationGuard::enter();
+        if let Some(perf) = self.env.perf() {
+            // There's no span for the instantiation itself, so we use the span of a type
+            // argument.
+            let span = params.values().next().map(|ty| ty.span()).unwrap_or(DUMMY_SP);
+            perf.record_op(OpKind::Instantiation, span, instantiation.depth(), || {
+                params
+                    .iter()
+                    .map(|(name, ty)| format!("{} = {}", name, dump_type_name(ty)))
+                    .join(", ")
+            });
+        }
+
         let ty = ty
             .fold_with(&mut GenericExpander {
                 cm: self.cm.clone(),
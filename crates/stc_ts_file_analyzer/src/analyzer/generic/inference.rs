@@ -1,6 +1,7 @@
 use std::{
     borrow::Cow,
     collections::{hash_map::Entry, HashMap},
+    mem::take,
 };
 
 use fxhash::FxHashMap;
@@ -11,8 +12,8 @@ use stc_ts_generics::expander::InferTypeResult;
 use stc_ts_type_form::{compare_type_forms, max_path, TypeForm};
 use stc_ts_type_ops::generalization::prevent_generalize;
 use stc_ts_types::{
-    Array, ArrayMetadata, Class, ClassDef, ClassMember, Function, Id, Interface, KeywordType, KeywordTypeMetadata, LitType, Operator, Ref,
-    Type, TypeElement, TypeLit, TypeParam, TypeParamMetadata, Union,
+    Array, ArrayMetadata, Class, ClassDef, ClassMember, Function, Id, Interface, Intersection, KeywordType, KeywordTypeMetadata, LitType,
+    Operator, PropertySignature, Ref, Type, TypeElement, TypeLit, TypeParam, TypeParamMetadata, Union,
 };
 use stc_utils::cache::Freeze;
 use swc_common::{Span, Spanned, SyntaxContext, TypeEq};
@@ -30,6 +31,22 @@ use crate::{
     VResult,
 };
 
+/// Priority of an inference candidate. Lower values have higher priority, and
+/// candidates with lower priority are discarded if there's a candidate with
+/// higher priority.
+///
+/// See `InferencePriority` of `tsc`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub(crate) enum InferencePriority {
+    /// Inferred directly from an argument.
+    #[default]
+    None,
+    /// Inferred from the contextual type of the return type.
+    ReturnType,
+    /// Inferred from `keyof T` and a literal type.
+    LiteralKeyof,
+}
+
 /// # Default
 ///
 /// All fields default to `false`.
@@ -60,6 +77,25 @@ pub(crate) struct InferTypeOpts {
     ///
     /// because literals are present in the another type.
     pub is_type_ann: bool,
+
+    pub priority: InferencePriority,
+
+    /// `true` if we are inferring from a contravariant position, like
+    /// parameters of a function.
+    ///
+    /// Contravariant candidates are used only if there's no covariant
+    /// candidate.
+    pub contravariant: bool,
+
+    /// `true` if the return type of the callee is a non-generic function, so
+    /// a generic function argument can pass its type parameters to it.
+    ///
+    /// ```ts
+    /// declare function pipe<A, B, C>(ab: (a: A) => B, bc: (b: B) => C): (a: A) => C;
+    ///
+    /// const f = pipe(list, box); // <T>(a: T) => { value: T[] }
+    /// ```
+    pub keep_arg_type_params: bool,
 }
 
 impl Analyzer<'_, '_> {
@@ -216,6 +252,28 @@ impl Analyzer<'_, '_> {
             return Ok(());
         }
 
+        if opts.contravariant {
+            let candidates = inferred.contra_candidates.entry(name).or_default();
+            if candidates.iter().all(|prev| !prev.type_eq(&*ty)) {
+                candidates.push(ty.into_owned().freezed());
+            }
+            return Ok(());
+        }
+
+        if inferred.type_params.contains_key(&name) {
+            let prev_priority = inferred.priorities.get(&name).copied().unwrap_or_default();
+
+            if opts.priority > prev_priority {
+                return Ok(());
+            }
+
+            if opts.priority < prev_priority {
+                inferred.type_params.remove(&name);
+                inferred.errored.remove(&name);
+            }
+        }
+        inferred.priorities.insert(name.clone(), opts.priority);
+
         match inferred.type_params.entry(name.clone()) {
             Entry::Occupied(mut e) => {
                 if let InferredType::Union(_) = e.get() {
@@ -292,7 +350,7 @@ impl Analyzer<'_, '_> {
         self.infer_type(span, &mut inferred, param, arg, InferTypeOpts { skip_union: true, ..opts })
             .context("tried to infer type using two type")?;
 
-        let map = self.finalize_inference(inferred);
+        let map = self.finalize_inference(span, inferred);
 
         Ok(map.types)
     }
@@ -592,9 +650,48 @@ impl Analyzer<'_, '_> {
                         continue;
                     }
 
+                    (TypeElement::Index(p), TypeElement::Method(a)) => {
+                        if p.params.len() == 1
+                            && (p.params[0].ty.is_kwd(TsKeywordTypeKind::TsStringKeyword)
+                                || self.assign(span, &mut Default::default(), &p.params[0].ty, &a.key.ty()).is_ok())
+                        {
+                            if let Some(p_ty) = &p.type_ann {
+                                let span = span.with_ctxt(SyntaxContext::empty());
+
+                                self.infer_type(
+                                    span,
+                                    inferred,
+                                    p_ty,
+                                    &Type::Function(Function {
+                                        span,
+                                        type_params: a.type_params.clone(),
+                                        params: a.params.clone(),
+                                        ret_ty: a.ret_ty.clone().unwrap_or_else(|| box Type::any(span, Default::default())),
+                                        metadata: Default::default(),
+                                    }),
+                                    InferTypeOpts {
+                                        append_type_as_union: true,
+                                        ..opts
+                                    },
+                                )?;
+                            }
+                        }
+
+                        continue;
+                    }
+
                     (TypeElement::Method(p), TypeElement::Method(a)) => {
                         if self.assign(span, &mut Default::default(), &p.key.ty(), &a.key.ty()).is_ok() {
-                            self.infer_type_of_fn_params(span, inferred, &p.params, &a.params, opts)?;
+                            self.infer_type_of_fn_params(
+                                span,
+                                inferred,
+                                &p.params,
+                                &a.params,
+                                InferTypeOpts {
+                                    contravariant: !opts.contravariant,
+                                    ..opts
+                                },
+                            )?;
 
                             if let Some(p_ret) = &p.ret_ty {
                                 if let Some(a_ret) = &a.ret_ty {
@@ -607,7 +704,16 @@ impl Analyzer<'_, '_> {
                     }
 
                     (TypeElement::Constructor(p), TypeElement::Constructor(a)) => {
-                        self.infer_type_of_fn_params(span, inferred, &p.params, &a.params, opts)?;
+                        self.infer_type_of_fn_params(
+                            span,
+                            inferred,
+                            &p.params,
+                            &a.params,
+                            InferTypeOpts {
+                                contravariant: !opts.contravariant,
+                                ..opts
+                            },
+                        )?;
 
                         if let Some(p_ret) = &p.ret_ty {
                             if let Some(a_ret) = &a.ret_ty {
@@ -619,7 +725,16 @@ impl Analyzer<'_, '_> {
                     }
 
                     (TypeElement::Call(p), TypeElement::Call(a)) => {
-                        self.infer_type_of_fn_params(span, inferred, &p.params, &a.params, opts)?;
+                        self.infer_type_of_fn_params(
+                            span,
+                            inferred,
+                            &p.params,
+                            &a.params,
+                            InferTypeOpts {
+                                contravariant: !opts.contravariant,
+                                ..opts
+                            },
+                        )?;
 
                         if let Some(p_ret) = &p.ret_ty {
                             if let Some(a_ret) = &a.ret_ty {
@@ -654,7 +769,53 @@ impl Analyzer<'_, '_> {
         opts: InferTypeOpts,
     ) -> VResult<()> {
         match param.op {
-            TsTypeOperatorOp::KeyOf => {}
+            TsTypeOperatorOp::KeyOf => {
+                // `keyof T` <= `"a" | "b"` results in `T = { a: {}; b: {} }`, with a
+                // low priority.
+                if let Type::Param(type_param) = param.ty.normalize() {
+                    if arg.iter_union().all(|ty| matches!(ty.normalize(), Type::Lit(..))) {
+                        if let Some(keys) = self.convert_type_to_keys(span, arg)? {
+                            let span = span.with_ctxt(SyntaxContext::empty());
+                            let members = keys
+                                .into_iter()
+                                .map(|key| {
+                                    TypeElement::Property(PropertySignature {
+                                        span,
+                                        accessibility: None,
+                                        readonly: false,
+                                        key,
+                                        optional: false,
+                                        params: vec![],
+                                        type_ann: Some(box Type::TypeLit(TypeLit {
+                                            span,
+                                            members: vec![],
+                                            metadata: Default::default(),
+                                        })),
+                                        type_params: None,
+                                        metadata: Default::default(),
+                                        accessor: Default::default(),
+                                    })
+                                })
+                                .collect();
+
+                            return self.insert_inferred(
+                                span,
+                                inferred,
+                                type_param,
+                                Cow::Owned(Type::TypeLit(TypeLit {
+                                    span,
+                                    members,
+                                    metadata: Default::default(),
+                                })),
+                                InferTypeOpts {
+                                    priority: InferencePriority::LiteralKeyof,
+                                    ..opts
+                                },
+                            );
+                        }
+                    }
+                }
+            }
             TsTypeOperatorOp::Unique => {}
             TsTypeOperatorOp::ReadOnly => return self.infer_type(span, inferred, &param.ty, arg, opts),
         }
@@ -707,7 +868,79 @@ impl Analyzer<'_, '_> {
         Ok(())
     }
 
-    pub(super) fn finalize_inference(&self, inferred: InferData) -> InferTypeResult {
+    /// Uses contravariant candidates if there's no covariant candidate or the
+    /// covariant candidate is not assignable to any of them.
+    ///
+    /// If there are multiple candidates, the one assignable to all others is
+    /// selected. Otherwise, the intersection of candidates is used.
+    pub(super) fn apply_contravariant_inferences(&mut self, span: Span, inferred: &mut InferData) {
+        for (name, candidates) in take(&mut inferred.contra_candidates) {
+            if candidates.is_empty() {
+                continue;
+            }
+
+            if let Some(covariant) = inferred.type_params.get(&name) {
+                // Provided by user.
+                if !inferred.priorities.contains_key(&name) {
+                    continue;
+                }
+
+                let covariant = match covariant {
+                    InferredType::Union(ty) => ty.clone(),
+                    InferredType::Other(types) => Type::union(types.clone()),
+                };
+
+                let prefer_covariant = !covariant.is_never()
+                    && candidates.iter().any(|candidate| {
+                        self.assign_with_opts(
+                            &mut Default::default(),
+                            candidate,
+                            &covariant,
+                            AssignOpts {
+                                span,
+                                ..Default::default()
+                            },
+                        )
+                        .is_ok()
+                    });
+                if prefer_covariant {
+                    continue;
+                }
+            }
+
+            let common_subtype = candidates.iter().find(|&candidate| {
+                candidates.iter().all(|other| {
+                    self.assign_with_opts(
+                        &mut Default::default(),
+                        other,
+                        candidate,
+                        AssignOpts {
+                            span,
+                            ..Default::default()
+                        },
+                    )
+                    .is_ok()
+                })
+            });
+
+            let ty = match common_subtype {
+                Some(ty) => ty.clone(),
+                None => Type::Intersection(Intersection {
+                    span,
+                    types: candidates,
+                    metadata: Default::default(),
+                })
+                .freezed(),
+            };
+
+            inferred.errored.remove(&name);
+            inferred.type_params.insert(name, InferredType::Other(vec![ty]));
+        }
+    }
+
+    pub(super) fn finalize_inference(&mut self, span: Span, mut inferred: InferData) -> InferTypeResult {
+        self.apply_contravariant_inferences(span, &mut inferred);
+
         let mut map = HashMap::default();
 
         for (k, v) in inferred.type_params {
@@ -12,7 +12,7 @@ use stc_ts_generics::{
     expander::InferTypeResult,
     type_param::{finder::TypeParamUsageFinder, remover::TypeParamRemover, renamer::TypeParamRenamer},
 };
-use stc_ts_type_ops::{generalization::prevent_generalize, Fix};
+use stc_ts_type_ops::{generalization::prevent_generalize, tuple_to_array::prevent_tuple_to_array, Fix};
 use stc_ts_types::{
     Array, ClassMember, FnParam, Function, Id, IndexSignature, IndexedAccessType, Intersection, Key, KeywordType, KeywordTypeMetadata,
     LitType, LitTypeMetadata, Mapped, Operator, OptionalType, PropertySignature, Ref, Tuple, TupleElement, TupleMetadata, Type,
@@ -28,9 +28,16 @@ use swc_common::{EqIgnoreSpan, Span, Spanned, SyntaxContext, TypeEq, DUMMY_SP};
 use swc_ecma_ast::*;
 use tracing::{debug, error, info, span, trace, warn, Level};
 
-pub(crate) use self::{expander::ExtendsOpts, inference::InferTypeOpts};
+pub(crate) use self::{
+    expander::ExtendsOpts,
+    inference::{InferTypeOpts, InferencePriority},
+};
 use crate::{
-    analyzer::{assign::AssignOpts, scope::ExpandOpts, Analyzer, Ctx},
+    analyzer::{
+        assign::{tpl::match_tpl_parts, AssignOpts},
+        scope::ExpandOpts,
+        Analyzer, Ctx,
+    },
     ty::TypeExt,
     util::{unwrap_ref_with_single_arg, RemoveTypes},
     VResult,
@@ -56,6 +63,12 @@ pub(super) struct InferData {
 
     errored: FxHashSet<Id>,
 
+    /// Priorities of candidates in `type_params`.
+    priorities: FxHashMap<Id, InferencePriority>,
+
+    /// Candidates inferred from contravariant positions.
+    contra_candidates: FxHashMap<Id, Vec<Type>>,
+
     /// For the code below, we can know that `T` defaults to `unknown` while
     /// inferring type of funcation parametrs. We cannot know the type before
     /// it. So we store the default type while it.
@@ -75,6 +88,10 @@ pub(super) struct InferData {
 impl Analyzer<'_, '_> {
     /// This method accepts Option<&[TypeParamInstantiation]> because user may
     /// provide only some of type arguments.
+    ///
+    /// `ret_ty` is the pair of the declared return type and the contextual
+    /// type of the call. Inferences from it have lower priority than the
+    /// ones from arguments.
     pub(super) fn infer_arg_types(
         &mut self,
         span: Span,
@@ -83,6 +100,7 @@ impl Analyzer<'_, '_> {
         params: &[FnParam],
         args: &[TypeOrSpread],
         default_ty: Option<&Type>,
+        ret_ty: Option<(&Type, &Type)>,
         opts: InferTypeOpts,
     ) -> VResult<InferTypeResult> {
         warn!(
@@ -101,6 +119,32 @@ impl Analyzer<'_, '_> {
                     .type_params
                     .insert(type_param.name.clone(), InferredType::Other(vec![param.clone()]));
             }
+
+            // Omitted type arguments default to the default types instead of being
+            // inferred.
+            for type_param in type_params.iter().skip(base.params.len()) {
+                if let Some(default) = &type_param.default {
+                    let default = self.instantiate_type_param_default(&inferred, default)?;
+                    inferred
+                        .type_params
+                        .insert(type_param.name.clone(), InferredType::Other(vec![default]));
+                }
+            }
+        }
+
+        if let Some((ret_ty, type_ann)) = ret_ty {
+            self.infer_type(
+                span,
+                &mut inferred,
+                ret_ty,
+                type_ann,
+                InferTypeOpts {
+                    skip_union: true,
+                    priority: InferencePriority::ReturnType,
+                    ..opts
+                },
+            )
+            .context("tried to infer type parameters using the return type")?;
         }
 
         // We allocate a new vertor only if required.
@@ -151,7 +195,7 @@ impl Analyzer<'_, '_> {
                 }
             } else {
                 match p.ty.normalize_instance() {
-                    Type::Param(param) => {
+                    Type::Param(..) | Type::Tuple(..) => {
                         self.infer_type(
                             span,
                             &mut inferred,
@@ -192,6 +236,8 @@ impl Analyzer<'_, '_> {
             }
         }
 
+        self.apply_contravariant_inferences(span, &mut inferred);
+
         info!("infer_type is finished:\n{:?}", &inferred.type_params);
 
         // Defaults
@@ -200,6 +246,14 @@ impl Analyzer<'_, '_> {
                 continue;
             }
 
+            // A type parameter without candidates, or omitted from explicit type
+            // arguments, defaults to the default type of it.
+            if let Some(default) = &type_param.default {
+                let default = self.instantiate_type_param_default(&inferred, default)?;
+                self.insert_inferred(span, &mut inferred, type_param, Cow::Owned(default), opts)?;
+                continue;
+            }
+
             if let Some(Type::Param(ref p)) = type_param.constraint.as_deref().map(Type::normalize) {
                 // TODO(kdy1): Handle complex inheritance like
                 //      function foo<A extends B, B extends C>(){ }
@@ -258,11 +312,6 @@ impl Analyzer<'_, '_> {
                 if let Some(default_ty) = inferred.defaults.remove(&type_param.name) {
                     self.insert_inferred(span, &mut inferred, type_param, Cow::Owned(default_ty), opts)?;
                 } else {
-                    if let Some(default) = &type_param.default {
-                        self.insert_inferred(span, &mut inferred, type_param, Cow::Borrowed(default), opts)?;
-                        continue;
-                    }
-
                     if let Some(default_ty) = default_ty {
                         error!("infer: A type parameter {} defaults to {:?}", type_param.name, default_ty);
 
@@ -274,7 +323,7 @@ impl Analyzer<'_, '_> {
 
         self.prevent_generalization_of_inferred_types(type_params, &mut inferred, opts.is_type_ann);
 
-        let map = self.finalize_inference(inferred);
+        let map = self.finalize_inference(span, inferred);
 
         let end = Instant::now();
 
@@ -283,9 +332,27 @@ impl Analyzer<'_, '_> {
         Ok(map)
     }
 
+    /// Instantiates the default of a type parameter, which may reference type
+    /// parameters declared before it, like `<T, U = T[]>`.
+    fn instantiate_type_param_default(&mut self, inferred: &InferData, default: &Type) -> VResult<Type> {
+        let map = inferred
+            .type_params
+            .iter()
+            .map(|(name, ty)| {
+                let ty = match ty.clone() {
+                    InferredType::Union(ty) => ty,
+                    InferredType::Other(types) => Type::union(types),
+                };
+                (name.clone(), ty.freezed())
+            })
+            .collect::<FxHashMap<_, _>>();
+
+        self.expand_type_params(&map, default.clone(), Default::default())
+    }
+
     /// Handles `infer U`.
     #[cfg_attr(debug_assertions, tracing::instrument(skip_all))]
-    pub(super) fn infer_ts_infer_types(
+    pub(crate) fn infer_ts_infer_types(
         &mut self,
         span: Span,
         base: &Type,
@@ -294,7 +361,7 @@ impl Analyzer<'_, '_> {
     ) -> VResult<FxHashMap<Id, Type>> {
         let mut inferred = InferData::default();
         self.infer_type(span, &mut inferred, base, concrete, opts)?;
-        let map = self.finalize_inference(inferred);
+        let map = self.finalize_inference(span, inferred);
 
         Ok(map.types)
     }
@@ -802,13 +869,37 @@ impl Analyzer<'_, '_> {
             // }
             Type::Function(p) => match arg {
                 Type::Function(a) => {
-                    self.infer_type_of_fn_params(span, inferred, &p.params, &a.params, opts)?;
+                    if opts.keep_arg_type_params && !opts.for_fn_assignment && p.type_params.is_none() {
+                        if let Some(arg_type_params) = &a.type_params {
+                            return self.infer_type_using_generic_fn(span, inferred, p, a, arg_type_params, opts);
+                        }
+                    }
+
+                    self.infer_type_of_fn_params(
+                        span,
+                        inferred,
+                        &p.params,
+                        &a.params,
+                        InferTypeOpts {
+                            contravariant: !opts.contravariant,
+                            ..opts
+                        },
+                    )?;
                     self.infer_type(span, inferred, &p.ret_ty, &a.ret_ty, InferTypeOpts { ..opts })?;
 
                     if !opts.for_fn_assignment {
                         if let Some(arg_type_params) = &a.type_params {
                             let mut data = InferData::default();
-                            self.infer_type_of_fn_params(span, &mut data, &a.params, &p.params, InferTypeOpts { ..opts })?;
+                            self.infer_type_of_fn_params(
+                                span,
+                                &mut data,
+                                &a.params,
+                                &p.params,
+                                InferTypeOpts {
+                                    contravariant: false,
+                                    ..opts
+                                },
+                            )?;
 
                             for name in data.errored {
                                 if !inferred.type_params.contains_key(&name) {
@@ -887,7 +978,7 @@ impl Analyzer<'_, '_> {
                     }
                 }
 
-                Type::Interface(..) | Type::Enum(..) | Type::Alias(..) => {
+                Type::Interface(..) | Type::Enum(..) | Type::Alias(..) | Type::Class(..) => {
                     if let Some(arg) = self.convert_type_to_type_lit(span, Cow::Borrowed(arg))? {
                         return self.infer_type_using_type_lit_and_type_lit(span, inferred, param, &arg, opts);
                     }
@@ -1005,6 +1096,28 @@ impl Analyzer<'_, '_> {
                 }
             }
 
+            Type::Tpl(param) => {
+                if let Type::Lit(LitType { lit: RTsLit::Str(s), .. }) = arg {
+                    // Placeholders are inferred from the matched parts of the string.
+                    if let Some(parts) = match_tpl_parts(param, &s.value) {
+                        for (ty, part) in param.types.iter().zip(parts) {
+                            let part = Type::Lit(LitType {
+                                span: s.span,
+                                lit: RTsLit::Str(RStr {
+                                    span: s.span,
+                                    value: part.into(),
+                                    raw: None,
+                                }),
+                                metadata: Default::default(),
+                            });
+                            self.infer_type(span, inferred, ty, &part, opts)?;
+                        }
+                    }
+
+                    return Ok(());
+                }
+            }
+
             Type::Alias(param) => {
                 self.infer_type(span, inferred, &param.ty, arg, opts)?;
                 if let Some(type_params) = &param.type_params {
@@ -1449,8 +1562,11 @@ impl Analyzer<'_, '_> {
                                             },
                                         }));
                                     }
+                                    Key::Computed(key) if matches!(key.ty.normalize(), Type::Lit(..)) => {
+                                        key_types.push(*key.ty.clone());
+                                    }
                                     _ => {
-                                        unimplemented!("Inference of keys except ident in mapped type.\nKey: {:?}", key)
+                                        error!("unimplemented: inference of a key in mapped type: {:?}", key);
                                     }
                                 }
                             }
@@ -1468,6 +1584,8 @@ impl Analyzer<'_, '_> {
 
                                             let mut data = InferData::default();
                                             self.infer_type(span, &mut data, &param_ty, arg_prop_ty, opts)?;
+                                            // `T[P]` may be used as a parameter, like `(v: T[P]) => void`.
+                                            self.apply_contravariant_inferences(span, &mut data);
                                             let inferred_ty = data.type_params.remove(&name).map(|ty| match ty {
                                                 InferredType::Union(ty) => ty,
                                                 InferredType::Other(types) => Type::union(types).freezed(),
@@ -1539,7 +1657,7 @@ impl Analyzer<'_, '_> {
                                         let mut data = InferData::default();
                                         self.infer_type(span, &mut data, &param_ty, &arg_prop_ty, opts)?;
                                         let mut defaults = take(&mut data.defaults);
-                                        let mut map = self.finalize_inference(data);
+                                        let mut map = self.finalize_inference(span, data);
                                         let inferred_ty = map.types.remove(&name);
 
                                         self.mapped_type_param_name = old;
@@ -1607,7 +1725,7 @@ impl Analyzer<'_, '_> {
 
                             let mut data = InferData::default();
                             self.infer_type(span, &mut data, param_ty, &arg.elem_type, opts)?;
-                            let mut map = self.finalize_inference(data);
+                            let mut map = self.finalize_inference(span, data);
                             let mut inferred_ty = map.types.remove(&name);
 
                             self.mapped_type_param_name = old;
@@ -1959,6 +2077,51 @@ impl Analyzer<'_, '_> {
             }
         }
 
+        {
+            // The keys do not depend on type parameters, like
+            //
+            // declare function values<T>(r: Record<string, T>): T;
+            //
+            // so the template type is inferred from types of all matching members.
+            if let (Some(constraint), Some(param_ty)) = (&param.type_param.constraint, &param.ty) {
+                let mut usage_visitor = TypeParamUsageFinder::default();
+                constraint.visit_with(&mut usage_visitor);
+
+                if usage_visitor.params.is_empty() {
+                    if let Some(arg) = self.convert_type_to_type_lit(span, Cow::Borrowed(arg))?.map(Cow::into_owned) {
+                        let opts = InferTypeOpts {
+                            append_type_as_union: true,
+                            ..opts
+                        };
+
+                        for member in &arg.members {
+                            let matches = match member {
+                                TypeElement::Index(i) => {
+                                    i.params.len() == 1 && self.assign(span, &mut Default::default(), constraint, &i.params[0].ty).is_ok()
+                                }
+                                _ => match member.key() {
+                                    Some(key) => {
+                                        constraint.is_kwd(TsKeywordTypeKind::TsStringKeyword)
+                                            || self.assign(span, &mut Default::default(), constraint, &key.ty()).is_ok()
+                                    }
+                                    None => false,
+                                },
+                            };
+                            if !matches {
+                                continue;
+                            }
+
+                            if let Some(member_ty) = member_type(member) {
+                                self.infer_type(span, inferred, param_ty, &member_ty, opts)?;
+                            }
+                        }
+
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+
         Ok(false)
     }
 
@@ -1970,6 +2133,35 @@ impl Analyzer<'_, '_> {
         arg: &Tuple,
         opts: InferTypeOpts,
     ) -> VResult<()> {
+        // Handle variadic elements like `[A, ...T, B]`.
+        if let Some(rest_idx) = param.elems.iter().position(|elem| elem.ty.is_rest()) {
+            let suffix_len = param.elems.len() - rest_idx - 1;
+
+            if arg.elems.len() >= rest_idx + suffix_len && !arg.elems[..rest_idx].iter().any(|elem| elem.ty.is_rest()) {
+                for (param, arg) in param.elems[..rest_idx].iter().zip(&arg.elems[..rest_idx]) {
+                    self.infer_type(span, inferred, &param.ty, &arg.ty, opts)?;
+                }
+
+                let middle_end = arg.elems.len() - suffix_len;
+                for (param, arg) in param.elems[rest_idx + 1..].iter().zip(&arg.elems[middle_end..]) {
+                    self.infer_type(span, inferred, &param.ty, &arg.ty, opts)?;
+                }
+
+                let mut middle = Type::Tuple(Tuple {
+                    span: arg.span,
+                    elems: arg.elems[rest_idx..middle_end].to_vec(),
+                    metadata: arg.metadata,
+                });
+                prevent_tuple_to_array(&mut middle);
+
+                let rest_ty = match param.elems[rest_idx].ty.normalize() {
+                    Type::Rest(rest) => &rest.ty,
+                    _ => unreachable!(),
+                };
+                return self.infer_type(span, inferred, rest_ty, &middle, opts);
+            }
+        }
+
         for item in param
             .elems
             .iter()
@@ -2034,6 +2226,98 @@ impl Analyzer<'_, '_> {
         Ok(())
     }
 
+    /// Infers type parameters from a generic function argument without
+    /// instantiating it, if the argument is the only source of inferences for
+    /// them. The type parameters of the argument are then added to the return
+    /// type by [Analyzer::add_required_type_params].
+    ///
+    /// Otherwise the argument is instantiated in the context of the parameter,
+    /// using the inferences made so far.
+    fn infer_type_using_generic_fn(
+        &mut self,
+        span: Span,
+        inferred: &mut InferData,
+        p: &Function,
+        a: &Function,
+        arg_type_params: &TypeParamDecl,
+        opts: InferTypeOpts,
+    ) -> VResult<()> {
+        let mut data = InferData::default();
+        self.infer_type_of_fn_params(
+            span,
+            &mut data,
+            &p.params,
+            &a.params,
+            InferTypeOpts {
+                contravariant: !opts.contravariant,
+                ..opts
+            },
+        )?;
+        self.infer_type(span, &mut data, &p.ret_ty, &a.ret_ty, opts)?;
+
+        let overlaps = data
+            .type_params
+            .keys()
+            .chain(data.contra_candidates.keys())
+            .any(|name| inferred.type_params.contains_key(name) || inferred.contra_candidates.contains_key(name));
+
+        if !overlaps && data.errored.is_empty() {
+            inferred.type_params.extend(data.type_params);
+            inferred.priorities.extend(data.priorities);
+            inferred.contra_candidates.extend(data.contra_candidates);
+            for (name, ty) in data.defaults {
+                inferred.defaults.entry(name).or_insert(ty);
+            }
+            return Ok(());
+        }
+
+        let fixed = inferred
+            .type_params
+            .iter()
+            .map(|(name, ty)| {
+                let ty = match ty.clone() {
+                    InferredType::Union(ty) => ty,
+                    InferredType::Other(types) => Type::union(types),
+                };
+                (name.clone(), ty.freezed())
+            })
+            .collect::<FxHashMap<_, _>>();
+        let p_params = match self
+            .expand_type_params(&fixed, Type::Function(p.clone()), Default::default())?
+            .foldable()
+        {
+            Type::Function(f) => f.params,
+            _ => unreachable!(),
+        };
+
+        let mut data = InferData::default();
+        self.infer_type_of_fn_params(
+            span,
+            &mut data,
+            &a.params,
+            &p_params,
+            InferTypeOpts {
+                contravariant: false,
+                keep_arg_type_params: false,
+                ..opts
+            },
+        )?;
+        data.type_params
+            .retain(|name, _| arg_type_params.params.iter().any(|param| param.name == *name));
+        let map = self.finalize_inference(span, data);
+
+        let a = self.expand_type_params(
+            &map.types,
+            Type::Function(Function {
+                type_params: None,
+                ..a.clone()
+            }),
+            Default::default(),
+        )?;
+
+        self.infer_type(span, inferred, &Type::Function(p.clone()), &a, opts)
+    }
+
     fn rename_inferred(&mut self, inferred: &mut InferData, arg_type_params: &TypeParamDecl) -> VResult<()> {
         info!("rename_inferred");
         struct Renamer<'a> {
@@ -2131,7 +2415,7 @@ impl Analyzer<'_, '_> {
                 type_ann
             );
 
-            let map = self.finalize_inference(inferred);
+            let map = self.finalize_inference(span, inferred);
 
             // TODO(kdy1): PERF
             return Ok(ty
@@ -2430,6 +2714,22 @@ fn handle_optional_for_element(element_ty: &mut Type, optional: Option<TruePlusM
     }
 }
 
+/// Returns the type of a property, a method or an index signature.
+fn member_type(member: &TypeElement) -> Option<Type> {
+    match member {
+        TypeElement::Property(p) => p.type_ann.as_deref().cloned(),
+        TypeElement::Method(m) => Some(Type::Function(Function {
+            span: m.span,
+            type_params: m.type_params.clone(),
+            params: m.params.clone(),
+            ret_ty: m.ret_ty.clone().unwrap_or_else(|| box Type::any(m.span, Default::default())),
+            metadata: Default::default(),
+        })),
+        TypeElement::Index(i) => i.type_ann.as_deref().cloned(),
+        _ => None,
+    }
+}
+
 fn is_ok_to_append(prev: &[Type], arg: &Type) -> bool {
     for p in prev {
         if p.is_num_lit() && arg.is_num_lit() {
@@ -1,10 +1,14 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
 use fxhash::{FxHashMap, FxHashSet};
 use rnode::{Visit, VisitWith};
 use stc_ts_ast_rnode::{RDecl, RIdent, RModuleDecl, RStmt};
+use stc_ts_errors::ErrorKind;
 use stc_ts_ordering::{calc_eval_order, stmt::TypedId, types::Sortable};
 use stc_ts_types::Id;
 use stc_ts_utils::{AsModuleDecl, HasNodeId};
 use stc_utils::dedup;
+use swc_common::Spanned;
 
 use crate::{analyzer::Analyzer, util::ModuleItemOrStmt};
 
@@ -15,7 +19,7 @@ impl Analyzer<'_, '_> {
     #[allow(clippy::ptr_arg)]
     pub(super) fn validate_stmts_with_hoisting<T>(&mut self, stmts: &Vec<&T>)
     where
-        T: AsModuleDecl + ModuleItemOrStmt + VisitWith<Self> + From<RStmt> + HasNodeId + Sortable<Id = TypedId>,
+        T: AsModuleDecl + ModuleItemOrStmt + VisitWith<Self> + From<RStmt> + HasNodeId + Sortable<Id = TypedId> + Spanned,
     {
         let (mut order, skip) = self.reorder_stmts(stmts);
         let mut type_decls = FxHashMap::<Id, Vec<usize>>::with_capacity_and_hasher(order.len(), Default::default());
@@ -43,7 +47,11 @@ impl Analyzer<'_, '_> {
                 let type_decl_id = type_decl_id(stmts[idx]);
 
                 let node_id = stmts[idx].node_id();
-                stmts[idx].visit_with(self);
+                if self.scope.is_root() {
+                    self.validate_stmt_with_recovery(stmts[idx]);
+                } else {
+                    stmts[idx].visit_with(self);
+                }
 
                 if self.scope.is_root() {
                     let prepended = self.prepend_stmts.drain(..);
@@ -61,6 +69,42 @@ impl Analyzer<'_, '_> {
         }
     }
 
+    /// Validates `stmt`, reporting [ErrorKind::InternalError] instead of
+    /// aborting the whole check if the analyzer panics.
+    ///
+    /// This is used only for top-level statements, as states are saved for
+    /// each call. States of the statement are rolled back on panic, and
+    /// states of child scopes are restored by
+    /// [Analyzer::with_child_with_hook]. The panic hook still prints the panic
+    /// to stderr.
+    fn validate_stmt_with_recovery<T>(&mut self, stmt: &T)
+    where
+        T: VisitWith<Self> + Spanned,
+    {
+        let scope = self.scope.snapshot();
+        let cur_facts = self.cur_facts.clone();
+        let flow = self.data.flow.checkpoint();
+
+        let payload = match catch_unwind(AssertUnwindSafe(|| stmt.visit_with(self))) {
+            Ok(()) => return,
+            Err(payload) => payload,
+        };
+
+        self.scope.restore(scope);
+        self.cur_facts = cur_facts;
+        self.data.flow.rollback(flow);
+
+        let msg = match payload.downcast::<String>() {
+            Ok(msg) => *msg,
+            Err(payload) => match payload.downcast::<&'static str>() {
+                Ok(msg) => msg.to_string(),
+                Err(_) => "unknown panic".into(),
+            },
+        };
+
+        self.storage.report(ErrorKind::InternalError { span: stmt.span(), msg }.into());
+    }
+
     /// A special method is require code like
     ///
     /// ```ts
@@ -73,7 +117,7 @@ impl Analyzer<'_, '_> {
     /// ```
     pub(super) fn validate_stmts_and_collect<T>(&mut self, stmts: &Vec<&T>)
     where
-        T: AsModuleDecl + ModuleItemOrStmt + VisitWith<Self> + From<RStmt> + HasNodeId + Sortable<Id = TypedId>,
+        T: AsModuleDecl + ModuleItemOrStmt + VisitWith<Self> + From<RStmt> + HasNodeId + Sortable<Id = TypedId> + Spanned,
     {
         self.validate_stmts_with_hoisting(stmts);
     }
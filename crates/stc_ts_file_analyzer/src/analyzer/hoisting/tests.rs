@@ -1,3 +1,5 @@
+use stc_ts_errors::ErrorKind;
+
 use crate::analyzer::tests::run_test;
 
 #[track_caller]
@@ -210,3 +212,35 @@ fn type_alias_01() {
         vec![1, 0],
     );
 }
+
+#[test]
+fn recovers_from_panic() {
+    run_test(|tester| {
+        // Class static blocks are not implemented yet, and the analyzer panics while
+        // `a` is being declared.
+        let module = tester.parse(
+            "main.ts",
+            "
+        const z = 1;
+
+        const a = class {
+            static {}
+        };
+
+        const b: string = 1;
+        ",
+        );
+        tester
+            .analyzer
+            .validate_stmts_with_hoisting(&module.body.iter().collect::<Vec<_>>());
+
+        assert!(tester.analyzer.scope.declaring.is_empty());
+        // States taken by the scope of the class are restored.
+        assert!(tester.analyzer.data.var_spans.keys().any(|id| *id.sym() == *"z"));
+
+        let errors = ErrorKind::flatten(tester.analyzer.storage.take_errors().into());
+        assert!(errors.iter().any(|err| matches!(**err, ErrorKind::InternalError { .. })));
+        assert!(errors.iter().any(|err| ErrorKind::normalize_error_code(err.code()) == 2322));
+    })
+    .unwrap();
+}
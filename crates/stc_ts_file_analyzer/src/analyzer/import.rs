@@ -1,18 +1,21 @@
 use rayon::prelude::*;
 use rnode::{Visit, VisitWith};
 use stc_ts_ast_rnode::{
-    RCallExpr, RCallee, RExportAll, RExpr, RImportDecl, RImportSpecifier, RLit, RModuleItem, RNamedExport, RStr, RTsExternalModuleRef,
+    RCallExpr, RCallee, RExportAll, RExpr, RImportDecl, RImportSpecifier, RJSXElement, RJSXFragment, RLit, RModuleItem, RNamedExport, RStr,
+    RTsExternalModuleRef,
 };
+use stc_ts_builtin_types::Lib;
 use stc_ts_errors::ErrorKind;
 use stc_ts_file_analyzer_macros::extra_validator;
 use stc_ts_storage::Storage;
 use stc_ts_types::{Id, ModuleId, Type};
-use stc_ts_utils::imports::find_imports_in_comments;
+use stc_ts_utils::imports::{find_imports_in_comments, ImportRef};
 use swc_atoms::{js_word, JsWord};
 use swc_common::{comments::Comments, Span, Spanned};
 
 use crate::{
-    analyzer::{scope::VarKind, util::ResultExt, Analyzer},
+    analyzer::{export::EXPORT_EQUALS, scope::VarKind, util::ResultExt, Analyzer},
+    env::load_builtin,
     loader::ModuleInfo,
     validator, DepInfo, VResult,
 };
@@ -29,7 +32,7 @@ impl Analyzer<'_, '_> {
         let dep_id = match dep_id {
             Some(v) => v,
             None => {
-                self.storage.report(ErrorKind::ModuleNotFound { span }.into());
+                self.report_module_not_found(span, dst);
 
                 return (ctxt, Type::any(span, Default::default()));
             }
@@ -46,6 +49,19 @@ impl Analyzer<'_, '_> {
         (dep_id, data)
     }
 
+    fn report_module_not_found(&mut self, span: Span, src: &JsWord) {
+        let err = match self.loader.path_mapping_of(src) {
+            Some(pattern) => ErrorKind::UnresolvedPathMapping {
+                span,
+                src: src.clone(),
+                pattern,
+            },
+            None => ErrorKind::ModuleNotFound { span },
+        };
+
+        self.storage.report(err.into());
+    }
+
     pub(super) fn find_imported_var(&self, id: &Id) -> VResult<Option<Type>> {
         if let Some(ModuleInfo { module_id, data }) = self.imports_by_id.get(id) {
             match data.normalize() {
@@ -65,19 +81,68 @@ impl Analyzer<'_, '_> {
         Ok(None)
     }
 
+    /// Declares `id` using the value and the types assigned with `export =`.
+    pub(super) fn declare_export_equals(
+        &mut self,
+        span: Span,
+        id: Id,
+        is_export: bool,
+        (var, types): (Option<Type>, Vec<Type>),
+    ) -> VResult<()> {
+        let module_id = self.ctx.module_id;
+
+        for ty in types {
+            self.register_type(id.clone(), ty.clone());
+            if is_export {
+                self.storage.reexport_type(span, module_id, id.sym().clone(), ty);
+            }
+        }
+
+        if let Some(var) = var {
+            self.declare_var(span, VarKind::Import, id.clone(), Some(var.clone()), None, true, false, false)?;
+            if is_export {
+                self.storage.reexport_var(span, module_id, id.sym().clone(), var);
+            }
+        }
+
+        Ok(())
+    }
+
     fn insert_import_info(&mut self, ctxt: ModuleId, dep_module_id: ModuleId, ty: Type) -> VResult<()> {
         self.imports.entry((ctxt, dep_module_id)).or_insert(ty);
 
         Ok(())
     }
 
+    /// Handles `/// <reference lib="..." />`.
+    fn load_lib_references(&mut self, libs: Vec<(Span, JsWord)>) {
+        for (span, name) in libs {
+            let libs = Lib::load(&name);
+            if libs.is_empty() {
+                self.storage.report(ErrorKind::NoSuchLib { span, name }.into());
+                continue;
+            }
+
+            let builtin = load_builtin(self.env.shared(), &libs);
+            self.env.declare_lib(name, &builtin);
+        }
+    }
+
     #[extra_validator]
     pub(super) fn load_normal_imports(&mut self, module_spans: Vec<(ModuleId, Span)>, items: &Vec<&RModuleItem>) {
         if self.is_builtin {
             return;
         }
+        let jsx_runtime = if self.env.jsx().is_automatic() {
+            Some(self.env.jsx().runtime_module())
+        } else {
+            None
+        };
+
         // We first load non-circular imports.
-        let imports = ImportFinder::find_imports(&self.comments, module_spans, &self.storage, items);
+        let (imports, libs) = ImportFinder::find_imports(&self.comments, module_spans, &self.storage, jsx_runtime.clone(), items);
+
+        self.load_lib_references(libs);
 
         let loader = self.loader;
         let mut normal_imports = vec![];
@@ -89,7 +154,17 @@ impl Analyzer<'_, '_> {
             let dep_id = match dep_id {
                 Some(v) => v,
                 None => {
-                    self.storage.report(ErrorKind::ModuleNotFound { span }.into());
+                    if jsx_runtime.as_ref() == Some(&import.src) {
+                        self.storage.report(
+                            ErrorKind::JsxRuntimeNotFound {
+                                span,
+                                src: import.src.clone(),
+                            }
+                            .into(),
+                        );
+                    } else {
+                        self.report_module_not_found(span, &import.src);
+                    }
                     continue;
                 }
             };
@@ -126,6 +201,25 @@ impl Analyzer<'_, '_> {
     }
 }
 
+/// Returns the value and the types assigned with `export =` in the module
+/// `data`, if any.
+pub(super) fn find_export_equals(data: &Type) -> Option<(Option<Type>, Vec<Type>)> {
+    let name = JsWord::from(EXPORT_EQUALS);
+
+    match data.normalize() {
+        Type::Module(m) => {
+            let var = m.exports.vars.get(&name).cloned();
+            let types = m.exports.types.get(&name).cloned().unwrap_or_default();
+            if var.is_none() && types.is_empty() {
+                return None;
+            }
+
+            Some((var, types))
+        }
+        _ => None,
+    }
+}
+
 impl Analyzer<'_, '_> {
     fn handle_import(&mut self, span: Span, ctxt: ModuleId, target: ModuleId, orig: Id, id: Id) {
         let mut found_entry = false;
@@ -134,6 +228,13 @@ impl Analyzer<'_, '_> {
         if ctxt != target {
             if let Some(data) = self.imports.get(&(ctxt, target)) {
                 match data.normalize() {
+                    // Everything imported from `declare module "foo";` is `any`.
+                    _ if data.is_any() => {
+                        found_entry = true;
+                        self.storage.store_private_var(ctxt, id.clone(), data.clone());
+                        self.storage.store_private_type(ctxt, id.clone(), data.clone(), false);
+                    }
+
                     Type::Module(data) => {
                         for (i, ty) in &data.exports.vars {
                             if orig.sym() == i {
@@ -190,8 +291,18 @@ impl Analyzer<'_, '_> {
         let base = self.ctx.module_id;
 
         let (dep, data) = self.get_imported_items(span, &node.src.value);
+        let export_equals = if base == dep { None } else { find_export_equals(&data) };
 
         for specifier in &node.specifiers {
+            let (local, is_type_only) = match specifier {
+                RImportSpecifier::Named(named) => (&named.local, node.type_only || named.is_type_only),
+                RImportSpecifier::Default(default) => (&default.local, node.type_only),
+                RImportSpecifier::Namespace(ns) => (&ns.local, node.type_only),
+            };
+            if is_type_only {
+                self.data.type_only_imports.insert(Id::from(local));
+            }
+
             match specifier {
                 RImportSpecifier::Named(named) => {
                     //
@@ -205,10 +316,21 @@ impl Analyzer<'_, '_> {
                     }
                 }
                 RImportSpecifier::Default(default) => {
-                    self.handle_import(default.span, base, dep, Id::word(js_word!("default")), Id::from(&default.local));
+                    if export_equals.is_some() {
+                        if !self.rule().allow_synthetic_default_imports {
+                            self.storage
+                                .report(ErrorKind::DefaultImportOfExportEqualsWithoutInterop { span: default.span }.into());
+                        }
+
+                        self.handle_import(default.span, base, dep, Id::word(EXPORT_EQUALS.into()), Id::from(&default.local));
+                    } else {
+                        self.handle_import(default.span, base, dep, Id::word(js_word!("default")), Id::from(&default.local));
+                    }
                 }
                 RImportSpecifier::Namespace(ns) => {
-                    if base == dep {
+                    if let Some(export_equals) = export_equals.clone() {
+                        self.declare_export_equals(ns.span, ns.local.clone().into(), false, export_equals)?;
+                    } else if base == dep {
                         // Import failed
                         self.declare_var(
                             ns.span,
@@ -247,6 +369,10 @@ where
     storage: &'a Storage<'a>,
     cur_ctxt: ModuleId,
     to: Vec<(ModuleId, DepInfo)>,
+    /// `/// <reference lib="..." />`
+    libs: Vec<(Span, JsWord)>,
+    /// Imported implicitly by files using JSX.
+    jsx_runtime: Option<JsWord>,
     comments: C,
 }
 
@@ -262,11 +388,36 @@ where
         let ctxt = self.cur_ctxt;
         let deps = find_imports_in_comments(&self.comments, span);
 
-        self.to
-            .extend(deps.into_iter().map(|src| (ctxt, DepInfo { span, src: src.to_path() })));
+        for dep in deps {
+            match dep {
+                ImportRef::Lib(name) => self.libs.push((span, name)),
+                _ => self.to.extend(dep.to_path().map(|src| (ctxt, DepInfo { span, src }))),
+            }
+        }
     }
 
-    pub fn find_imports<T>(comments: C, module_span: Vec<(ModuleId, Span)>, storage: &'a Storage<'a>, node: &T) -> Vec<(ModuleId, DepInfo)>
+    fn add_jsx_runtime(&mut self, span: Span) {
+        let src = match &self.jsx_runtime {
+            Some(src) => src.clone(),
+            None => return,
+        };
+        let ctxt = self.cur_ctxt;
+
+        if self.to.iter().any(|(c, dep)| *c == ctxt && dep.src == src) {
+            return;
+        }
+
+        self.to.push((ctxt, DepInfo { span, src }));
+    }
+
+    /// Returns `(imports, lib_references)`.
+    pub fn find_imports<T>(
+        comments: C,
+        module_span: Vec<(ModuleId, Span)>,
+        storage: &'a Storage<'a>,
+        jsx_runtime: Option<JsWord>,
+        node: &T,
+    ) -> (Vec<(ModuleId, DepInfo)>, Vec<(Span, JsWord)>)
     where
         T: for<'any> VisitWith<ImportFinder<'any, C>>,
     {
@@ -274,6 +425,8 @@ where
             comments,
             storage,
             to: Default::default(),
+            libs: Default::default(),
+            jsx_runtime,
             cur_ctxt: ModuleId::builtin(),
         };
 
@@ -286,7 +439,7 @@ where
 
         node.visit_with(&mut v);
 
-        v.to
+        (v.to, v.libs)
     }
 }
 
@@ -400,3 +553,25 @@ where
         ));
     }
 }
+
+impl<C> Visit<RJSXElement> for ImportFinder<'_, C>
+where
+    C: Comments,
+{
+    fn visit(&mut self, e: &RJSXElement) {
+        e.visit_children_with(self);
+
+        self.add_jsx_runtime(e.span);
+    }
+}
+
+impl<C> Visit<RJSXFragment> for ImportFinder<'_, C>
+where
+    C: Comments,
+{
+    fn visit(&mut self, e: &RJSXFragment) {
+        e.visit_children_with(self);
+
+        self.add_jsx_runtime(e.span);
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     fmt::Debug,
     mem::take,
     ops::{Deref, DerefMut},
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
     sync::Arc,
 };
 
@@ -13,11 +14,11 @@ use stc_ts_ast_rnode::{
 };
 use stc_ts_base_type_ops::bindings::Bindings;
 use stc_ts_dts_mutations::Mutations;
-use stc_ts_env::{Env, Marks, ModuleConfig, Rule, StableEnv};
+use stc_ts_env::{Env, Marks, ModuleConfig, ModuleDetection, Rule, StableEnv};
 use stc_ts_errors::{debug::debugger::Debugger, DebugExt, ErrorKind};
 use stc_ts_storage::{Builtin, Info, Storage};
 use stc_ts_type_cache::TypeCache;
-use stc_ts_types::{Id, IdCtx, ModuleId, ModuleTypeData, Namespace};
+use stc_ts_types::{name::Name, Id, IdCtx, ModuleId, ModuleTypeData, Namespace};
 use stc_ts_utils::StcComments;
 use stc_utils::{cache::Freeze, panic_ctx, AHashMap, AHashSet};
 use swc_atoms::{js_word, JsWord};
@@ -25,7 +26,10 @@ use swc_common::{FileName, SourceMap, Span, Spanned, DUMMY_SP, GLOBALS};
 use swc_ecma_ast::*;
 
 use self::{
+    assign::cache::RelationCache,
     control_flow::{CondFacts, Facts},
+    flow::FlowGraph,
+    import::find_export_equals,
     pat::PatMode,
     props::ComputedPropMode,
     scope::{Scope, VarKind},
@@ -36,6 +40,7 @@ use crate::{
     loader::{Load, ModuleInfo},
     ty,
     ty::Type,
+    util::contains_import_meta,
     validator,
     validator::ValidateWith,
     VResult,
@@ -58,6 +63,7 @@ mod decl_merging;
 mod enums;
 mod export;
 mod expr;
+mod flow;
 mod function;
 mod generalize;
 mod generic;
@@ -67,6 +73,7 @@ mod pat;
 mod props;
 mod scope;
 mod stmt;
+mod suggest;
 #[cfg(test)]
 mod tests;
 mod types;
@@ -197,12 +204,19 @@ pub(crate) struct Ctx {
     /// Should be modified directly instead of using `with_ctx`.
     in_unreachable: bool,
 
+    /// `true` for the operand of `a!`, which is not checked for definite
+    /// assignment.
+    in_non_null_expr: bool,
+
     /// `true` for top-level type annotations.
     is_not_topmost_type: bool,
 
     is_fn_param: bool,
 
     in_module: bool,
+    /// `true` if the file is a module according to `moduleDetection`, so that
+    /// top-level declarations are not properties of `globalThis`.
+    is_external_module: bool,
 
     checking_switch_discriminant_as_bin: bool,
 }
@@ -277,6 +291,9 @@ struct AnalyzerData {
 
     unresolved_imports: AHashSet<Id>,
 
+    /// Bindings imported using `import type`.
+    type_only_imports: AHashSet<Id>,
+
     /// Spans of declared variables.
     var_spans: AHashMap<Id, Vec<(VarKind, Span)>>,
 
@@ -295,13 +312,37 @@ struct AnalyzerData {
 
     cache: TypeCache,
 
+    relation_cache: RelationCache,
+
     checked_for_async_iterator: bool,
+
+    flow: FlowGraph,
+
+    /// Facts created by conditions stored in `const` variables, like `ok` in
+    /// `const ok = x !== undefined`.
+    aliased_conds: FxHashMap<Id, Facts>,
+
+    /// Properties destructured into `const` variables, like `action.kind` for
+    /// `kind` in `const { kind } = action`.
+    destructured_props: FxHashMap<Id, Name>,
+
+    /// Variables assigned anywhere in the modules. Filled only once, by
+    /// `fill_known_type_names`.
+    assigned_vars: FxHashSet<Id>,
+
+    /// Spans of declarations of `let` and `const` variables, which cannot be
+    /// referenced before the declaration.
+    block_scoped_decls: FxHashMap<Id, Span>,
 }
 
 #[derive(Debug, Default)]
 struct PerModuleData {
     /// Spans exported items.
     exports_spans: FxHashMap<(JsWord, IdCtx), Vec<Span>>,
+
+    /// `(module, span, dep)` of `export * from 'dep'`, which are handled after
+    /// other exports because local exports take precedence.
+    star_exports: Vec<(ModuleId, Span, ModuleId)>,
 }
 
 /// TODO
@@ -530,9 +571,11 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
                 allow_new_target: false,
                 disallow_suggesting_property_on_no_var: false,
                 in_unreachable: false,
+                in_non_null_expr: false,
                 is_not_topmost_type: false,
                 is_fn_param: false,
                 in_module: false,
+                is_external_module: false,
                 checking_switch_discriminant_as_bin: false,
             },
             loader,
@@ -590,7 +633,8 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
         } else {
             Default::default()
         };
-        let data = take(&mut self.data);
+        let mut data = take(&mut self.data);
+        let parent_flow = if kind.is_flow_container() { Some(data.flow.start()) } else { None };
 
         let child_scope = Scope::new(&self.scope, kind, facts);
         let (ret, errors, imports, imports_by_id, cur_facts, mut child_scope, prepend_stmts, append_stmts, mutations, data) = {
@@ -601,7 +645,9 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
             child.cur_facts = cur_facts;
             child.ctx = ctx;
 
-            let ret = op(&mut child);
+            // States taken from `self` are restored below even if `op` panics, so that
+            // [Analyzer::validate_stmt_with_recovery] can continue with the module.
+            let ret = catch_unwind(AssertUnwindSafe(|| op(&mut child)));
 
             let errors = if child.ctx.ignore_errors {
                 Default::default()
@@ -629,6 +675,19 @@ impl<'scope, 'b> Analyzer<'scope, 'b> {
         self.cur_facts = cur_facts;
         self.mutations = mutations;
         self.data = data;
+        if let Some(parent_flow) = parent_flow {
+            self.data.flow.restore(parent_flow);
+        }
+
+        let ret = match ret {
+            Ok(ret) => ret,
+            Err(payload) => {
+                if kind == ScopeKind::Module {
+                    self.data.for_module = module_data;
+                }
+                resume_unwind(payload)
+            }
+        };
 
         hook(self);
 
@@ -757,6 +816,8 @@ impl Analyzer<'_, '_> {
 
         self.validate_stmts_with_hoisting(&items);
 
+        self.reexport_star_exports();
+
         Ok(())
     }
 }
@@ -765,6 +826,7 @@ impl Analyzer<'_, '_> {
 impl Analyzer<'_, '_> {
     fn validate(&mut self, m: &RModule) {
         self.ctx.in_module = true;
+        self.ctx.is_external_module = self.is_external_module(m);
         let is_dts = self.ctx.is_dts;
 
         let globals = self.env.shared().swc_globals().clone();
@@ -822,6 +884,8 @@ impl Analyzer<'_, '_> {
                 m.body.visit_children_with(self);
             } else {
                 self.validate_stmts_and_collect(&items_ref);
+
+                self.reexport_star_exports();
             }
 
             Ok(())
@@ -829,6 +893,19 @@ impl Analyzer<'_, '_> {
     }
 }
 
+impl Analyzer<'_, '_> {
+    /// Detects if `m` is a module according to `moduleDetection`.
+    fn is_external_module(&self, m: &RModule) -> bool {
+        let has_module_decl = m.body.iter().any(|item| matches!(item, RModuleItem::ModuleDecl(..)));
+
+        match self.rule().module_detection {
+            ModuleDetection::Legacy => has_module_decl,
+            ModuleDetection::Auto => has_module_decl || contains_import_meta(m),
+            ModuleDetection::Force => has_module_decl || !self.ctx.is_dts,
+        }
+    }
+}
+
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, items: &Vec<RStmt>) {
@@ -864,38 +941,51 @@ impl Analyzer<'_, '_> {
             ..self.ctx
         };
         self.with_ctx(ctx).with(|analyzer: &mut Analyzer| {
+            let mut value = None;
             let ty = match node.module_ref {
-                RTsModuleRef::TsEntityName(ref e) => analyzer
-                    .type_of_ts_entity_name(node.span, &e.clone().into(), None)
-                    .convert_err(|err| match err {
-                        ErrorKind::TypeNotFound {
-                            span,
-                            name,
-                            ctxt,
-                            type_args,
-                        } => ErrorKind::NamspaceNotFound {
-                            span,
-                            name,
-                            ctxt,
-                            type_args,
-                        },
-                        _ => err,
-                    })
-                    .unwrap_or_else(|err| {
-                        analyzer.storage.report(err);
-                        Type::any(node.span, Default::default())
-                    })
-                    .freezed(),
+                RTsModuleRef::TsEntityName(ref e) => {
+                    // `import X = Ns.Inner` aliases both of the value and the type.
+                    value = analyzer.type_of_ts_entity_name_as_value(node.span, e).ok().map(Type::freezed);
+
+                    analyzer
+                        .type_of_ts_entity_name(node.span, &e.clone().into(), None)
+                        .convert_err(|err| match err {
+                            ErrorKind::TypeNotFound {
+                                span,
+                                name,
+                                ctxt,
+                                type_args,
+                            } => ErrorKind::NamspaceNotFound {
+                                span,
+                                name,
+                                ctxt,
+                                type_args,
+                            },
+                            _ => err,
+                        })
+                        .or_else(|err| value.clone().ok_or(err))
+                        .unwrap_or_else(|err| {
+                            analyzer.storage.report(err);
+                            Type::any(node.span, Default::default())
+                        })
+                        .freezed()
+                }
                 RTsModuleRef::TsExternalModuleRef(ref e) => {
                     let (dep, data) = analyzer.get_imported_items(e.span, &e.expr.value);
 
                     // Import successful
                     if ctxt != dep {
-                        analyzer
+                        let data = analyzer
                             .imports
                             .get(&(ctxt, dep))
                             .cloned()
-                            .unwrap_or_else(|| Type::any(e.span, Default::default()))
+                            .unwrap_or_else(|| Type::any(e.span, Default::default()));
+
+                        if let Some(export_equals) = find_export_equals(&data) {
+                            return analyzer.declare_export_equals(node.span, node.id.clone().into(), node.is_export, export_equals);
+                        }
+
+                        data
                     } else {
                         Type::any(e.span, Default::default())
                     }
@@ -910,6 +1000,27 @@ impl Analyzer<'_, '_> {
                 _ => (false, true),
             };
 
+            // The value of an alias to a type, like a function merged with an interface.
+            let value = value.filter(|value| !is_var && !matches!(value.normalize(), Type::Module(..) | Type::Namespace(..)));
+            if let Some(value) = value {
+                analyzer.declare_var(
+                    node.span,
+                    VarKind::Import,
+                    node.id.clone().into(),
+                    Some(value.clone()),
+                    None,
+                    true,
+                    false,
+                    false,
+                )?;
+
+                if node.is_export {
+                    analyzer
+                        .storage
+                        .reexport_var(node.span, analyzer.ctx.module_id, node.id.sym.clone(), value)
+                }
+            }
+
             if is_type {
                 analyzer.register_type(node.id.clone().into(), ty.clone());
                 if node.is_export {
@@ -1064,7 +1175,12 @@ impl Analyzer<'_, '_> {
                         }
                     }
 
-                    self.loader.declare_module(&s.value, ty.clone());
+                    // Everything imported from `declare module "foo";` is `any`.
+                    if decl.body.is_none() {
+                        self.loader.declare_module(&s.value, Type::any(span, Default::default()));
+                    } else {
+                        self.loader.declare_module(&s.value, ty.clone());
+                    }
                 }
             }
         }
@@ -163,8 +163,6 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RParam) -> VResult<ty::FnParam> {
-        node.decorators.visit_with(self);
-
         self.default_any_pat(&node.pat);
 
         let ctx = Ctx {
@@ -60,6 +60,10 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, n: &RPrivateName) -> VResult<PrivateName> {
+        if !self.is_builtin && self.env.target() < EsVersion::Es2015 {
+            self.storage.report(ErrorKind::PrivateNameNotAvailable { span: n.span }.into());
+        }
+
         Ok(PrivateName {
             span: n.span,
             id: n.id.clone().into(),
@@ -67,6 +71,18 @@ impl Analyzer<'_, '_> {
     }
 }
 
+impl Analyzer<'_, '_> {
+    /// Reports an error for a getter or a setter named `key_span` if the
+    /// target is ES3.
+    pub(crate) fn report_error_for_accessor_below_es5(&mut self, key_span: Span) {
+        if self.is_builtin || self.ctx.in_declare || self.env.target() >= EsVersion::Es5 {
+            return;
+        }
+
+        self.storage.report(ErrorKind::AccessorNotAvailable { span: key_span }.into());
+    }
+}
+
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RComputedPropName) -> VResult<Key> {
@@ -413,6 +429,8 @@ impl Analyzer<'_, '_> {
             RProp::Assign(ref p) => unimplemented!("validate_key(AssignProperty): {:?}", p),
             RProp::Getter(ref p) => p.validate_with(self)?,
             RProp::Setter(ref p) => {
+                self.report_error_for_accessor_below_es5(p.key.span());
+
                 let key = p.key.validate_with(self)?;
                 let computed = matches!(p.key, RPropName::Computed(_));
                 let param_span = p.param.span();
@@ -534,6 +552,8 @@ impl Analyzer<'_, '_> {
     fn validate(&mut self, n: &RGetterProp) -> VResult<TypeElement> {
         self.record(n);
 
+        self.report_error_for_accessor_below_es5(n.key.span());
+
         let key = n.key.validate_with(self)?;
         let computed = key.is_computed();
 
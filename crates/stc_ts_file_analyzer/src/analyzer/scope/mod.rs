@@ -28,7 +28,7 @@ use stc_utils::{
     cache::{Freeze, ALLOW_DEEP_CLONE},
     debug_ctx, panic_ctx, stack,
 };
-use swc_atoms::js_word;
+use swc_atoms::{js_word, JsWord};
 use swc_common::{util::move_map::MoveMap, Span, Spanned, SyntaxContext, TypeEq, DUMMY_SP};
 use swc_ecma_ast::*;
 use tracing::{debug, error, info, instrument};
@@ -39,6 +39,7 @@ use crate::{
         assign::AssignOpts,
         class::ClassState,
         control_flow::CondFacts,
+        decl_merging::remove_merged_namespaces,
         expr::{IdCtx, TypeOfMode},
         generic::InferTypeOpts,
         scope::vars::DeclareVarsOpts,
@@ -122,7 +123,51 @@ pub(crate) struct Scope<'a> {
     pub(super) class: ClassState,
 }
 
+/// States of a [Scope] which are changed only while validating a statement.
+pub(crate) struct ScopeSnapshot {
+    declaring: Vec<Id>,
+    declaring_type_params: FxHashSet<Id>,
+    facts: CondFacts,
+    declaring_fn: Option<Id>,
+    declaring_prop: Option<Id>,
+    this: Option<Type>,
+    this_class_name: Option<Id>,
+    expand_triage_depth: u8,
+    is_call_arg_count_unknown: bool,
+    cur_module_name: Option<Id>,
+}
+
 impl Scope<'_> {
+    pub(crate) fn snapshot(&self) -> ScopeSnapshot {
+        ScopeSnapshot {
+            declaring: self.declaring.clone(),
+            declaring_type_params: self.declaring_type_params.clone(),
+            facts: self.facts.clone(),
+            declaring_fn: self.declaring_fn.clone(),
+            declaring_prop: self.declaring_prop.clone(),
+            this: self.this.clone(),
+            this_class_name: self.this_class_name.clone(),
+            expand_triage_depth: self.expand_triage_depth,
+            is_call_arg_count_unknown: self.is_call_arg_count_unknown,
+            cur_module_name: self.cur_module_name.clone(),
+        }
+    }
+
+    /// Restores states saved by [Scope::snapshot]. Declared variables and types
+    /// are kept.
+    pub(crate) fn restore(&mut self, snapshot: ScopeSnapshot) {
+        self.declaring = snapshot.declaring;
+        self.declaring_type_params = snapshot.declaring_type_params;
+        self.facts = snapshot.facts;
+        self.declaring_fn = snapshot.declaring_fn;
+        self.declaring_prop = snapshot.declaring_prop;
+        self.this = snapshot.this;
+        self.this_class_name = snapshot.this_class_name;
+        self.expand_triage_depth = snapshot.expand_triage_depth;
+        self.is_call_arg_count_unknown = snapshot.is_call_arg_count_unknown;
+        self.cur_module_name = snapshot.cur_module_name;
+    }
+
     pub fn parent(&self) -> Option<&Self> {
         self.parent
     }
@@ -632,6 +677,49 @@ impl Scope<'_> {
         None
     }
 
+    /// Returns a variable declared in the root scope.
+    pub fn get_root_var(&self, sym: &JsWord) -> Option<&VarInfo> {
+        match self.parent {
+            Some(parent) => parent.get_root_var(sym),
+            None => self.vars.iter().find(|(id, _)| id.sym() == sym).map(|(_, v)| v),
+        }
+    }
+
+    /// Finds a type by name, regardless of the syntax context. Used for types
+    /// referenced by the compiler, like `JSX`.
+    pub fn find_type_by_sym(&self, sym: &JsWord) -> Option<&Type> {
+        match self.types.iter().find(|(id, _)| id.sym() == sym) {
+            Some((_, ty)) => Some(ty),
+            None => self.parent?.find_type_by_sym(sym),
+        }
+    }
+
+    /// Same as [Scope::find_type_by_sym], but for variables.
+    pub fn find_var_by_sym(&self, sym: &JsWord) -> Option<&VarInfo> {
+        match self.vars.iter().find(|(id, _)| id.sym() == sym) {
+            Some((_, v)) => Some(v),
+            None => self.parent?.find_var_by_sym(sym),
+        }
+    }
+
+    /// Returns `true` if the scope is not in a function, a class or a
+    /// namespace.
+    pub fn is_top_level(&self) -> bool {
+        self.first(|scope| {
+            !scope.is_root()
+                && matches!(
+                    scope.kind,
+                    ScopeKind::Fn
+                        | ScopeKind::Method { .. }
+                        | ScopeKind::Constructor
+                        | ScopeKind::ArrowFn
+                        | ScopeKind::Class
+                        | ScopeKind::Module
+                )
+        })
+        .is_none()
+    }
+
     pub fn mark_as_super_called(&self) {
         if self.kind == ScopeKind::Class {
             *self.class.need_super_call.borrow_mut() = false;
@@ -827,7 +915,8 @@ impl Analyzer<'_, '_> {
             // Override class definitions.
             if should_override {
                 if let Some(kind) = self.scope.get_var(&name).map(|v| v.kind) {
-                    self.override_var(kind, name.clone(), ty.clone()).report(&mut self.storage);
+                    let var_ty = remove_merged_namespaces(&ty).unwrap_or_else(|| ty.clone());
+                    self.override_var(kind, name.clone(), var_ty).report(&mut self.storage);
                 }
             }
 
@@ -955,6 +1044,16 @@ impl Analyzer<'_, '_> {
         None
     }
 
+    /// Narrowed types are looked up in the order below, and the first one
+    /// wins.
+    ///
+    ///  1. `cur_facts`, for the right operand of `&&` and the like.
+    ///  2. Facts of scopes. Unlike the flow graph, which starts from scratch
+    ///     for each function, they are visible to closures. For `if` and
+    ///     `switch`, they are the same as the conditions stored in the flow
+    ///     graph.
+    ///  3. The flow graph, which knows about assignments and join points.
+    ///  4. The actual type of the variable.
     pub(super) fn find_var_type(&self, name: &Id, mode: TypeOfMode) -> Option<Cow<Type>> {
         let ty = (|| {
             if let Some(v) = self.cur_facts.true_facts.vars.get(&Name::from(name)) {
@@ -1015,8 +1114,8 @@ impl Analyzer<'_, '_> {
                         Some(ty) => ty.clone(),
                         _ => return None,
                     },
-                    TypeOfMode::RValue => match &var.actual_ty {
-                        Some(ty) => ty.clone(),
+                    TypeOfMode::RValue => match self.data.flow.narrowed_type(&name).or_else(|| var.actual_ty.clone()) {
+                        Some(ty) => ty,
                         _ => return None,
                     },
                 };
@@ -1217,6 +1316,12 @@ impl Analyzer<'_, '_> {
             ty.assert_valid();
         }
 
+        if !self.is_builtin && !self.ctx.reevaluating() {
+            if let Some(ty) = &ty {
+                self.dump_decl_type(span, ty);
+            }
+        }
+
         let allow_multiple = allow_multiple && {
             // Consult previous variable declarations to know if we can declare
             // this variable.
@@ -1343,6 +1448,10 @@ impl Analyzer<'_, '_> {
             ty.assert_valid();
         }
 
+        if let VarKind::Var(..) = kind {
+            self.data.flow.add_assign(Name::from(&name), actual_ty.clone());
+        }
+
         if self.ctx.in_global {
             match kind {
                 VarKind::Var(_) | VarKind::Class | VarKind::Fn | VarKind::Enum => {
@@ -1435,6 +1544,9 @@ impl Analyzer<'_, '_> {
                                     }
 
                                     _ => {
+                                        // Subsequent declarations must have the same type.
+                                        let identical = self.is_type_identical_to(span, &generalized_var_ty, &ty);
+
                                         let res = self
                                             .assign_with_opts(
                                                 &mut Default::default(),
@@ -1447,6 +1559,20 @@ impl Analyzer<'_, '_> {
                                                     ..Default::default()
                                                 },
                                             )
+                                            .and_then(|()| {
+                                                if identical? {
+                                                    Ok(())
+                                                } else {
+                                                    Err(ErrorKind::AssignFailed {
+                                                        span,
+                                                        left: box ty.clone(),
+                                                        right_ident: None,
+                                                        right: box generalized_var_ty.clone(),
+                                                        cause: vec![],
+                                                    }
+                                                    .into())
+                                                }
+                                            })
                                             .context("tried to validate a varaible declared multiple times")
                                             .convert_err(|err| ErrorKind::VarDeclNotCompatible {
                                                 span: err.span(),
@@ -1854,6 +1980,15 @@ impl ScopeKind {
     pub fn allows_respanning(self) -> bool {
         !matches!(self, ScopeKind::Flow | ScopeKind::Class | ScopeKind::ObjectLit)
     }
+
+    /// Returns `true` for scopes which have their own control flow, like
+    /// functions.
+    pub fn is_flow_container(self) -> bool {
+        matches!(
+            self,
+            ScopeKind::Fn | ScopeKind::Method { .. } | ScopeKind::Constructor | ScopeKind::ArrowFn | ScopeKind::Class | ScopeKind::Module
+        )
+    }
 }
 
 struct Expander<'a, 'b, 'c> {
@@ -1983,6 +2118,7 @@ impl Expander<'_, '_, '_> {
                                             members: vec![],
                                             metadata: Default::default(),
                                         })),
+                                        None,
                                         InferTypeOpts { ..Default::default() },
                                     )?;
                                     inferred.types.iter_mut().for_each(|(_, ty)| {
@@ -2077,19 +2213,29 @@ impl Expander<'_, '_, '_> {
                 let left = self.expand_ts_entity_name(span, left, None, was_top_level, trying_primitive_expansion)?;
 
                 if let Some(left) = &left {
+                    let key = Key::Normal {
+                        span,
+                        sym: right.sym.clone(),
+                    };
+
+                    // Declarations merged with a namespace are searched one by one, as
+                    // `Color.Red` may be a member of the enum or of the namespace.
+                    if let Some(i) = left.normalize().as_intersection() {
+                        if i.types.iter().any(|ty| ty.normalize().is_module()) {
+                            for ty in &i.types {
+                                if let Ok(ty) =
+                                    self.analyzer
+                                        .access_property(span, ty, &key, TypeOfMode::RValue, IdCtx::Type, Default::default())
+                                {
+                                    return Ok(Some(ty));
+                                }
+                            }
+                        }
+                    }
+
                     let ty = self
                         .analyzer
-                        .access_property(
-                            span,
-                            left,
-                            &Key::Normal {
-                                span,
-                                sym: right.sym.clone(),
-                            },
-                            TypeOfMode::RValue,
-                            IdCtx::Type,
-                            Default::default(),
-                        )
+                        .access_property(span, left, &key, TypeOfMode::RValue, IdCtx::Type, Default::default())
                         .context("tried to access property as a part of type expansion")
                         .report(&mut self.analyzer.storage)
                         .unwrap_or_else(|| Type::any(span, Default::default()));
@@ -2120,6 +2266,17 @@ impl Expander<'_, '_, '_> {
         let mut ty = self.expand_ts_entity_name(span, &type_name, type_args.as_deref(), was_top_level, trying_primitive_expansion)?;
 
         if let Some(ty) = &mut ty {
+            if let Some(stripped) = remove_merged_namespaces(ty) {
+                *ty = match stripped.foldable() {
+                    Type::ClassDef(def) => Type::Class(Class {
+                        span,
+                        def: box def,
+                        metadata: Default::default(),
+                    }),
+                    ty => ty,
+                };
+            }
+
             ty.reposition(r_span);
 
             if let Type::Enum(e) = ty.normalize() {
@@ -2488,14 +2645,27 @@ impl Expander<'_, '_, '_> {
                 }
             }
 
-            return Type::Conditional(Conditional {
+            let c = Conditional {
                 span,
                 check_type,
                 extends_type,
                 true_type,
                 false_type,
                 metadata,
-            });
+            };
+
+            // Evaluated at here, so tail-recursive aliases are not expanded recursively.
+            if let Ok(Some(ty)) = self.analyzer.eval_conditional_type(self.span, &c) {
+                return match self.analyzer.eval_tail_conditional_types(self.span, ty) {
+                    Ok(ty) => ty,
+                    Err(err) => {
+                        self.analyzer.storage.report(err);
+                        Type::any(self.span, Default::default())
+                    }
+                };
+            }
+
+            return Type::Conditional(c);
         }
 
         ty
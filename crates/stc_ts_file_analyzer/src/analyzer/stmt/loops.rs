@@ -6,14 +6,14 @@ use stc_ts_ast_rnode::{
 };
 use stc_ts_errors::{DebugExt, ErrorKind};
 use stc_ts_file_analyzer_macros::extra_validator;
-use stc_ts_types::{Id, KeywordType, KeywordTypeMetadata, Operator, Ref, RefMetadata, TypeParamInstantiation};
+use stc_ts_types::{name::Name, Id, KeywordType, KeywordTypeMetadata, Operator, Ref, RefMetadata, TypeParamInstantiation};
 use stc_ts_utils::{find_ids_in_pat, PatExt};
 use stc_utils::cache::Freeze;
 use swc_common::{Span, Spanned, DUMMY_SP};
 use swc_ecma_ast::{EsVersion, TsKeywordTypeKind, TsTypeOperatorOp, VarDeclKind};
 
 use crate::{
-    analyzer::{control_flow::CondFacts, types::NormalizeTypeOpts, util::ResultExt, Analyzer, Ctx, ScopeKind},
+    analyzer::{control_flow::CondFacts, flow::is_always_true, types::NormalizeTypeOpts, util::ResultExt, Analyzer, Ctx, ScopeKind},
     ty::Type,
     util::is_str_or_union,
     validator,
@@ -41,6 +41,11 @@ impl Analyzer<'_, '_> {
         let mut last = false;
         let mut orig_vars = Some(self.scope.vars.clone());
 
+        let loop_label = self.data.flow.new_loop_label();
+        let break_label = self.data.flow.new_label();
+        self.data.flow.add_antecedent(loop_label);
+        self.data.flow.push_jump_target(break_label, Some(loop_label));
+
         loop {
             let mut facts_from_body: CondFacts = self.with_child_with_hook(
                 ScopeKind::LoopBody { last },
@@ -48,6 +53,14 @@ impl Analyzer<'_, '_> {
                 |child: &mut Analyzer| {
                     child.ctx.ignore_errors |= !last;
 
+                    // Edges added by the previous validation of the body are replaced, but back
+                    // edges are kept until the end of the body so that types at the loop header
+                    // include assignments in the body.
+                    let stale_back_edges = child.data.flow.antecedents(loop_label).get(1..).unwrap_or_default().to_vec();
+                    let stale_exits = child.data.flow.antecedents(break_label).to_vec();
+                    child.data.flow.remove_antecedents(break_label, &stale_exits);
+                    child.data.flow.set_cur(loop_label);
+
                     {
                         let ctx = Ctx {
                             in_cond: true,
@@ -56,8 +69,19 @@ impl Analyzer<'_, '_> {
                         test.visit_with(&mut *child.with_ctx(ctx));
                     }
 
+                    let flow_of_test = child.data.flow.cur();
+                    if !test.map_or(false, is_always_true) {
+                        child.data.flow.add_cond(flow_of_test, &child.cur_facts.false_facts);
+                        child.data.flow.add_antecedent(break_label);
+                    }
+                    child.data.flow.add_cond(flow_of_test, &child.cur_facts.true_facts);
+
                     body.visit_with(child);
 
+                    // `continue` is handled by the jump target.
+                    child.data.flow.remove_antecedents(loop_label, &stale_back_edges);
+                    child.data.flow.add_antecedent(loop_label);
+
                     Ok(child.cur_facts.true_facts.take())
                 },
                 |analyzer: &mut Analyzer| {
@@ -87,6 +111,9 @@ impl Analyzer<'_, '_> {
             prev_facts += facts_from_body;
         }
 
+        self.data.flow.pop_jump_target();
+        self.data.flow.finish_label(break_label);
+
         self.cur_facts.true_facts += prev_facts;
         self.cur_facts.false_facts += prev_false_facts;
 
@@ -119,6 +146,11 @@ impl Analyzer<'_, '_> {
 
                 // Store variables
                 v.visit_with(self);
+
+                // Variables are assigned by each iteration.
+                for id in find_ids_in_pat(&v.decls) {
+                    self.data.flow.add_assign(Name::from(&id), None);
+                }
             }
             RVarDeclOrPat::Pat(ref pat) => {
                 self.try_assign_pat(span, pat, elem_ty)
@@ -335,6 +367,8 @@ impl Analyzer<'_, '_> {
                         child.storage.report(ErrorKind::ForOfStringUsedInEs3 { span }.into())
                     }
                 }
+
+                child.report_error_for_iteration_below_es2015(rhs.span(), &rty);
             }
 
             let mut elem_ty = match kind {
@@ -387,6 +421,10 @@ impl Analyzer<'_, '_> {
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, s: &RForOfStmt) {
+        if s.await_token.is_some() {
+            self.validate_top_level_await(s.span, true);
+        }
+
         self.check_for_of_in_loop(
             s.span,
             &s.left,
@@ -1,17 +1,21 @@
-use std::time::Instant;
-
 use rnode::VisitWith;
 use stc_ts_ast_rnode::{RBlockStmt, RBool, RExpr, RExprStmt, RForStmt, RModuleItem, RStmt, RTsExprWithTypeArgs, RTsLit, RWithStmt};
+use stc_ts_env::perf::reset_instantiation_count;
 use stc_ts_errors::{DebugExt, ErrorKind};
 use stc_ts_types::{LitType, Type};
 use stc_utils::stack;
 use swc_common::{Spanned, DUMMY_SP};
 use swc_ecma_utils::Value::Known;
-use tracing::{instrument, span, trace, warn, Level};
+use tracing::{instrument, span, trace, Level};
 
 use self::return_type::LoopBreakerFinder;
 use crate::{
-    analyzer::{scope::ScopeKind, util::ResultExt, Analyzer},
+    analyzer::{
+        flow::{is_always_true, JumpKind},
+        scope::ScopeKind,
+        util::ResultExt,
+        Analyzer,
+    },
     validator,
     validator::ValidateWith,
 };
@@ -26,6 +30,7 @@ mod var_decl;
 impl Analyzer<'_, '_> {
     fn validate(&mut self, i: &RModuleItem) {
         let _stack = stack::start(100);
+        reset_instantiation_count();
 
         i.visit_children_with(self);
 
@@ -42,9 +47,6 @@ impl Analyzer<'_, '_> {
         let tracing_span = span!(Level::ERROR, "Stmt", line_col = &*line_col);
         let _tracing_guard = tracing_span.enter();
 
-        warn!("Statement start");
-        let start = Instant::now();
-
         if self.rule().always_strict && !self.rule().allow_unreachable_code && self.ctx.in_unreachable {
             self.storage.report(ErrorKind::UnreachableCode { span: s.span() }.into());
         }
@@ -57,17 +59,14 @@ impl Analyzer<'_, '_> {
 
         s.visit_children_with(self);
 
-        self.scope.return_values.in_conditional = old_in_conditional;
-
-        let end = Instant::now();
+        match s {
+            RStmt::Return(..) | RStmt::Throw(..) => self.data.flow.mark_unreachable(),
+            RStmt::Break(s) => self.data.flow.jump(JumpKind::Break, s.label.is_some()),
+            RStmt::Continue(s) => self.data.flow.jump(JumpKind::Continue, s.label.is_some()),
+            _ => {}
+        }
 
-        warn!(
-            kind = "perf",
-            op = "validate (Stmt)",
-            "({}): Statement validation done. (time = {:?}",
-            line_col,
-            end - start
-        );
+        self.scope.return_values.in_conditional = old_in_conditional;
 
         Ok(())
     }
@@ -97,7 +96,16 @@ impl Analyzer<'_, '_> {
     fn validate(&mut self, node: &RForStmt) {
         node.init.visit_with(self);
 
+        let loop_label = self.data.flow.new_loop_label();
+        let break_label = self.data.flow.new_label();
+        self.data.flow.add_antecedent(loop_label);
+        self.data.flow.finish_label(loop_label);
+
         let test = try_opt!(node.test.validate_with_default(self));
+        // `for (;;)` is exited only by `break`.
+        if node.test.as_deref().map_or(false, |test| !is_always_true(test)) {
+            self.data.flow.add_antecedent(break_label);
+        }
         let always_true = Type::Lit(LitType {
             span: node.span,
             lit: RTsLit::Bool(RBool {
@@ -108,10 +116,16 @@ impl Analyzer<'_, '_> {
         });
         self.check_for_inifinite_loop(test.as_ref().unwrap_or(&always_true), &node.body);
 
+        self.data.flow.push_jump_target(break_label, Some(loop_label));
+
         node.update.visit_with(self);
-        node.body.validate_with(self)?;
+        let res = node.body.validate_with(self);
+        self.data.flow.add_antecedent(loop_label);
 
-        Ok(())
+        self.data.flow.pop_jump_target();
+        self.data.flow.finish_label(break_label);
+
+        res
     }
 }
 
@@ -177,7 +191,16 @@ impl Analyzer<'_, '_> {
 
         let prev_cond_facts = self.cur_facts.clone();
 
-        node.expr.visit_with(self);
+        match node.expr.validate_with_default(self) {
+            Ok(ty) => {
+                // Like tsc, only calls in expression statements terminate the flow, like
+                // `fail();` or `process.exit(1);`.
+                if ty.is_never() && is_call(&node.expr) {
+                    self.data.flow.mark_unreachable();
+                }
+            }
+            Err(err) => self.storage.report(err),
+        }
 
         if preserve_cond_facts {
             self.cur_facts = prev_cond_facts;
@@ -186,3 +209,11 @@ impl Analyzer<'_, '_> {
         Ok(())
     }
 }
+
+fn is_call(e: &RExpr) -> bool {
+    match e {
+        RExpr::Call(..) => true,
+        RExpr::Paren(e) => is_call(&e.expr),
+        _ => false,
+    }
+}
@@ -444,6 +444,8 @@ impl Analyzer<'_, '_> {
     fn validate(&mut self, e: &RYieldExpr) -> VResult<Type> {
         let span = e.span;
 
+        let mut ret_ty = None;
+
         if let Some(res) = e.arg.validate_with_default(self) {
             let ty = res?;
 
@@ -453,6 +455,14 @@ impl Analyzer<'_, '_> {
                         .context("tried to convert argument as an async iterator for delegating yield")?
                         .into_owned()
                 } else {
+                    if let Some(declared) = self.scope.declared_return_type().cloned() {
+                        self.check_next_type_of_delegated_iterator(span, &declared, &ty)
+                            .report(&mut self.storage);
+                    }
+
+                    // `TReturn` of the delegated iterator is the result of `yield*`.
+                    ret_ty = self.get_iterator_return_type(span, Cow::Borrowed(&ty)).ok();
+
                     self.get_iterator_element_type(e.span, Cow::Owned(ty), false, GetIteratorOpts { ..Default::default() })
                         .context("tried to convert argument as an iterator for delegating yield")?
                         .into_owned()
@@ -514,7 +524,54 @@ impl Analyzer<'_, '_> {
             }));
         }
 
-        Ok(Type::any(e.span, Default::default()))
+        Ok(ret_ty.unwrap_or_else(|| Type::any(e.span, Default::default())))
+    }
+}
+
+impl Analyzer<'_, '_> {
+    /// Ensures that `TNext` of the containing generator is assignable to
+    /// `TNext` of the iterator we are delegating to.
+    ///
+    /// ```ts
+    /// declare const it: Iterator<number, void, number>;
+    /// function* g(): Generator<number, void, string> {
+    ///     yield* it; // TS2766
+    /// }
+    /// ```
+    fn check_next_type_of_delegated_iterator(&mut self, span: Span, declared: &Type, delegated: &Type) -> VResult<()> {
+        let iterator = match self.get_iterator(span, Cow::Borrowed(delegated), Default::default()) {
+            Ok(iterator) => iterator,
+            // Reported while calculating the element type.
+            Err(..) => return Ok(()),
+        };
+
+        if iterator.is_str() || iterator.is_any() || matches!(iterator.normalize(), Type::Array(..) | Type::Tuple(..)) {
+            return Ok(());
+        }
+
+        let expected = match self.get_iterator_next_arg_type(span, &iterator).ok().flatten() {
+            Some(ty) => ty,
+            None => return Ok(()),
+        };
+        let sent = match self.get_iterator_next_arg_type(span, declared).ok().flatten() {
+            Some(ty) => ty,
+            None => return Ok(()),
+        };
+
+        self.assign_with_opts(
+            &mut Default::default(),
+            &expected,
+            &sent,
+            AssignOpts {
+                span,
+                ..Default::default()
+            },
+        )
+        .context("tried to assign `TNext` of a generator to `TNext` of the delegated iterator")
+        .convert_err(|err| ErrorKind::CannotDelegateIterationToValue {
+            span,
+            cause: box err.into(),
+        })
     }
 }
 
@@ -1,5 +1,5 @@
 use rnode::VisitWith;
-use stc_ts_ast_rnode::RCatchClause;
+use stc_ts_ast_rnode::{RCatchClause, RTryStmt};
 
 use crate::{
     analyzer::{pat::PatMode, scope::ScopeKind, Analyzer, Ctx},
@@ -7,6 +7,58 @@ use crate::{
     validator::ValidateWith,
 };
 
+#[validator]
+impl Analyzer<'_, '_> {
+    fn validate(&mut self, s: &RTryStmt) {
+        let flow_of_try = self.data.flow.cur();
+
+        // An exception can be thrown anywhere in the block, so the handler and the
+        // finalizer start from all assignments in the block.
+        self.data.flow.enter_try();
+        s.block.visit_with(self);
+        let end_of_block = self.data.flow.cur();
+        let mut abrupt = self.data.flow.exit_try();
+        abrupt.push(flow_of_try);
+
+        let end_of_handler = match &s.handler {
+            Some(handler) => {
+                let catch_label = self.data.flow.new_label();
+                self.data.flow.add_antecedents(catch_label, &abrupt);
+                self.data.flow.finish_label(catch_label);
+
+                self.data.flow.enter_try();
+                handler.visit_with(self);
+                abrupt.extend(self.data.flow.exit_try());
+
+                self.data.flow.cur()
+            }
+            None => end_of_block,
+        };
+
+        let end_label = self.data.flow.new_label();
+        self.data.flow.add_antecedents(end_label, &[end_of_block, end_of_handler]);
+
+        if let Some(finalizer) = &s.finalizer {
+            let completes = !self.data.flow.is_unreachable(end_of_block) || !self.data.flow.is_unreachable(end_of_handler);
+
+            // The finalizer is also executed after `return` or an uncaught exception.
+            self.data.flow.add_antecedents(end_label, &abrupt);
+            self.data.flow.finish_label(end_label);
+
+            finalizer.visit_with(self);
+
+            if !completes {
+                self.data.flow.mark_unreachable();
+            }
+            return Ok(());
+        }
+
+        self.data.flow.finish_label(end_label);
+
+        Ok(())
+    }
+}
+
 #[validator]
 impl Analyzer<'_, '_> {
     fn validate(&mut self, s: &RCatchClause) {
@@ -1,14 +1,18 @@
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    mem::{replace, take},
+};
 
 use rnode::{FoldWith, Visit, VisitWith};
 use stc_ts_ast_rnode::{
-    RArrayPat, RCallExpr, RCallee, RExpr, RIdent, RPat, RTsAsExpr, RTsEntityName, RTsTypeAssertion, RVarDecl, RVarDeclarator,
+    RArrayPat, RAssignPatProp, RBinExpr, RCallExpr, RCallee, RClassExpr, RExpr, RIdent, RKeyValuePatProp, RObjectPatProp, RPat, RPropName,
+    RTsAsExpr, RTsEntityName, RTsTypeAssertion, RUnaryExpr, RVarDecl, RVarDeclarator,
 };
 use stc_ts_errors::{debug::dump_type_as_string, DebugExt, ErrorKind, Errors};
 use stc_ts_type_ops::{generalization::prevent_generalize, Fix};
 use stc_ts_types::{
-    Array, EnumVariant, Id, Instance, InstanceMetadata, KeywordType, KeywordTypeMetadata, Operator, OperatorMetadata, QueryExpr, QueryType,
-    Symbol, SymbolMetadata,
+    name::Name, Array, EnumVariant, Id, Instance, InstanceMetadata, KeywordType, KeywordTypeMetadata, Operator, OperatorMetadata,
+    QueryExpr, QueryType, Symbol, SymbolMetadata,
 };
 use stc_ts_utils::{find_ids_in_pat, PatExt};
 use stc_utils::cache::Freeze;
@@ -101,6 +105,11 @@ impl Analyzer<'_, '_> {
                 None
             };
             let ids: Vec<Id> = find_ids_in_pat(&v.name);
+            if matches!(kind, VarDeclKind::Let | VarDeclKind::Const) && !self.ctx.in_declare {
+                for id in &ids {
+                    self.data.block_scoped_decls.insert(id.clone(), v_span);
+                }
+            }
             let prev_declaring_len = self.scope.declaring.len();
             self.scope.declaring.extend(ids);
 
@@ -128,6 +137,11 @@ impl Analyzer<'_, '_> {
                     Some(box RExpr::TsAs(RTsAsExpr { type_ann, .. })) => Some(type_ann.validate_with(self)?),
 
                     Some(box RExpr::TsTypeAssertion(RTsTypeAssertion { type_ann, .. })) => Some(type_ann.validate_with(self)?),
+
+                    // let a = {} satisfies Foo
+                    //
+                    // `satisfies` does not change the type of the variable.
+                    Some(box RExpr::TsSatisfies(..)) => None,
                     _ => None,
                 }
             };
@@ -185,11 +199,12 @@ impl Analyzer<'_, '_> {
 
                 macro_rules! get_value_ty {
                     ($ty:expr) => {{
-                        match init.validate_with_args(self, (TypeOfMode::RValue, None, $ty)) {
-                            Ok(ty) => {
+                        match self.validate_var_init(kind, &v.name, init, $ty) {
+                            Ok(mut ty) => {
                                 if creates_new_this {
                                     self.scope.this = old_this;
                                 }
+                                name_anonymous_class(&v.name, init, &mut ty);
                                 ty
                             }
                             Err(err) => {
@@ -643,6 +658,7 @@ impl Analyzer<'_, '_> {
                     RPat::Ident(ref i) => {
                         //
                         let sym: Id = (&i.id).into();
+                        let name = Name::from(&sym);
                         let mut ty = try_opt!(i.type_ann.validate_with(self));
                         ty.fix();
                         ty = ty.map(|ty| {
@@ -694,6 +710,11 @@ impl Analyzer<'_, '_> {
                                 self.storage.report(err);
                             }
                         };
+
+                        // `let a!: string;` is assumed to be assigned.
+                        if !self.ctx.in_declare && !v.definite {
+                            self.data.flow.add_declare(name);
+                        }
                     }
                     _ => {
                         // For ambient contexts and loops, we add variables to the scope.
@@ -720,10 +741,97 @@ impl Analyzer<'_, '_> {
 
         res.report(&mut self.storage);
 
+        if kind == VarDeclKind::Const {
+            self.record_destructured_props(v);
+        }
+
         Ok(())
     }
 }
 
+impl Analyzer<'_, '_> {
+    /// Validates the initializer of a variable.
+    ///
+    /// If a `const` variable stores a condition, like `ok` in
+    /// `const ok = x !== undefined`, the facts created by the condition are
+    /// stored so that `if (ok)` narrows `x`.
+    fn validate_var_init(&mut self, kind: VarDeclKind, name: &RPat, init: &RExpr, type_ann: Option<&Type>) -> VResult<Type> {
+        let id = match name {
+            RPat::Ident(i) if kind == VarDeclKind::Const && is_cond(init) => Id::from(&i.id),
+            _ => return init.validate_with_args(self, (TypeOfMode::RValue, None, type_ann)),
+        };
+
+        let prev_facts = take(&mut self.cur_facts);
+        let ctx = Ctx {
+            in_cond: true,
+            should_store_truthy_for_access: true,
+            ..self.ctx
+        };
+        let res = init.validate_with_args(&mut *self.with_ctx(ctx), (TypeOfMode::RValue, None, type_ann));
+        let mut facts = replace(&mut self.cur_facts, prev_facts);
+
+        // Narrowing by an alias is valid only if the narrowed reference is not
+        // reassigned.
+        facts.true_facts.retain_names(|name| self.is_stable_reference(name));
+        facts.false_facts.retain_names(|name| self.is_stable_reference(name));
+        self.data.aliased_conds.insert(id, facts);
+
+        res
+    }
+
+    /// Records properties destructured from a reference, like `kind` in
+    /// `const { kind } = action`, to narrow `action` by `kind`.
+    fn record_destructured_props(&mut self, v: &RVarDeclarator) {
+        let (obj, name) = match (&v.name, v.init.as_deref().map(Name::try_from)) {
+            (RPat::Object(obj), Some(Ok(name))) => (obj, name),
+            _ => return,
+        };
+        if !self.is_stable_reference(&name) {
+            return;
+        }
+
+        for prop in &obj.props {
+            let (key, id) = match prop {
+                RObjectPatProp::Assign(RAssignPatProp { key, value: None, .. }) => (key.sym.clone(), Id::from(key)),
+                RObjectPatProp::KeyValue(RKeyValuePatProp {
+                    key: RPropName::Ident(key),
+                    value: box RPat::Ident(value),
+                }) => (key.sym.clone(), Id::from(&value.id)),
+                _ => continue,
+            };
+
+            let mut prop_name = name.clone();
+            prop_name.push(key);
+            self.data.destructured_props.insert(id, prop_name);
+        }
+    }
+
+    /// Returns `true` if `name` refers to a `const` variable or a parameter
+    /// which is never assigned.
+    fn is_stable_reference(&self, name: &Name) -> bool {
+        let top = name.top();
+
+        match self.find_var(&top).map(|v| &v.kind) {
+            Some(VarKind::Var(VarDeclKind::Const)) => true,
+            Some(VarKind::Param) => !self.data.assigned_vars.contains(&top),
+            _ => false,
+        }
+    }
+}
+
+/// Returns `true` if `e` can narrow types when it's used as a condition.
+fn is_cond(e: &RExpr) -> bool {
+    match e {
+        RExpr::Bin(RBinExpr { op, .. }) => matches!(
+            op,
+            op!("===") | op!("!==") | op!("==") | op!("!=") | op!("instanceof") | op!("in") | op!("&&") | op!("||")
+        ),
+        RExpr::Unary(RUnaryExpr { op: op!("!"), .. }) => true,
+        RExpr::Paren(e) => is_cond(&e.expr),
+        _ => false,
+    }
+}
+
 struct TypeParamFinder {
     found: bool,
 }
@@ -744,3 +852,14 @@ where
 
     v.found
 }
+
+/// `const Foo = class {}` is named `Foo`.
+fn name_anonymous_class(name: &RPat, init: &RExpr, ty: &mut Type) {
+    if let (RPat::Ident(i), RExpr::Class(RClassExpr { ident: None, .. })) = (name, init) {
+        if let Type::ClassDef(def) = ty.normalize_mut() {
+            if def.name.is_none() {
+                def.name = Some(i.id.clone().into());
+            }
+        }
+    }
+}
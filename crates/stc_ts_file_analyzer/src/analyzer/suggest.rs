@@ -0,0 +1,95 @@
+use std::borrow::Cow;
+
+use stc_ts_errors::{Error, ErrorKind};
+use stc_ts_types::{Id, Key, MethodSignature, PropertySignature, TypeElement};
+use stc_utils::suggest::spelling_suggestion;
+use swc_atoms::JsWord;
+use swc_common::Span;
+
+use crate::{
+    analyzer::{
+        expr::{IdCtx, TypeOfMode},
+        Analyzer,
+    },
+    ty::Type,
+    util::unwrap_ref_with_single_arg,
+};
+
+impl Analyzer<'_, '_> {
+    /// Finds a variable with a name similar to `name`, for `Did you mean
+    /// ...?`.
+    pub(crate) fn suggest_var_name(&self, name: &Id) -> Option<JsWord> {
+        let mut candidates = self.imports_by_id.keys().map(|id| id.sym().clone()).collect::<Vec<_>>();
+
+        let mut scope = Some(&self.scope);
+        while let Some(s) = scope {
+            candidates.extend(s.vars.keys().map(|id| id.sym().clone()));
+            scope = s.parent();
+        }
+
+        candidates.extend(self.env.global_var_names());
+
+        spelling_suggestion(name.sym(), candidates.iter().map(|s| &**s)).map(JsWord::from)
+    }
+
+    /// Adds a suggestion to [ErrorKind::NoSuchProperty].
+    ///
+    /// The error becomes [ErrorKind::NoSuchPropertyInPromise] if the promised
+    /// type of `obj` has the property, and
+    /// [ErrorKind::NoSuchPropertyWithSuggestion] if `obj` has a property with a
+    /// similar name.
+    pub(crate) fn suggest_for_missing_property(&mut self, span: Span, obj_span: Span, obj: &Type, err: Error) -> Error {
+        let (err_span, prop) = match &*err {
+            ErrorKind::NoSuchProperty {
+                span, prop: Some(prop), ..
+            } => (*span, (**prop).clone()),
+            _ => return err,
+        };
+
+        if let Some(promised) = unwrap_ref_with_single_arg(obj, "Promise") {
+            if self
+                .access_property(span, promised, &prop, TypeOfMode::RValue, IdCtx::Var, Default::default())
+                .is_ok()
+            {
+                let can_await = self.ctx.in_async;
+                return err.convert(|_| ErrorKind::NoSuchPropertyInPromise {
+                    span: err_span,
+                    obj_span,
+                    prop: box prop,
+                    can_await,
+                });
+            }
+        }
+
+        let sym = match &prop {
+            Key::Normal { sym, .. } => sym,
+            _ => return err,
+        };
+
+        let members = match self.convert_type_to_type_lit(span, Cow::Borrowed(obj)) {
+            Ok(Some(lit)) => lit.into_owned().members,
+            _ => return err,
+        };
+
+        let candidates = members.iter().filter_map(|member| match member {
+            TypeElement::Property(PropertySignature {
+                key: Key::Normal { sym, .. },
+                ..
+            })
+            | TypeElement::Method(MethodSignature {
+                key: Key::Normal { sym, .. },
+                ..
+            }) => Some(&**sym),
+            _ => None,
+        });
+
+        match spelling_suggestion(sym, candidates) {
+            Some(suggestion) => err.convert(|_| ErrorKind::NoSuchPropertyWithSuggestion {
+                span: err_span,
+                prop: box prop,
+                suggestion: suggestion.into(),
+            }),
+            None => err,
+        }
+    }
+}
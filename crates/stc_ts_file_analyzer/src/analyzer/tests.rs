@@ -47,6 +47,8 @@ where
             path: Arc::new(FileName::Real(PathBuf::new())),
             is_dts: false,
             info: Default::default(),
+            filter: None,
+            directives: None,
         };
 
         let handler = Arc::new(handler);
@@ -136,6 +138,8 @@ where
             path,
             info: Default::default(),
             is_dts: false,
+            filter: None,
+            directives: None,
         };
 
         {
@@ -0,0 +1,249 @@
+use std::borrow::Cow;
+
+use fxhash::FxHashSet;
+use stc_ts_ast_rnode::RTsEntityName;
+use stc_ts_env::perf::MAX_TAIL_RECURSION_DEPTH;
+use stc_ts_errors::{DebugExt, ErrorKind};
+use stc_ts_type_ops::{metadata::TypeFinder, Fix};
+use stc_ts_types::{Conditional, Id, Intersection, Ref, Type, TypeElement, Union};
+use stc_utils::cache::Freeze;
+use swc_common::{Span, SyntaxContext};
+
+use crate::{
+    analyzer::{scope::ExpandOpts, Analyzer},
+    util::contains_infer_type,
+    VResult,
+};
+
+impl Analyzer<'_, '_> {
+    /// Evaluates a conditional type.
+    ///
+    /// If the check type was a naked type parameter, the conditional type is
+    /// distributed over the members of the instantiated union.
+    ///
+    /// Returns `None` if the evaluation should be deferred because the check
+    /// type or the extends type depends on unresolved type parameters.
+    pub(crate) fn eval_conditional_type(&mut self, span: Span, c: &Conditional) -> VResult<Option<Type>> {
+        let span = span.with_ctxt(SyntaxContext::empty());
+
+        let mut check_type = self
+            .normalize(Some(span), Cow::Borrowed(&c.check_type), Default::default())
+            .context("tried to normalize the `check` type of a conditional type")?
+            .into_owned();
+        check_type.make_clone_cheap();
+
+        if !c.metadata.distributive {
+            return self.eval_conditional_type_for(span, &check_type, c);
+        }
+
+        let members = match check_type.normalize() {
+            Type::Union(u) => u.types.clone(),
+            _ if check_type.is_never() => return Ok(Some(Type::never(span, Default::default()))),
+            _ => return self.eval_conditional_type_for(span, &check_type, c),
+        };
+
+        let mut types = vec![];
+        for member in members {
+            match self.eval_conditional_type_for(span, &member, c)? {
+                Some(ty) => types.push(ty),
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(
+            Type::Union(Union {
+                span,
+                types,
+                metadata: Default::default(),
+            })
+            .fixed(),
+        ))
+    }
+
+    /// Evaluates conditional types in the tail position of an evaluated
+    /// conditional type iteratively, so recursive types like parsers of
+    /// template literal types do not nest.
+    ///
+    /// Returns the first type which is not an evaluable conditional type.
+    pub(crate) fn eval_tail_conditional_types(&mut self, span: Span, mut ty: Type) -> VResult<Type> {
+        for _ in 0..MAX_TAIL_RECURSION_DEPTH {
+            let c = match ty.normalize() {
+                Type::Conditional(c) => c.clone(),
+                Type::Ref(r @ Ref { type_args: Some(..), .. }) if self.is_alias_ref(r) => {
+                    let mut expanded = self
+                        .expand_top_ref(span, Cow::Borrowed(&ty), ExpandOpts::default())
+                        .context("tried to expand a ref type in the tail position of a conditional type")?
+                        .into_owned();
+                    expanded.make_clone_cheap();
+
+                    match expanded.normalize() {
+                        Type::Conditional(c) => c.clone(),
+                        _ => return Ok(ty),
+                    }
+                }
+                _ => return Ok(ty),
+            };
+
+            match self.eval_conditional_type(span, &c)? {
+                Some(next) => ty = next,
+                None => return Ok(Type::Conditional(c)),
+            }
+        }
+
+        Err(ErrorKind::InstantiationExcessivelyDeep { span }.into())
+    }
+
+    /// Returns `true` if `r` refers to a type alias, which may be expanded to a
+    /// conditional type.
+    fn is_alias_ref(&mut self, r: &Ref) -> bool {
+        let name = match &r.type_name {
+            RTsEntityName::Ident(i) => Id::from(i),
+            _ => return false,
+        };
+
+        match self.find_type(&name) {
+            Ok(Some(mut types)) => types.any(|ty| matches!(ty.normalize(), Type::Alias(..))),
+            _ => false,
+        }
+    }
+
+    /// Evaluates `c` using `check_type` as the check type.
+    fn eval_conditional_type_for(&mut self, span: Span, check_type: &Type, c: &Conditional) -> VResult<Option<Type>> {
+        if TypeFinder::find(check_type, |ty| ty.is_type_param()) {
+            return Ok(None);
+        }
+
+        if !contains_infer_type(&c.extends_type) {
+            if TypeFinder::find(&*c.extends_type, |ty| ty.is_type_param()) {
+                return Ok(None);
+            }
+
+            let extends_type = self
+                .normalize(Some(span), Cow::Borrowed(&c.extends_type), Default::default())
+                .context("tried to normalize the `extends` type of a conditional type")?;
+
+            // `any` matches both branches.
+            if check_type.is_any() && !extends_type.is_any() && !extends_type.is_unknown() {
+                return Ok(Some(Type::new_union(span, vec![*c.true_type.clone(), *c.false_type.clone()])));
+            }
+
+            return Ok(self.extends(span, check_type, &extends_type, Default::default()).map(|v| {
+                if v {
+                    *c.true_type.clone()
+                } else {
+                    *c.false_type.clone()
+                }
+            }));
+        }
+
+        // Bind `infer X` using the check type.
+        // Failing to infer does not mean that the check type does not match.
+        let mut inferred = match self.infer_ts_infer_types(span, &c.extends_type, check_type, Default::default()) {
+            Ok(v) => v,
+            Err(..) => return Ok(None),
+        };
+
+        let mut positions = Default::default();
+        collect_infer_positions(&c.extends_type, false, &mut positions);
+        for (name, ty) in inferred.iter_mut() {
+            // Candidates from parameter positions are intersected.
+            if positions.contravariant.contains(name) && !positions.covariant.contains(name) {
+                let intersection = match ty.normalize() {
+                    Type::Union(u) => Type::Intersection(Intersection {
+                        span: u.span,
+                        types: u.types.clone(),
+                        metadata: Default::default(),
+                    }),
+                    _ => continue,
+                };
+                *ty = intersection.fixed().freezed();
+            }
+        }
+
+        let extends_type = self.expand_type_params(&inferred, *c.extends_type.clone(), Default::default())?;
+        // Type parameters of the outer scope are not known yet.
+        if TypeFinder::find(&extends_type, |ty| ty.is_type_param()) {
+            return Ok(None);
+        }
+        if contains_infer_type(&extends_type) {
+            return Ok(Some(*c.false_type.clone()));
+        }
+
+        match self.extends(span, check_type, &extends_type, Default::default()) {
+            Some(true) => Ok(Some(self.expand_type_params(
+                &inferred,
+                *c.true_type.clone(),
+                Default::default(),
+            )?)),
+            Some(false) => Ok(Some(*c.false_type.clone())),
+            None => Ok(None),
+        }
+    }
+}
+
+#[derive(Default)]
+struct InferPositions {
+    covariant: FxHashSet<Id>,
+    contravariant: FxHashSet<Id>,
+}
+
+/// Records whether each `infer X` in `ty` appears in a parameter position.
+fn collect_infer_positions(ty: &Type, contravariant: bool, positions: &mut InferPositions) {
+    match ty.normalize() {
+        Type::Infer(i) => {
+            if contravariant {
+                positions.contravariant.insert(i.type_param.name.clone());
+            } else {
+                positions.covariant.insert(i.type_param.name.clone());
+            }
+        }
+        Type::Function(f) => {
+            for param in &f.params {
+                collect_infer_positions(&param.ty, !contravariant, positions);
+            }
+            collect_infer_positions(&f.ret_ty, contravariant, positions);
+        }
+        Type::Constructor(f) => {
+            for param in &f.params {
+                collect_infer_positions(&param.ty, !contravariant, positions);
+            }
+            collect_infer_positions(&f.type_ann, contravariant, positions);
+        }
+        Type::TypeLit(lit) => {
+            for member in &lit.members {
+                let (params, ret_ty) = match member {
+                    TypeElement::Call(m) => (&m.params, m.ret_ty.as_deref()),
+                    TypeElement::Constructor(m) => (&m.params, m.ret_ty.as_deref()),
+                    TypeElement::Property(m) => (&m.params, m.type_ann.as_deref()),
+                    TypeElement::Method(m) => (&m.params, m.ret_ty.as_deref()),
+                    TypeElement::Index(m) => (&m.params, m.type_ann.as_deref()),
+                };
+                for param in params {
+                    collect_infer_positions(&param.ty, !contravariant, positions);
+                }
+                if let Some(ret_ty) = ret_ty {
+                    collect_infer_positions(ret_ty, contravariant, positions);
+                }
+            }
+        }
+        Type::Tuple(t) => {
+            for elem in &t.elems {
+                collect_infer_positions(&elem.ty, contravariant, positions);
+            }
+        }
+        Type::Array(a) => collect_infer_positions(&a.elem_type, contravariant, positions),
+        Type::Union(Union { types, .. }) | Type::Intersection(Intersection { types, .. }) => {
+            for ty in types {
+                collect_infer_positions(ty, contravariant, positions);
+            }
+        }
+        Type::Ref(r) => {
+            if let Some(args) = &r.type_args {
+                for arg in &args.params {
+                    collect_infer_positions(arg, contravariant, positions);
+                }
+            }
+        }
+        _ => {}
+    }
+}
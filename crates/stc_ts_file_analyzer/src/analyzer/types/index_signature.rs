@@ -23,9 +23,13 @@ impl Analyzer<'_, '_> {
         .with_context(|| format!("tried to get index signature of '{}'", dump_type_as_string(ty)))
     }
 
+    /// Get the instance index signature of `class` or its super classes.
     pub(crate) fn get_index_signature_from_class(&mut self, span: Span, class: &ClassDef) -> VResult<Option<IndexSignature>> {
         for member in &class.body {
             if let ClassMember::IndexSignature(i) = member {
+                if i.is_static {
+                    continue;
+                }
                 return i.clone().as_some().as_ok();
             }
         }
@@ -0,0 +1,116 @@
+use std::borrow::Cow;
+
+use stc_ts_errors::DebugExt;
+use stc_ts_type_ops::{metadata::TypeFinder, Fix};
+use stc_ts_types::{IndexedAccessType, Intersection, Type, TypeParam};
+use stc_utils::cache::Freeze;
+use swc_common::Span;
+
+use crate::{analyzer::Analyzer, VResult};
+
+impl Analyzer<'_, '_> {
+    /// Resolves `T[K]` by replacing type parameters with their constraints.
+    ///
+    /// This is the type which can be read from `T[K]`. Returns `None` if
+    /// `iat` is not generic or a type parameter is not constrained.
+    pub(crate) fn base_constraint_of_indexed_access(&mut self, span: Span, iat: &IndexedAccessType) -> VResult<Option<Type>> {
+        if !is_generic_indexed_access(iat) {
+            return Ok(None);
+        }
+
+        let obj_type = match constraint_of(&iat.obj_type) {
+            Some(ty) => ty,
+            None => return Ok(None),
+        };
+        let index_type = match constraint_of(&iat.index_type) {
+            Some(ty) => ty,
+            None => return Ok(None),
+        };
+
+        let ty = Type::IndexedAccessType(IndexedAccessType {
+            span: iat.span,
+            readonly: iat.readonly,
+            obj_type: box obj_type,
+            index_type: box index_type,
+            metadata: iat.metadata,
+        })
+        .freezed();
+
+        let ty = self
+            .normalize(Some(span), Cow::Owned(ty), Default::default())
+            .context("tried to normalize the base constraint of an indexed access type")?
+            .into_owned();
+
+        match ty.normalize() {
+            Type::IndexedAccessType(..) => Ok(None),
+            _ => Ok(Some(ty)),
+        }
+    }
+
+    /// Returns the type which can be written to `T[K]`, where `T` is not
+    /// generic and `K` is a type parameter.
+    ///
+    /// As `K` can be any of the keys, this is the intersection of the types
+    /// of properties.
+    pub(crate) fn write_type_of_indexed_access(&mut self, span: Span, iat: &IndexedAccessType) -> VResult<Option<Type>> {
+        if TypeFinder::find(&*iat.obj_type, |ty| ty.is_type_param()) {
+            return Ok(None);
+        }
+
+        let keys = match iat.index_type.normalize() {
+            Type::Param(TypeParam {
+                constraint: Some(constraint),
+                ..
+            }) => self
+                .normalize(Some(span), Cow::Borrowed(constraint), Default::default())
+                .context("tried to normalize the constraint of an index type")?
+                .into_owned(),
+            _ => return Ok(None),
+        };
+
+        let mut types = vec![];
+        for key in keys.iter_union() {
+            let ty = Type::IndexedAccessType(IndexedAccessType {
+                span: iat.span,
+                readonly: iat.readonly,
+                obj_type: iat.obj_type.clone(),
+                index_type: box key.clone(),
+                metadata: iat.metadata,
+            })
+            .freezed();
+
+            let ty = self
+                .normalize(Some(span), Cow::Owned(ty), Default::default())
+                .context("tried to get the type of a property to write to an indexed access type")?
+                .into_owned();
+            if ty.is_indexed_access_type() {
+                return Ok(None);
+            }
+            types.push(ty);
+        }
+
+        Ok(Some(
+            Type::Intersection(Intersection {
+                span: iat.span,
+                types,
+                metadata: Default::default(),
+            })
+            .fixed()
+            .freezed(),
+        ))
+    }
+}
+
+/// Returns true if the object type or the index type of `iat` depends on a
+/// type parameter.
+pub(crate) fn is_generic_indexed_access(iat: &IndexedAccessType) -> bool {
+    TypeFinder::find(&*iat.obj_type, |ty| ty.is_type_param()) || TypeFinder::find(&*iat.index_type, |ty| ty.is_type_param())
+}
+
+/// Replaces a type parameter with its constraint.
+fn constraint_of(ty: &Type) -> Option<Type> {
+    match ty.normalize() {
+        Type::Param(TypeParam { constraint, .. }) => constraint_of(constraint.as_deref()?),
+        _ => Some(ty.clone()),
+    }
+}
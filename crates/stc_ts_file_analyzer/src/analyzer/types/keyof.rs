@@ -9,13 +9,13 @@ use stc_ts_errors::{
 };
 use stc_ts_type_ops::is_str_lit_or_union;
 use stc_ts_types::{
-    Class, ClassMember, ClassProperty, KeywordType, KeywordTypeMetadata, LitType, Method, MethodSignature, PropertySignature, Ref, Type,
-    TypeElement, Union,
+    Class, ClassMember, ClassProperty, Key, KeywordType, KeywordTypeMetadata, LitType, Method, MethodSignature, Operator,
+    PropertySignature, Ref, Type, TypeElement, Union,
 };
 use stc_utils::{cache::Freeze, debug_ctx, ext::TypeVecExt, try_cache};
 use swc_atoms::js_word;
 use swc_common::{Span, SyntaxContext, TypeEq, DUMMY_SP};
-use swc_ecma_ast::TsKeywordTypeKind;
+use swc_ecma_ast::{TsKeywordTypeKind, TsTypeOperatorOp};
 
 use crate::{
     analyzer::{types::NormalizeTypeOpts, Analyzer},
@@ -126,7 +126,11 @@ impl Analyzer<'_, '_> {
                             metadata: Default::default(),
                         }));
                     }
-                    TsKeywordTypeKind::TsNumberKeyword | TsKeywordTypeKind::TsBooleanKeyword | TsKeywordTypeKind::TsStringKeyword => {
+                    TsKeywordTypeKind::TsNumberKeyword
+                    | TsKeywordTypeKind::TsBooleanKeyword
+                    | TsKeywordTypeKind::TsStringKeyword
+                    | TsKeywordTypeKind::TsBigIntKeyword
+                    | TsKeywordTypeKind::TsSymbolKeyword => {
                         let name = match kind {
                             TsKeywordTypeKind::TsNumberKeyword => {
                                 js_word!("Number")
@@ -137,6 +141,10 @@ impl Analyzer<'_, '_> {
                             TsKeywordTypeKind::TsStringKeyword => {
                                 js_word!("String")
                             }
+                            TsKeywordTypeKind::TsBigIntKeyword => "BigInt".into(),
+                            TsKeywordTypeKind::TsSymbolKeyword => {
+                                js_word!("Symbol")
+                            }
                             _ => unreachable!(),
                         };
                         return self
@@ -152,8 +160,6 @@ impl Analyzer<'_, '_> {
                             .context("tried to get keys of builitin interface types");
                     }
 
-                    TsKeywordTypeKind::TsBigIntKeyword => {}
-                    TsKeywordTypeKind::TsSymbolKeyword => {}
                     TsKeywordTypeKind::TsNeverKeyword => {
                         return Ok(Type::Union(Union {
                             span,
@@ -186,15 +192,12 @@ impl Analyzer<'_, '_> {
                         for member in &l.members {
                             match member {
                                 TypeElement::Property(PropertySignature { key, .. }) | TypeElement::Method(MethodSignature { key, .. }) => {
-                                    if !key.is_computed() {
-                                        types.push(key.ty().into_owned());
-                                    }
+                                    types.extend(key_type(key));
                                 }
 
                                 TypeElement::Index(i) => {
-                                    // TODO(kdy1): Check if this is correct.
                                     if let Some(p) = i.params.first() {
-                                        types.push(*p.ty.clone());
+                                        types.extend(index_key_types(span, &p.ty));
                                     }
                                 }
 
@@ -217,15 +220,15 @@ impl Analyzer<'_, '_> {
                     let mut key_types = vec![];
                     for member in &cls.body {
                         match member {
-                            ClassMember::Property(ClassProperty { key, .. }) | ClassMember::Method(Method { key, .. }) => {
-                                if !key.is_computed() {
-                                    key_types.push(key.ty().into_owned());
-                                }
+                            ClassMember::Property(ClassProperty { key, is_static: false, .. })
+                            | ClassMember::Method(Method { key, is_static: false, .. }) => {
+                                key_types.extend(key_type(key));
                             }
+                            ClassMember::Property(..) | ClassMember::Method(..) => {}
                             ClassMember::Constructor(_) => {}
                             ClassMember::IndexSignature(i) => {
                                 if let Some(p) = i.params.first() {
-                                    key_types.push(*p.ty.clone());
+                                    key_types.extend(index_key_types(span, &p.ty));
                                 }
                             }
                         }
@@ -257,8 +260,9 @@ impl Analyzer<'_, '_> {
                                 ..Default::default()
                             },
                         )?;
-                        if let Some(rest) = elem_ty.as_rest() {
-                            types.push(self.keyof(elem.span, &rest.ty)?);
+                        if elem_ty.is_rest() {
+                            // Rest elements can be at any index.
+                            continue;
                         } else {
                             types.push(Type::Lit(LitType {
                                 span,
@@ -272,6 +276,20 @@ impl Analyzer<'_, '_> {
                         }
                     }
 
+                    // Tuples also have the members of arrays.
+                    types.push(
+                        self.keyof(
+                            span,
+                            &Type::Ref(Ref {
+                                span,
+                                type_name: RTsEntityName::Ident(RIdent::new(js_word!("Array"), DUMMY_SP)),
+                                type_args: None,
+                                metadata: Default::default(),
+                            }),
+                        )
+                        .context("tried to get keys of Array (builtin) for a tuple")?,
+                    );
+
                     return Ok(Type::new_union(span, types));
                 }
 
@@ -350,18 +368,25 @@ impl Analyzer<'_, '_> {
                     return Ok(Type::new_union(span, key_types));
                 }
 
-                Type::Param(..) => {
-                    return Ok(Type::Keyword(KeywordType {
+                Type::Param(param) => {
+                    // Deferred, as the keys of a type argument can be more than the keys of the
+                    // constraint.
+                    return Ok(Type::Operator(Operator {
                         span,
-                        kind: TsKeywordTypeKind::TsStringKeyword,
+                        op: TsTypeOperatorOp::KeyOf,
+                        ty: box Type::Param(param.clone()),
                         metadata: Default::default(),
-                    }))
+                    }));
                 }
 
                 Type::Mapped(m) => {
-                    //
-                    if let Some(ty) = m.type_param.constraint.as_deref() {
-                        return self.keyof(span, ty);
+                    if m.name_type.is_some() {
+                        // Keys are remapped, so we have to expand it.
+                        if let Some(expanded) = self.expand_mapped(span, m)? {
+                            return self.keyof(span, &expanded).context("tried to get keys of an expanded mapped type");
+                        }
+                    } else if let Some(ty) = m.type_param.constraint.as_deref() {
+                        return Ok(ty.clone());
                     }
                 }
 
@@ -376,3 +401,35 @@ impl Analyzer<'_, '_> {
         Ok(ty)
     }
 }
+
+/// Returns the type of `key` if it can be a member of `keyof`.
+fn key_type(key: &Key) -> Option<Type> {
+    match key {
+        Key::Private(..) => None,
+        Key::Computed(c) => match c.ty.normalize() {
+            Type::Lit(..) => Some(*c.ty.clone()),
+            ty if ty.is_symbol_like() => Some(*c.ty.clone()),
+            _ => None,
+        },
+        _ => Some(key.ty().into_owned()),
+    }
+}
+
+/// Returns the types of keys of an index signature with `ty` as the type of
+/// the key.
+///
+/// A string index signature also accepts numeric keys.
+fn index_key_types(span: Span, ty: &Type) -> Vec<Type> {
+    if ty.is_kwd(TsKeywordTypeKind::TsStringKeyword) {
+        return vec![
+            ty.clone(),
+            Type::Keyword(KeywordType {
+                span,
+                kind: TsKeywordTypeKind::TsNumberKeyword,
+                metadata: Default::default(),
+            }),
+        ];
+    }
+
+    vec![ty.clone()]
+}
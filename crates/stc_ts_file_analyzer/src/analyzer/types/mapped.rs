@@ -1,7 +1,7 @@
 use std::{borrow::Cow, collections::HashMap};
 
 use rnode::{NodeId, Visit, VisitMut, VisitMutWith, VisitWith};
-use stc_ts_ast_rnode::{RBindingIdent, RIdent, RPat, RTsEnumMemberId, RTsLit};
+use stc_ts_ast_rnode::{RBindingIdent, RIdent, RNumber, RPat, RTsEnumMemberId, RTsLit};
 use stc_ts_base_type_ops::apply_mapped_flags;
 use stc_ts_errors::{
     debug::{dump_type_as_string, force_dump_type_as_string},
@@ -9,8 +9,8 @@ use stc_ts_errors::{
 };
 use stc_ts_generics::type_param::finder::TypeParamNameUsageFinder;
 use stc_ts_types::{
-    Array, Conditional, FnParam, Id, IndexSignature, IndexedAccessType, Key, KeywordType, LitType, Mapped, Operator, PropertySignature,
-    Type, TypeElement, TypeLit, TypeParam,
+    Array, Conditional, FnParam, Id, IndexSignature, IndexedAccessType, Key, KeywordType, LitType, Mapped, Operator, OptionalType,
+    PropertySignature, Tuple, TupleElement, Type, TypeElement, TypeLit, TypeParam,
 };
 use stc_utils::cache::{Freeze, ALLOW_DEEP_CLONE};
 use swc_common::{Span, Spanned, SyntaxContext, TypeEq};
@@ -58,7 +58,13 @@ impl Analyzer<'_, '_> {
                     .normalize(Some(span), Cow::Borrowed(keyof_operand), Default::default())
                     .context("tried to normalize the operand of `in keyof`")?;
 
-                if let Some(mapped_ty) = m.ty.as_deref().map(Type::normalize) {
+                if m.name_type.is_none() {
+                    if let Some(ty) = self.expand_mapped_over_array(span, m, &keyof_operand)? {
+                        return Ok(Some(ty));
+                    }
+                }
+
+                if let Some(mapped_ty) = m.ty.as_deref().map(Type::normalize).filter(|_| m.name_type.is_none()) {
                     // Special case, but many usages can be handled with this check.
                     if (*keyof_operand).type_eq(mapped_ty) {
                         let new_type = self
@@ -76,16 +82,6 @@ impl Analyzer<'_, '_> {
                     }
                 }
 
-                if let Some(array) = keyof_operand.as_array_without_readonly() {
-                    let ty = Type::Array(Array {
-                        span,
-                        elem_type: m.ty.clone().unwrap_or_else(|| box Type::any(span, Default::default())),
-                        metadata: array.metadata,
-                    })
-                    .freezed();
-                    return Ok(Some(ty));
-                }
-
                 if let Type::Param(TypeParam {
                     constraint: Some(constraint),
                     ..
@@ -104,64 +100,23 @@ impl Analyzer<'_, '_> {
 
                 let keys = self.get_property_names_for_mapped_type(span, &keyof_operand)?;
                 if let Some(keys) = keys {
-                    let members = keys
-                        .into_iter()
-                        .map(|key| -> VResult<_> {
-                            match key {
-                                PropertyName::Key(key) => {
-                                    let ty = match &m.ty {
-                                        Some(mapped_ty) => self
-                                            .expand_key_in_mapped(m.type_param.name.clone(), mapped_ty, &key)
-                                            .map(Box::new)
-                                            .map(Some)?,
-                                        None => None,
-                                    };
-
-                                    let p = PropertySignature {
-                                        span: key.span(),
-                                        accessibility: None,
-                                        readonly: false,
-                                        key,
-                                        optional: false,
-                                        params: Default::default(),
-                                        type_ann: ty,
-                                        type_params: Default::default(),
-                                        metadata: Default::default(),
-                                        accessor: Default::default(),
-                                    };
-                                    let mut el = TypeElement::Property(p);
-
-                                    apply_mapped_flags(&mut el, m.optional, m.readonly);
-                                    Ok(el)
-                                }
-                                PropertyName::IndexSignature { span, params, readonly } => {
-                                    let ty = match &m.ty {
-                                        Some(mapped_ty) => {
-                                            let mut map = HashMap::default();
-                                            map.insert(m.type_param.name.clone(), *params[0].ty.clone());
-                                            self.expand_type_params(&map, m.ty.clone(), Default::default())?
-                                        }
-                                        None => None,
-                                    };
-
-                                    Ok(TypeElement::Index(IndexSignature {
-                                        span,
-                                        is_static: false,
-                                        params,
-                                        type_ann: ty,
-                                        readonly: match m.readonly {
-                                            Some(v) => match v {
-                                                TruePlusMinus::True => true,
-                                                TruePlusMinus::Plus => true,
-                                                TruePlusMinus::Minus => false,
-                                            },
-                                            None => readonly,
-                                        },
-                                    }))
-                                }
+                    // Used to preserve modifiers of properties.
+                    let orig = self
+                        .convert_type_to_type_lit(span, Cow::Borrowed(&keyof_operand))?
+                        .map(Cow::into_owned);
+
+                    let mut members = vec![];
+                    for key in keys {
+                        match key {
+                            PropertyName::Key(key) => match self.expand_mapped_property(span, m, key, orig.as_ref())? {
+                                Some(elements) => members.extend(elements),
+                                None => return Ok(None),
+                            },
+                            PropertyName::IndexSignature { span, params, readonly } => {
+                                members.push(self.expand_mapped_index_signature(m, span, params, readonly)?);
                             }
-                        })
-                        .collect::<Result<_, _>>()?;
+                        }
+                    }
 
                     return Ok(Some(Type::TypeLit(TypeLit {
                         span: m.span,
@@ -238,34 +193,13 @@ impl Analyzer<'_, '_> {
                     }
 
                     if let Some(keys) = self.convert_type_to_keys(span, constraint)? {
-                        let members = keys
-                            .into_iter()
-                            .map(|key| -> VResult<_> {
-                                let ty = match &m.ty {
-                                    Some(mapped_ty) => self
-                                        .expand_key_in_mapped(m.type_param.name.clone(), mapped_ty, &key)
-                                        .map(Box::new)
-                                        .map(Some)?,
-                                    None => None,
-                                };
-                                let p = PropertySignature {
-                                    span: key.span(),
-                                    accessibility: None,
-                                    readonly: false,
-                                    key,
-                                    optional: false,
-                                    params: Default::default(),
-                                    type_ann: ty,
-                                    type_params: Default::default(),
-                                    metadata: Default::default(),
-                                    accessor: Default::default(),
-                                };
-                                let mut el = TypeElement::Property(p);
-                                apply_mapped_flags(&mut el, m.optional, m.readonly);
-
-                                Ok(el)
-                            })
-                            .collect::<Result<_, _>>()?;
+                        let mut members = vec![];
+                        for key in keys {
+                            match self.expand_mapped_property(span, m, key, None)? {
+                                Some(elements) => members.extend(elements),
+                                None => return Ok(None),
+                            }
+                        }
 
                         return Ok(Some(Type::TypeLit(TypeLit {
                             span: m.span,
@@ -280,6 +214,220 @@ impl Analyzer<'_, '_> {
         Ok(None)
     }
 
+    /// Creates properties for `key` of a mapped type.
+    ///
+    /// `orig` is the operand of `keyof` of a homomorphic mapped type, and
+    /// modifiers of its properties are preserved.
+    ///
+    /// Returns `None` if the key remapped by the `as` clause cannot be
+    /// resolved.
+    fn expand_mapped_property(&mut self, span: Span, m: &Mapped, key: Key, orig: Option<&TypeLit>) -> VResult<Option<Vec<TypeElement>>> {
+        let keys = match self.remap_mapped_key(span, m, &key)? {
+            Some(keys) => keys,
+            None => return Ok(None),
+        };
+        if keys.is_empty() {
+            return Ok(Some(vec![]));
+        }
+
+        let mut ty = match &m.ty {
+            Some(mapped_ty) => Some(self.expand_key_in_mapped(m.type_param.name.clone(), mapped_ty, &key)?),
+            None => None,
+        };
+        if let (Some(TruePlusMinus::Minus), Some(Type::Union(u))) = (m.optional, ty.as_ref().map(Type::normalize)) {
+            // `-?` removes `undefined` from the type of optional properties.
+            let types = u
+                .types
+                .iter()
+                .filter(|ty| !ty.is_kwd(TsKeywordTypeKind::TsUndefinedKeyword))
+                .cloned()
+                .collect::<Vec<_>>();
+            ty = Some(Type::new_union(u.span, types));
+        }
+        let ty = ty.map(|ty| box ty.freezed());
+
+        let (optional, readonly) = orig
+            .and_then(|orig| {
+                orig.members.iter().find_map(|member| match member {
+                    TypeElement::Property(p) if p.key.type_eq(&key) => Some((p.optional, p.readonly)),
+                    TypeElement::Method(method) if method.key.type_eq(&key) => Some((method.optional, method.readonly)),
+                    _ => None,
+                })
+            })
+            .unwrap_or_default();
+
+        Ok(Some(
+            keys.into_iter()
+                .map(|key| {
+                    let mut el = TypeElement::Property(PropertySignature {
+                        span: key.span(),
+                        accessibility: None,
+                        readonly,
+                        key,
+                        optional,
+                        params: Default::default(),
+                        type_ann: ty.clone(),
+                        type_params: Default::default(),
+                        metadata: Default::default(),
+                        accessor: Default::default(),
+                    });
+                    apply_mapped_flags(&mut el, m.optional, m.readonly);
+                    el
+                })
+                .collect(),
+        ))
+    }
+
+    fn expand_mapped_index_signature(&mut self, m: &Mapped, span: Span, params: Vec<FnParam>, readonly: bool) -> VResult<TypeElement> {
+        let ty = match &m.ty {
+            Some(..) => {
+                let mut map = HashMap::default();
+                map.insert(m.type_param.name.clone(), *params[0].ty.clone());
+                self.expand_type_params(&map, m.ty.clone(), Default::default())?
+            }
+            None => None,
+        };
+
+        Ok(TypeElement::Index(IndexSignature {
+            span,
+            is_static: false,
+            params,
+            type_ann: ty,
+            readonly: match m.readonly {
+                Some(v) => match v {
+                    TruePlusMinus::True => true,
+                    TruePlusMinus::Plus => true,
+                    TruePlusMinus::Minus => false,
+                },
+                None => readonly,
+            },
+        }))
+    }
+
+    /// Applies the `as` clause of a mapped type to `key`.
+    ///
+    /// Returns an empty vector if the key is filtered out using `never`.
+    fn remap_mapped_key(&mut self, span: Span, m: &Mapped, key: &Key) -> VResult<Option<Vec<Key>>> {
+        let name_type = match &m.name_type {
+            Some(name_type) => name_type,
+            None => return Ok(Some(vec![key.clone()])),
+        };
+
+        let name_type = self.expand_key_in_mapped(m.type_param.name.clone(), name_type, key)?;
+        let mut name_type = self
+            .normalize(Some(span), Cow::Owned(name_type), Default::default())
+            .context("tried to normalize the `as` clause of a mapped type")?
+            .into_owned();
+        let tpl = match name_type.normalize() {
+            Type::Tpl(tpl) => Some((tpl.quasis.clone(), tpl.types.clone())),
+            _ => None,
+        };
+        if let Some((quasis, types)) = tpl {
+            name_type = self.create_tpl_type(span, &quasis, types)?;
+        }
+
+        self.convert_type_to_keys(span, &name_type)
+    }
+
+    /// Expands a homomorphic mapped type over an array or a tuple, preserving
+    /// its shape.
+    fn expand_mapped_over_array(&mut self, span: Span, m: &Mapped, operand: &Type) -> VResult<Option<Type>> {
+        let mapped_ty = match &m.ty {
+            Some(mapped_ty) => mapped_ty,
+            None => return Ok(None),
+        };
+
+        let (was_readonly, operand) = match operand.normalize() {
+            Type::Operator(Operator {
+                op: TsTypeOperatorOp::ReadOnly,
+                ty,
+                ..
+            }) => (true, ty.normalize()),
+            ty => (false, ty),
+        };
+        let readonly = match m.readonly {
+            Some(TruePlusMinus::True | TruePlusMinus::Plus) => true,
+            Some(TruePlusMinus::Minus) => false,
+            None => was_readonly,
+        };
+
+        let ty = match operand {
+            Type::Array(array) => {
+                let mut map = HashMap::default();
+                map.insert(
+                    m.type_param.name.clone(),
+                    Type::Keyword(KeywordType {
+                        span,
+                        kind: TsKeywordTypeKind::TsNumberKeyword,
+                        metadata: Default::default(),
+                    }),
+                );
+
+                Type::Array(Array {
+                    span,
+                    elem_type: box self.expand_type_params(&map, *mapped_ty.clone(), Default::default())?,
+                    metadata: array.metadata,
+                })
+            }
+            Type::Tuple(tuple) => {
+                let mut elems = vec![];
+                for (idx, elem) in tuple.elems.iter().enumerate() {
+                    let mut map = HashMap::default();
+                    map.insert(
+                        m.type_param.name.clone(),
+                        Type::Lit(LitType {
+                            span,
+                            lit: RTsLit::Number(RNumber {
+                                span,
+                                value: idx as f64,
+                                raw: None,
+                            }),
+                            metadata: Default::default(),
+                        }),
+                    );
+                    let mut ty = self.expand_type_params(&map, *mapped_ty.clone(), Default::default())?;
+
+                    let is_optional = matches!(elem.ty.normalize(), Type::Optional(..));
+                    let optional = match m.optional {
+                        Some(TruePlusMinus::True | TruePlusMinus::Plus) => true,
+                        Some(TruePlusMinus::Minus) => false,
+                        None => is_optional,
+                    };
+                    if optional {
+                        ty = Type::Optional(OptionalType {
+                            span: elem.span,
+                            ty: box ty,
+                            metadata: Default::default(),
+                        });
+                    }
+
+                    elems.push(TupleElement {
+                        ty: box ty,
+                        ..elem.clone()
+                    });
+                }
+
+                Type::Tuple(Tuple {
+                    span,
+                    elems,
+                    metadata: tuple.metadata,
+                })
+            }
+            _ => return Ok(None),
+        };
+
+        if readonly {
+            return Ok(Some(Type::Operator(Operator {
+                span,
+                op: TsTypeOperatorOp::ReadOnly,
+                ty: box ty,
+                metadata: Default::default(),
+            })));
+        }
+
+        Ok(Some(ty))
+    }
+
     /// TODO(kdy1): Optimize
     fn expand_key_in_mapped(&mut self, mapped_type_param: Id, mapped_ty: &Type, key: &Key) -> VResult<Type> {
         let mapped_ty = mapped_ty.clone();
@@ -291,7 +439,7 @@ impl Analyzer<'_, '_> {
     /// Evaluate a type and convert it to keys.
     ///
     /// Used for types like `'foo' | 'bar'` or alias of them.
-    fn convert_type_to_keys(&mut self, span: Span, ty: &Type) -> VResult<Option<Vec<Key>>> {
+    pub(crate) fn convert_type_to_keys(&mut self, span: Span, ty: &Type) -> VResult<Option<Vec<Key>>> {
         let ty = ty.normalize();
 
         match ty {
@@ -325,6 +473,12 @@ impl Analyzer<'_, '_> {
                 RTsLit::Bool(_) | RTsLit::Tpl(_) => Ok(None),
             },
 
+            // Filtered out by an `as` clause.
+            Type::Keyword(KeywordType {
+                kind: TsKeywordTypeKind::TsNeverKeyword,
+                ..
+            }) => Ok(Some(vec![])),
+
             Type::Union(u) => {
                 let mut keys = vec![];
 
@@ -6,11 +6,12 @@ use rnode::{VisitMutWith, VisitWith};
 use stc_ts_ast_rnode::{RExpr, RIdent, RInvalid, RLit, RNumber, RStr, RTplElement, RTsEntityName, RTsEnumMemberId, RTsLit};
 use stc_ts_base_type_ops::bindings::{collect_bindings, BindingCollector, KnownTypeVisitor};
 use stc_ts_errors::{
-    debug::{dump_type_as_string, force_dump_type_as_string, print_backtrace},
+    debug::{dump_type_as_string, dump_type_name, force_dump_type_as_string, print_backtrace},
     DebugExt, ErrorKind,
 };
+use stc_ts_env::perf::{instantiation_depth, InstantiationGuard, OpKind};
 use stc_ts_generics::ExpandGenericOpts;
-use stc_ts_type_ops::{tuple_normalization::TupleNormalizer, Fix};
+use stc_ts_type_ops::{metadata::TypeFinder, tuple_normalization::TupleNormalizer, Fix};
 use stc_ts_types::{
     name::Name, Accessor, Array, Class, ClassDef, ClassMember, ClassMetadata, ComputedKey, Conditional, ConditionalMetadata,
     ConstructorSignature, EnumVariant, Id, IdCtx, IndexedAccessType, Instance, InstanceMetadata, Intersection, Intrinsic, IntrinsicKind,
@@ -32,11 +33,15 @@ use tracing::{debug, error, instrument, span, Level};
 use crate::{
     analyzer::{expr::TypeOfMode, generic::ExtendsOpts, scope::ExpandOpts, Analyzer, Ctx},
     type_facts::TypeFacts,
-    util::unwrap_ref_with_single_arg,
+    util::{contains_infer_type, find_assigned_vars, unwrap_ref_with_single_arg, AssignedVarFinder},
     VResult,
 };
 
+pub(crate) use self::indexed_access::is_generic_indexed_access;
+
+mod conditional;
 mod index_signature;
+mod indexed_access;
 mod keyof;
 mod mapped;
 mod narrowing;
@@ -105,8 +110,7 @@ impl Analyzer<'_, '_> {
             | Type::EnumVariant(..)
             | Type::Enum(..)
             | Type::Param(_)
-            | Type::Module(_)
-            | Type::Tpl(..) => return Ok(ty),
+            | Type::Module(_) => return Ok(ty),
             _ => {}
         }
 
@@ -114,9 +118,25 @@ impl Analyzer<'_, '_> {
         let input = dump_type_as_string(&ty);
 
         let res = (|| {
-            let _stack = stack::track(actual_span)?;
+            let _stack = match stack::track(actual_span) {
+                Ok(v) => v,
+                // Recursion caused by instantiation of recursive types.
+                Err(..) if instantiation_depth() > 0 => return Err(ErrorKind::InstantiationExcessivelyDeep { span: actual_span }.into()),
+                Err(err) => return Err(err.into()),
+            };
             let _context = debug_ctx!(format!("Normalize: {}", dump_type_as_string(&ty)));
 
+            let _instantiation = match ty.normalize() {
+                Type::Ref(Ref { type_args: Some(..), .. }) | Type::Conditional(..) => {
+                    let guard = InstantiationGuard::enter();
+                    if guard.is_excessive() {
+                        return Err(ErrorKind::InstantiationExcessivelyDeep { span: actual_span }.into());
+                    }
+                    Some(guard)
+                }
+                _ => None,
+            };
+
             if matches!(&*ty, Type::Arc(..)) {
                 let ty = self.normalize(span, Cow::Borrowed(ty.normalize()), opts)?.into_owned();
 
@@ -205,6 +225,23 @@ impl Analyzer<'_, '_> {
                         return Ok(Cow::Owned(ty));
                     }
 
+                    Type::Tpl(tpl) => {
+                        // Generic template literal types are resolved on instantiation.
+                        if tpl
+                            .types
+                            .iter()
+                            .any(|ty| contains_infer_type(ty) || TypeFinder::find(ty, |ty| ty.is_type_param()))
+                        {
+                            return Ok(ty);
+                        }
+
+                        let ty = self
+                            .create_tpl_type(actual_span, &tpl.quasis, tpl.types.clone())
+                            .context("tried to resolve a template literal type")?;
+
+                        return Ok(Cow::Owned(ty));
+                    }
+
                     // Leaf types.
                     Type::Array(arr) => {
                         // TODO(kdy1): Optimize
@@ -276,6 +313,14 @@ impl Analyzer<'_, '_> {
                     }
 
                     Type::Conditional(c) => {
+                        if let Some(ty) = self.eval_conditional_type(actual_span, c)? {
+                            let ty = self.eval_tail_conditional_types(actual_span, ty)?;
+
+                            return self
+                                .normalize(span, Cow::Owned(ty), opts)
+                                .context("tried to normalize the evaluated type of a conditional type");
+                        }
+
                         let mut check_type = self
                             .normalize(span, Cow::Borrowed(&c.check_type), Default::default())
                             .context("tried to normalize the `check` type of a conditional type")?
@@ -290,9 +335,10 @@ impl Analyzer<'_, '_> {
 
                         if let Some(v) = self.extends(ty.span(), &check_type, &extends_type, Default::default()) {
                             let ty = if v { &c.true_type } else { &c.false_type };
+                            let ty = self.eval_tail_conditional_types(actual_span, (**ty).clone())?;
                             // TODO(kdy1): Optimize
                             let ty = self
-                                .normalize(span, Cow::Borrowed(ty), opts)
+                                .normalize(span, Cow::Owned(ty), opts)
                                 .context("tried to normalize the calculated type of a conditional type")?
                                 .into_owned();
                             return Ok(Cow::Owned(ty));
@@ -321,41 +367,6 @@ impl Analyzer<'_, '_> {
                             }
                         }
 
-                        if let Type::Union(check_type_union) = check_type.normalize() {
-                            let mut all = true;
-                            let mut types = vec![];
-                            for check_type in &check_type_union.types {
-                                let res = self.extends(ty.span(), check_type, &extends_type, Default::default());
-                                if let Some(v) = res {
-                                    if v {
-                                        if !c.true_type.is_never() {
-                                            types.push(check_type.clone());
-                                        }
-                                    } else {
-                                        if !c.false_type.is_never() {
-                                            types.push(check_type.clone());
-                                        }
-                                    }
-                                } else {
-                                    all = false;
-                                    break;
-                                }
-                            }
-
-                            if all {
-                                let new = Type::Union(Union {
-                                    span: actual_span.with_ctxt(SyntaxContext::empty()),
-                                    types,
-                                    metadata: Default::default(),
-                                })
-                                .fixed();
-
-                                new.assert_valid();
-
-                                return Ok(Cow::Owned(new));
-                            }
-                        }
-
                         // TOOD: Optimize
                         // If we can calculate type using constraints, do so.
 
@@ -499,6 +510,17 @@ impl Analyzer<'_, '_> {
                             .into_owned()
                             .freezed();
 
+                        // `T[K]` is resolved on instantiation of `K`.
+                        if index_ty.is_type_param() {
+                            return Ok(Cow::Owned(Type::IndexedAccessType(IndexedAccessType {
+                                span: iat.span,
+                                readonly: iat.readonly,
+                                obj_type: obj_ty,
+                                index_type: index_ty,
+                                metadata: iat.metadata,
+                            })));
+                        }
+
                         let ctx = Ctx {
                             disallow_unknown_object_property: true,
                             ..self.ctx
@@ -565,6 +587,12 @@ impl Analyzer<'_, '_> {
         })();
 
         if let Ok(res) = &res {
+            if let Some(perf) = self.env.perf() {
+                if let Type::Union(u) = res.normalize() {
+                    perf.record_op(OpKind::NormalizeUnion, actual_span, u.types.len() as u64, || dump_type_name(res));
+                }
+            }
+
             #[cfg(debug_assertions)]
             let output = dump_type_as_string(res);
 
@@ -1665,6 +1693,27 @@ impl Analyzer<'_, '_> {
                 }));
             }
 
+            Type::Union(u) => {
+                let types = u
+                    .types
+                    .iter()
+                    .map(|member| {
+                        self.expand_intrinsic_types(
+                            span,
+                            &Intrinsic {
+                                type_args: TypeParamInstantiation {
+                                    span: ty.type_args.span,
+                                    params: vec![member.clone()],
+                                },
+                                ..ty.clone()
+                            },
+                        )
+                    })
+                    .collect::<VResult<Vec<_>>>()?;
+
+                return Ok(Type::new_union(span, types));
+            }
+
             Type::Param(TypeParam {
                 span: param_span,
                 name,
@@ -1945,7 +1994,7 @@ impl Analyzer<'_, '_> {
     /// We precomputes all type declarations in the scope, using this method.
     pub(crate) fn fill_known_type_names<N>(&mut self, node: &N)
     where
-        N: Send + Sync + for<'aa> VisitWith<BindingCollector<'aa>> + VisitWith<KnownTypeVisitor>,
+        N: Send + Sync + for<'aa> VisitWith<BindingCollector<'aa>> + VisitWith<KnownTypeVisitor> + VisitWith<AssignedVarFinder>,
     {
         if self.is_builtin {
             return;
@@ -1955,6 +2004,7 @@ impl Analyzer<'_, '_> {
         }
 
         self.data.bindings = collect_bindings(node);
+        self.data.assigned_vars = find_assigned_vars(node);
     }
 }
 
@@ -24,7 +24,7 @@ use crate::{
 impl Analyzer<'_, '_> {
     /// Prints type for visualization testing.
     pub(crate) fn dump_type(&mut self, span: Span, ty: &Type) {
-        if !cfg!(debug_assertions) {
+        if self.debugger.is_none() || (!cfg!(debug_assertions) && !self.is_recording_types()) {
             return;
         }
         let ty = match ty.normalize() {
@@ -41,6 +41,23 @@ impl Analyzer<'_, '_> {
         }
     }
 
+    /// Records the type of a variable declaration for the type dump.
+    pub(crate) fn dump_decl_type(&self, span: Span, ty: &Type) {
+        if span.is_dummy() || !self.is_recording_types() {
+            return;
+        }
+
+        if let Some(debugger) = &self.debugger {
+            ALLOW_DEEP_CLONE.set(&(), || {
+                debugger.dump_decl_type(span, ty);
+            });
+        }
+    }
+
+    fn is_recording_types(&self) -> bool {
+        matches!(&self.debugger, Some(debugger) if debugger.records.is_some())
+    }
+
     /// `span` and `callee` is used only for error reporting.
     #[cfg_attr(debug_assertions, tracing::instrument(skip_all))]
     fn make_instance_from_type_elements(&mut self, span: Span, callee: &Type, elements: &[TypeElement]) -> VResult<Type> {
@@ -5,7 +5,7 @@ use stc_ts_ast_rnode::{
     RArrowExpr, RBlockStmt, RCatchClause, RClass, RClassDecl, RClassExpr, RClassMember, RClassMethod, RComputedPropName, RConstructor,
     RDoWhileStmt, RExportAll, RExportDecl, RExportDefaultDecl, RExportDefaultExpr, RExportNamedSpecifier, RExpr, RExprStmt, RFnDecl,
     RFnExpr, RForInStmt, RForOfStmt, RForStmt, RFunction, RIfStmt, RImportDecl, RModule, RModuleItem, RNamedExport, RObjectLit, RParam,
-    RParamOrTsParamProp, RPrivateMethod, RPrivateProp, RPropName, RReturnStmt, RSeqExpr, RStmt, RSwitchStmt, RTpl, RTsEnumDecl,
+    RParamOrTsParamProp, RPrivateMethod, RPrivateProp, RPropName, RReturnStmt, RSeqExpr, RStmt, RSwitchStmt, RTpl, RTryStmt, RTsEnumDecl,
     RTsExportAssignment, RTsFnParam, RTsFnType, RTsImportEqualsDecl, RTsInterfaceBody, RTsInterfaceDecl, RTsModuleBlock, RTsModuleDecl,
     RTsNamespaceDecl, RTsParamProp, RTsTplLitType, RTsType, RTsTypeAliasDecl, RTsTypeElement, RVarDecl, RVarDeclarator, RWhileStmt,
     RWithStmt, RYieldExpr,
@@ -79,6 +79,7 @@ forward!(visit_mut_import_decl, RImportDecl);
 forward!(visit_mut_export_all, RExportAll);
 forward!(visit_mut_named_export, RNamedExport);
 forward!(visit_mut_catch_clause, RCatchClause);
+forward!(visit_mut_try_stmt, RTryStmt);
 forward!(visit_mut_ts_namespace_decl, RTsNamespaceDecl);
 forward!(visit_do_while_stmt, RDoWhileStmt);
 forward!(visit_while_stmt, RWhileStmt);
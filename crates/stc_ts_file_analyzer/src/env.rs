@@ -1,4 +1,13 @@
-use std::{collections::hash_map::Entry, path::Path, sync::Arc, time::Instant};
+use std::{
+    collections::hash_map::Entry,
+    env,
+    fmt::{self, Display, Formatter},
+    fs,
+    path::{Path, PathBuf},
+    process,
+    sync::Arc,
+    time::Instant,
+};
 
 use dashmap::DashMap;
 use once_cell::sync::{Lazy, OnceCell};
@@ -7,7 +16,7 @@ use rustc_hash::FxHashMap;
 use sha1::{Digest, Sha1};
 use stc_ts_ast_rnode::{RDecl, RIdent, RModule, RModuleItem, RStmt, RTsModuleName, RVarDecl};
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{BuiltIn, Env, ModuleConfig, Rule, StableEnv};
+use stc_ts_env::{BuiltIn, BuiltInProvider, Env, ModuleConfig, Rule, StableEnv};
 use stc_ts_storage::Builtin;
 use stc_ts_type_ops::Fix;
 use stc_ts_types::{ClassDef, ModuleTypeData, Type};
@@ -15,7 +24,7 @@ use stc_utils::{cache::Freeze, stack};
 use swc_atoms::JsWord;
 use swc_common::DUMMY_SP;
 use swc_ecma_ast::*;
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::{
     analyzer::{Analyzer, ScopeKind},
@@ -27,25 +36,18 @@ pub trait BuiltInGen: Sized {
     fn new(vars: FxHashMap<JsWord, Type>, types: FxHashMap<JsWord, Type>) -> BuiltIn;
 
     fn from_ts_libs(env: &StableEnv, libs: &[Lib]) -> BuiltIn {
-        debug_assert_ne!(libs, &[], "No typescript library file is specified");
-
-        // Loading builtin is very slow, so we cache it to a file using serde_json
-
-        let key = {
-            let mut hasher = Sha1::new();
-            hasher.update(format!("{:?}", libs).as_bytes());
-            let result = hasher.finalize();
-
-            format!("{:x}", result)
-        };
+        Self::from_ts_libs_with_cache(env, libs, builtin_cache_dir().as_deref())
+    }
 
-        let cache_path = Path::new(".stc").join(".builtin-cache").join(&format!("{}.rmp", key));
+    /// Same as [BuiltInGen::from_ts_libs], but the cache is stored in
+    /// `cache_dir` instead of the directory selected by
+    /// `STC_BUILTIN_CACHE_DIR`. [None] disables the cache.
+    fn from_ts_libs_with_cache(env: &StableEnv, libs: &[Lib], cache_dir: Option<&Path>) -> BuiltIn {
+        debug_assert_ne!(libs, &[], "No typescript library file is specified");
 
-        if cache_path.is_file() {
-            let data =
-                std::fs::read(&cache_path).unwrap_or_else(|err| panic!("failed to read builtin cache at {:?}: {:?}", cache_path, err));
-            let builtin = rmp_serde::decode::from_slice(&data)
-                .unwrap_or_else(|err| panic!("failed to deserialize builtin cache at {:?}: {:?}", cache_path, err));
+        // Loading builtin is very slow, so we cache it to a file.
+        let cache_path = cache_dir.map(|dir| builtin_cache_path(dir, libs));
+        if let Some(builtin) = cache_path.as_deref().and_then(read_builtin_cache) {
             return builtin;
         }
 
@@ -68,15 +70,35 @@ pub trait BuiltInGen: Sized {
 
         let builtin = Self::from_module_items(env, iter);
 
-        let json_data = rmp_serde::encode::to_vec(&builtin).unwrap_or_else(|err| panic!("failed to serialize builtin cache: {:?}", err));
+        if let Some(cache_path) = &cache_path {
+            write_builtin_cache(cache_path, &builtin);
+        }
 
-        std::fs::create_dir_all(cache_path.parent().unwrap())
-            .unwrap_or_else(|err| panic!("failed to create directory for builtin cache at {:?}: {:?}", cache_path, err));
+        builtin
+    }
 
-        std::fs::write(&cache_path, &json_data)
-            .unwrap_or_else(|err| panic!("failed to write builtin cache at {:?}: {:?}", cache_path, err));
+    /// Loads the declarations provided by `provider`.
+    fn from_provider(env: &StableEnv, provider: &dyn BuiltInProvider) -> Result<BuiltIn, ProviderError> {
+        let _stack = stack::start(300);
 
-        builtin
+        let mut node_id_gen = NodeIdGenerator::default();
+
+        info!("Loading builtin from provider: {}", provider.id());
+
+        let mut items = vec![];
+        for src in provider.sources() {
+            let stmts = stc_ts_builtin_types::parse_declarations(&src).map_err(|err| ProviderError {
+                id: provider.id().into_owned(),
+                error: err,
+            })?;
+            items.extend(
+                stmts
+                    .into_iter()
+                    .map(|stmt| RModuleItem::from_orig(&mut node_id_gen, ModuleItem::Stmt(stmt))),
+            );
+        }
+
+        Ok(Self::from_module_items(env, items))
     }
 
     fn from_modules(env: &StableEnv, modules: Vec<RModule>) -> BuiltIn {
@@ -263,36 +285,153 @@ pub trait BuiltInGen: Sized {
     }
 }
 
+/// The cache is stored in `.stc/.builtin-cache`, which can be changed using
+/// `STC_BUILTIN_CACHE_DIR`. Returns [None] if it's set to an empty string.
+fn builtin_cache_dir() -> Option<PathBuf> {
+    match env::var_os("STC_BUILTIN_CACHE_DIR") {
+        Some(dir) if dir.is_empty() => None,
+        Some(dir) => Some(PathBuf::from(dir)),
+        None => Some(Path::new(".stc").join(".builtin-cache")),
+    }
+}
+
+/// The key includes the version of stc and the content of libs, so stale
+/// caches are not used.
+fn builtin_cache_path(dir: &Path, libs: &[Lib]) -> PathBuf {
+    let mut hasher = Sha1::new();
+    hasher.update(env!("CARGO_PKG_VERSION"));
+    for lib in libs {
+        hasher.update(format!("{:?}", lib));
+        hasher.update(lib.content());
+    }
+
+    dir.join(format!("{:x}.rmp", hasher.finalize()))
+}
+
+/// Invalid caches are ignored, as they are rebuilt anyway.
+fn read_builtin_cache(path: &Path) -> Option<BuiltIn> {
+    let data = fs::read(path).ok()?;
+
+    match rmp_serde::decode::from_slice(&data) {
+        Ok(builtin) => Some(builtin),
+        Err(err) => {
+            warn!("ignoring invalid builtin cache at {}: {:?}", path.display(), err);
+            None
+        }
+    }
+}
+
+/// Failures are logged and ignored, as the cache is only an optimization.
+fn write_builtin_cache(path: &Path, builtin: &BuiltIn) {
+    let data = match rmp_serde::encode::to_vec(builtin) {
+        Ok(data) => data,
+        Err(err) => {
+            warn!("failed to serialize builtin cache: {:?}", err);
+            return;
+        }
+    };
+
+    // Other processes may read the cache while we are writing it, so we write
+    // it to a temporary file first.
+    let tmp_path = path.with_extension(format!("rmp.{}", process::id()));
+    let res = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| fs::write(&tmp_path, &data))
+        .and_then(|_| fs::rename(&tmp_path, path));
+
+    if let Err(err) = res {
+        let _ = fs::remove_file(&tmp_path);
+        warn!("failed to write builtin cache at {}: {:?}", path.display(), err);
+    }
+}
+
+/// Declarations of a [BuiltInProvider] could not be parsed.
+#[derive(Debug)]
+pub struct ProviderError {
+    /// [BuiltInProvider::id] of the provider.
+    pub id: String,
+    pub error: swc_ecma_parser::error::Error,
+}
+
+impl Display for ProviderError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "failed to parse builtin declarations of `{}`: {}",
+            self.id,
+            self.error.kind().msg()
+        )
+    }
+}
+
+impl std::error::Error for ProviderError {}
+
 impl BuiltInGen for BuiltIn {
     fn new(vars: FxHashMap<JsWord, Type>, types: FxHashMap<JsWord, Type>) -> BuiltIn {
         BuiltIn::new(vars, types)
     }
 }
 
+static STABLE_ENV: Lazy<StableEnv> = Lazy::new(Default::default);
+
 pub trait EnvFactory {
     #[allow(clippy::new_ret_no_self)]
     fn new(env: StableEnv, rule: Rule, target: EsVersion, module: ModuleConfig, builtin: Arc<BuiltIn>) -> Env;
     fn simple(rule: Rule, target: EsVersion, module: ModuleConfig, libs: &[Lib]) -> Env {
-        static STABLE_ENV: Lazy<StableEnv> = Lazy::new(Default::default);
-        static CACHE: Lazy<DashMap<Vec<Lib>, Arc<OnceCell<Arc<BuiltIn>>>, ahash::RandomState>> = Lazy::new(Default::default);
-
-        // TODO(kdy1): Include `env` in cache
-        let mut libs = libs.to_vec();
-        libs.sort();
-        libs.dedup();
-
-        let cell = CACHE.entry(libs.clone()).or_default().clone();
-
-        let builtin = swc_common::GLOBALS.set(STABLE_ENV.swc_globals(), || {
-            let builtin = cell.get_or_init(|| {
-                let builtin = BuiltIn::from_ts_libs(&STABLE_ENV, &libs);
-                Arc::new(builtin)
-            });
-            (*builtin).clone()
-        });
+        let builtin = load_builtin(&STABLE_ENV, libs);
 
         Self::new(STABLE_ENV.clone(), rule, target, module, builtin)
     }
+
+    /// Same as [EnvFactory::simple], but declarations of globals are provided
+    /// by `provider`.
+    fn with_provider(rule: Rule, target: EsVersion, module: ModuleConfig, provider: &dyn BuiltInProvider) -> Result<Env, ProviderError> {
+        let builtin = load_builtin_from(&STABLE_ENV, provider)?;
+
+        Ok(Self::new(STABLE_ENV.clone(), rule, target, module, builtin))
+    }
+
+    /// Same as `base`, but declarations of globals are loaded from `libs`.
+    ///
+    /// This is used to check some files against other libs, like worker
+    /// entries against `webworker` instead of `dom`.
+    fn with_libs(base: &Env, libs: &[Lib]) -> Env {
+        base.with_builtin(load_builtin(base.shared(), libs))
+    }
+}
+
+/// Loads the builtin types of `libs`, reusing the result of previous calls.
+pub(crate) fn load_builtin(env: &StableEnv, libs: &[Lib]) -> Arc<BuiltIn> {
+    static CACHE: Lazy<DashMap<Vec<Lib>, Arc<OnceCell<Arc<BuiltIn>>>, ahash::RandomState>> = Lazy::new(Default::default);
+
+    // TODO(kdy1): Include `env` in cache
+    let mut libs = libs.to_vec();
+    libs.sort();
+    libs.dedup();
+
+    let cell = CACHE.entry(libs.clone()).or_default().clone();
+
+    swc_common::GLOBALS.set(env.swc_globals(), || {
+        let builtin = cell.get_or_init(|| {
+            let builtin = BuiltIn::from_ts_libs(env, &libs);
+            Arc::new(builtin)
+        });
+        (*builtin).clone()
+    })
+}
+
+/// Loads the declarations of `provider`, reusing the result of previous calls
+/// with a provider with same id. Failures are not cached.
+pub(crate) fn load_builtin_from(env: &StableEnv, provider: &dyn BuiltInProvider) -> Result<Arc<BuiltIn>, ProviderError> {
+    static CACHE: Lazy<DashMap<String, Arc<OnceCell<Arc<BuiltIn>>>, ahash::RandomState>> = Lazy::new(Default::default);
+
+    let cell = CACHE.entry(provider.id().into_owned()).or_default().clone();
+
+    swc_common::GLOBALS.set(env.swc_globals(), || {
+        let builtin = cell.get_or_try_init(|| BuiltIn::from_provider(env, provider).map(Arc::new))?;
+        Ok((*builtin).clone())
+    })
 }
 
 impl EnvFactory for Env {
@@ -22,6 +22,12 @@ pub struct ModuleInfo {
 pub trait Load: 'static + Send + Sync {
     fn module_id(&self, base: &Arc<FileName>, src: &JsWord) -> Option<ModuleId>;
 
+    /// Returns the pattern of `paths` in `tsconfig.json` matching `src`, which
+    /// is used to report a failure of resolution.
+    fn path_mapping_of(&self, _src: &JsWord) -> Option<JsWord> {
+        None
+    }
+
     /// Note: This method called within a thread
     fn is_in_same_circular_group(&self, base: ModuleId, dep: ModuleId) -> bool;
 
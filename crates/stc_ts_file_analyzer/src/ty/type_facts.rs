@@ -34,6 +34,8 @@ impl Analyzer<'_, '_> {
         if facts.contains(TypeFacts::TypeofEQNumber)
             || facts.contains(TypeFacts::TypeofEQString)
             || facts.contains(TypeFacts::TypeofEQBoolean)
+            || facts.contains(TypeFacts::TypeofEQBigInt)
+            || facts.contains(TypeFacts::TypeofEQSymbol)
         {
             match ty {
                 Type::Param(..) | Type::IndexedAccessType(..) => {
@@ -96,6 +98,7 @@ impl Analyzer<'_, '_> {
 
             // TODO(kdy1): PERF
             match ty.normalize_mut() {
+                ty if ty.is_unknown() => *ty = fn_type,
                 Type::Union(u) => {
                     let has_fn = u.types.iter().any(|ty| matches!(ty.normalize(), Type::Function(..)));
 
@@ -128,27 +131,151 @@ struct TypeFactsHandler<'a, 'b, 'c> {
 }
 
 impl TypeFactsHandler<'_, '_, '_> {
-    fn can_be_primitive(&mut self, ty: &Type) -> bool {
-        let ty = if let Ok(ty) = self.analyzer.normalize(
-            Some(ty.span()),
-            Cow::Borrowed(ty),
-            NormalizeTypeOpts {
-                preserve_global_this: true,
-                ..Default::default()
+    /// Returns the possible results of `typeof` for `ty`, as `TypeofEQ*`
+    /// facts. `"undefined"` is represented by [TypeFacts::EQUndefined].
+    ///
+    /// Returns [None] if the result cannot be determined.
+    fn typeof_facts(&mut self, ty: &Type) -> Option<TypeFacts> {
+        let ty = self
+            .analyzer
+            .normalize(
+                Some(ty.span()),
+                Cow::Borrowed(ty),
+                NormalizeTypeOpts {
+                    preserve_global_this: true,
+                    ..Default::default()
+                },
+            )
+            .ok()?;
+
+        Some(match ty.normalize() {
+            Type::Keyword(KeywordType { kind, .. }) => match kind {
+                TsKeywordTypeKind::TsStringKeyword => TypeFacts::TypeofEQString,
+                TsKeywordTypeKind::TsNumberKeyword => TypeFacts::TypeofEQNumber,
+                TsKeywordTypeKind::TsBigIntKeyword => TypeFacts::TypeofEQBigInt,
+                TsKeywordTypeKind::TsBooleanKeyword => TypeFacts::TypeofEQBoolean,
+                TsKeywordTypeKind::TsSymbolKeyword => TypeFacts::TypeofEQSymbol,
+                TsKeywordTypeKind::TsUndefinedKeyword | TsKeywordTypeKind::TsVoidKeyword => TypeFacts::EQUndefined,
+                TsKeywordTypeKind::TsNullKeyword => TypeFacts::TypeofEQObject,
+                TsKeywordTypeKind::TsObjectKeyword => TypeFacts::TypeofEQObject | TypeFacts::TypeofEQFunction,
+                _ => return None,
             },
-        ) {
-            ty
-        } else {
-            return true;
-        };
 
-        match ty.normalize() {
-            Type::Interface(..) | Type::TypeLit(..) | Type::Class(..) => return false,
-            _ => {}
-        }
+            Type::Lit(LitType { lit, .. }) => match lit {
+                RTsLit::Str(..) | RTsLit::Tpl(..) => TypeFacts::TypeofEQString,
+                RTsLit::Number(..) => TypeFacts::TypeofEQNumber,
+                RTsLit::BigInt(..) => TypeFacts::TypeofEQBigInt,
+                RTsLit::Bool(..) => TypeFacts::TypeofEQBoolean,
+            },
+
+            Type::Tpl(..) => TypeFacts::TypeofEQString,
 
-        true
+            Type::Symbol(..) => TypeFacts::TypeofEQSymbol,
+
+            Type::Function(..) | Type::Constructor(..) | Type::ClassDef(..) => TypeFacts::TypeofEQFunction,
+
+            Type::Array(..) | Type::Tuple(..) | Type::Class(..) => TypeFacts::TypeofEQObject,
+
+            Type::Interface(i) => {
+                if matches!(&**i.name.sym(), "Function" | "CallableFunction" | "NewableFunction") {
+                    return Some(TypeFacts::TypeofEQFunction);
+                }
+
+                if i.body.is_empty() {
+                    return None;
+                }
+
+                typeof_facts_of_type_elements(&i.body)
+            }
+
+            Type::TypeLit(t) => {
+                if t.members.is_empty() {
+                    return None;
+                }
+
+                typeof_facts_of_type_elements(&t.members)
+            }
+
+            _ => return None,
+        })
+    }
+}
+
+fn typeof_facts_of_type_elements(members: &[TypeElement]) -> TypeFacts {
+    if members
+        .iter()
+        .any(|m| matches!(m, TypeElement::Call(..) | TypeElement::Constructor(..)))
+    {
+        TypeFacts::TypeofEQFunction
+    } else {
+        TypeFacts::TypeofEQObject
+    }
+}
+
+/// `typeof` results which are known to be possible.
+fn typeof_eq_facts(facts: TypeFacts) -> TypeFacts {
+    let eq = facts
+        & (TypeFacts::TypeofEQString
+            | TypeFacts::TypeofEQNumber
+            | TypeFacts::TypeofEQBigInt
+            | TypeFacts::TypeofEQBoolean
+            | TypeFacts::TypeofEQSymbol
+            | TypeFacts::TypeofEQObject
+            | TypeFacts::TypeofEQFunction);
+
+    // `x == undefined` also creates `EQUndefined`, but it matches `null` too.
+    if eq.is_empty()
+        && facts.contains(TypeFacts::EQUndefined)
+        && !facts.intersects(TypeFacts::EQNull | TypeFacts::EQUndefinedOrNull)
+    {
+        return TypeFacts::EQUndefined;
+    }
+
+    eq
+}
+
+/// `typeof` results which are known to be impossible, as `TypeofEQ*` facts.
+fn typeof_ne_facts(facts: TypeFacts) -> TypeFacts {
+    let mut ne = TypeFacts::None;
+
+    for (neq, eq) in [
+        (TypeFacts::TypeofNEString, TypeFacts::TypeofEQString),
+        (TypeFacts::TypeofNENumber, TypeFacts::TypeofEQNumber),
+        (TypeFacts::TypeofNEBigInt, TypeFacts::TypeofEQBigInt),
+        (TypeFacts::TypeofNEBoolean, TypeFacts::TypeofEQBoolean),
+        (TypeFacts::TypeofNESymbol, TypeFacts::TypeofEQSymbol),
+        (TypeFacts::TypeofNEObject, TypeFacts::TypeofEQObject),
+        (TypeFacts::TypeofNEFunction, TypeFacts::TypeofEQFunction),
+        (TypeFacts::NEUndefined, TypeFacts::EQUndefined),
+    ] {
+        if facts.contains(neq) {
+            ne |= eq;
+        }
     }
+
+    ne
+}
+
+/// Returns the keyword type if `facts` narrows a value to single primitive
+/// type.
+fn primitive_kind_of_typeof(facts: TypeFacts) -> Option<TsKeywordTypeKind> {
+    let eq = typeof_eq_facts(facts);
+
+    Some(if eq == TypeFacts::TypeofEQString {
+        TsKeywordTypeKind::TsStringKeyword
+    } else if eq == TypeFacts::TypeofEQNumber {
+        TsKeywordTypeKind::TsNumberKeyword
+    } else if eq == TypeFacts::TypeofEQBigInt {
+        TsKeywordTypeKind::TsBigIntKeyword
+    } else if eq == TypeFacts::TypeofEQBoolean {
+        TsKeywordTypeKind::TsBooleanKeyword
+    } else if eq == TypeFacts::TypeofEQSymbol {
+        TsKeywordTypeKind::TsSymbolKeyword
+    } else if eq == TypeFacts::EQUndefined {
+        TsKeywordTypeKind::TsUndefinedKeyword
+    } else {
+        return None;
+    })
 }
 
 impl Fold<TypeElement> for TypeFactsHandler<'_, '_, '_> {
@@ -288,6 +415,8 @@ impl Fold<Intersection> for TypeFactsHandler<'_, '_, '_> {
         let has_str = has_keyword(TsKeywordTypeKind::TsStringKeyword);
         let has_num = has_keyword(TsKeywordTypeKind::TsNumberKeyword);
         let has_bool = has_keyword(TsKeywordTypeKind::TsBooleanKeyword);
+        let has_bigint = has_keyword(TsKeywordTypeKind::TsBigIntKeyword);
+        let has_symbol = has_keyword(TsKeywordTypeKind::TsSymbolKeyword);
 
         if !has_str && self.facts.contains(TypeFacts::TypeofEQString) {
             ty.types.push(Type::Keyword(KeywordType {
@@ -313,51 +442,58 @@ impl Fold<Intersection> for TypeFactsHandler<'_, '_, '_> {
             }));
         }
 
+        if !has_bigint && self.facts.contains(TypeFacts::TypeofEQBigInt) {
+            ty.types.push(Type::Keyword(KeywordType {
+                span: DUMMY_SP,
+                kind: TsKeywordTypeKind::TsBigIntKeyword,
+                metadata: Default::default(),
+            }));
+        }
+
+        if !has_symbol && self.facts.contains(TypeFacts::TypeofEQSymbol) {
+            ty.types.push(Type::Keyword(KeywordType {
+                span: DUMMY_SP,
+                kind: TsKeywordTypeKind::TsSymbolKeyword,
+                metadata: Default::default(),
+            }));
+        }
+
         ty
     }
 }
 
 impl Fold<Union> for TypeFactsHandler<'_, '_, '_> {
     fn fold(&mut self, mut u: Union) -> Union {
+        // typeof x === 'string' where x: T | undefined
+        // => x = T & string
+        if matches!(primitive_kind_of_typeof(self.facts), Some(kind) if kind != TsKeywordTypeKind::TsUndefinedKeyword) {
+            for ty in u.types.iter_mut() {
+                if let Type::Param(..) | Type::IndexedAccessType(..) = ty.normalize() {
+                    let ty_to_wrap = ty.take();
+                    *ty = Type::Intersection(Intersection {
+                        span: ty_to_wrap.span(),
+                        metadata: IntersectionMetadata {
+                            common: ty_to_wrap.metadata(),
+                            ..Default::default()
+                        },
+                        types: vec![ty_to_wrap],
+                    });
+                }
+            }
+        }
+
         u = u.fold_children_with(self);
 
         u.types.retain(|v| !v.is_never());
 
-        if self.facts.contains(TypeFacts::TypeofNEFunction) {
-            u.types.retain(|ty| !matches!(ty.normalize(), Type::Function(..)));
-        }
-
-        if self.facts != TypeFacts::None {
-            if self.facts.contains(TypeFacts::TypeofEQString)
-                || self.facts.contains(TypeFacts::TypeofEQBoolean)
-                || self.facts.contains(TypeFacts::TypeofEQNumber)
-            {
-                u.types.retain(|ty| match ty.normalize() {
-                    Type::Lit(LitType { lit: RTsLit::Str(..), .. })
-                    | Type::Keyword(KeywordType {
-                        kind: TsKeywordTypeKind::TsStringKeyword,
-                        ..
-                    }) if !self.facts.contains(TypeFacts::TypeofEQString) => false,
-
-                    Type::Lit(LitType { lit: RTsLit::Bool(..), .. })
-                    | Type::Keyword(KeywordType {
-                        kind: TsKeywordTypeKind::TsBooleanKeyword,
-                        ..
-                    }) if !self.facts.contains(TypeFacts::TypeofEQBoolean) => false,
-
-                    Type::Lit(LitType {
-                        lit: RTsLit::Number(..), ..
-                    })
-                    | Type::Keyword(KeywordType {
-                        kind: TsKeywordTypeKind::TsNumberKeyword,
-                        ..
-                    }) if !self.facts.contains(TypeFacts::TypeofEQNumber) => false,
-
-                    Type::Param(..) => false,
+        let eq = typeof_eq_facts(self.facts);
+        let ne = typeof_ne_facts(self.facts);
 
-                    _ => self.can_be_primitive(ty),
-                });
-            }
+        if !eq.is_empty() || !ne.is_empty() {
+            u.types.retain(|ty| match self.typeof_facts(ty) {
+                Some(possible) => (eq.is_empty() || possible.intersects(eq)) && !ne.contains(possible),
+                None => true,
+            });
         }
 
         u
@@ -407,6 +543,16 @@ impl Fold<Type> for TypeFactsHandler<'_, '_, '_> {
                 metadata: Default::default(),
             })
             .freezed();
+        } else if ty.is_unknown() {
+            // typeof x === 'string'
+            // => x = string
+            if let Some(kind) = primitive_kind_of_typeof(self.facts) {
+                return Type::Keyword(KeywordType {
+                    span,
+                    kind,
+                    metadata: Default::default(),
+                });
+            }
         }
 
         // TODO(kdy1): Don't do anything if type fact is none.
@@ -1,7 +1,12 @@
-use rnode::VisitWith;
-use stc_ts_ast_rnode::{RBlockStmt, RBool, RModuleDecl, RModuleItem, RStmt, RTsEntityName, RTsLit};
+use fxhash::FxHashSet;
+use rnode::{Visit, VisitWith};
+use stc_ts_ast_rnode::{
+    RAssignExpr, RBlockStmt, RBool, RExpr, RForInStmt, RForOfStmt, RMetaPropExpr, RModuleDecl, RModuleItem, RPat, RPatOrExpr, RStmt,
+    RTsEntityName, RTsLit, RUpdateExpr, RVarDeclOrPat,
+};
 use stc_ts_type_ops::metadata::TypeFinder;
-use stc_ts_types::{KeywordType, KeywordTypeMetadata, LitType, Ref};
+use stc_ts_types::{Id, KeywordType, KeywordTypeMetadata, LitType, Ref};
+use stc_ts_utils::find_ids_in_pat;
 use swc_ecma_ast::*;
 use tracing::instrument;
 
@@ -45,6 +50,28 @@ where
     TypeFinder::find(n, check)
 }
 
+/// Check if `import.meta` is used in `n`.
+pub(crate) fn contains_import_meta<N>(n: &N) -> bool
+where
+    N: VisitWith<ImportMetaFinder>,
+{
+    let mut v = ImportMetaFinder { found: false };
+    n.visit_with(&mut v);
+    v.found
+}
+
+pub(crate) struct ImportMetaFinder {
+    found: bool,
+}
+
+impl Visit<RMetaPropExpr> for ImportMetaFinder {
+    fn visit(&mut self, e: &RMetaPropExpr) {
+        if let MetaPropKind::ImportMeta = e.kind {
+            self.found = true;
+        }
+    }
+}
+
 pub(crate) fn is_str_or_union(t: &Type) -> bool {
     match t.normalize() {
         Type::Lit(LitType { lit: RTsLit::Str(..), .. }) => true,
@@ -318,3 +345,74 @@ pub(crate) fn unwrap_ref_with_single_arg<'a>(ty: &'a Type, wanted_ref_name: &str
 
     None
 }
+
+/// Returns variables which are assigned in `node`, including by `++` and the
+/// left of `for-in` or `for-of` statements.
+pub(crate) fn find_assigned_vars<N>(node: &N) -> FxHashSet<Id>
+where
+    N: VisitWith<AssignedVarFinder>,
+{
+    let mut v = AssignedVarFinder::default();
+    node.visit_with(&mut v);
+    v.found
+}
+
+#[derive(Default)]
+pub(crate) struct AssignedVarFinder {
+    found: FxHashSet<Id>,
+}
+
+impl AssignedVarFinder {
+    fn add_pat(&mut self, p: &RPat) {
+        match p {
+            RPat::Expr(box RExpr::Ident(i)) => {
+                self.found.insert(i.into());
+            }
+            _ => self.found.extend(find_ids_in_pat::<_, Id>(p)),
+        }
+    }
+}
+
+impl Visit<RAssignExpr> for AssignedVarFinder {
+    fn visit(&mut self, e: &RAssignExpr) {
+        match &e.left {
+            RPatOrExpr::Expr(box RExpr::Ident(i)) => {
+                self.found.insert(i.into());
+            }
+            RPatOrExpr::Expr(..) => {}
+            RPatOrExpr::Pat(p) => self.add_pat(p),
+        }
+
+        e.visit_children_with(self);
+    }
+}
+
+impl Visit<RUpdateExpr> for AssignedVarFinder {
+    fn visit(&mut self, e: &RUpdateExpr) {
+        if let RExpr::Ident(i) = &*e.arg {
+            self.found.insert(i.into());
+        }
+
+        e.visit_children_with(self);
+    }
+}
+
+impl Visit<RForInStmt> for AssignedVarFinder {
+    fn visit(&mut self, s: &RForInStmt) {
+        if let RVarDeclOrPat::Pat(p) = &s.left {
+            self.add_pat(p);
+        }
+
+        s.visit_children_with(self);
+    }
+}
+
+impl Visit<RForOfStmt> for AssignedVarFinder {
+    fn visit(&mut self, s: &RForOfStmt) {
+        if let RVarDeclOrPat::Pat(p) = &s.left {
+            self.add_pat(p);
+        }
+
+        s.visit_children_with(self);
+    }
+}
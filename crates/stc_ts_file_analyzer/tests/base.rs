@@ -5,26 +5,29 @@ use std::{
     env,
     path::{Path, PathBuf},
     process::Command,
-    sync::Arc,
+    sync::{Arc, Mutex},
 };
 
 use itertools::Itertools;
 use rnode::{NodeIdGenerator, RNode, VisitWith};
 use stc_testing::logger;
-use stc_ts_ast_rnode::RModule;
+use stc_ts_ast_rnode::{RModule, RStr, RTsModuleName};
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{Env, ModuleConfig, Rule};
-use stc_ts_errors::{debug::debugger::Debugger, ErrorKind};
+use stc_ts_env::{Env, JsxConfig, ModuleConfig, Rule};
+use stc_ts_errors::{debug::debugger::Debugger, Error, ErrorKind};
 use stc_ts_file_analyzer::{
     analyzer::{Analyzer, NoopLoader},
     env::EnvFactory,
+    loader::Load,
     validator::ValidateWith,
+    ModuleTypeData, VResult,
 };
 use stc_ts_storage::{ErrorStore, Single};
 use stc_ts_testing::tsc::TscError;
-use stc_ts_types::module_id;
+use stc_ts_types::{module_id, Module, ModuleId, Type};
 use stc_ts_utils::StcComments;
-use swc_common::{errors::DiagnosticId, input::SourceFileInput, FileName, GLOBALS};
+use swc_atoms::JsWord;
+use swc_common::{errors::DiagnosticId, input::SourceFileInput, sync::Lrc, FileName, SourceFile, SourceMap, DUMMY_SP, GLOBALS};
 use swc_ecma_ast::EsVersion;
 use swc_ecma_parser::{lexer::Lexer, Parser, Syntax, TsConfig};
 use swc_ecma_transforms::resolver;
@@ -38,7 +41,26 @@ struct StcError {
     code: usize,
 }
 
-fn get_env() -> Env {
+/// Parses the value of a `//@target:` directive.
+fn parse_target(s: &str) -> EsVersion {
+    match s.trim().to_ascii_lowercase().as_str() {
+        "es3" => EsVersion::Es3,
+        "es5" => EsVersion::Es5,
+        "es2015" => EsVersion::Es2015,
+        "es2020" => EsVersion::Es2020,
+        v => panic!("Invalid target: {:?}", v),
+    }
+}
+
+/// Returns the value of a directive like `//@target: es5`.
+fn directive<'a>(src: &'a str, name: &str) -> Option<&'a str> {
+    src.lines()
+        .find_map(|line| line.strip_prefix("//@")?.strip_prefix(name)?.strip_prefix(':'))
+        .map(str::trim)
+}
+
+/// Returns the env configured by directives like `//@target:` and `//@jsx:`.
+fn get_env(src: &str) -> Env {
     let mut libs = vec![];
     let ls = &["es2017.full", "es2016.full", "es2015.full"];
     for s in ls {
@@ -47,15 +69,51 @@ fn get_env() -> Env {
     libs.sort();
     libs.dedup();
 
+    let target = directive(src, "target").map(parse_target).unwrap_or_else(EsVersion::latest);
+    let module = directive(src, "module")
+        .map(|v| v.to_lowercase().parse().unwrap())
+        .unwrap_or(ModuleConfig::None);
+    let module_detection = directive(src, "moduleDetection")
+        .map(|v| v.to_lowercase().parse().unwrap())
+        .unwrap_or_default();
+    let strict = directive(src, "strict").map(|v| v.parse().unwrap()).unwrap_or(false);
+    let exact_optional_property_types = directive(src, "exactOptionalPropertyTypes")
+        .map(|v| v.parse().unwrap())
+        .unwrap_or(false);
+    let isolated_modules = directive(src, "isolatedModules").map(|v| v.parse().unwrap()).unwrap_or(false);
+    let no_implicit_override = directive(src, "noImplicitOverride").map(|v| v.parse().unwrap()).unwrap_or(false);
+    let experimental_decorators = directive(src, "experimentalDecorators")
+        .map(|v| v.parse().unwrap())
+        .unwrap_or(false);
+    let es_module_interop = directive(src, "esModuleInterop").map(|v| v.parse().unwrap()).unwrap_or(false);
+    let allow_synthetic_default_imports = directive(src, "allowSyntheticDefaultImports")
+        .map(|v| v.parse().unwrap())
+        .unwrap_or(es_module_interop);
+    let jsx = JsxConfig {
+        mode: directive(src, "jsx").map(|v| v.to_lowercase().parse().unwrap()),
+        factory: directive(src, "jsxFactory").map(From::from),
+        fragment_factory: directive(src, "jsxFragmentFactory").map(From::from),
+        import_source: directive(src, "jsxImportSource").map(From::from),
+    };
+
     Env::simple(
         Rule {
             strict_function_types: true,
+            strict_null_checks: strict,
+            strict_property_initialization: strict,
+            exact_optional_property_types,
+            module_detection,
+            isolated_modules,
+            no_implicit_override,
+            experimental_decorators,
+            allow_synthetic_default_imports,
             ..Default::default()
         },
-        EsVersion::latest(),
-        ModuleConfig::None,
+        target,
+        module,
         &libs,
     )
+    .with_jsx(jsx)
 }
 
 fn validate(input: &Path) -> Vec<StcError> {
@@ -66,7 +124,7 @@ fn validate(input: &Path) -> Vec<StcError> {
 
             let fm = cm.load_file(input).unwrap();
 
-            let env = get_env();
+            let env = get_env(&fm.src);
 
             let generator = module_id::ModuleIdGenerator::default();
             let path = Arc::new(FileName::Real(input.to_path_buf()));
@@ -76,7 +134,10 @@ fn validate(input: &Path) -> Vec<StcError> {
             let mut node_id_gen = NodeIdGenerator::default();
             let mut module = {
                 let lexer = Lexer::new(
-                    Syntax::Typescript(TsConfig { ..Default::default() }),
+                    Syntax::Typescript(TsConfig {
+                        tsx: input.extension().map_or(false, |ext| ext == "tsx"),
+                        ..Default::default()
+                    }),
                     EsVersion::Es2021,
                     SourceFileInput::from(&*fm),
                     None,
@@ -96,6 +157,8 @@ fn validate(input: &Path) -> Vec<StcError> {
                 path,
                 is_dts: false,
                 info: Default::default(),
+                filter: None,
+                directives: None,
             };
 
             {
@@ -148,24 +211,118 @@ fn validate(input: &Path) -> Vec<StcError> {
         .collect()
 }
 
-#[fixture("tests/errors/**/*.ts")]
-fn errors(input: PathBuf) {
-    testing::run_test2(false, |cm, handler| {
-        cm.new_source_file(FileName::Anon, "".to_string());
+/// Resolves imports of a fixture to the files above the importer and to the
+/// ambient modules declared by them.
+#[derive(Default)]
+struct FixtureLoader {
+    generator: module_id::ModuleIdGenerator,
+    modules: Mutex<Vec<(JsWord, ModuleId, Type)>>,
+}
+
+impl FixtureLoader {
+    fn add_module(&self, name: JsWord, id: ModuleId, module: Type) {
+        self.modules.lock().unwrap().push((name, id, module));
+    }
+}
+
+impl Load for FixtureLoader {
+    fn module_id(&self, _: &Arc<FileName>, src: &JsWord) -> Option<ModuleId> {
+        let modules = self.modules.lock().unwrap();
+
+        modules
+            .iter()
+            .rev()
+            .find(|(name, ..)| name == src)
+            .or_else(|| {
+                modules.iter().rev().find(|(name, ..)| match name.split_once('*') {
+                    Some((prefix, suffix)) => src.len() >= prefix.len() + suffix.len() && src.starts_with(prefix) && src.ends_with(suffix),
+                    None => false,
+                })
+            })
+            .map(|(_, id, _)| *id)
+    }
+
+    fn is_in_same_circular_group(&self, _: ModuleId, _: ModuleId) -> bool {
+        false
+    }
+
+    fn load_circular_dep(&self, _: ModuleId, _: ModuleId, _: &ModuleTypeData) -> VResult<Type> {
+        unreachable!("fixtures cannot contain circular imports")
+    }
+
+    fn load_non_circular_dep(&self, _: ModuleId, dep: ModuleId) -> VResult<Type> {
+        let modules = self.modules.lock().unwrap();
+
+        Ok(modules.iter().find(|(_, id, _)| *id == dep).map(|(.., ty)| ty.clone()).unwrap())
+    }
+
+    fn declare_module(&self, name: &JsWord, module: Type) {
+        let (id, _) = self.generator.generate(&Arc::new(FileName::Custom(name.to_string())));
+
+        self.add_module(name.clone(), id, module);
+    }
+}
 
-        let fm = cm.load_file(&input).unwrap();
+/// Splits a fixture into the files separated by `//@filename:`, like the tests
+/// of `tsc`.
+fn split_files(cm: &SourceMap, input: &Path, fm: Lrc<SourceFile>) -> Vec<Lrc<SourceFile>> {
+    let mut files = vec![];
+    let mut cur: Option<(String, String)> = None;
+    let mut header = String::new();
+
+    for line in fm.src.lines() {
+        if let Some(name) = line.strip_prefix("//@filename:") {
+            files.extend(cur.take());
+            cur = Some((name.trim().to_string(), header.clone()));
+            continue;
+        }
+
+        let src = match &mut cur {
+            Some((_, src)) => src,
+            None => &mut header,
+        };
+        src.push_str(line);
+        src.push('\n');
+    }
+    files.extend(cur);
+
+    if files.is_empty() {
+        return vec![fm];
+    }
+
+    files
+        .into_iter()
+        .map(|(name, src)| cm.new_source_file(FileName::Real(input.with_file_name(name)), src))
+        .collect()
+}
+
+/// Analyzes a fixture and returns the errors of all files in it.
+///
+/// Files are analyzed in order, and a file can import the files above it as
+/// `./name` or the ambient modules declared by them.
+fn analyze(cm: Arc<SourceMap>, input: &Path) -> (Env, Vec<Error>) {
+    cm.new_source_file(FileName::Anon, "".to_string());
+
+    let fm = cm.load_file(input).unwrap();
+
+    let env = get_env(&fm.src);
 
-        let env = get_env();
+    let loader = FixtureLoader::default();
+    let mut errors = vec![];
 
-        let generator = module_id::ModuleIdGenerator::default();
-        let path = Arc::new(FileName::Real(input.to_path_buf()));
+    for fm in split_files(&cm, input, fm) {
+        let path = Arc::new(fm.name.clone());
+        let file_name = fm.name.to_string();
 
-        let (module_id, top_level_mark) = generator.generate(&path);
+        let (module_id, top_level_mark) = loader.generator.generate(&path);
 
         let mut node_id_gen = NodeIdGenerator::default();
         let mut module = {
             let lexer = Lexer::new(
-                Syntax::Typescript(TsConfig { ..Default::default() }),
+                Syntax::Typescript(TsConfig {
+                    tsx: file_name.ends_with(".tsx"),
+                    ..Default::default()
+                }),
                 EsVersion::Es2021,
                 SourceFileInput::from(&*fm),
                 None,
@@ -184,18 +341,44 @@ fn errors(input: PathBuf) {
             id: module_id,
             path,
             info: Default::default(),
-            is_dts: false,
+            is_dts: file_name.ends_with(".d.ts"),
+            filter: None,
+            directives: None,
         };
 
         {
             // Don't print logs from builtin modules.
             let _tracing = tracing::subscriber::set_default(logger(Level::DEBUG));
 
-            let mut analyzer = Analyzer::root(env.clone(), cm, Default::default(), box &mut storage, &NoopLoader, None);
+            let mut analyzer = Analyzer::root(env.clone(), cm.clone(), Default::default(), box &mut storage, &loader, None);
             module.visit_with(&mut analyzer);
         }
 
-        let errors = ::stc_ts_errors::ErrorKind::flatten(storage.info.errors.into_iter().collect());
+        errors.extend(storage.info.errors);
+
+        let name = Path::new(&file_name).file_name().unwrap().to_string_lossy();
+        let name = name.trim_end_matches(".tsx").trim_end_matches(".ts").trim_end_matches(".d");
+        let module = Type::Module(Module {
+            span: module.span,
+            name: RTsModuleName::Str(RStr {
+                span: DUMMY_SP,
+                value: format!("{:?}", module_id).into(),
+                raw: None,
+            }),
+            exports: box storage.info.exports,
+            metadata: Default::default(),
+        })
+        .freezed();
+        loader.add_module(format!("./{}", name).into(), module_id, module);
+    }
+
+    (env, ErrorKind::flatten(errors))
+}
+
+#[fixture("tests/errors/**/*.ts*")]
+fn errors(input: PathBuf) {
+    testing::run_test2(false, |cm, handler| {
+        let (env, errors) = analyze(cm, &input);
 
         if errors.is_empty() {
             panic!("Should emit at least one error")
@@ -216,54 +399,10 @@ fn errors(input: PathBuf) {
     .unwrap_err();
 }
 
-#[fixture("tests/pass-only/**/*.ts")]
+#[fixture("tests/pass-only/**/*.ts*")]
 fn pass_only(input: PathBuf) {
     testing::run_test2(false, |cm, handler| {
-        cm.new_source_file(FileName::Anon, "".to_string());
-
-        let fm = cm.load_file(&input).unwrap();
-
-        let env = get_env();
-
-        let generator = module_id::ModuleIdGenerator::default();
-        let path = Arc::new(FileName::Real(input.to_path_buf()));
-
-        let (module_id, top_level_mark) = generator.generate(&path);
-
-        let mut node_id_gen = NodeIdGenerator::default();
-        let mut module = {
-            let lexer = Lexer::new(
-                Syntax::Typescript(TsConfig { ..Default::default() }),
-                EsVersion::Es2021,
-                SourceFileInput::from(&*fm),
-                None,
-            );
-            let mut parser = Parser::new_from(lexer);
-
-            parser.parse_module().unwrap()
-        };
-        module = GLOBALS.set(env.shared().swc_globals(), || {
-            module.fold_with(&mut resolver(env.shared().marks().unresolved_mark(), top_level_mark, true))
-        });
-        let module = RModule::from_orig(&mut node_id_gen, module);
-
-        let mut storage = Single {
-            parent: None,
-            id: module_id,
-            path,
-            info: Default::default(),
-            is_dts: false,
-        };
-
-        {
-            // Don't print logs from builtin modules.
-            let _tracing = tracing::subscriber::set_default(logger(Level::DEBUG));
-
-            let mut analyzer = Analyzer::root(env.clone(), cm, Default::default(), box &mut storage, &NoopLoader, None);
-            module.visit_with(&mut analyzer);
-        }
-
-        let errors = ::stc_ts_errors::ErrorKind::flatten(storage.info.errors.into_iter().collect());
+        let (env, errors) = analyze(cm, &input);
         let ok = errors.is_empty();
 
         GLOBALS.set(env.shared().swc_globals(), || {
@@ -364,7 +503,20 @@ fn run_test(file_name: PathBuf, for_error: bool) -> Option<NormalizedOutput> {
                 suppress_excess_property_errors: false,
                 suppress_implicit_any_index_errors: false,
                 use_define_property_for_class_fields: false,
+                experimental_decorators: false,
+                no_implicit_override: false,
+                strict_property_initialization: false,
+                exact_optional_property_types: false,
+                isolated_modules: false,
+                allow_synthetic_default_imports: false,
+                check_js: false,
+                downlevel_iteration: false,
+                skip_lib_check: false,
+                skip_default_lib_check: false,
+                module_detection: Default::default(),
             };
+            let mut target = EsVersion::Es2020;
+            let mut module = ModuleConfig::None;
 
             for line in fm.src.lines() {
                 if !line.starts_with("//@") {
@@ -375,6 +527,44 @@ fn run_test(file_name: PathBuf, for_error: bool) -> Option<NormalizedOutput> {
                     let value = line["strict:".len()..].trim().parse::<bool>().unwrap();
                     rule.strict_function_types = value;
                     rule.strict_null_checks = value;
+                    rule.strict_property_initialization = value;
+                    continue;
+                }
+                if line.starts_with("exactOptionalPropertyTypes:") {
+                    let value = line["exactOptionalPropertyTypes:".len()..].trim().parse::<bool>().unwrap();
+                    rule.exact_optional_property_types = value;
+                    continue;
+                }
+                if line.starts_with("isolatedModules:") {
+                    let value = line["isolatedModules:".len()..].trim().parse::<bool>().unwrap();
+                    rule.isolated_modules = value;
+                    continue;
+                }
+                if line.starts_with("esModuleInterop:") {
+                    let value = line["esModuleInterop:".len()..].trim().parse::<bool>().unwrap();
+                    rule.allow_synthetic_default_imports |= value;
+                    continue;
+                }
+                if line.starts_with("allowSyntheticDefaultImports:") {
+                    let value = line["allowSyntheticDefaultImports:".len()..].trim().parse::<bool>().unwrap();
+                    rule.allow_synthetic_default_imports = value;
+                    continue;
+                }
+                if line.starts_with("target:") {
+                    target = parse_target(&line["target:".len()..]);
+                    continue;
+                }
+                if line.starts_with("module:") {
+                    module = line["module:".len()..].trim().to_lowercase().parse().unwrap();
+                    continue;
+                }
+                if line.starts_with("moduleDetection:") {
+                    rule.module_detection = line["moduleDetection:".len()..].trim().to_lowercase().parse().unwrap();
+                    continue;
+                }
+                if line.starts_with("downlevelIteration:") {
+                    let value = line["downlevelIteration:".len()..].trim().parse::<bool>().unwrap();
+                    rule.downlevel_iteration = value;
                     continue;
                 }
                 if line.to_ascii_lowercase().starts_with(&"allowUnreachableCode:".to_ascii_lowercase()) {
@@ -386,7 +576,7 @@ fn run_test(file_name: PathBuf, for_error: bool) -> Option<NormalizedOutput> {
                 panic!("Invalid directive: {:?}", line)
             }
 
-            let env = Env::simple(rule, EsVersion::Es2020, ModuleConfig::None, &libs);
+            let env = Env::simple(rule, target, module, &libs);
             let stable_env = env.shared().clone();
             let generator = module_id::ModuleIdGenerator::default();
             let path = Arc::new(FileName::Real(file_name.clone()));
@@ -399,6 +589,8 @@ fn run_test(file_name: PathBuf, for_error: bool) -> Option<NormalizedOutput> {
                 path,
                 info: Default::default(),
                 is_dts: false,
+                filter: None,
+                directives: None,
             };
 
             let mut node_id_gen = NodeIdGenerator::default();
@@ -434,6 +626,7 @@ fn run_test(file_name: PathBuf, for_error: bool) -> Option<NormalizedOutput> {
                             Some(Debugger {
                                 cm: cm.clone(),
                                 handler: handler.clone(),
+                                records: None,
                             })
                         },
                     );
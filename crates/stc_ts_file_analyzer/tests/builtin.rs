@@ -1,7 +1,7 @@
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{BuiltIn, Env, ModuleConfig, StableEnv};
+use stc_ts_env::{BuiltIn, BuiltInProvider, Env, ModuleConfig, StableEnv};
 use stc_ts_file_analyzer::env::BuiltInGen;
 use swc_common::{Globals, DUMMY_SP, GLOBALS};
 
@@ -101,3 +101,109 @@ pub fn intl() {
     })
     .unwrap();
 }
+
+struct CustomRuntime;
+
+impl BuiltInProvider for CustomRuntime {
+    fn id(&self) -> Cow<'_, str> {
+        Cow::Borrowed("custom-runtime")
+    }
+
+    fn sources(&self) -> Vec<Cow<'static, str>> {
+        vec![
+            Cow::Borrowed("interface Array<T> { length: number; }\ninterface String { length: number; }"),
+            Cow::Borrowed("interface Runtime { version: string; }\ndeclare var runtime: Runtime;"),
+        ]
+    }
+}
+
+#[test]
+pub fn provider() {
+    testing::run_test2(false, |_, _| {
+        let globals = Arc::new(Globals::default());
+
+        GLOBALS.set(&globals, || {
+            let shared = StableEnv::new(globals.clone());
+            let data = BuiltIn::from_provider(&shared, &CustomRuntime).expect("failed to parse declarations");
+
+            let env = Env::new(
+                shared,
+                Default::default(),
+                swc_ecma_ast::EsVersion::Es2020,
+                ModuleConfig::None,
+                Arc::new(data),
+            );
+
+            env.get_global_var(DUMMY_SP, &"runtime".into())
+                .expect("failed to get global var runtime");
+            env.get_global_type(DUMMY_SP, &"Runtime".into())
+                .expect("failed to get global type Runtime");
+            assert!(env.get_global_type(DUMMY_SP, &"Promise".into()).is_err());
+
+            Ok(())
+        })
+    })
+    .unwrap();
+}
+
+struct BrokenRuntime;
+
+impl BuiltInProvider for BrokenRuntime {
+    fn id(&self) -> Cow<'_, str> {
+        Cow::Borrowed("broken-runtime")
+    }
+
+    fn sources(&self) -> Vec<Cow<'static, str>> {
+        vec![Cow::Borrowed("interface Runtime {")]
+    }
+}
+
+#[test]
+pub fn provider_with_syntax_error() {
+    let globals = Arc::new(Globals::default());
+
+    GLOBALS.set(&globals, || {
+        let shared = StableEnv::new(globals.clone());
+        let err = BuiltIn::from_provider(&shared, &BrokenRuntime).expect_err("should fail to parse declarations");
+
+        assert_eq!(err.id, "broken-runtime");
+    })
+}
+
+#[test]
+pub fn cache() {
+    let dir = std::env::temp_dir().join("stc_builtin_cache_test");
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let caches = || {
+        std::fs::read_dir(&dir)
+            .map(|entries| entries.map(|e| e.unwrap().path()).collect::<Vec<_>>())
+            .unwrap_or_default()
+    };
+
+    testing::run_test2(false, |_, _| {
+        let globals = Arc::new(Globals::default());
+
+        GLOBALS.set(&globals, || {
+            let shared = StableEnv::new(globals.clone());
+            let libs = Lib::load("es5");
+
+            let built = BuiltIn::from_ts_libs_with_cache(&shared, &libs, Some(&dir));
+            assert!(!caches().is_empty(), "builtin cache should be written");
+
+            let cached = BuiltIn::from_ts_libs_with_cache(&shared, &libs, Some(&dir));
+            assert_eq!(built.types().len(), cached.types().len());
+            assert_eq!(built.vars().len(), cached.vars().len());
+
+            // Invalid caches are rebuilt instead of causing a panic.
+            for path in caches() {
+                std::fs::write(path, "invalid").unwrap();
+            }
+            let rebuilt = BuiltIn::from_ts_libs_with_cache(&shared, &libs, Some(&dir));
+            assert_eq!(built.types().len(), rebuilt.types().len());
+
+            Ok(())
+        })
+    })
+    .unwrap();
+}
@@ -0,0 +1,10 @@
+abstract class Shape {
+    abstract area(): number;
+    abstract readonly name: string;
+}
+
+abstract class Polygon extends Shape {
+    readonly name = "polygon";
+}
+
+export class Square extends Polygon {}
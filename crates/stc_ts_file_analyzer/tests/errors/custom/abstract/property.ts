@@ -0,0 +1,5 @@
+abstract class Shape {
+    abstract readonly name: string;
+}
+
+export class Square extends Shape {}
@@ -0,0 +1,7 @@
+export class Thing {
+    get size(): string {
+        return "";
+    }
+
+    set size(value: number) {}
+}
@@ -0,0 +1,8 @@
+export class Thing {
+    get size(): number {
+        return 0;
+    }
+}
+
+const t = new Thing();
+t.size = 1;
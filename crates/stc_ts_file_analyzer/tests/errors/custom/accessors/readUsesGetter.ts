@@ -0,0 +1,10 @@
+export class Thing {
+    get size(): number {
+        return 0;
+    }
+
+    set size(value: string | number) {}
+}
+
+const t = new Thing();
+export const s: string = t.size;
@@ -0,0 +1,10 @@
+//@filename: types.d.ts
+declare module "*.css" {
+    const classes: { [key: string]: string };
+    export default classes;
+}
+
+//@filename: index.ts
+import { button } from "./button.css";
+
+export const b = button;
@@ -0,0 +1,4 @@
+declare let a: bigint;
+declare let n: number;
+
+export const sum = a + n;
@@ -0,0 +1,3 @@
+declare let a: bigint;
+
+export const b = +a;
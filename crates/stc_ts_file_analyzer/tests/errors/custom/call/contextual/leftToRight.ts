@@ -0,0 +1,11 @@
+//@strict: true
+
+declare function chain<T, U>(items: T[], map: (item: T) => U, cb: (u: U) => void): void;
+
+chain(
+    [1],
+    (item) => item.toFixed(),
+    (u) => u.toFixed()
+);
+
+export {};
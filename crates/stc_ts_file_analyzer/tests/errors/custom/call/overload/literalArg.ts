@@ -0,0 +1,15 @@
+interface ClickEvent {
+    x: number;
+    y: number;
+}
+
+interface KeyEvent {
+    key: string;
+}
+
+declare function on(type: "click", listener: (e: ClickEvent) => void): void;
+declare function on(type: "key", listener: (e: KeyEvent) => void): void;
+
+on("key", (e) => {
+    const x: number = e.x;
+});
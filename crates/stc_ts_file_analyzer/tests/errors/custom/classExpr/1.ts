@@ -0,0 +1,5 @@
+export const Point = class {
+    constructor(public x: number) {}
+};
+
+export const p = new Point("");
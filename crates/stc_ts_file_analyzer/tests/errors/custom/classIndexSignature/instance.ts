@@ -0,0 +1,5 @@
+export class C {
+    [key: string]: number;
+
+    a: string = "";
+}
@@ -0,0 +1,5 @@
+export class C {
+    static [key: string]: number;
+
+    static a() {}
+}
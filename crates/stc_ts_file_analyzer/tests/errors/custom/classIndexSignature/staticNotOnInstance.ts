@@ -0,0 +1,7 @@
+export class C {
+    static [key: string]: number;
+}
+
+declare const c: C;
+
+c.foo;
@@ -0,0 +1,5 @@
+//@strict: true
+
+declare const o: { a: string };
+
+export const x = o as { a: number };
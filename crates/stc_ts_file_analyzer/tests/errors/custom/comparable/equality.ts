@@ -0,0 +1,13 @@
+//@strict: true
+
+interface A {
+    x: string;
+}
+interface B {
+    x: number;
+}
+
+declare const a: A;
+declare const b: B;
+
+export const eq = a === b;
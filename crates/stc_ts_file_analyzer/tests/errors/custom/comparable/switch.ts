@@ -0,0 +1,8 @@
+//@strict: true
+
+export function f(x: "a" | "b") {
+    switch (x) {
+        case "c":
+            return 1;
+    }
+}
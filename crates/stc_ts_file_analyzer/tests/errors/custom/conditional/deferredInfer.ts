@@ -0,0 +1,7 @@
+//@strict: true
+
+function f<U>(m: [string, number] extends [infer A, U] ? A : boolean) {
+    const b: boolean = m;
+}
+
+export {};
@@ -0,0 +1,11 @@
+//@isolatedModules: true
+
+//@filename: decl.d.ts
+export declare const enum E {
+    A,
+}
+
+//@filename: index.ts
+import { E } from "./decl";
+
+export const a = E.A;
@@ -0,0 +1,7 @@
+//@isolatedModules: true
+
+declare const enum E {
+    A,
+}
+
+export const a = E.A;
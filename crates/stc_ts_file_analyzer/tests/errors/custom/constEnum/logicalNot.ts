@@ -0,0 +1,3 @@
+export const enum E {
+    A = !0,
+}
@@ -0,0 +1,5 @@
+declare function f(): number;
+
+export const enum E {
+    A = f(),
+}
@@ -0,0 +1,9 @@
+//@isolatedModules: true
+
+//@filename: decl.d.ts
+export declare const enum E {
+    A,
+}
+
+//@filename: index.ts
+export { E } from "./decl";
@@ -0,0 +1,10 @@
+class Point {
+    x: number = 0;
+}
+
+namespace Point {
+    export const origin = new Point();
+}
+
+const p: Point = Point.origin;
+const n: string = p.x;
@@ -0,0 +1,11 @@
+enum Color {
+    Red,
+}
+
+namespace Color {
+    export function parse(s: string): Color {
+        return Color.Red;
+    }
+}
+
+const s: string = Color.parse("red");
@@ -0,0 +1,10 @@
+//@experimentalDecorators: true
+
+function enumerable(target: Object, key: string, descriptor: PropertyDescriptor) {
+    return 1;
+}
+
+export class Greeter {
+    @enumerable
+    greet() {}
+}
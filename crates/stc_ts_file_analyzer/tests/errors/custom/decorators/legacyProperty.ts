@@ -0,0 +1,8 @@
+//@experimentalDecorators: true
+
+function format(target: Object, key: number) {}
+
+export class Greeter {
+    @format
+    greeting = "";
+}
@@ -0,0 +1,6 @@
+function dec(value: string, context: ClassMethodDecoratorContext) {}
+
+export class Foo {
+    @dec
+    method() {}
+}
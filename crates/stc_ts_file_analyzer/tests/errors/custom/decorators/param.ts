@@ -0,0 +1,5 @@
+function dec(value: unknown, context: unknown) {}
+
+export class Foo {
+    method(@dec a: number) {}
+}
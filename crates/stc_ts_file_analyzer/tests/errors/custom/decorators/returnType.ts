@@ -0,0 +1,6 @@
+function dec(value: Function, context: ClassDecoratorContext) {
+    return 1;
+}
+
+@dec
+export class Foo {}
@@ -0,0 +1,8 @@
+declare let x: number;
+declare function f(): { a?: number };
+
+delete x;
+delete f();
+delete 1;
+
+export { };
@@ -0,0 +1,7 @@
+export class A {
+    #a?: number;
+
+    remove(other: A) {
+        delete other.#a;
+    }
+}
@@ -0,0 +1,8 @@
+//@strict: true
+
+declare const o: { a: string; b?: string };
+
+delete o.b;
+delete o.a;
+
+export { };
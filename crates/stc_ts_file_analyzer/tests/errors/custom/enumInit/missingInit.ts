@@ -0,0 +1,4 @@
+enum E {
+    A = "a",
+    B,
+}
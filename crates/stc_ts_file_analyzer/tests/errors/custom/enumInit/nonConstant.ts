@@ -0,0 +1,5 @@
+declare function compute(): number;
+
+const enum E {
+    A = compute(),
+}
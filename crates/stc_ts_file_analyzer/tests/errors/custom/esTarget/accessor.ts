@@ -0,0 +1,7 @@
+//@target: es3
+
+export const obj = {
+    get value() {
+        return 1;
+    },
+};
@@ -0,0 +1,8 @@
+//@target: es5
+
+declare const set: Set<number>;
+
+for (const v of set) {
+}
+
+export {};
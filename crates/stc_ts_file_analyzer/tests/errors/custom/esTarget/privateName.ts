@@ -0,0 +1,5 @@
+//@target: es5
+
+export class C {
+    #value = 1;
+}
@@ -0,0 +1,8 @@
+//@strict: true
+//@exactOptionalPropertyTypes: true
+
+declare const a: { a: undefined };
+
+const b: { a?: string } = a;
+
+export {};
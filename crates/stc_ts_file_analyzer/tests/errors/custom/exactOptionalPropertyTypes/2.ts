@@ -0,0 +1,8 @@
+//@strict: true
+//@exactOptionalPropertyTypes: true
+
+declare const o: { a?: string };
+
+o.a = undefined;
+
+export {};
@@ -0,0 +1,7 @@
+//@strict: true
+
+type Shape = { kind: "circle"; radius: number } | { kind: "square"; size: number };
+
+const s: Shape = { kind: "circle", radius: 1, size: 1 };
+
+export {};
@@ -0,0 +1,16 @@
+//@strict: true
+
+interface Options {
+    style: {
+        color: string;
+    };
+}
+
+const o: Options = {
+    style: {
+        color: "red",
+        colour: "red",
+    },
+};
+
+export {};
@@ -0,0 +1,7 @@
+//@strict: true
+
+declare function configure(options: { style: { color: string } }): void;
+
+configure({ style: { color: "red", colour: "red" } });
+
+export {};
@@ -0,0 +1,11 @@
+//@filename: model.ts
+class Model {
+    id: number = 0;
+}
+
+export = Model;
+
+//@filename: index.ts
+import Model from "./model";
+
+export const model: Model = new Model();
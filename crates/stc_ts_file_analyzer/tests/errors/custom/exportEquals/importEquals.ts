@@ -0,0 +1,16 @@
+//@filename: model.ts
+class Model {
+    id: number = 0;
+}
+
+namespace Model {
+    export const version = 1;
+}
+
+export = Model;
+
+//@filename: index.ts
+import Model = require("./model");
+
+export const id: string = new Model().id;
+export const version: string = Model.version;
@@ -0,0 +1,9 @@
+//@filename: a.ts
+export const value = 1;
+
+//@filename: b.ts
+export const value = "b";
+
+//@filename: index.ts
+export * from "./a";
+export * from "./b";
@@ -0,0 +1,10 @@
+//@filename: base.ts
+export const unit = 1;
+
+//@filename: middle.ts
+export * as base from "./base";
+
+//@filename: index.ts
+import { base } from "./middle";
+
+export const unit: string = base.unit;
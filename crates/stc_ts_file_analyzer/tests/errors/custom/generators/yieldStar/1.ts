@@ -0,0 +1,6 @@
+declare const it: Iterator<number, void, number>;
+declare const iterable: { [Symbol.iterator](): typeof it };
+
+export function* g(): Generator<number, void, string> {
+    yield* iterable;
+}
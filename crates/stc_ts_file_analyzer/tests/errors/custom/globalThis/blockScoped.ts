@@ -0,0 +1,5 @@
+const y = 1;
+let z = "";
+
+globalThis.y;
+globalThis["z"];
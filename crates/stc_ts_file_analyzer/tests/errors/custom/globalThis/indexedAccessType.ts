@@ -0,0 +1,3 @@
+const y = 1;
+
+type Y = (typeof globalThis)["y"];
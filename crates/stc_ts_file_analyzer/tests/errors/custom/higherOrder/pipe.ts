@@ -0,0 +1,12 @@
+//@strict: true
+
+declare function pipe<A, B, C>(ab: (a: A) => B, bc: (b: B) => C): (a: A) => C;
+
+declare function list<T>(a: T): T[];
+declare function box<V>(x: V): { value: V };
+
+const listBox = pipe(list, box);
+
+const x: { value: string[] } = listBox(42);
+
+export {};
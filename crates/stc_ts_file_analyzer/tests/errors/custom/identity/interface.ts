@@ -0,0 +1,8 @@
+interface Foo {
+    x: string;
+}
+interface Foo {
+    x: string | number;
+}
+
+export { };
@@ -0,0 +1,4 @@
+var a: { f: <T>(x: T) => void };
+var a: { f: <T, U>(x: T) => void };
+
+export { };
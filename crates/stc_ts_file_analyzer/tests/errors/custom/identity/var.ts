@@ -0,0 +1,4 @@
+var a: { x: string; y?: number };
+var a: { x: string; y: number };
+
+export { };
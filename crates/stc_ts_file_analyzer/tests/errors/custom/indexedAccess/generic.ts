@@ -0,0 +1,7 @@
+//@strict: true
+
+function write<K extends "a" | "b">(o: { a: string; b: number }, k: K) {
+    o[k] = "x";
+}
+
+export {};
@@ -0,0 +1,11 @@
+//@strict: true
+
+declare function values<T>(o: { [k: string]: T }): T;
+
+const a: number = values({ a: 1, b: "x" });
+
+declare function record<T>(r: Record<string, T>): T;
+
+const b: number = record({ a: 1, b: "x" });
+
+export {};
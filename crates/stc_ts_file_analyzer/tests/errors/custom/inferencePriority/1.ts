@@ -0,0 +1,7 @@
+//@strict: true
+
+declare function callback<T>(cb: (x: T) => void): T;
+
+const a: number = callback((x: string) => {});
+
+export {};
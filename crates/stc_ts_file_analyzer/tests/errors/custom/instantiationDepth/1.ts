@@ -0,0 +1,5 @@
+type Loop<T> = T extends any ? Loop<[T]> : never;
+
+declare const l: Loop<string>;
+
+export {};
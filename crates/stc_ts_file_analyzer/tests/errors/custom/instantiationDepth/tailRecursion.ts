@@ -0,0 +1,5 @@
+type Grow<S extends string> = S extends string ? Grow<`${S}a`> : never;
+
+declare const g: Grow<"">;
+
+export {};
@@ -0,0 +1,13 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+    interface ElementAttributesProperty {
+        props: {};
+    }
+}
+
+declare class Counter {
+    props: { initial: number };
+}
+
+<Counter initial="1" />;
@@ -0,0 +1,17 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+    interface ElementAttributesProperty {
+        props: {};
+    }
+    type LibraryManagedAttributes<C, P> = C extends { defaultProps: infer D } ? Defaultize<P, D> : P;
+}
+
+type Defaultize<P, D> = Pick<P, Exclude<keyof P, keyof D>> & Partial<Pick<P, Extract<keyof P, keyof D>>>;
+
+declare class Greeting {
+    static defaultProps: { greeting: string };
+    props: { name: string; greeting: string };
+}
+
+<Greeting greeting="hi" />;
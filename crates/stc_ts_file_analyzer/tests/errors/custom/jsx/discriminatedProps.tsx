@@ -0,0 +1,10 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+}
+
+type Props = { variant: "link"; href: string } | { variant: "button"; onClick: () => void };
+
+declare function Action(props: Props): JSX.Element;
+
+<Action variant="link" onClick={() => {}} />;
@@ -0,0 +1,20 @@
+//@jsx: react
+//@jsxFactory: h
+declare namespace JSX {
+    interface Element {}
+    interface IntrinsicElements {
+        div: { id: number };
+    }
+}
+
+declare function h(type: any, props: any): any;
+declare namespace h {
+    namespace JSX {
+        interface Element {}
+        interface IntrinsicElements {
+            div: { id: string };
+        }
+    }
+}
+
+<div id={1} />;
@@ -0,0 +1,6 @@
+//@jsx: react
+declare namespace JSX {
+    interface Element {}
+}
+
+<>text</>;
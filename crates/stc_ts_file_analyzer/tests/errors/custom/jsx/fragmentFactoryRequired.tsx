@@ -0,0 +1,9 @@
+//@jsx: react
+//@jsxFactory: h
+declare namespace JSX {
+    interface Element {}
+}
+
+declare function h(type: any, props: any, ...children: any[]): JSX.Element;
+
+<>text</>;
@@ -0,0 +1,8 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+}
+
+declare function List<T>(props: { items: T[]; render: (item: T) => string }): JSX.Element;
+
+<List items={[1, 2]} render={(item) => item.toUpperCase()} />;
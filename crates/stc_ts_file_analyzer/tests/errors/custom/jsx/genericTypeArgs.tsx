@@ -0,0 +1,8 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+}
+
+declare function List<T>(props: { items: T[] }): JSX.Element;
+
+<List<string> items={[1, 2]} />;
@@ -0,0 +1,11 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+    interface IntrinsicAttributes {
+        key?: string | number;
+    }
+}
+
+declare function Item(props: { label: string }): JSX.Element;
+
+<Item key={true} label="b" />;
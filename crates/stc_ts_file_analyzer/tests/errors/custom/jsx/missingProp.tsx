@@ -0,0 +1,8 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+}
+
+declare function Greeting(props: { name: string }): JSX.Element;
+
+<Greeting />;
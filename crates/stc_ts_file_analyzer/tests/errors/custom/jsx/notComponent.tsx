@@ -0,0 +1,8 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+}
+
+declare const Comp: { a: number };
+
+<Comp />;
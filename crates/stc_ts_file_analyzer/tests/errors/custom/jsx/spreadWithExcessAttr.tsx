@@ -0,0 +1,9 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+}
+
+declare function Button(props: { label: string; disabled?: boolean }): JSX.Element;
+declare const base: { label: string };
+
+<Button {...base} extra={1} />;
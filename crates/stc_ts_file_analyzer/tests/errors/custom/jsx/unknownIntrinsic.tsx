@@ -0,0 +1,9 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+    interface IntrinsicElements {
+        div: {};
+    }
+}
+
+<span />;
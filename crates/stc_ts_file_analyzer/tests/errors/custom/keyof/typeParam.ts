@@ -0,0 +1,9 @@
+//@strict: true
+
+function f<T extends { x: number }>(s: string) {
+    const x: keyof T = "x";
+    const z: keyof T = "z";
+    const k: keyof T = s;
+}
+
+export {};
@@ -0,0 +1,3 @@
+/// <reference lib="es2099.nothing" />
+
+export const a = 1;
@@ -0,0 +1,7 @@
+//@strict: true
+
+export function f(c: boolean): number {
+    if (c) {
+        return 1;
+    }
+}
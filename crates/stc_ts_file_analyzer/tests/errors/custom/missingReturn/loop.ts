@@ -0,0 +1,13 @@
+//@strict: true
+
+declare function next(): number;
+
+export function f(): number {
+    while (true) {
+        const v = next();
+        if (v > 0) {
+            break;
+        }
+        return v;
+    }
+}
@@ -0,0 +1,11 @@
+//@strict: true
+
+declare function fail(msg: string): never;
+
+// Only calls in expression statements terminate the flow.
+export function stored(c: boolean): number {
+    if (c) {
+        return 1;
+    }
+    const v = fail("unreachable");
+}
@@ -0,0 +1,2 @@
+//@module: commonjs
+const meta: ImportMeta = import.meta;
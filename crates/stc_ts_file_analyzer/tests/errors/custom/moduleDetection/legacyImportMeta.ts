@@ -0,0 +1,5 @@
+//@module: esnext
+//@moduleDetection: legacy
+declare const p: Promise<number>;
+const meta: ImportMeta = import.meta;
+await p;
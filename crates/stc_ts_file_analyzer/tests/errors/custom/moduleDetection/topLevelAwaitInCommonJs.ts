@@ -0,0 +1,4 @@
+//@module: commonjs
+declare const p: Promise<number>;
+await p;
+export {};
@@ -0,0 +1,3 @@
+//@module: esnext
+declare const p: Promise<number>;
+await p;
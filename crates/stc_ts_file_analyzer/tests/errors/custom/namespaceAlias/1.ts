@@ -0,0 +1,11 @@
+namespace Outer {
+    export namespace Inner {
+        export function make(x: number) {
+            return { x };
+        }
+    }
+}
+
+import make = Outer.Inner.make;
+
+make("");
@@ -0,0 +1,11 @@
+//@strict: true
+
+type Action = { kind: "add"; value: number } | { kind: "reset" };
+
+export function f(action: Action) {
+    const { kind } = action;
+    if (kind === "reset") {
+        return action.value;
+    }
+    return 0;
+}
@@ -0,0 +1,11 @@
+//@strict: true
+
+export function f(x: string | undefined) {
+    let y: string | undefined = x;
+    const ok = y !== undefined;
+    if (ok) {
+        const s: string = y;
+        return s.length;
+    }
+    return 0;
+}
@@ -0,0 +1,10 @@
+//@strict: true
+
+export function f(x?: string) {
+    const ok = x !== undefined;
+    x = undefined;
+    if (ok) {
+        return x.length;
+    }
+    return 0;
+}
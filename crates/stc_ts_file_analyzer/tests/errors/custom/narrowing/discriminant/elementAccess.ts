@@ -0,0 +1,10 @@
+type Shape = { kind: "circle"; radius: number } | { kind: "square"; size: number };
+
+const kind = "kind" as const;
+
+export function constKey(s: Shape): number {
+    if (s[kind] === "circle") {
+        return s.size;
+    }
+    return 0;
+}
@@ -0,0 +1,27 @@
+//@strict: true
+
+interface Circle {
+    kind: "circle";
+    radius: number;
+}
+
+interface Square {
+    kind: "square";
+    size: number;
+}
+
+interface Triangle {
+    kind: "triangle";
+    base: number;
+}
+
+type Shape = Circle | Square | Triangle;
+
+export function f(s: Shape): number {
+    switch (s.kind) {
+        case "circle":
+            return s.radius;
+        case "square":
+            return s.size;
+    }
+}
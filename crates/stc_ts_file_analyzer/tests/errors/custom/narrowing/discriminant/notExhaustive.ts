@@ -0,0 +1,28 @@
+interface Circle {
+    kind: "circle";
+    radius: number;
+}
+
+interface Square {
+    kind: "square";
+    size: number;
+}
+
+interface Triangle {
+    kind: "triangle";
+    base: number;
+}
+
+type Shape = Circle | Square | Triangle;
+
+export function f(s: Shape) {
+    switch (s.kind) {
+        case "circle":
+            return s.radius;
+        case "square":
+            return s.size;
+        default:
+            const n: never = s;
+            return n;
+    }
+}
@@ -0,0 +1,8 @@
+export function f(c: boolean) {
+    let v: string | number | boolean = "a";
+    if (c) {
+        v = 1;
+    }
+    const s: string = v;
+    return s;
+}
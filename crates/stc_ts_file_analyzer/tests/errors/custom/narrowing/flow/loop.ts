@@ -0,0 +1,10 @@
+declare function cond(): boolean;
+
+export function f() {
+    let v: string | number | boolean = "a";
+    while (cond()) {
+        v = 1;
+    }
+    const n: number = v;
+    return n;
+}
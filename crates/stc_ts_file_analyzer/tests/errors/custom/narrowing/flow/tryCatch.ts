@@ -0,0 +1,15 @@
+declare function run(): void;
+
+export function f() {
+    let v: string | number = "a";
+    try {
+        v = 1;
+        run();
+        v = "b";
+    } catch {
+        const s: string = v;
+        return s;
+    }
+    const s: string = v;
+    return s;
+}
@@ -0,0 +1,14 @@
+interface Circle {
+    radius: number;
+}
+
+interface Square {
+    size: number;
+}
+
+export function area(s: Circle | Square): number {
+    if ("radius" in s) {
+        return s.size;
+    }
+    return 0;
+}
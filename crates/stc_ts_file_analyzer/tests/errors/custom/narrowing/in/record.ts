@@ -0,0 +1,11 @@
+interface A {
+    a: number;
+}
+
+export function undeclared(v: A) {
+    if ("c" in v) {
+        const c: number = v.c;
+        return c;
+    }
+    return v.a;
+}
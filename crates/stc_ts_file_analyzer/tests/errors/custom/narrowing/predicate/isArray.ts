@@ -0,0 +1,7 @@
+export function f(x: string | number[]) {
+    if (Array.isArray(x)) {
+        const s: string[] = x;
+        return s.length;
+    }
+    return 0;
+}
@@ -0,0 +1,20 @@
+class Entry {
+    isFile(): this is FileEntry {
+        return this instanceof FileEntry;
+    }
+}
+
+class FileEntry extends Entry {
+    content = "";
+}
+
+class DirectoryEntry extends Entry {
+    children: Entry[] = [];
+}
+
+export function size(e: Entry): number {
+    if (e.isFile()) {
+        return e.children.length;
+    }
+    return 0;
+}
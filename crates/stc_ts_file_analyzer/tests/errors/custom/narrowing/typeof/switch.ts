@@ -0,0 +1,9 @@
+export function f(v: string | number | boolean) {
+    switch (typeof v) {
+        case "number":
+        case "boolean":
+            const n: number = v;
+            return n;
+    }
+    return undefined;
+}
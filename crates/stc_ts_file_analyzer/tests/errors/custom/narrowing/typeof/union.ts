@@ -0,0 +1,8 @@
+declare const v: string | number;
+
+if (typeof v === "string") {
+} else {
+    const s: string = v;
+}
+
+export { };
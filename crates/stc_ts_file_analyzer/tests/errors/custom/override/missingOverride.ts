@@ -0,0 +1,9 @@
+//@noImplicitOverride: true
+
+class Base {
+    greet() {}
+}
+
+export class Derived extends Base {
+    greet() {}
+}
@@ -0,0 +1,9 @@
+//@noImplicitOverride: true
+
+abstract class Base {
+    abstract greet(): void;
+}
+
+export abstract class Derived extends Base {
+    abstract greet(): void;
+}
@@ -0,0 +1,11 @@
+//@noImplicitOverride: true
+
+class Base {
+    name = "";
+}
+
+export class Derived extends Base {
+    constructor(public name: string) {
+        super();
+    }
+}
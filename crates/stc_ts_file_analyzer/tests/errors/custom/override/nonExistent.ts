@@ -0,0 +1,9 @@
+class Base {
+    greet() {}
+}
+
+export class Derived extends Base {
+    override greet() {}
+
+    override farewell() {}
+}
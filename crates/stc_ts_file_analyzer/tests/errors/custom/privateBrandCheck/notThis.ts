@@ -0,0 +1,10 @@
+export class Base {
+    #brand = 1;
+
+    same(other: object): this {
+        if (#brand in other) {
+            return other;
+        }
+        return this;
+    }
+}
@@ -0,0 +1,7 @@
+export class C {
+    #value = 1;
+
+    equals(other: object) {
+        return #other in other;
+    }
+}
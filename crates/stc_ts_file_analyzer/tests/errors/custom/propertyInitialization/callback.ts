@@ -0,0 +1,13 @@
+//@strict: true
+
+declare function later(cb: () => void): void;
+
+export class C {
+    a: string;
+
+    constructor() {
+        later(() => {
+            this.a = "";
+        });
+    }
+}
@@ -0,0 +1,13 @@
+//@strict: true
+
+declare function check(): boolean;
+
+export class C {
+    a: number;
+
+    constructor() {
+        if (check()) {
+            this.a = 1;
+        }
+    }
+}
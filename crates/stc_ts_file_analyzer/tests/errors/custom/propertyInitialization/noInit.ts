@@ -0,0 +1,5 @@
+//@strict: true
+
+export class C {
+    a: number;
+}
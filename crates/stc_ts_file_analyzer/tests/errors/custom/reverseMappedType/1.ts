@@ -0,0 +1,12 @@
+//@strict: true
+
+type Setters<T> = { [K in keyof T]: (value: T[K]) => void };
+
+declare function createStore<T>(setters: Setters<T>): T;
+
+const store = createStore({
+    count: (value: number) => {},
+});
+const count: string = store.count;
+
+export {};
@@ -0,0 +1,6 @@
+interface Point {
+    x: number;
+    y: number;
+}
+
+export const p = { x: 1 } satisfies Point;
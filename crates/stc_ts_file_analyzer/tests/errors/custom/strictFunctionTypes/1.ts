@@ -0,0 +1,15 @@
+//@strict: true
+
+interface Animal {
+    name: string;
+}
+interface Dog extends Animal {
+    bark(): void;
+}
+
+declare let f1: (x: Animal) => void;
+declare let f2: (x: Dog) => void;
+
+f1 = f2;
+
+export {};
@@ -0,0 +1,7 @@
+//@strict: true
+
+interface WeakRef<T extends object> {
+    deref(): T | undefined;
+}
+
+export const r = WeakRef;
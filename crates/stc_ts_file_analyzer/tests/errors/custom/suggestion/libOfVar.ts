@@ -0,0 +1,3 @@
+//@strict: true
+
+export const r = new WeakRef({});
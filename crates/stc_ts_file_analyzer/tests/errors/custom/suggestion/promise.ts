@@ -0,0 +1,8 @@
+//@strict: true
+
+declare function fetchUser(): Promise<{ name: string }>;
+
+export async function f() {
+    const user = fetchUser();
+    return user.name;
+}
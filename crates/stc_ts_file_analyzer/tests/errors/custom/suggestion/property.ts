@@ -0,0 +1,5 @@
+//@strict: true
+
+declare const o: { length: number; push(v: number): void };
+
+export const x = o.lenght;
@@ -0,0 +1,5 @@
+//@strict: true
+
+const message = "hello";
+
+export const x = mesage;
@@ -0,0 +1,5 @@
+declare function f<T>(x: T): T;
+declare const x: "a" | "b";
+
+const a = f(`id-${x}`);
+export const b: "id-a" | "id-b" = a;
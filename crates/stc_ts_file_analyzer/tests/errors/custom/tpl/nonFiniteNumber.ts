@@ -0,0 +1,3 @@
+export const a: `${number}` = "NaN";
+export const b: `${number}px` = "infpx";
+export const c: `${number}` = "Infinity";
@@ -0,0 +1,7 @@
+//@strict: true
+
+type Id = `id-${number}`;
+
+const id: Id = "id-abc";
+
+export {};
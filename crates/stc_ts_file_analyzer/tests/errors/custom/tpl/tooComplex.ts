@@ -0,0 +1,4 @@
+type D = 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 | 8 | 9;
+declare const d: D;
+
+export const x = `${d}${d}${d}${d}${d}${d}` as const;
@@ -0,0 +1,3 @@
+declare const x: "a" | "b";
+
+export const a: "id-a" = `id-${x}`;
@@ -0,0 +1,11 @@
+//@isolatedModules: true
+
+//@filename: model.ts
+export interface Point {
+    x: number;
+}
+
+//@filename: index.ts
+import { Point } from "./model";
+
+export { Point };
@@ -0,0 +1,9 @@
+//@isolatedModules: true
+
+//@filename: model.ts
+export interface Point {
+    x: number;
+}
+
+//@filename: index.ts
+export { Point } from "./model";
@@ -0,0 +1,9 @@
+//@filename: model.ts
+export class Model {
+    id: number = 0;
+}
+
+//@filename: index.ts
+import type { Model } from "./model";
+
+export const model = new Model();
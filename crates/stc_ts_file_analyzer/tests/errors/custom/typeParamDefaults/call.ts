@@ -0,0 +1,11 @@
+//@strict: true
+
+declare function withDefault<T, U = T[]>(x: T): U;
+
+const a: string[] = withDefault(1);
+
+declare function pair<T, U = T>(): [T, U];
+
+pair<number, string, boolean>();
+
+export {};
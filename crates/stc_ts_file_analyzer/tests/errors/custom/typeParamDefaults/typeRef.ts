@@ -0,0 +1,19 @@
+//@strict: true
+
+interface Pair<T, U = T> {
+    first: T;
+    second: U;
+}
+
+const a: Pair<number> = { first: 1, second: "" };
+
+declare const b: Pair;
+declare const c: Pair<number, string, boolean>;
+
+interface Box<T> {
+    value: T;
+}
+
+declare const d: Box;
+
+export {};
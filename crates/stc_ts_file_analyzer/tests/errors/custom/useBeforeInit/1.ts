@@ -0,0 +1,11 @@
+//@strict: true
+
+declare function use(v: unknown): void;
+
+export function branch(c: boolean) {
+    let a: string;
+    if (c) {
+        a = "a";
+    }
+    use(a);
+}
@@ -0,0 +1,11 @@
+//@strict: true
+
+declare function use(v: unknown): void;
+
+export function loop(c: boolean) {
+    let a: number;
+    while (c) {
+        use(a);
+        a = 1;
+    }
+}
@@ -0,0 +1,6 @@
+declare function use(v: unknown): void;
+
+export function tdz() {
+    use(a);
+    let a = 1;
+}
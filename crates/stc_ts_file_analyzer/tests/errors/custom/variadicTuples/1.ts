@@ -0,0 +1,5 @@
+//@strict: true
+
+const bad: [string, ...number[], boolean] = ["a", 1, "b", true];
+
+export {};
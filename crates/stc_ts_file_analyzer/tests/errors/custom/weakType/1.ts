@@ -0,0 +1,12 @@
+//@strict: true
+
+interface Options {
+    color?: string;
+    width?: number;
+}
+
+declare const typo: { colour: string };
+
+const opts: Options = typo;
+
+export {};
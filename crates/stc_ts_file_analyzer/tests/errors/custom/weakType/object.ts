@@ -0,0 +1,7 @@
+//@strict: true
+
+declare const sym: symbol;
+
+const o: object = sym;
+
+export {};
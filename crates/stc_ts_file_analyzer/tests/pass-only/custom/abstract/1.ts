@@ -0,0 +1,22 @@
+abstract class Shape {
+    abstract readonly name: string;
+    abstract area(): number;
+
+    describe() {
+        return `${this.name}: ${this.area()}`;
+    }
+}
+
+abstract class Polygon extends Shape {
+    readonly name = "polygon";
+}
+
+export class Square extends Polygon {
+    constructor(private size: number) {
+        super();
+    }
+
+    area() {
+        return this.size * this.size;
+    }
+}
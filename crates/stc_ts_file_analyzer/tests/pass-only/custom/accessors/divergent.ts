@@ -0,0 +1,20 @@
+export class Thing {
+    #size = 0;
+
+    get size(): number {
+        return this.#size;
+    }
+
+    set size(value: string | number | boolean) {
+        this.#size = Number(value);
+    }
+
+    reset() {
+        this.size = "0";
+    }
+}
+
+const t = new Thing();
+t.size = "hello";
+t.size = true;
+export const n: number = t.size;
@@ -0,0 +1,11 @@
+//@filename: types.d.ts
+declare module "foo";
+
+//@filename: index.ts
+import foo, { Bar, baz } from "foo";
+import * as ns from "foo";
+
+export const a: string = foo.anything;
+export const b: Bar = 1;
+export const c: number = baz();
+export const d: boolean = ns.value;
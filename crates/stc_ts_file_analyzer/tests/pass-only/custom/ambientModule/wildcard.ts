@@ -0,0 +1,16 @@
+//@filename: types.d.ts
+declare module "*.css" {
+    const classes: { [key: string]: string };
+    export default classes;
+}
+
+declare module "theme.css" {
+    export const primary: string;
+}
+
+//@filename: index.ts
+import styles from "./button.css";
+import { primary } from "theme.css";
+
+export const button: string = styles.button;
+export const color: string = primary;
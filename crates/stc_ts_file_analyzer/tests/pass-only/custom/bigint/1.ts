@@ -0,0 +1,18 @@
+declare let a: bigint;
+declare let b: bigint;
+
+export const sum: bigint = a + b;
+export const product: bigint = a * b;
+export const quotient: bigint = a / b;
+export const remainder: bigint = a % b;
+export const power: bigint = a ** b;
+export const shifted: bigint = a << b;
+export const masked: bigint = a & 0xffn;
+export const negated: bigint = -a;
+export const inverted: bigint = ~a;
+
+export const lit: -1n = -1n;
+export const str: string = "value: " + a;
+export const cmp: boolean = a < 1;
+
+export const converted: bigint = BigInt(1) + 2n;
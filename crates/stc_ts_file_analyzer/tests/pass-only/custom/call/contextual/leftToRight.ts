@@ -0,0 +1,18 @@
+//@strict: true
+
+declare function each<T>(items: T[], cb: (item: T) => void): void;
+
+each([1, 2], (item) => item.toFixed());
+each(["a"], function (item) {
+    item.toUpperCase();
+});
+
+declare function chain<T, U>(items: T[], map: (item: T) => U, cb: (u: U) => void): void;
+
+chain(
+    [1],
+    (item) => item.toFixed(),
+    (u) => u.toUpperCase()
+);
+
+export {};
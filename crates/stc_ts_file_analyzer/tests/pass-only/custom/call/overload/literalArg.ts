@@ -0,0 +1,23 @@
+interface ClickEvent {
+    x: number;
+    y: number;
+}
+
+interface KeyEvent {
+    key: string;
+}
+
+declare function on(type: "click", listener: (e: ClickEvent) => void): void;
+declare function on(type: "key", listener: (e: KeyEvent) => void): void;
+
+on("click", (e) => {
+    const x: number = e.x;
+});
+
+on("key", (e) => {
+    const key: string = e.key;
+});
+
+on("key", function (e) {
+    const key: string = e.key;
+});
@@ -0,0 +1,23 @@
+interface ClickEvent {
+    x: number;
+}
+
+interface KeyEvent {
+    key: string;
+}
+
+declare function on(type: "click", listener: (e: ClickEvent) => void): void;
+declare function on(type: "key", listener: (e: KeyEvent) => void): void;
+declare function on(type: string, listener: (e: {}) => void): void;
+
+on("key", (e) => {
+    const key: string = e.key;
+});
+
+on(`click`, (e) => {
+    const x: number = e.x;
+});
+
+on("scroll", (e) => {
+    const v: {} = e;
+});
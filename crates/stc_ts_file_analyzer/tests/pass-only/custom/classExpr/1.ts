@@ -0,0 +1,21 @@
+class Base<T> {
+    constructor(public value: T) {}
+}
+
+function derive<T>(v: T) {
+    const Derived = class extends Base<T> {
+        get() {
+            return this.value;
+        }
+    };
+    return new Derived(v);
+}
+
+export const n: number = derive(1).get();
+
+export const Point = class {
+    constructor(public x: number, public y: number) {}
+};
+
+export const p = new Point(1, 2);
+export const x: number = p.x;
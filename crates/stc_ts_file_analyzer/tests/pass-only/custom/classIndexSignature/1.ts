@@ -0,0 +1,21 @@
+export class C {
+    [key: string]: number | (() => number);
+
+    a: number = 1;
+
+    b() {
+        this.other;
+        return this.a;
+    }
+
+    static [key: string]: boolean | string;
+    static c = true;
+    static d: string = "";
+}
+
+declare const c: C;
+
+export const a: number | (() => number) = c.foo;
+export const b: boolean | string = C.foo;
+
+C.bar = false;
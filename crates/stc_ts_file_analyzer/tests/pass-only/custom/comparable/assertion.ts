@@ -0,0 +1,14 @@
+//@strict: true
+
+interface Options {
+    a?: string;
+    b?: number;
+}
+
+declare const o: { a: string | number };
+
+// Optional properties are ignored.
+export const x = {} as Options;
+// A union property is comparable if one of the constituents is.
+export const y = o as { a: string };
+export const z = 1 as 1 | 2;
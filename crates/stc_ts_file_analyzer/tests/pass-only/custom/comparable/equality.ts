@@ -0,0 +1,14 @@
+//@strict: true
+
+interface A {
+    x: string | number;
+}
+interface B {
+    x: number;
+    y?: string;
+}
+
+declare const a: A;
+declare const b: B;
+
+export const eq = a === b;
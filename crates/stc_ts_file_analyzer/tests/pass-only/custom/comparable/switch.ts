@@ -0,0 +1,16 @@
+//@strict: true
+
+export function f(x: "a" | "b" | number) {
+    switch (x) {
+        case "a":
+        case 1:
+            return 1;
+    }
+}
+
+export function g<T>(x: T) {
+    switch (x) {
+        case "a":
+            return 1;
+    }
+}
@@ -0,0 +1,17 @@
+type MyExclude<T, U> = T extends U ? never : T;
+type MyReturnType<T> = T extends (...args: any[]) => infer R ? R : never;
+type Boxed<T> = T extends any ? { value: T } : never;
+type NotDistributed<T> = [T] extends [string] ? "yes" : "no";
+type Param<T> = T extends { a: (x: infer U) => void; b: (x: infer U) => void } ? U : never;
+
+declare function make(): number;
+
+const a: MyExclude<"a" | "b" | "c", "a"> = "b";
+const r: MyReturnType<typeof make> = 1;
+const b: Boxed<string | number> = { value: 1 };
+const n: NotDistributed<string | number> = "no";
+
+declare const p: Param<{ a: (x: { a: 1 }) => void; b: (x: { b: 2 }) => void }>;
+const q: { a: 1; b: 2 } = p;
+
+export {};
@@ -0,0 +1,9 @@
+type IsString<T> = T extends string ? true : false;
+
+function f<T>(x: T): IsString<T> {
+    return null as any;
+}
+
+const t: true = f("");
+
+export {};
@@ -0,0 +1,10 @@
+//@strict: true
+
+type First<T, U> = T extends [infer A, U] ? A : never;
+
+function f<U>(m: First<[string, number], U>, n: First<[string, number], number>) {
+    const s: string = n;
+    return m;
+}
+
+export const s: string = f<number>("", "");
@@ -0,0 +1,13 @@
+//@isolatedModules: true
+
+const enum E {
+    A = 1,
+    B = A << 1,
+}
+
+declare const enum D {
+    X,
+}
+
+export const a: E = E.B;
+export let d: D;
@@ -0,0 +1,12 @@
+const enum A {
+    X = 1,
+    Y = "y",
+}
+
+const enum B {
+    X = A.X << 1,
+    Y = A["Y"],
+}
+
+export const x: B = B.X;
+export const y: string = B.Y;
@@ -0,0 +1,12 @@
+//@isolatedModules: true
+
+//@filename: enums.ts
+export const enum E {
+    A,
+}
+
+//@filename: index.ts
+import { E as F } from "./enums";
+
+export { E } from "./enums";
+export const a: F = F.A;
@@ -0,0 +1,12 @@
+class Point {
+    constructor(public x: number, public y: number) {}
+}
+
+namespace Point {
+    export const origin = new Point(0, 0);
+    export type Pair = [number, number];
+}
+
+const p: Point = Point.origin;
+const x: number = p.x;
+const pair: Point.Pair = [1, 2];
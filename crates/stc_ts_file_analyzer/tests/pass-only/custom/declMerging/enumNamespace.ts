@@ -0,0 +1,14 @@
+enum Color {
+    Red,
+    Green,
+}
+
+namespace Color {
+    export function parse(s: string): Color {
+        return s === "red" ? Color.Red : Color.Green;
+    }
+}
+
+const c: Color = Color.parse("red");
+const r: Color = Color.Red;
+const g: Color.Green = Color.Green;
@@ -0,0 +1,10 @@
+function greet(name: string): string {
+    return greet.prefix + name;
+}
+
+namespace greet {
+    export const prefix = "Hello, ";
+}
+
+const s: string = greet("world");
+const prefix: string = greet.prefix;
@@ -0,0 +1,12 @@
+interface Color {
+    r: number;
+}
+
+namespace Color {
+    export function black(): Color {
+        return { r: 0 };
+    }
+}
+
+const c: Color = Color.black();
+const r: number = c.r;
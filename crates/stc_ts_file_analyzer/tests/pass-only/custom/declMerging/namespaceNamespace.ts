@@ -0,0 +1,10 @@
+namespace A {
+    export const a = 1;
+}
+
+namespace A {
+    export const b = "";
+}
+
+const a: number = A.a;
+const b: string = A.b;
@@ -0,0 +1,25 @@
+function sealed(target: Function, context: ClassDecoratorContext) {}
+
+function bound(value: Function, context: ClassMethodDecoratorContext) {}
+
+function logged(value: () => number, context: ClassGetterDecoratorContext) {
+    return value;
+}
+
+function field(value: undefined, context: ClassFieldDecoratorContext) {
+    return (initial: number) => initial * 2;
+}
+
+@sealed
+export class Foo {
+    @field
+    x = 1;
+
+    @bound
+    method() {}
+
+    @logged
+    get y() {
+        return 1;
+    }
+}
@@ -0,0 +1,33 @@
+//@experimentalDecorators: true
+
+function sealed(constructor: Function) {}
+
+function format(target: Object, key: string) {}
+
+function enumerable(target: Object, key: string, descriptor: PropertyDescriptor) {}
+
+function logged(target: Object, key: string, descriptor: TypedPropertyDescriptor<number>) {
+    return descriptor;
+}
+
+function required(target: Object, key: string | symbol | undefined, index: number) {}
+
+@sealed
+export class Greeter {
+    @format
+    greeting = "";
+
+    constructor(@required greeting: string) {
+        this.greeting = greeting;
+    }
+
+    @enumerable
+    greet(@required name: string) {
+        return name;
+    }
+
+    @logged
+    get value() {
+        return 1;
+    }
+}
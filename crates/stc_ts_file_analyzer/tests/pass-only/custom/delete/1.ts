@@ -0,0 +1,10 @@
+declare const o: { a?: string; b: any; c: unknown; d: string | undefined; [key: string]: unknown };
+
+delete o.a;
+delete o.b;
+delete o.c;
+delete o.d;
+delete o["e"];
+delete (o.a);
+
+export { };
@@ -0,0 +1,13 @@
+//@strict: true
+
+declare const o: { a?: string; b: any; c: unknown; d: string | undefined; e: never; [key: string]: unknown };
+
+delete o.a;
+delete o.b;
+delete o.c;
+delete o.d;
+delete o.e;
+delete o["f"];
+delete (o.a);
+
+export { };
@@ -0,0 +1,25 @@
+const enum Flags {
+    None = 0,
+    A = 1 << 2,
+    B = A | 1,
+    C = Flags.B << 1,
+    D = ~0 >>> 28,
+    E = 7 % 4,
+    F = 2 ** 3,
+}
+
+const a: 4 = Flags.A;
+const b: 5 = Flags.B;
+const c: 10 = Flags.C;
+const d: 15 = Flags.D;
+const e: 3 = Flags.E;
+const f: 8 = Flags.F;
+
+enum Str {
+    Prefix = "foo",
+    Full = Prefix + "bar",
+    Tpl = `${Full}-${1 << 2}`,
+}
+
+const full: "foobar" = Str.Full;
+const tpl: "foobar-4" = Str.Tpl;
@@ -0,0 +1,14 @@
+//@strict: true
+//@exactOptionalPropertyTypes: true
+
+declare const o: { a?: string; b?: string | undefined };
+
+o.a = "a";
+o.b = undefined;
+
+const a: string | undefined = o.a;
+
+declare const c: { a: string; b: undefined };
+const d: { a?: string; b?: string | undefined } = c;
+
+export {};
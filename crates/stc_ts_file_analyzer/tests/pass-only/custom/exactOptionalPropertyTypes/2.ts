@@ -0,0 +1,10 @@
+//@strict: true
+
+declare const a: { a: undefined };
+
+const b: { a?: string } = a;
+
+declare const o: { a?: string };
+o.a = undefined;
+
+export {};
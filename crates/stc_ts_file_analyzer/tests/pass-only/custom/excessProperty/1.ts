@@ -0,0 +1,19 @@
+//@strict: true
+
+interface Options {
+    style: {
+        color: string;
+    };
+}
+
+declare const style: { color: string; colour: string };
+
+const o: Options = { style };
+const o2: Options = { style: style };
+
+type Shape = { kind: "circle"; radius: number } | { kind: "square"; size: number };
+
+const c: Shape = { kind: "circle", radius: 1 };
+const s: Shape = { kind: "square", size: 1 };
+
+export {};
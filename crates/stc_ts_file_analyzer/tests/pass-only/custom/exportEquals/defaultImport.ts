@@ -0,0 +1,14 @@
+//@esModuleInterop: true
+
+//@filename: model.ts
+class Model {
+    id: number = 0;
+}
+
+export = Model;
+
+//@filename: index.ts
+import Model from "./model";
+
+export const model: Model = new Model();
+export const id: number = model.id;
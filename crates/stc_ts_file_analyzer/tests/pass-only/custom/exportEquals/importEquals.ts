@@ -0,0 +1,19 @@
+//@filename: model.ts
+class Model {
+    id: number = 0;
+}
+
+namespace Model {
+    export const version = 1;
+}
+
+export = Model;
+
+//@filename: index.ts
+import Model = require("./model");
+import * as ModelNs from "./model";
+
+export const model: Model = new Model();
+export const id: number = model.id;
+export const version: number = Model.version;
+export const other: Model = new ModelNs();
@@ -0,0 +1,17 @@
+//@filename: a.ts
+export const value = 1;
+
+//@filename: b.ts
+export const value = "b";
+export const other = true;
+
+//@filename: reexport.ts
+export * from "./a";
+export * from "./b";
+export { value } from "./a";
+
+//@filename: index.ts
+import { other, value } from "./reexport";
+
+export const v: number = value;
+export const o: boolean = other;
@@ -0,0 +1,23 @@
+//@filename: base.ts
+export interface Shape {
+    area(): number;
+}
+
+export const unit = 1;
+
+export default function make(): Shape {
+    return { area: () => unit };
+}
+
+//@filename: middle.ts
+export * from "./base";
+export * as base from "./base";
+
+export const extra = "extra";
+
+//@filename: index.ts
+import { base, extra, Shape, unit } from "./middle";
+
+export const s: Shape = base.default();
+export const n: number = unit + s.area();
+export const e: string = extra;
@@ -0,0 +1,5 @@
+declare function inner(): Generator<number, string, undefined>;
+
+export function* outer(): Generator<number, void, undefined> {
+    const result: string = yield* inner();
+}
@@ -0,0 +1,9 @@
+var x = 1;
+function f() {
+    return "";
+}
+
+const a: number = globalThis.x;
+const b: string = globalThis.f();
+type X = (typeof globalThis)["x"];
+const c: X = 2;
@@ -0,0 +1,17 @@
+//@strict: true
+
+declare function pipe<A, B, C>(ab: (a: A) => B, bc: (b: B) => C): (a: A) => C;
+
+declare function list<T>(a: T): T[];
+declare function box<V>(x: V): { value: V };
+
+const listBox = pipe(list, box);
+
+const x1: { value: number[] } = listBox(42);
+const x2: { value: string[] } = listBox("hello");
+
+const boxList = pipe(box, list);
+
+const y1: { value: number }[] = boxList(1);
+
+export {};
@@ -0,0 +1,15 @@
+var a: string | number;
+var a: number | string;
+
+var b: { x: string; y?: number };
+var b: { y?: number; x: string };
+
+interface Foo {
+    x: string;
+}
+interface Foo {
+    x: string;
+    y: number;
+}
+
+export { };
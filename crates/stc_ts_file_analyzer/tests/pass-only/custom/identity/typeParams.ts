@@ -0,0 +1,4 @@
+var a: { f: <T extends string>(x: T) => void };
+var a: { f: <T extends string>(x: T) => void };
+
+export { };
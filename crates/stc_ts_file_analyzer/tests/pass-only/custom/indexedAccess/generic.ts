@@ -0,0 +1,19 @@
+//@strict: true
+
+function get<T, K extends keyof T>(obj: T, key: K): T[K] {
+    return obj[key];
+}
+
+const n: number = get({ a: 1, b: "x" }, "a");
+const s: string = get({ a: 1, b: "x" }, "b");
+
+function read<T extends { a: string; b: string }, K extends "a" | "b">(t: T, k: K) {
+    const value: string = t[k];
+    return value;
+}
+
+function write<K extends "a" | "b">(o: { a: string; b: string }, k: K, v: string) {
+    o[k] = v;
+}
+
+export {};
@@ -0,0 +1,15 @@
+//@strict: true
+
+declare function values<T>(o: { [k: string]: T }): T;
+
+const a: number | string = values({ a: 1, b: "x" });
+
+declare function methods<T>(o: { [k: string]: T }): T;
+
+const b: () => number = methods({
+    foo() {
+        return 1;
+    },
+});
+
+export {};
@@ -0,0 +1,19 @@
+//@strict: true
+
+declare function values<T>(r: Record<string, T>): T;
+
+const a: number | boolean = values({ a: 1, b: true });
+
+declare function pick<T>(r: { [P in "a" | "b"]: T }): T;
+
+const b: string = pick({ a: "x", b: "y" });
+
+interface Options {
+    x: number;
+    y: number;
+}
+declare const options: Options;
+
+const c: number = values(options);
+
+export {};
@@ -0,0 +1,23 @@
+//@strict: true
+
+declare function callback<T>(cb: (x: T) => void): T;
+
+const a: string = callback((x: string) => {});
+
+declare function both<T>(a: (x: T) => void, b: (x: T) => void): T;
+
+const b: string = both(
+    (x: string) => {},
+    (x: string | number) => {}
+);
+
+declare function dependent<T>(x: T, cb: (v: T) => void): void;
+
+dependent(1, (v) => v.toFixed());
+dependent("", (v) => v.toUpperCase());
+
+declare function identity<T>(x: T): T;
+
+const c: string | number = identity(1);
+
+export {};
@@ -0,0 +1,8 @@
+//@strict: true
+
+declare function fromKeys<T>(key: keyof T): T;
+
+const o = fromKeys("a");
+o.a;
+
+export {};
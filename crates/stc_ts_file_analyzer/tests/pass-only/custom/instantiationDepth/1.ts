@@ -0,0 +1,9 @@
+type Json = string | number | boolean | null | Json[] | { [key: string]: Json };
+
+const json: Json = { a: [1, "b", { c: null }] };
+
+type Repeat<T, N extends number, Acc extends T[] = []> = Acc["length"] extends N ? Acc : Repeat<T, N, [...Acc, T]>;
+
+const three: Repeat<string, 3> = ["a", "b", "c"];
+
+export {};
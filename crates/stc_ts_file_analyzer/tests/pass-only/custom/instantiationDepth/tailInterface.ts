@@ -0,0 +1,13 @@
+interface Box<T> {
+    value: T;
+}
+
+type Wrap<T> = T extends string ? Box<T> : never;
+
+const boxed: Wrap<"a"> = { value: "a" };
+
+type Repeat<S extends string, N extends unknown[]> = N extends [unknown, ...infer R] ? Repeat<`${S}a`, R> : Box<S>;
+
+const repeated: Repeat<"", [0, 0, 0]> = { value: "aaa" };
+
+export {};
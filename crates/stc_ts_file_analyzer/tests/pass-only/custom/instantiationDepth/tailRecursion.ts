@@ -0,0 +1,9 @@
+type TrimLeft<S extends string> = S extends ` ${infer R}` ? TrimLeft<R> : S;
+
+const trimmed: TrimLeft<"                                                                                                                                                                                                                                                                                                            x"> = "x";
+
+type Reverse<S extends string, Acc extends string = ""> = S extends `${infer H}${infer R}` ? Reverse<R, `${H}${Acc}`> : Acc;
+
+const reversed: Reverse<"abc"> = "cba";
+
+export {};
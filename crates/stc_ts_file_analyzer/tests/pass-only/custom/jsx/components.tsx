@@ -0,0 +1,20 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+    interface ElementAttributesProperty {
+        props: {};
+    }
+    interface ElementChildrenAttribute {
+        children: {};
+    }
+}
+
+declare function Greeting(props: { name: string; children?: string }): JSX.Element;
+
+declare class Counter {
+    props: { initial: number; onChange?: (value: number) => void };
+}
+
+<Greeting name="a" />;
+<Greeting name="a">hello</Greeting>;
+<Counter initial={1} onChange={(value) => value.toFixed()} />;
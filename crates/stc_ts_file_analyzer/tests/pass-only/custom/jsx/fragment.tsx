@@ -0,0 +1,14 @@
+//@jsx: react
+declare namespace JSX {
+    interface Element {}
+}
+
+declare const React: {
+    createElement(type: any, props: any, ...children: any[]): JSX.Element;
+    Fragment: {};
+};
+
+<>text</>;
+<>
+    <>nested</>
+</>;
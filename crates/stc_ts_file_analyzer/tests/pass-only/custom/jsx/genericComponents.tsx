@@ -0,0 +1,18 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+    interface ElementAttributesProperty {
+        props: {};
+    }
+}
+
+declare function List<T>(props: { items: T[]; render: (item: T) => string }): JSX.Element;
+
+declare class Select<T> {
+    constructor(props: { options: T[]; onSelect: (option: T) => void });
+    props: { options: T[]; onSelect: (option: T) => void };
+}
+
+<List items={[1, 2]} render={(item) => item.toFixed()} />;
+<List<string> items={["a"]} render={(item) => item.toUpperCase()} />;
+<Select options={["a", "b"]} onSelect={(option) => option.toUpperCase()} />;
@@ -0,0 +1,10 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+    interface IntrinsicElements {
+        div: { id?: string; hidden?: boolean };
+    }
+}
+
+const a: JSX.Element = <div id="a" hidden />;
+const b: JSX.Element = <div>text</div>;
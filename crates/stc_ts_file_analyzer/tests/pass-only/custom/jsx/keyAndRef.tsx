@@ -0,0 +1,23 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+    interface ElementAttributesProperty {
+        props: {};
+    }
+    interface IntrinsicAttributes {
+        key?: string | number;
+    }
+    interface IntrinsicClassAttributes<T> {
+        ref?: (instance: T) => void;
+    }
+}
+
+declare function Item(props: { label: string }): JSX.Element;
+
+declare class List {
+    props: { size: number };
+    scrollTop(): void;
+}
+
+<Item key="a" label="b" />;
+<List key={1} size={2} ref={(list) => list.scrollTop()} />;
@@ -0,0 +1,14 @@
+//@jsx: preserve
+declare namespace JSX {
+    interface Element {}
+}
+
+declare function Button(props: { label: string; disabled?: boolean }): JSX.Element;
+declare const base: { label: string };
+
+declare const anything: any;
+
+<Button {...base} />;
+<Button {...base} disabled />;
+<Button label="a" {...{ disabled: true }} />;
+<Button {...anything} />;
@@ -0,0 +1,34 @@
+//@strict: true
+
+declare const sym: unique symbol;
+
+interface Keys {
+    a: string;
+    0: number;
+    [sym]: boolean;
+}
+
+const k1: keyof Keys = "a";
+const k2: keyof Keys = 0;
+const k3: keyof Keys = sym;
+
+interface Dict {
+    [key: string]: number;
+}
+
+const d1: keyof Dict = "x";
+const d2: keyof Dict = 1;
+
+type Remapped = { [K in "a" | "b" as `get_${K}`]: K };
+const r: keyof Remapped = "get_a";
+
+const arr: keyof string[] = 1;
+const len: keyof string[] = "length";
+const tupleIndex: keyof [string, number] = 1;
+const tupleMember: keyof [string, number] = "push";
+
+function f<T extends { x: number; y: string }>() {
+    const k: keyof T = "x";
+}
+
+export {};
@@ -0,0 +1,14 @@
+//@strict: true
+
+function get<T extends { x: number }, K extends keyof T>(obj: T, key: K): T[K] {
+    return obj[key];
+}
+
+function f<T extends { x: number; y: string }>(obj: T, key: keyof T) {
+    const x: keyof T = "x";
+    const y: keyof T = "y";
+    const k: keyof T = key;
+    return obj[key];
+}
+
+export const x: number = get({ x: 1, y: "" }, "x");
@@ -0,0 +1,37 @@
+//@strict: true
+
+interface Person {
+    readonly name: string;
+    age?: number;
+}
+
+type Getters<T> = {
+    [K in keyof T as `get${Capitalize<string & K>}`]: () => T[K];
+};
+
+declare const getters: Getters<Person>;
+export const name: string = getters.getName();
+
+type OmitAge<T> = {
+    [K in keyof T as K extends "age" ? never : K]: T[K];
+};
+
+declare const omitted: OmitAge<Person>;
+export const name2: string = omitted.name;
+
+type Concrete<T> = {
+    -readonly [K in keyof T]-?: T[K];
+};
+
+declare const concrete: Concrete<Person>;
+concrete.name = "a";
+export const age: number = concrete.age;
+
+type Boxed<T> = {
+    [K in keyof T]: { value: T[K] };
+};
+
+declare const tuple: Boxed<[string, number]>;
+export const first: string = tuple[0].value;
+export const second: number = tuple[1].value;
+export const len: 2 = tuple.length;
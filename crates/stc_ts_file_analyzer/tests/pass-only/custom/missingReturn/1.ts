@@ -0,0 +1,32 @@
+//@strict: true
+
+declare function next(): number;
+
+export function loop(): number {
+    while (true) {
+        const v = next();
+        if (v > 0) {
+            return v;
+        }
+    }
+}
+
+export function forever(): number {
+    for (;;) {
+        return next();
+    }
+}
+
+export function branches(c: boolean): number {
+    if (c) {
+        return 1;
+    } else {
+        return 2;
+    }
+}
+
+export function optional(c: boolean): number | undefined {
+    if (c) {
+        return 1;
+    }
+}
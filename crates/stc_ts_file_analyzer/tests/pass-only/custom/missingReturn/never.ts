@@ -0,0 +1,28 @@
+//@strict: true
+
+declare function fail(msg: string): never;
+declare const process: { exit(code?: number): never };
+
+export function call(c: boolean): number {
+    if (c) {
+        return 1;
+    }
+    fail("unreachable");
+}
+
+export function method(c: boolean): number {
+    if (c) {
+        return 1;
+    }
+    process.exit(1);
+}
+
+export function assigned(c: boolean) {
+    let a: string;
+    if (c) {
+        a = "a";
+    } else {
+        fail("no value");
+    }
+    return a.length;
+}
@@ -0,0 +1,4 @@
+//@module: esnext
+declare const p: Promise<number>;
+const meta: ImportMeta = import.meta;
+await p;
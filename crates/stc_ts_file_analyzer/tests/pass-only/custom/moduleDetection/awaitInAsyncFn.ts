@@ -0,0 +1,4 @@
+declare const p: Promise<number>;
+async function f() {
+    await p;
+}
@@ -0,0 +1,4 @@
+//@module: esnext
+//@moduleDetection: force
+declare const p: Promise<number>;
+await p;
@@ -0,0 +1,23 @@
+namespace Outer {
+    export namespace Inner {
+        export interface Point {
+            x: number;
+        }
+
+        export const origin: Point = { x: 0 };
+
+        export function make(x: number): Point {
+            return { x };
+        }
+    }
+}
+
+import I = Outer.Inner;
+import make = Outer.Inner.make;
+
+const p: I.Point = I.make(1);
+const q: I.Point = make(2);
+const o: Outer.Inner.Point = I.origin;
+
+const ns = Outer.Inner;
+const r: I.Point = ns.origin;
@@ -0,0 +1,28 @@
+//@strict: true
+
+export function f(x: string | undefined) {
+    const ok = x !== undefined;
+    if (ok) {
+        const s: string = x;
+        return s.length;
+    }
+    return 0;
+}
+
+export function g(x: string | number) {
+    const isString = typeof x === "string";
+    if (isString) {
+        return x.length;
+    } else {
+        return x.toFixed();
+    }
+}
+
+export function h(x: string | undefined) {
+    const missing = x === undefined;
+    if (!missing) {
+        const s: string = x;
+        return s.length;
+    }
+    return 0;
+}
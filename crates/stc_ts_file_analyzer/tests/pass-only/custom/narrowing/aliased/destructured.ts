@@ -0,0 +1,19 @@
+//@strict: true
+
+type Action = { kind: "add"; value: number } | { kind: "reset" };
+
+export function f(action: Action) {
+    const { kind } = action;
+    if (kind === "add") {
+        return action.value;
+    }
+    return 0;
+}
+
+export function g(action: Action) {
+    const { kind: k } = action;
+    if (k !== "reset") {
+        return action.value;
+    }
+    return 0;
+}
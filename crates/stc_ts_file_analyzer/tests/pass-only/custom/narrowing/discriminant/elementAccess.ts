@@ -0,0 +1,24 @@
+type Shape = { kind: "circle"; radius: number } | { kind: "square"; size: number };
+
+const kind = "kind" as const;
+
+export function literal(s: Shape): number {
+    if (s["kind"] === "circle") {
+        return s.radius;
+    }
+    return s.size;
+}
+
+export function constKey(s: Shape): number {
+    if (s[kind] === "circle") {
+        return s.radius;
+    }
+    return s.size;
+}
+
+export function tpl(s: Shape): number {
+    if (s[`kind`] === "square") {
+        return s.size;
+    }
+    return s.radius;
+}
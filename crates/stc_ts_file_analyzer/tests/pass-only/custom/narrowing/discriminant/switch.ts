@@ -0,0 +1,62 @@
+//@strict: true
+
+interface Circle {
+    kind: "circle";
+    radius: number;
+}
+
+interface Square {
+    kind: "square";
+    size: number;
+}
+
+interface Triangle {
+    kind: "triangle";
+    base: number;
+}
+
+type Shape = Circle | Square | Triangle;
+
+export function exhaustive(s: Shape): number {
+    switch (s.kind) {
+        case "circle":
+            return s.radius;
+        case "square":
+            return s.size;
+        case "triangle":
+            return s.base;
+    }
+}
+
+export function assertNever(s: Shape): number {
+    switch (s.kind) {
+        case "circle":
+            return s.radius;
+        case "square":
+            return s.size;
+        case "triangle":
+            return s.base;
+        default:
+            const n: never = s;
+            return n;
+    }
+}
+
+export function remaining(s: Shape): number {
+    switch (s.kind) {
+        case "circle":
+            return s.radius;
+        default:
+            const rest: Square | Triangle = s;
+            return rest.kind.length;
+    }
+}
+
+export function literals(v: "a" | "b"): number {
+    switch (v) {
+        case "a":
+            return 1;
+        case "b":
+            return 2;
+    }
+}
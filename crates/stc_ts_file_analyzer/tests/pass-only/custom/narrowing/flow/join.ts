@@ -0,0 +1,26 @@
+export function f(c: boolean) {
+    let v: string | number | boolean = true;
+    if (c) {
+        v = "a";
+    } else {
+        v = 1;
+    }
+    const sn: string | number = v;
+    return sn;
+}
+
+export function g(c: number) {
+    let v: string | number | boolean = true;
+    switch (c) {
+        case 0:
+            v = "a";
+            break;
+        case 1:
+            v = 1;
+            break;
+        default:
+            return 0;
+    }
+    const sn: string | number = v;
+    return sn;
+}
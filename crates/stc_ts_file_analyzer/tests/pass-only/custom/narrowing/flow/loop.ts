@@ -0,0 +1,24 @@
+declare function cond(): boolean;
+
+export function f() {
+    let v: string | number | boolean = "a";
+    while (cond()) {
+        const sn: string | number = v;
+        v = 1;
+    }
+    const sn: string | number = v;
+    return sn;
+}
+
+export function g() {
+    let v: string | number | boolean = "a";
+    for (let i = 0; i < 10; i++) {
+        if (cond()) {
+            v = 1;
+            continue;
+        }
+        v = "b";
+    }
+    const sn: string | number = v;
+    return sn;
+}
@@ -0,0 +1,38 @@
+interface Circle {
+    kind: "circle";
+    radius: number;
+}
+
+interface Square {
+    size: number;
+}
+
+interface Labeled {
+    label?: string;
+}
+
+export function area(s: Circle | Square): number {
+    if ("radius" in s) {
+        return s.radius * s.radius;
+    } else {
+        return s.size * s.size;
+    }
+}
+
+export function label(v: Labeled | Square): string | undefined {
+    if ("label" in v) {
+        return v.label;
+    }
+    if ("size" in v) {
+        return String(v.size);
+    }
+    return v.label;
+}
+
+export function obj(v: unknown) {
+    if (typeof v === "object" && v !== null && "kind" in v) {
+        const kind: unknown = v.kind;
+        return kind;
+    }
+    return undefined;
+}
@@ -0,0 +1,30 @@
+interface A {
+    a: number;
+}
+
+interface B {
+    b: string;
+}
+
+export function undeclared(v: A) {
+    if ("c" in v) {
+        const c: unknown = v.c;
+        return v.a;
+    }
+    return v.a;
+}
+
+export function union(v: A | B) {
+    if ("c" in v) {
+        const c: unknown = v.c;
+        return c;
+    }
+    return undefined;
+}
+
+export function constrained<T extends A>(v: T | B): number {
+    if ("a" in v) {
+        return v.a;
+    }
+    return v.b.length;
+}
@@ -0,0 +1,27 @@
+export function f(x: string | number[]) {
+    if (Array.isArray(x)) {
+        const n: number[] = x;
+        return n.length;
+    }
+    return 0;
+}
+
+export function g(x: any) {
+    if (Array.isArray(x)) {
+        const a: any[] = x;
+        return a.length;
+    }
+    return 0;
+}
+
+interface Box {
+    value: string | string[];
+}
+
+export function h(b: Box) {
+    if (Array.isArray(b.value)) {
+        const v: string[] = b.value;
+        return v.length;
+    }
+    return 0;
+}
@@ -0,0 +1,49 @@
+class Entry {
+    isFile(): this is FileEntry {
+        return this instanceof FileEntry;
+    }
+
+    isDirectory(): this is DirectoryEntry {
+        return this instanceof DirectoryEntry;
+    }
+
+    describe(): string {
+        if (this.isFile()) {
+            return this.content;
+        }
+        return "";
+    }
+}
+
+class FileEntry extends Entry {
+    content = "";
+}
+
+class DirectoryEntry extends Entry {
+    children: Entry[] = [];
+}
+
+export function size(e: Entry): number {
+    if (e.isFile()) {
+        return e.content.length;
+    }
+    if (e.isDirectory()) {
+        return e.children.length;
+    }
+    return 0;
+}
+
+interface Shape {
+    isCircle(): this is Circle;
+}
+
+interface Circle extends Shape {
+    radius: number;
+}
+
+export function radius(s: Shape): number {
+    if (s.isCircle()) {
+        return s.radius;
+    }
+    return 0;
+}
@@ -0,0 +1,15 @@
+export function f<T extends string | number>(v: T): string {
+    if (typeof v === "string") {
+        const s: string = v;
+        return s;
+    }
+    return String(v);
+}
+
+export function g<T>(v: T | undefined): T | string {
+    if (typeof v === "string") {
+        const s: string = v;
+        return s;
+    }
+    return "";
+}
@@ -0,0 +1,14 @@
+export function f(v: string | number | boolean | (() => void)) {
+    switch (typeof v) {
+        case "string":
+            const s: string = v;
+            return s;
+        case "number":
+        case "boolean":
+            const nb: number | boolean = v;
+            return nb;
+        default:
+            v();
+            return undefined;
+    }
+}
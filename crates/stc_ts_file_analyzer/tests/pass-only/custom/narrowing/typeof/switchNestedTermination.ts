@@ -0,0 +1,18 @@
+export function f(v: string | number | boolean, flag: boolean) {
+    switch (typeof v) {
+        case "string": {
+            v.length;
+            break;
+        }
+        case "number":
+            if (flag) {
+                return v;
+            } else {
+                throw new Error();
+            }
+        case "boolean":
+            const b: boolean = v;
+            return b;
+    }
+    return undefined;
+}
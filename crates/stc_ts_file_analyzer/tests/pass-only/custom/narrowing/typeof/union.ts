@@ -0,0 +1,21 @@
+declare const v: string | number | bigint | boolean | symbol | undefined | (() => void) | { a: number };
+
+if (typeof v === "string") {
+    const s: string = v;
+} else if (typeof v === "number") {
+    const n: number = v;
+} else if (typeof v === "bigint") {
+    const b: bigint = v;
+} else if (typeof v === "boolean") {
+    const b: boolean = v;
+} else if (typeof v === "symbol") {
+    const s: symbol = v;
+} else if (typeof v === "undefined") {
+    const u: undefined = v;
+} else if (typeof v === "function") {
+    v();
+} else {
+    const o: { a: number } = v;
+}
+
+export { };
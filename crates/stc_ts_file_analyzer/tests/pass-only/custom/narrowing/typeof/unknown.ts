@@ -0,0 +1,16 @@
+export function f(v: unknown) {
+    if (typeof v === "string") {
+        return v.length;
+    }
+    if (typeof v === "number") {
+        return v.toFixed();
+    }
+    if (typeof v === "bigint") {
+        const b: bigint = v;
+        return b;
+    }
+    if (typeof v === "function") {
+        return v();
+    }
+    return undefined;
+}
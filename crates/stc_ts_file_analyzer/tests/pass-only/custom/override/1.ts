@@ -0,0 +1,21 @@
+class Base {
+    name = "";
+    static create() {
+        return new Base();
+    }
+    greet = () => {};
+}
+
+class Middle extends Base {}
+
+export class Derived extends Middle {
+    override name = "derived";
+
+    constructor(public override readonly greet: () => void) {
+        super();
+    }
+
+    static override create() {
+        return new Derived(() => {});
+    }
+}
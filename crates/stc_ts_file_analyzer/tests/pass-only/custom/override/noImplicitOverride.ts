@@ -0,0 +1,19 @@
+//@noImplicitOverride: true
+
+abstract class Base {
+    name = "";
+    greet() {}
+    abstract farewell(): void;
+}
+
+export class Derived extends Base {
+    constructor(public override name: string) {
+        super();
+    }
+
+    override greet() {}
+
+    farewell() {}
+
+    wave() {}
+}
@@ -0,0 +1,14 @@
+export class C {
+    #value = 1;
+
+    static isC(obj: unknown) {
+        return #value in (obj as object);
+    }
+
+    equals(other: unknown) {
+        if (typeof other === "object" && other !== null && #value in other) {
+            return this.#value === other.#value;
+        }
+        return false;
+    }
+}
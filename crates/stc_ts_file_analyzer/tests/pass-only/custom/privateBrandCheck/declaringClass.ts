@@ -0,0 +1,19 @@
+export class Base {
+    #brand = 1;
+    static #count = 0;
+
+    static isBase(obj: object): Base | undefined {
+        if (#brand in obj) {
+            const base: Base = obj;
+            return base;
+        }
+        return undefined;
+    }
+
+    static count(ctor: object): number {
+        if (#count in ctor) {
+            return ctor.#count;
+        }
+        return 0;
+    }
+}
@@ -0,0 +1,40 @@
+//@strict: true
+
+declare function check(): boolean;
+
+export class C {
+    a: number;
+    b: string;
+    c!: number;
+    d?: string;
+    e: number | undefined;
+    f = 1;
+    #g: boolean;
+    h: number;
+
+    constructor() {
+        this.a = 1;
+        if (check()) {
+            this.b = "";
+        } else {
+            this.b = "x";
+        }
+        this.#g = true;
+
+        try {
+            this.h = 1;
+        } finally {
+        }
+    }
+}
+
+export class D {
+    a: number;
+
+    constructor() {
+        if (check()) {
+            throw new Error();
+        }
+        this.a = 1;
+    }
+}
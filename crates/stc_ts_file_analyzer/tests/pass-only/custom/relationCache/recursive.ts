@@ -0,0 +1,27 @@
+//@strict: true
+
+interface List<T> {
+    value: T;
+    next: List<T> | undefined;
+}
+
+interface OtherList<T> {
+    value: T;
+    next: OtherList<T> | undefined;
+}
+
+declare let a: List<string>;
+declare let b: OtherList<string>;
+
+a = b;
+b = a;
+
+type Json = string | number | boolean | null | Json[] | { [key: string]: Json };
+
+declare function parse(s: string): Json;
+declare function stringify(value: Json): string;
+
+stringify(parse(""));
+stringify(parse(""));
+
+export {};
@@ -0,0 +1,27 @@
+//@strict: true
+
+declare function fromPartial<T>(value: Partial<T>): T;
+
+const a = fromPartial({ x: 1, y: "" });
+const x: number | undefined = a.x;
+
+type Setters<T> = { [K in keyof T]: (value: T[K]) => void };
+
+declare function createStore<T>(setters: Setters<T>): T;
+
+const store = createStore({
+    count: (value: number) => {},
+    name: (value: string) => {},
+});
+const count: number = store.count;
+const name: string = store.name;
+
+type Boxed<T> = { [K in keyof T]: { value: T[K] } };
+
+declare function unbox<T>(boxed: Boxed<T>): T;
+
+const unboxed = unbox({ a: { value: 1 }, b: { value: true } });
+const n: number = unboxed.a;
+const b: boolean = unboxed.b;
+
+export {};
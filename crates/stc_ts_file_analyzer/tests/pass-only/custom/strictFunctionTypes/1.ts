@@ -0,0 +1,28 @@
+//@strict: true
+
+interface Animal {
+    name: string;
+}
+interface Dog extends Animal {
+    bark(): void;
+}
+
+declare let f1: (x: Animal) => void;
+declare let f2: (x: Dog) => void;
+
+f2 = f1;
+
+interface Comparer<T> {
+    compare(a: T, b: T): number;
+}
+
+declare let animalComparer: Comparer<Animal>;
+declare let dogComparer: Comparer<Dog>;
+
+animalComparer = dogComparer;
+dogComparer = animalComparer;
+
+declare let withProperty: { compare: (a: Dog, b: Dog) => number };
+animalComparer = withProperty;
+
+export {};
@@ -0,0 +1,2 @@
+const a = Symbol("a");
+const b = Symbol(1);
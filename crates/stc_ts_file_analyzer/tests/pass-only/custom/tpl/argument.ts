@@ -0,0 +1,10 @@
+declare function f<T>(x: T): T;
+declare function g<T extends string>(x: T): T;
+declare const x: "a" | "b";
+
+// Widened to `string` as `T` does not request a literal type.
+let a = f(`id-${x}`);
+a = "other";
+export { a };
+
+export const b: "id-a" | "id-b" = g(`id-${x}`);
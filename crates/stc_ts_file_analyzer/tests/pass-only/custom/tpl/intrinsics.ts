@@ -0,0 +1,27 @@
+//@strict: true
+
+type Event = "click" | "focus";
+type Handler = `on${Capitalize<Event>}`;
+
+const h1: Handler = "onClick";
+const h2: Handler = "onFocus";
+const upper: Uppercase<"a" | "b"> = "B";
+const lower: Lowercase<"ABC"> = "abc";
+const uncap: Uncapitalize<"Foo"> = "foo";
+
+type Id = `id-${number}`;
+const id: Id = "id-42";
+
+type Shout = `${Uppercase<string>}!`;
+const shout: Shout = "HEY!";
+
+type Head<S> = S extends `${infer H}${infer _}` ? H : never;
+type Split<S> = S extends `${infer L}.${infer R}` ? [L, R] : never;
+
+const head: Head<"abc"> = "a";
+const split: Split<"foo.bar"> = ["foo", "bar"];
+
+declare function route<P extends string>(path: `/users/${P}`): P;
+const user: "alice" = route("/users/alice");
+
+export {};
@@ -0,0 +1,14 @@
+declare const x: "a" | "b";
+declare const n: 1 | 2;
+
+export const a: "id-a" | "id-b" = `id-${x}`;
+export const b: "1-a" | "1-b" | "2-a" | "2-b" = `${n}-${x}`;
+export const c: `id-${string}` = `id-${x}`;
+
+const d = `id-${x}` as const;
+export const e: "id-a" | "id-b" = d;
+
+// Widened to `string` without a contextual type.
+let f = `id-${x}`;
+f = "other";
+export { f };
@@ -0,0 +1,24 @@
+//@isolatedModules: true
+
+//@filename: model.ts
+export interface Point {
+    x: number;
+}
+
+export class Model {
+    point: Point = { x: 1 };
+}
+
+export const origin: Point = { x: 0 };
+
+//@filename: reexport.ts
+export type { Point } from "./model";
+export { type Model, origin } from "./model";
+
+//@filename: index.ts
+import type { Model } from "./model";
+import { type Point, origin } from "./reexport";
+
+export const p: Point = origin;
+export let m: Model | undefined;
+export let o: typeof origin = p;
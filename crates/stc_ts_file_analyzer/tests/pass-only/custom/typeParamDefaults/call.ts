@@ -0,0 +1,19 @@
+//@strict: true
+
+declare function create<T = string>(): T;
+
+const a: string = create();
+
+declare function withDefault<T, U = T[]>(x: T): U;
+
+const b: number[] = withDefault(1);
+
+declare function partial<T, U = boolean>(x: T, y?: U): [T, U];
+
+const c: [number, boolean] = partial<number>(1);
+
+declare function one<T = string>(handler: (t: T) => void): T;
+
+const d: string = one(() => {});
+
+export {};
@@ -0,0 +1,15 @@
+//@strict: true
+
+interface Pair<T, U = T> {
+    first: T;
+    second: U;
+}
+
+const a: Pair<number> = { first: 1, second: 2 };
+const b: Pair<number, string> = { first: 1, second: "" };
+
+type List<T, L = T[]> = { items: L };
+
+const c: List<string> = { items: [""] };
+
+export {};
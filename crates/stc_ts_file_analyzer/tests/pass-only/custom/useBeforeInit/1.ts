@@ -0,0 +1,40 @@
+//@strict: true
+
+declare function use(v: unknown): void;
+
+export function branches(c: boolean) {
+    let a: string;
+    if (c) {
+        a = "a";
+    } else {
+        a = "b";
+    }
+    use(a.length);
+}
+
+export function optional() {
+    let a: string | undefined;
+    use(a);
+
+    let b!: string;
+    use(b);
+}
+
+export function closure() {
+    let a: string;
+    const read = () => a;
+    a = "a";
+    use(read());
+}
+
+export function loops() {
+    let i: number;
+    for (i = 0; i < 3; i++) {
+        use(i);
+    }
+    use(i);
+
+    for (const v of [1, 2]) {
+        use(v);
+    }
+}
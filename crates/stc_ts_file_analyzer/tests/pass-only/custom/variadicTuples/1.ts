@@ -0,0 +1,17 @@
+//@strict: true
+
+type Wrap<T extends unknown[]> = [string, ...T, boolean];
+
+const w: Wrap<[number, number]> = ["a", 1, 2, true];
+const empty: Wrap<[]> = ["a", false];
+
+declare function tail<T extends unknown[]>(t: [unknown, ...T]): T;
+const t: [number, string] = tail([true, 1, "x"] as [boolean, number, string]);
+
+declare function f<T extends unknown[]>(...args: [string, ...T]): T;
+const r: [number, boolean] = f("a", 1, true);
+
+const mixed: [string, ...number[], boolean] = ["a", 1, 2, 3, true];
+const short: [string, ...number[], boolean] = ["a", true];
+
+export {};
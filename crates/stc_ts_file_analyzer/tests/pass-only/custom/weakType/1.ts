@@ -0,0 +1,21 @@
+//@strict: true
+
+interface Options {
+    color?: string;
+    width?: number;
+}
+
+declare const partial: { color: string; extra: boolean };
+declare const empty: {};
+
+const a: Options = partial;
+const b: Options = empty;
+const c: Options = {};
+
+const o1: object = { a: 1 };
+const o2: object = () => {};
+const e1: {} = 1;
+const e2: {} = "str";
+const obj: Object = 1;
+
+export {};
@@ -54,6 +54,8 @@ fn profile_file(name: &str, path: &Path) {
             path: Arc::new(FileName::Real(path.to_path_buf())),
             info: Default::default(),
             is_dts: false,
+            filter: None,
+            directives: None,
         };
 
         {
@@ -4,7 +4,8 @@ use swc_common::{comments::Comments, Span, Spanned};
 use swc_ecma_ast::*;
 use swc_ecma_visit::{Visit, VisitWith};
 
-pub(crate) fn find_modules_and_deps<C>(comments: &C, m: &Module) -> (Vec<JsWord>, Vec<JsWord>)
+/// `jsx_runtime` is added to the dependencies if `m` uses JSX.
+pub(crate) fn find_modules_and_deps<C>(comments: &C, jsx_runtime: Option<&JsWord>, m: &Module) -> (Vec<JsWord>, Vec<JsWord>)
 where
     C: Comments,
 {
@@ -12,10 +13,15 @@ where
         comments,
         declared_modules: Default::default(),
         deps: Default::default(),
+        has_jsx: false,
     };
 
     m.visit_with(&mut v);
 
+    if v.has_jsx {
+        v.deps.extend(jsx_runtime.cloned());
+    }
+
     (v.declared_modules, v.deps)
 }
 
@@ -26,6 +32,7 @@ where
     comments: C,
     declared_modules: Vec<JsWord>,
     deps: Vec<JsWord>,
+    has_jsx: bool,
 }
 
 impl<C> DepFinder<C>
@@ -35,7 +42,7 @@ where
     fn check_comments(&mut self, span: Span) {
         let deps = find_imports_in_comments(&self.comments, span);
 
-        self.deps.extend(deps.into_iter().map(|i| i.to_path()));
+        self.deps.extend(deps.into_iter().filter_map(|i| i.to_path()));
     }
 }
 
@@ -69,6 +76,18 @@ where
         }
     }
 
+    fn visit_jsx_element(&mut self, e: &JSXElement) {
+        e.visit_children_with(self);
+
+        self.has_jsx = true;
+    }
+
+    fn visit_jsx_fragment(&mut self, e: &JSXFragment) {
+        e.visit_children_with(self);
+
+        self.has_jsx = true;
+    }
+
     fn visit_ts_external_module_ref(&mut self, import: &TsExternalModuleRef) {
         self.deps.push(import.expr.value.clone());
     }
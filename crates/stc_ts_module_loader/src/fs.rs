@@ -0,0 +1,222 @@
+//! File system abstraction used by the module loader and resolvers.
+//!
+//! Embedders can check projects which do not exist on the disk (tests,
+//! playgrounds, virtual modules of bundlers) by using [MemoryFileSystem] or
+//! their own implementation of [FileSystem].
+
+use std::{
+    collections::BTreeSet,
+    fs,
+    path::{Component, Path, PathBuf},
+    sync::Arc,
+    time::SystemTime,
+};
+
+use anyhow::{bail, Context, Error};
+use parking_lot::RwLock;
+use path_clean::PathClean;
+use swc_common::collections::AHashMap;
+
+pub trait FileSystem: Send + Sync {
+    fn read_file(&self, path: &Path) -> Result<String, Error>;
+
+    /// Returns paths of the direct children of `path`.
+    fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>, Error>;
+
+    /// Returns the last modification time of `path`.
+    fn mtime(&self, path: &Path) -> Result<SystemTime, Error>;
+
+    fn is_file(&self, path: &Path) -> bool;
+
+    fn is_dir(&self, path: &Path) -> bool;
+}
+
+impl<T> FileSystem for Arc<T>
+where
+    T: ?Sized + FileSystem,
+{
+    fn read_file(&self, path: &Path) -> Result<String, Error> {
+        (**self).read_file(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>, Error> {
+        (**self).read_dir(path)
+    }
+
+    fn mtime(&self, path: &Path) -> Result<SystemTime, Error> {
+        (**self).mtime(path)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        (**self).is_file(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        (**self).is_dir(path)
+    }
+}
+
+/// [FileSystem] backed by the disk.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RealFileSystem;
+
+impl FileSystem for RealFileSystem {
+    fn read_file(&self, path: &Path) -> Result<String, Error> {
+        fs::read_to_string(path).with_context(|| format!("failed to read `{}`", path.display()))
+    }
+
+    fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>, Error> {
+        let entries = fs::read_dir(path).with_context(|| format!("failed to read directory `{}`", path.display()))?;
+
+        Ok(entries.filter_map(Result::ok).map(|e| e.path()).collect())
+    }
+
+    fn mtime(&self, path: &Path) -> Result<SystemTime, Error> {
+        let metadata = fs::metadata(path).with_context(|| format!("failed to read metadata of `{}`", path.display()))?;
+
+        Ok(metadata.modified()?)
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+}
+
+#[derive(Debug)]
+struct MemoryFile {
+    content: String,
+    mtime: SystemTime,
+}
+
+/// In-memory [FileSystem].
+///
+/// Directories are implicit, which means a directory exists if a file exists
+/// in it.
+#[derive(Debug, Default)]
+pub struct MemoryFileSystem {
+    files: RwLock<AHashMap<PathBuf, MemoryFile>>,
+}
+
+impl MemoryFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or overwrites a file.
+    pub fn add_file(&self, path: impl AsRef<Path>, content: impl Into<String>) {
+        self.files.write().insert(
+            normalize(path.as_ref()),
+            MemoryFile {
+                content: content.into(),
+                mtime: SystemTime::now(),
+            },
+        );
+    }
+
+    /// Returns `true` if the file existed.
+    pub fn remove_file(&self, path: &Path) -> bool {
+        self.files.write().remove(&normalize(path)).is_some()
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn read_file(&self, path: &Path) -> Result<String, Error> {
+        match self.files.read().get(&normalize(path)) {
+            Some(file) => Ok(file.content.clone()),
+            None => bail!("file not found: {}", path.display()),
+        }
+    }
+
+    fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>, Error> {
+        let dir = normalize(path);
+
+        let children = self
+            .files
+            .read()
+            .keys()
+            .filter_map(|file| {
+                let rest = file.strip_prefix(&dir).ok()?;
+
+                match rest.components().next()? {
+                    Component::Normal(name) => Some(dir.join(name)),
+                    _ => None,
+                }
+            })
+            .collect::<BTreeSet<_>>();
+
+        if children.is_empty() {
+            bail!("directory not found: {}", path.display())
+        }
+
+        Ok(children.into_iter().collect())
+    }
+
+    fn mtime(&self, path: &Path) -> Result<SystemTime, Error> {
+        match self.files.read().get(&normalize(path)) {
+            Some(file) => Ok(file.mtime),
+            None => bail!("file not found: {}", path.display()),
+        }
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.read().contains_key(&normalize(path))
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        let dir = normalize(path);
+
+        self.files.read().keys().any(|file| file != &dir && file.starts_with(&dir))
+    }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    path.to_path_buf().clean()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{FileSystem, MemoryFileSystem};
+
+    #[test]
+    fn memory_fs_implicit_dirs() {
+        let fs = MemoryFileSystem::new();
+        fs.add_file("/project/src/index.ts", "export {}");
+        fs.add_file("/project/src/lib/a.ts", "export const a = 1;");
+        fs.add_file("/project/package.json", "{}");
+
+        assert!(fs.is_file(Path::new("/project/src/index.ts")));
+        assert!(fs.is_file(Path::new("/project/src/../src/index.ts")));
+        assert!(!fs.is_file(Path::new("/project/src")));
+
+        assert!(fs.is_dir(Path::new("/project/src")));
+        assert!(fs.is_dir(Path::new("/project")));
+        assert!(!fs.is_dir(Path::new("/project/src/index.ts")));
+        assert!(!fs.is_dir(Path::new("/other")));
+
+        assert_eq!(
+            fs.read_dir(Path::new("/project/src")).unwrap(),
+            vec![PathBuf::from("/project/src/index.ts"), PathBuf::from("/project/src/lib")]
+        );
+        assert!(fs.read_dir(Path::new("/other")).is_err());
+    }
+
+    #[test]
+    fn memory_fs_overwrite_and_remove() {
+        let fs = MemoryFileSystem::new();
+        fs.add_file("/a.ts", "1");
+        fs.add_file("/a.ts", "2");
+
+        assert_eq!(fs.read_file(Path::new("/a.ts")).unwrap(), "2");
+        assert!(fs.mtime(Path::new("/a.ts")).is_ok());
+
+        assert!(fs.remove_file(Path::new("/a.ts")));
+        assert!(!fs.remove_file(Path::new("/a.ts")));
+        assert!(fs.read_file(Path::new("/a.ts")).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 #![deny(warnings)]
 
-use std::{mem::take, sync::Arc};
+use std::{mem::take, path::PathBuf, sync::Arc};
 
 use anyhow::{anyhow, bail, Error};
 use dashmap::DashMap;
@@ -10,8 +10,12 @@ use rayon::prelude::*;
 use stc_ts_types::{module_id::ModuleIdGenerator, ModuleId};
 use stc_utils::panic_ctx;
 use swc_atoms::JsWord;
-use swc_common::{collections::AHashMap, comments::Comments, FileName, Mark, SourceMap, DUMMY_SP};
-use swc_ecma_ast::{EsVersion, Module};
+use swc_common::{
+    collections::{AHashMap, AHashSet},
+    comments::Comments,
+    FileName, Mark, SourceMap, DUMMY_SP,
+};
+use swc_ecma_ast::{Decl, EsVersion, Module, ModuleItem, Stmt, TsNamespaceBody};
 use swc_ecma_loader::resolve::Resolve;
 use swc_ecma_parser::{lexer::Lexer, Parser, StringInput, Syntax, TsConfig};
 use swc_fast_graph::digraph::FastDiGraphMap;
@@ -19,9 +23,13 @@ use swc_graph_analyzer::{DepGraph, GraphAnalyzer};
 use tracing::{debug, error};
 
 use self::analyzer::find_modules_and_deps;
-use crate::resolvers::typescript::TsResolver;
+use crate::{
+    fs::{FileSystem, RealFileSystem},
+    resolvers::{paths::PathMappings, typescript::TsResolver},
+};
 
 mod analyzer;
+pub mod fs;
 pub mod resolvers;
 
 #[derive(Debug, Clone)]
@@ -53,11 +61,14 @@ where
     parser_config: TsConfig,
     target: EsVersion,
     comments: C,
+    fs: Arc<dyn FileSystem>,
 
     id_generator: ModuleIdGenerator,
     loaded: DashMap<ModuleId, Result<ModuleRecord, ()>, FxBuildHasher>,
     started: DashMap<ModuleId, Arc<Module>, FxBuildHasher>,
     resolver: TsResolver<R>,
+    /// Imported implicitly by files using JSX.
+    jsx_runtime: Option<JsWord>,
 
     errors: Mutex<Vec<Error>>,
     parsing_errors: Mutex<Vec<swc_ecma_parser::error::Error>>,
@@ -83,15 +94,31 @@ where
     R: Resolve,
 {
     pub fn new(cm: Arc<SourceMap>, comments: C, resolver: R, parser_config: TsConfig, target: EsVersion) -> Self {
+        Self::new_with_fs(cm, comments, resolver, parser_config, target, Arc::new(RealFileSystem))
+    }
+
+    /// Creates a module graph which reads files using `fs`.
+    ///
+    /// Note that `resolver` should use the same file system.
+    pub fn new_with_fs(
+        cm: Arc<SourceMap>,
+        comments: C,
+        resolver: R,
+        parser_config: TsConfig,
+        target: EsVersion,
+        fs: Arc<dyn FileSystem>,
+    ) -> Self {
         ModuleGraph {
             cm,
             parser_config,
             target,
             comments,
+            fs,
             id_generator: Default::default(),
             loaded: Default::default(),
             started: Default::default(),
             resolver: TsResolver::new(resolver),
+            jsx_runtime: None,
             errors: Default::default(),
             parsing_errors: Default::default(),
             deps: Default::default(),
@@ -99,10 +126,43 @@ where
         }
     }
 
+    /// Resolves non-relative module specifiers using `paths` of
+    /// `tsconfig.json`.
+    ///
+    /// This should be called before loading modules.
+    pub fn set_path_mappings(&mut self, paths: PathMappings) {
+        self.resolver.set_path_mappings(paths);
+    }
+
+    /// Loads `src`, like `react/jsx-runtime`, as a dependency of files using
+    /// JSX.
+    ///
+    /// This should be called before loading modules.
+    pub fn set_jsx_runtime(&mut self, src: JsWord) {
+        self.jsx_runtime = Some(src);
+    }
+
+    /// Resolves `/// <reference types="..." />` using `typeRoots` of
+    /// `tsconfig.json`.
+    ///
+    /// This should be called before loading modules.
+    pub fn set_type_roots(&mut self, type_roots: Vec<PathBuf>) {
+        self.resolver.set_type_roots(type_roots);
+    }
+
+    /// Returns the pattern of `paths` matching `specifier`, if any.
+    pub fn path_mapping_of(&self, specifier: &str) -> Option<&str> {
+        self.resolver.path_mapping_of(specifier)
+    }
+
     pub fn comments(&self) -> &C {
         &self.comments
     }
 
+    pub fn fs(&self) -> &Arc<dyn FileSystem> {
+        &self.fs
+    }
+
     /// TODO: Fix race condition of `errors`.
     pub fn load_all(&self, entry: &Arc<FileName>) -> Result<ModuleId, (ModuleId, Error)> {
         self.load_including_deps(entry, false);
@@ -199,6 +259,18 @@ where
         self.with_module(id, |m| m.map(|v| v.body.len()).unwrap_or(0))
     }
 
+    /// Returns the loaded modules containing `declare global`, in the order
+    /// they are loaded.
+    pub fn modules_with_global_augmentations(&self) -> Vec<ModuleId> {
+        let all = self.deps.read().all.clone();
+
+        let mut done = AHashSet::default();
+        all.into_iter()
+            .filter(|&id| done.insert(id))
+            .filter(|&id| self.with_module(id, |m| m.map_or(false, |m| m.body.iter().any(is_global_augmentation))))
+            .collect()
+    }
+
     fn load_including_deps(&self, path: &Arc<FileName>, resolve_all: bool) {
         let (id, _) = self.id_generator.generate(path);
 
@@ -295,7 +367,7 @@ where
 
         let _panic = panic_ctx!(format!("ModuleGraph.load({}, span = {:?})", filename, module.span));
 
-        let (declared_modules, deps) = find_modules_and_deps(&self.comments, &module);
+        let (declared_modules, deps) = find_modules_and_deps(&self.comments, self.jsx_runtime.as_ref(), &module);
 
         for decl in declared_modules {
             self.resolver.declare_module(decl);
@@ -332,7 +404,8 @@ where
             }
         };
 
-        let fm = self.cm.load_file(path)?;
+        let src = self.fs.read_file(path)?;
+        let fm = self.cm.new_source_file(FileName::Real(path.clone()), src);
         let lexer = Lexer::new(
             Syntax::Typescript(TsConfig {
                 dts: path.as_os_str().to_string_lossy().ends_with(".d.ts"),
@@ -389,3 +462,17 @@ where
         }
     }
 }
+
+/// Returns true for `declare global`, including ones in `declare module`.
+fn is_global_augmentation(item: &ModuleItem) -> bool {
+    match item {
+        ModuleItem::Stmt(Stmt::Decl(Decl::TsModule(decl))) => {
+            decl.global
+                || match &decl.body {
+                    Some(TsNamespaceBody::TsModuleBlock(block)) => block.body.iter().any(is_global_augmentation),
+                    _ => false,
+                }
+        }
+        _ => false,
+    }
+}
@@ -1,2 +1,3 @@
 pub mod node;
+pub mod paths;
 pub(crate) mod typescript;
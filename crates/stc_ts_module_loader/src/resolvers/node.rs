@@ -1,7 +1,6 @@
 use std::{
-    fs::File,
-    io::BufReader,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use anyhow::{bail, Context, Error};
@@ -10,20 +9,34 @@ use serde::Deserialize;
 use swc_common::FileName;
 use swc_ecma_loader::resolve::Resolve;
 
+use crate::fs::{FileSystem, RealFileSystem};
+
 static EXTENSIONS: &[&str] = &["tsx", "ts", "d.ts"];
 
 #[derive(Deserialize)]
 struct PackageJson {
-    #[serde(default)]
+    #[serde(default, alias = "typings")]
     types: Option<String>,
 }
 
-#[derive(Default)]
-pub struct NodeResolver;
+pub struct NodeResolver {
+    fs: Arc<dyn FileSystem>,
+}
+
+impl Default for NodeResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl NodeResolver {
+    /// Creates a resolver which uses the disk.
     pub fn new() -> Self {
-        Self
+        Self::with_fs(Arc::new(RealFileSystem))
+    }
+
+    pub fn with_fs(fs: Arc<dyn FileSystem>) -> Self {
+        Self { fs }
     }
 
     fn wrap(&self, path: PathBuf) -> Result<FileName, Error> {
@@ -35,13 +48,13 @@ impl NodeResolver {
     /// otherwise the `path` + each extension is tried.
     pub fn resolve_as_file(&self, path: &Path) -> Result<PathBuf, Error> {
         // 1. If X is a file, load X as JavaScript text.
-        if path.is_file() {
+        if self.fs.is_file(path) {
             return Ok(path.to_path_buf());
         }
 
         for ext in EXTENSIONS {
             let ext_path = path.with_extension(ext);
-            if ext_path.is_file() {
+            if self.fs.is_file(&ext_path) {
                 return Ok(ext_path);
             }
         }
@@ -54,7 +67,7 @@ impl NodeResolver {
     pub fn resolve_as_directory(&self, path: &Path) -> Result<PathBuf, Error> {
         // 1. If X/package.json is a file, use it.
         let pkg_path = path.join("package.json");
-        if pkg_path.is_file() {
+        if self.fs.is_file(&pkg_path) {
             let main = self.resolve_using_package_json(&pkg_path);
             if main.is_ok() {
                 return main;
@@ -70,9 +83,8 @@ impl NodeResolver {
         // TODO: how to not always initialize this here?
         let root = PathBuf::from("/");
         let pkg_dir = pkg_path.parent().unwrap_or(&root);
-        let file = File::open(pkg_path)?;
-        let reader = BufReader::new(file);
-        let pkg: PackageJson = serde_json::from_reader(reader).context("failed to deserialize package.json")?;
+        let content = self.fs.read_file(pkg_path)?;
+        let pkg: PackageJson = serde_json::from_str(&content).context("failed to deserialize package.json")?;
 
         if let Some(target) = &pkg.types {
             let path = pkg_dir.join(target);
@@ -89,7 +101,7 @@ impl NodeResolver {
         // 3. If X/index.node is a file, load X/index.node as binary addon.
         for ext in EXTENSIONS {
             let ext_path = path.join(format!("index.{}", ext));
-            if ext_path.is_file() {
+            if self.fs.is_file(&ext_path) {
                 return Ok(ext_path);
             }
         }
@@ -104,7 +116,7 @@ impl NodeResolver {
     /// Resolve by walking up node_modules folders.
     fn resolve_node_modules(&self, base_dir: &Path, target: &str) -> Result<PathBuf, Error> {
         let node_modules = base_dir.join("node_modules");
-        if node_modules.is_dir() {
+        if self.fs.is_dir(&node_modules) {
             let path = node_modules.join(target);
             let result = self.try_package(&path);
             if result.is_ok() {
@@ -114,7 +126,7 @@ impl NodeResolver {
             {
                 let types = node_modules.join("@types").join(target);
 
-                if types.is_dir() {
+                if self.fs.is_dir(&types) {
                     let result = self.try_package(&types);
 
                     if result.is_ok() {
@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use super::typescript::match_wildcard;
+
+/// `paths` of `tsconfig.json`, which maps non-relative module specifiers like
+/// `@app/*` to paths.
+#[derive(Debug, Clone, Default)]
+pub struct PathMappings {
+    /// `baseUrl`, or the directory of the config which declares `paths`.
+    base: PathBuf,
+    entries: Vec<(String, Vec<String>)>,
+    /// If set, non-relative module specifiers are also resolved relative to
+    /// this directory.
+    base_url: Option<PathBuf>,
+}
+
+impl PathMappings {
+    /// `entries` are pairs of a pattern and its substitutions, like
+    /// `("@app/*", vec!["src/app/*"])`.
+    pub fn new(base: PathBuf, entries: Vec<(String, Vec<String>)>) -> Self {
+        PathMappings {
+            base,
+            entries,
+            base_url: None,
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: PathBuf) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty() && self.base_url.is_none()
+    }
+
+    /// Returns the path relative to `baseUrl`, which is tried after `paths`.
+    pub fn base_url_candidate(&self, specifier: &str) -> Option<PathBuf> {
+        if is_relative(specifier) {
+            return None;
+        }
+
+        self.base_url.as_ref().map(|base_url| base_url.join(specifier))
+    }
+
+    /// Returns the pattern matching `specifier`, and the paths to try in order.
+    ///
+    /// Like `tsc`, an exact match is preferred over wildcard patterns, and the
+    /// pattern with the longest prefix wins among wildcard patterns.
+    pub fn candidates(&self, specifier: &str) -> Option<(&str, Vec<PathBuf>)> {
+        if is_relative(specifier) {
+            return None;
+        }
+
+        if let Some((pat, substitutions)) = self.entries.iter().find(|(pat, _)| pat == specifier) {
+            let paths = substitutions.iter().map(|s| self.base.join(s)).collect();
+            return Some((pat.as_str(), paths));
+        }
+
+        let (_, pat, substitutions) = self
+            .entries
+            .iter()
+            .filter_map(|(pat, substitutions)| Some((match_wildcard(pat, specifier)?, pat, substitutions)))
+            .max_by_key(|(prefix_len, ..)| *prefix_len)?;
+
+        let (prefix, suffix) = pat.split_once('*')?;
+        let matched = &specifier[prefix.len()..specifier.len() - suffix.len()];
+        let paths = substitutions.iter().map(|s| self.base.join(s.replacen('*', matched, 1))).collect();
+
+        Some((pat.as_str(), paths))
+    }
+}
+
+fn is_relative(specifier: &str) -> bool {
+    specifier.starts_with('.') || specifier.starts_with('/')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates() {
+        let paths = PathMappings::new(
+            PathBuf::from("/root"),
+            vec![
+                ("*".into(), vec!["types/*".into()]),
+                ("@app/*".into(), vec!["src/app/*".into(), "generated/*".into()]),
+                ("@app/config".into(), vec!["config/index.ts".into()]),
+            ],
+        );
+
+        assert_eq!(
+            paths.candidates("@app/foo/bar"),
+            Some((
+                "@app/*",
+                vec![PathBuf::from("/root/src/app/foo/bar"), PathBuf::from("/root/generated/foo/bar")]
+            ))
+        );
+        assert_eq!(
+            paths.candidates("@app/config"),
+            Some(("@app/config", vec![PathBuf::from("/root/config/index.ts")]))
+        );
+        assert_eq!(paths.candidates("lodash"), Some(("*", vec![PathBuf::from("/root/types/lodash")])));
+        assert_eq!(paths.candidates("./foo"), None);
+        assert_eq!(paths.base_url_candidate("lodash"), None);
+
+        let paths = paths.with_base_url(PathBuf::from("/root/src"));
+        assert_eq!(paths.base_url_candidate("lodash"), Some(PathBuf::from("/root/src/lodash")));
+        assert_eq!(paths.base_url_candidate("./foo"), None);
+    }
+}
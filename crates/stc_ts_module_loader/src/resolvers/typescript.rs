@@ -1,16 +1,22 @@
-use std::sync::Arc;
+use std::{path::PathBuf, sync::Arc};
 
 use anyhow::{Context, Error};
 use parking_lot::RwLock;
+use stc_ts_utils::imports::TYPES_PREFIX;
 use swc_atoms::JsWord;
 use swc_common::FileName;
 use swc_ecma_loader::resolve::Resolve;
 
+use super::paths::PathMappings;
+
 pub(crate) struct TsResolver<R>
 where
     R: Resolve,
 {
     declared_modules: RwLock<Vec<(JsWord, Arc<FileName>)>>,
+    paths: PathMappings,
+    /// Empty if `node_modules/@types` of the ancestors should be used.
+    type_roots: Vec<PathBuf>,
     resolver: R,
 }
 impl<R> TsResolver<R>
@@ -21,15 +27,72 @@ where
         TsResolver {
             resolver,
             declared_modules: Default::default(),
+            paths: Default::default(),
+            type_roots: Default::default(),
         }
     }
 
+    pub(crate) fn set_path_mappings(&mut self, paths: PathMappings) {
+        self.paths = paths;
+    }
+
+    pub(crate) fn set_type_roots(&mut self, type_roots: Vec<PathBuf>) {
+        self.type_roots = type_roots;
+    }
+
+    /// Returns the pattern of `paths` matching `module_specifier`.
+    pub(crate) fn path_mapping_of(&self, module_specifier: &str) -> Option<&str> {
+        self.paths.candidates(module_specifier).map(|(pat, _)| pat)
+    }
+
     /// This returns [FileName::Custom] for `declare module "http"`-s.
+    ///
+    /// Like `tsc`, an exact match is preferred over wildcard patterns like
+    /// `declare module "*.css"`, and the pattern with the longest prefix wins
+    /// among wildcard patterns.
     pub(crate) fn resolve(&self, base: &FileName, module_specifier: &str) -> Result<Arc<FileName>, Error> {
-        for (pat, path) in self.declared_modules.read().iter() {
-            if matches(pat, module_specifier) {
+        {
+            let declared_modules = self.declared_modules.read();
+
+            if let Some((_, path)) = declared_modules.iter().find(|(pat, _)| &**pat == module_specifier) {
                 return Ok(path.clone());
             }
+
+            let best = declared_modules
+                .iter()
+                .filter_map(|(pat, path)| Some((match_wildcard(pat, module_specifier)?, path)))
+                .max_by_key(|(prefix_len, _)| *prefix_len);
+            if let Some((_, path)) = best {
+                return Ok(path.clone());
+            }
+        }
+
+        if let Some(name) = module_specifier.strip_prefix(TYPES_PREFIX) {
+            return self.resolve_type_reference(base, name);
+        }
+
+        let mapped = self.paths.candidates(module_specifier);
+        if let Some((_, candidates)) = &mapped {
+            for path in candidates {
+                if let Ok(resolved) = self.resolver.resolve(base, &path.to_string_lossy()) {
+                    return Ok(Arc::new(resolved));
+                }
+            }
+        }
+        if let Some(path) = self.paths.base_url_candidate(module_specifier) {
+            if let Ok(resolved) = self.resolver.resolve(base, &path.to_string_lossy()) {
+                return Ok(Arc::new(resolved));
+            }
+        }
+
+        if let Some((pat, candidates)) = mapped {
+            // Like `tsc`, we fall back to the normal resolution.
+            return self.resolver.resolve(base, module_specifier).map(Arc::new).with_context(|| {
+                format!(
+                    "`{}` matches the path mapping `{}`, but none of {:?} could be resolved",
+                    module_specifier, pat, candidates
+                )
+            });
         }
 
         let resolved = self
@@ -40,6 +103,33 @@ where
         Ok(Arc::new(resolved))
     }
 
+    /// Resolves `/// <reference types="name" />` like `tsc`. `name` is looked
+    /// up in the type roots first, and then in `node_modules`.
+    fn resolve_type_reference(&self, base: &FileName, name: &str) -> Result<Arc<FileName>, Error> {
+        if self.type_roots.is_empty() {
+            if let Ok(resolved) = self.resolver.resolve(base, &format!("{}{}", TYPES_PREFIX, name)) {
+                return Ok(Arc::new(resolved));
+            }
+        }
+
+        for root in &self.type_roots {
+            if let Ok(resolved) = self.resolver.resolve(base, &root.join(name).to_string_lossy()) {
+                return Ok(Arc::new(resolved));
+            }
+        }
+
+        // `babel__core` is `@babel/core`.
+        let package = match name.split_once("__") {
+            Some((scope, name)) => format!("@{}/{}", scope, name),
+            None => name.to_string(),
+        };
+
+        self.resolver
+            .resolve(base, &package)
+            .map(Arc::new)
+            .with_context(|| format!("failed to resolve type reference `{}`", package))
+    }
+
     pub(crate) fn declare_module(&self, decl: JsWord) {
         log::debug!("Declaring module '{}'", decl);
 
@@ -49,10 +139,33 @@ where
     }
 }
 
-fn matches(pat: &JsWord, module_specifier: &str) -> bool {
-    if &**pat == module_specifier {
-        return true;
+/// Returns the length of the prefix if `module_specifier` matches a wildcard
+/// pattern like `*.css` or `foo/*`.
+pub(super) fn match_wildcard(pat: &str, module_specifier: &str) -> Option<usize> {
+    let (prefix, suffix) = pat.split_once('*')?;
+    if suffix.contains('*') {
+        return None;
+    }
+
+    if module_specifier.len() >= prefix.len() + suffix.len() && module_specifier.starts_with(prefix) && module_specifier.ends_with(suffix) {
+        Some(prefix.len())
+    } else {
+        None
     }
+}
 
-    false
+#[cfg(test)]
+mod tests {
+    use super::match_wildcard;
+
+    #[test]
+    fn wildcard() {
+        assert_eq!(match_wildcard("*.css", "./foo.css"), Some(0));
+        assert_eq!(match_wildcard("foo/*", "foo/bar"), Some(4));
+        assert_eq!(match_wildcard("foo/*/baz", "foo/bar/baz"), Some(4));
+        assert_eq!(match_wildcard("*.css", "./foo.scss.js"), None);
+        assert_eq!(match_wildcard("foo", "foo"), None);
+        assert_eq!(match_wildcard("a*a", "a"), None);
+        assert_eq!(match_wildcard("*a*", "aa"), None);
+    }
 }
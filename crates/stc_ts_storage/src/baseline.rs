@@ -0,0 +1,171 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    path::PathBuf,
+};
+
+use fxhash::FxHashMap;
+use stc_ts_errors::{Error, ErrorKind};
+use swc_common::{FileName, SourceMap, Spanned};
+
+/// Known diagnostics of a project, used to report only new diagnostics while
+/// adopting stc on a legacy codebase.
+///
+/// Diagnostics are matched by error code, file and a fuzzy span. A diagnostic
+/// matches a recorded one if the first lines of the source code of their spans
+/// are identical, or if they are on the same line. If there are multiple
+/// candidates, the nearest one is used. Each recorded diagnostic matches at
+/// most one diagnostic.
+///
+/// The file format is a line per diagnostic, like
+///
+/// ```text
+/// TS2322	src/a.ts	12	const a: number = "";
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    /// Paths are stored relative to this directory.
+    root: PathBuf,
+    entries: FxHashMap<(usize, String), Vec<BaselineEntry>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BaselineEntry {
+    /// 1-based.
+    line: usize,
+    text: String,
+}
+
+impl Baseline {
+    pub fn new(root: PathBuf) -> Self {
+        Baseline {
+            root,
+            entries: Default::default(),
+        }
+    }
+
+    /// Creates a baseline containing `errors`.
+    pub fn record(root: PathBuf, cm: &SourceMap, errors: &[Error]) -> Self {
+        let mut baseline = Baseline::new(root);
+        for err in ErrorKind::flatten(errors.to_vec()) {
+            if let Some((key, entry)) = baseline.entry_of(cm, &err) {
+                baseline.entries.entry(key).or_default().push(entry);
+            }
+        }
+        baseline
+    }
+
+    pub fn parse(root: PathBuf, s: &str) -> Result<Self, String> {
+        let mut baseline = Baseline::new(root);
+
+        for (idx, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let invalid = || format!("invalid baseline entry at line {}: `{}`", idx + 1, line);
+
+            let mut parts = line.splitn(4, '\t');
+            let code = parts
+                .next()
+                .and_then(|code| code.trim_start_matches("TS").parse::<usize>().ok())
+                .ok_or_else(invalid)?;
+            let file = parts.next().ok_or_else(invalid)?;
+            let line_no = parts.next().and_then(|l| l.parse().ok()).ok_or_else(invalid)?;
+            let text = parts.next().unwrap_or_default();
+
+            baseline
+                .entries
+                .entry((ErrorKind::normalize_error_code(code), file.to_string()))
+                .or_default()
+                .push(BaselineEntry {
+                    line: line_no,
+                    text: text.to_string(),
+                });
+        }
+
+        Ok(baseline)
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.values().map(|v| v.len()).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns errors which are not in the baseline.
+    pub fn filter_new(&self, cm: &SourceMap, errors: Vec<Error>) -> Vec<Error> {
+        // Entries which are not matched yet.
+        let mut remaining = self.entries.clone();
+
+        ErrorKind::flatten(errors)
+            .into_iter()
+            .filter(|err| {
+                let (key, entry) = match self.entry_of(cm, err) {
+                    Some(v) => v,
+                    None => return true,
+                };
+                let candidates = match remaining.get_mut(&key) {
+                    Some(v) => v,
+                    None => return true,
+                };
+
+                let best = candidates
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, c)| c.text == entry.text || c.line == entry.line)
+                    .min_by_key(|(_, c)| (c.text != entry.text, c.line.abs_diff(entry.line)))
+                    .map(|(idx, _)| idx);
+
+                match best {
+                    Some(idx) => {
+                        candidates.swap_remove(idx);
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect()
+    }
+
+    fn entry_of(&self, cm: &SourceMap, err: &Error) -> Option<((usize, String), BaselineEntry)> {
+        let span = err.span();
+        if span.is_dummy() {
+            return None;
+        }
+
+        let loc = cm.lookup_char_pos(span.lo);
+        let file = match &loc.file.name {
+            FileName::Real(path) => path.strip_prefix(&self.root).unwrap_or(path).display().to_string(),
+            name => name.to_string(),
+        };
+        let text = cm
+            .span_to_snippet(span)
+            .ok()
+            .and_then(|s| s.lines().next().map(|l| l.trim().replace('\t', " ")))
+            .unwrap_or_default();
+
+        Some((
+            (ErrorKind::normalize_error_code(err.code()), file),
+            BaselineEntry { line: loc.line, text },
+        ))
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut lines = self
+            .entries
+            .iter()
+            .flat_map(|((code, file), entries)| entries.iter().map(move |e| (file, e.line, *code, &e.text)))
+            .collect::<Vec<_>>();
+        lines.sort();
+
+        for (file, line, code, text) in lines {
+            writeln!(f, "TS{}\t{}\t{}\t{}", code, file, line, text)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use stc_ts_errors::{Error, ErrorKind, Errors};
+use swc_common::{
+    comments::{Comment, CommentKind},
+    BytePos, SourceMap, Span, Spanned,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectiveKind {
+    /// `// @ts-ignore`
+    Ignore,
+    /// `// @ts-expect-error`
+    ExpectError,
+}
+
+/// File-level comment directives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilePragma {
+    /// `// @ts-check`
+    Check,
+    /// `// @ts-nocheck`
+    NoCheck,
+    /// `/// <reference no-default-lib="true"/>`
+    NoDefaultLib,
+}
+
+impl FilePragma {
+    pub fn parse(c: &Comment) -> Option<Self> {
+        let text = directive_text(c);
+
+        if is_directive(text, "@ts-nocheck") {
+            return Some(FilePragma::NoCheck);
+        }
+        if is_directive(text, "@ts-check") {
+            return Some(FilePragma::Check);
+        }
+        if c.kind == CommentKind::Line && text.starts_with("<reference") && text.contains("no-default-lib=\"true\"") {
+            return Some(FilePragma::NoDefaultLib);
+        }
+
+        None
+    }
+}
+
+#[derive(Debug)]
+struct Directive {
+    /// Span of the comment.
+    span: Span,
+    kind: DirectiveKind,
+    /// Start of the line where errors are suppressed.
+    lo: BytePos,
+    /// End of the line where errors are suppressed.
+    hi: BytePos,
+    used: AtomicBool,
+}
+
+/// `@ts-ignore` and `@ts-expect-error` comments, which suppress errors on the
+/// next line, and files which are not checked.
+///
+/// Applied by [crate::Single] and [crate::Group] before storing errors.
+#[derive(Debug, Default)]
+pub struct CommentDirectives {
+    directives: Vec<Directive>,
+    /// Ranges of files which are not checked.
+    unchecked: Vec<(BytePos, BytePos)>,
+}
+
+impl CommentDirectives {
+    pub fn new<'a>(cm: &SourceMap, comments: impl IntoIterator<Item = &'a Comment>) -> Self {
+        let mut directives = comments
+            .into_iter()
+            .filter_map(|c| {
+                let kind = parse_directive(c)?;
+
+                let loc = cm.lookup_char_pos(c.span.hi);
+                let file = &loc.file;
+                let line_range = |line: usize| {
+                    let lo = *file.lines.get(line)?;
+                    Some((lo, file.lines.get(line + 1).copied().unwrap_or(file.end_pos)))
+                };
+
+                // `loc.line` is 1-based, so it's the index of the next line.
+                // Like `tsc`, blank lines and line comments between the directive and the
+                // suppressed line are skipped.
+                let mut line = loc.line;
+                let (lo, hi) = loop {
+                    let (lo, hi) = line_range(line)?;
+                    let text = file.src[(lo - file.start_pos).0 as usize..(hi - file.start_pos).0 as usize].trim();
+                    if !text.is_empty() && !text.starts_with("//") {
+                        break (lo, hi);
+                    }
+                    line += 1;
+                };
+
+                Some(Directive {
+                    span: c.span,
+                    kind,
+                    lo,
+                    hi,
+                    used: AtomicBool::new(false),
+                })
+            })
+            .collect::<Vec<_>>();
+        directives.sort_by_key(|d| d.span.lo);
+        directives.dedup_by_key(|d| d.span.lo);
+
+        Self {
+            directives,
+            unchecked: Default::default(),
+        }
+    }
+
+    /// Suppresses all errors in the range, which is typically a file marked
+    /// with `@ts-nocheck`.
+    pub fn add_unchecked_range(&mut self, lo: BytePos, hi: BytePos) {
+        self.unchecked.push((lo, hi));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.directives.is_empty() && self.unchecked.is_empty()
+    }
+
+    fn is_unchecked(&self, pos: BytePos) -> bool {
+        self.unchecked.iter().any(|&(lo, hi)| lo <= pos && pos <= hi)
+    }
+
+    /// Returns [None] if `err` is suppressed by a directive.
+    pub fn apply(&self, err: Error) -> Option<Error> {
+        let span = err.span();
+        if self.is_empty() || span.is_dummy() {
+            return Some(err);
+        }
+
+        if self.is_unchecked(span.lo) {
+            return None;
+        }
+
+        // The closest directive is used if there are multiple directives for a line.
+        match self.directives.iter().rev().find(|d| d.lo <= span.lo && span.lo < d.hi) {
+            Some(d) => {
+                d.used.store(true, Ordering::Relaxed);
+                None
+            }
+            None => Some(err),
+        }
+    }
+
+    pub fn apply_all(&self, errors: Errors) -> Errors {
+        if self.is_empty() {
+            return errors;
+        }
+
+        let mut buf = Errors::default();
+        buf.extend(ErrorKind::flatten(errors.into()).into_iter().filter_map(|err| self.apply(err)));
+        buf
+    }
+
+    /// Returns errors for `@ts-expect-error` comments which did not suppress
+    /// any error.
+    pub fn unused_errors(&self) -> Vec<Error> {
+        self.directives
+            .iter()
+            .filter(|d| d.kind == DirectiveKind::ExpectError && !d.used.load(Ordering::Relaxed) && !self.is_unchecked(d.span.lo))
+            .map(|d| ErrorKind::UnusedTsExpectError { span: d.span }.into())
+            .collect()
+    }
+}
+
+fn parse_directive(c: &Comment) -> Option<DirectiveKind> {
+    let text = directive_text(c);
+
+    if is_directive(text, "@ts-expect-error") {
+        return Some(DirectiveKind::ExpectError);
+    }
+    if is_directive(text, "@ts-ignore") {
+        return Some(DirectiveKind::Ignore);
+    }
+
+    None
+}
+
+fn directive_text(c: &Comment) -> &str {
+    let text = match c.kind {
+        // `/// @ts-ignore` is also a directive.
+        CommentKind::Line => c.text.trim_start_matches('/'),
+        CommentKind::Block => c.text.trim_start_matches(|c: char| c == '*' || c.is_whitespace()),
+    };
+    text.trim_start()
+}
+
+fn is_directive(text: &str, name: &str) -> bool {
+    match text.strip_prefix(name) {
+        Some(rest) => rest.chars().next().map_or(true, |c| !c.is_alphanumeric() && c != '-' && c != '_'),
+        None => false,
+    }
+}
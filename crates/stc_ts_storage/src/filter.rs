@@ -0,0 +1,133 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::Arc,
+};
+
+use globset::{Glob, GlobMatcher};
+use stc_ts_errors::{Error, ErrorKind, Errors};
+use swc_common::{FileName, SourceMap, Spanned};
+
+use crate::severity::SeverityOverrides;
+
+/// What to do with diagnostics matching a [DiagnosticFilter] rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterAction {
+    /// Drop the diagnostic.
+    Suppress,
+    /// Report the diagnostic as a warning.
+    Downgrade,
+}
+
+#[derive(Debug)]
+struct FilterRule {
+    glob: GlobMatcher,
+    code: Option<usize>,
+    action: FilterAction,
+}
+
+/// Suppresses or downgrades diagnostics by (path glob, error code) pairs, so
+/// large codebases can adopt stc incrementally.
+///
+/// Diagnostics which do not match any rule use the project-wide
+/// [SeverityOverrides].
+///
+/// Applied by [crate::Single] and [crate::Group] before storing errors.
+pub struct DiagnosticFilter {
+    cm: Arc<SourceMap>,
+    rules: Vec<FilterRule>,
+    overrides: SeverityOverrides,
+}
+
+impl Debug for DiagnosticFilter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DiagnosticFilter")
+            .field("rules", &self.rules)
+            .field("overrides", &self.overrides)
+            .finish()
+    }
+}
+
+impl DiagnosticFilter {
+    pub fn new(cm: Arc<SourceMap>) -> Self {
+        Self {
+            cm,
+            rules: vec![],
+            overrides: Default::default(),
+        }
+    }
+
+    pub fn set_severity_overrides(&mut self, overrides: SeverityOverrides) {
+        self.overrides = overrides;
+    }
+
+    fn is_empty(&self) -> bool {
+        self.rules.is_empty() && self.overrides.is_empty()
+    }
+
+    /// Adds a rule. The first matching rule wins.
+    ///
+    /// Relative globs like `generated/**` match in any directory. `code` is
+    /// the numeric part of a code like `TS2345`, and [None] matches all
+    /// codes.
+    pub fn add_rule(&mut self, glob: &str, code: Option<usize>, action: FilterAction) -> Result<(), globset::Error> {
+        let glob = if glob.starts_with('/') || glob.starts_with("**") {
+            Glob::new(glob)?
+        } else {
+            Glob::new(&format!("**/{}", glob))?
+        };
+
+        self.rules.push(FilterRule {
+            glob: glob.compile_matcher(),
+            code,
+            action,
+        });
+
+        Ok(())
+    }
+
+    /// Returns [None] if `err` is suppressed.
+    pub fn apply(&self, err: Error) -> Option<Error> {
+        if self.is_empty() {
+            return Some(err);
+        }
+
+        match self.matching_action(&err) {
+            Some(FilterAction::Suppress) => None,
+            Some(FilterAction::Downgrade) => Some(err.into_warning()),
+            None => self.overrides.apply(err),
+        }
+    }
+
+    fn matching_action(&self, err: &Error) -> Option<FilterAction> {
+        if self.rules.is_empty() {
+            return None;
+        }
+
+        let span = err.span();
+        if span.is_dummy() {
+            return None;
+        }
+
+        let file = self.cm.lookup_char_pos(span.lo).file;
+        let path = match &file.name {
+            FileName::Real(path) => path,
+            _ => return None,
+        };
+        let code = ErrorKind::normalize_error_code(err.code());
+
+        self.rules
+            .iter()
+            .find(|rule| rule.code.map_or(true, |c| c == code) && rule.glob.is_match(path))
+            .map(|rule| rule.action)
+    }
+
+    pub fn apply_all(&self, errors: Errors) -> Errors {
+        if self.is_empty() {
+            return errors;
+        }
+
+        let mut buf = Errors::default();
+        buf.extend(ErrorKind::flatten(errors.into()).into_iter().filter_map(|err| self.apply(err)));
+        buf
+    }
+}
@@ -10,12 +10,39 @@ use stc_utils::cache::Freeze;
 use swc_atoms::JsWord;
 use swc_common::{iter::IdentifyLast, FileName, Span, TypeEq, DUMMY_SP};
 
+pub use self::{
+    baseline::Baseline,
+    directives::{CommentDirectives, DirectiveKind, FilePragma},
+    filter::{DiagnosticFilter, FilterAction},
+    order::sort_and_dedup_errors,
+    query::DiagnosticQuery,
+    severity::{ErrorGroup, Severity, SeverityOverrides},
+};
+
+mod baseline;
+mod directives;
+mod filter;
+mod order;
+mod query;
+mod severity;
+
 #[derive(Debug, Default)]
 pub struct Info {
     pub errors: Errors,
     pub exports: ModuleTypeData,
 }
 
+impl Info {
+    pub fn errors_matching<'a>(&'a self, query: &'a DiagnosticQuery) -> impl 'a + Iterator<Item = &'a Error> {
+        self.errors.iter().filter(move |err| query.matches(err))
+    }
+
+    /// Removes errors matching `query` and returns them, leaving other errors.
+    pub fn take_errors_matching(&mut self, query: &DiagnosticQuery) -> Vec<Error> {
+        self.errors.take_if(|err| query.matches(err))
+    }
+}
+
 pub type Storage<'b> = Box<dyn 'b + Mode>;
 
 #[auto_impl(&mut, Box)]
@@ -29,6 +56,8 @@ pub trait ErrorStore {
 pub trait TypeStore: Send + Sync {
     fn get_local_type(&self, ctxt: ModuleId, id: Id) -> Option<Type>;
     fn get_local_var(&self, ctxt: ModuleId, id: Id) -> Option<Type>;
+    /// Returns `true` if `ctxt` exports a variable or a type named `name`.
+    fn is_exported(&self, ctxt: ModuleId, name: &JsWord) -> bool;
 
     fn store_private_type(&mut self, ctxt: ModuleId, id: Id, ty: Type, should_override: bool);
     fn store_private_var(&mut self, ctxt: ModuleId, id: Id, ty: Type);
@@ -71,14 +100,40 @@ pub struct Single<'a> {
     pub path: Arc<FileName>,
     pub is_dts: bool,
     pub info: Info,
+    pub filter: Option<Arc<DiagnosticFilter>>,
+    pub directives: Option<Arc<CommentDirectives>>,
 }
 
 impl ErrorStore for Single<'_> {
     fn report(&mut self, err: Error) {
+        let err = match &self.directives {
+            Some(directives) => match directives.apply(err) {
+                Some(err) => err,
+                None => return,
+            },
+            None => err,
+        };
+        let err = match &self.filter {
+            Some(filter) => match filter.apply(err) {
+                Some(err) => err,
+                None => return,
+            },
+            None => err,
+        };
+
         self.info.errors.push(err);
     }
 
     fn report_all(&mut self, err: Errors) {
+        let err = match &self.directives {
+            Some(directives) => directives.apply_all(err),
+            None => err,
+        };
+        let err = match &self.filter {
+            Some(filter) => filter.apply_all(err),
+            None => err,
+        };
+
         self.info.errors.extend(err);
     }
 
@@ -168,6 +223,14 @@ impl TypeStore for Single<'_> {
         }
     }
 
+    fn is_exported(&self, ctxt: ModuleId, name: &JsWord) -> bool {
+        debug_assert_eq!(ctxt, self.id);
+
+        self.info.exports.vars.contains_key(name)
+            || self.info.exports.types.contains_key(name)
+            || self.parent.map_or(false, |parent| parent.is_exported(ctxt, name))
+    }
+
     fn take_info(&mut self, ctxt: ModuleId) -> ModuleTypeData {
         debug_assert_eq!(ctxt, self.id);
         take(&mut self.info.exports)
@@ -210,6 +273,8 @@ impl<'a> Mode for Single<'a> {
             id: self.id,
             path: self.path.clone(),
             info: Default::default(),
+            filter: self.filter.clone(),
+            directives: self.directives.clone(),
         }
     }
 }
@@ -227,14 +292,40 @@ pub struct Group<'a> {
     pub files: Arc<Vec<File>>,
     pub errors: Errors,
     pub info: FxHashMap<ModuleId, ModuleTypeData>,
+    pub filter: Option<Arc<DiagnosticFilter>>,
+    pub directives: Option<Arc<CommentDirectives>>,
 }
 
 impl ErrorStore for Group<'_> {
     fn report(&mut self, err: Error) {
+        let err = match &self.directives {
+            Some(directives) => match directives.apply(err) {
+                Some(err) => err,
+                None => return,
+            },
+            None => err,
+        };
+        let err = match &self.filter {
+            Some(filter) => match filter.apply(err) {
+                Some(err) => err,
+                None => return,
+            },
+            None => err,
+        };
+
         self.errors.push(err);
     }
 
     fn report_all(&mut self, err: Errors) {
+        let err = match &self.directives {
+            Some(directives) => directives.apply_all(err),
+            None => err,
+        };
+        let err = match &self.filter {
+            Some(filter) => filter.apply_all(err),
+            None => err,
+        };
+
         self.errors.extend(err);
     }
 
@@ -313,6 +404,13 @@ impl TypeStore for Group<'_> {
         }
     }
 
+    fn is_exported(&self, ctxt: ModuleId, name: &JsWord) -> bool {
+        self.info
+            .get(&ctxt)
+            .map_or(false, |data| data.vars.contains_key(name) || data.types.contains_key(name))
+            || self.parent.map_or(false, |parent| parent.is_exported(ctxt, name))
+    }
+
     fn take_info(&mut self, ctxt: ModuleId) -> ModuleTypeData {
         self.info.remove(&ctxt).unwrap_or_default()
     }
@@ -365,6 +463,8 @@ impl Mode for Group<'_> {
             files: self.files.clone(),
             errors: Default::default(),
             info: Default::default(),
+            filter: self.filter.clone(),
+            directives: self.directives.clone(),
         }
     }
 }
@@ -427,6 +527,10 @@ impl TypeStore for Builtin {
         self.vars.get(id.sym()).cloned()
     }
 
+    fn is_exported(&self, _: ModuleId, _: &JsWord) -> bool {
+        false
+    }
+
     fn take_info(&mut self, _ctxt: ModuleId) -> ModuleTypeData {
         unimplemented!("builtin.take_info")
     }
@@ -459,6 +563,10 @@ mod tests {
     use std::path::PathBuf;
 
     use stc_ts_types::module_id;
+    use swc_common::{
+        comments::{Comment, CommentKind},
+        BytePos, SourceFile, Spanned,
+    };
 
     use super::*;
 
@@ -484,6 +592,8 @@ mod tests {
                 files: Arc::new(vec![file1.clone(), file2.clone()]),
                 info: Default::default(),
                 errors: Default::default(),
+                filter: None,
+                directives: None,
             };
 
             assert_eq!(group.module_id(0), file1.id);
@@ -501,4 +611,233 @@ mod tests {
         })
         .unwrap();
     }
+
+    #[test]
+    fn diagnostic_filter() {
+        testing::run_test(false, |cm, _| {
+            let generated = cm.new_source_file(FileName::Real(PathBuf::from("/project/generated/a.ts")), "let a = 1;".into());
+            let src = cm.new_source_file(FileName::Real(PathBuf::from("/project/src/a.ts")), "let a = 1;".into());
+
+            let mut filter = DiagnosticFilter::new(cm.clone());
+            filter.add_rule("generated/**", Some(2365), FilterAction::Suppress).unwrap();
+            filter.add_rule("generated/**", None, FilterAction::Downgrade).unwrap();
+
+            let span = |lo| Span::new(lo, lo + swc_common::BytePos(1), Default::default());
+
+            assert!(filter.apply(ErrorKind::TS2365 { span: span(generated.start_pos) }.into()).is_none());
+
+            let downgraded = filter.apply(ErrorKind::TS2370 { span: span(generated.start_pos) }.into()).unwrap();
+            assert!(downgraded.is_warning());
+
+            let kept = filter.apply(ErrorKind::TS2365 { span: span(src.start_pos) }.into()).unwrap();
+            assert!(!kept.is_warning());
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn severity_overrides() {
+        testing::run_test(false, |cm, _| {
+            let src = cm.new_source_file(FileName::Real(PathBuf::from("/project/src/a.ts")), "let a = 1;".into());
+            let span = Span::new(src.start_pos, src.start_pos + BytePos(1), Default::default());
+
+            let mut overrides = SeverityOverrides::default();
+            overrides.add_entry("TS2365=off").unwrap();
+            overrides.add_entry("property-not-found=warning").unwrap();
+            overrides.add_entry("var-not-found=warning").unwrap();
+            overrides.add_entry("TS2304=error").unwrap();
+            assert!(overrides.add_entry("assign").is_err());
+            assert!(overrides.add_entry("assign=fatal").is_err());
+
+            let mut filter = DiagnosticFilter::new(cm.clone());
+            filter.set_severity_overrides(overrides);
+
+            assert!(filter.apply(ErrorKind::TS2365 { span }.into()).is_none());
+
+            let downgraded = filter
+                .apply(
+                    ErrorKind::NoSuchProperty {
+                        span,
+                        obj: None,
+                        prop: None,
+                    }
+                    .into(),
+                )
+                .unwrap();
+            assert!(downgraded.is_warning());
+
+            // Error codes take precedence over groups.
+            let kept = filter
+                .apply(
+                    ErrorKind::NoSuchVar {
+                        span,
+                        name: Id::word("b".into()),
+                    }
+                    .into(),
+                )
+                .unwrap();
+            assert!(!kept.is_warning());
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn comment_directives() {
+        testing::run_test(false, |cm, _| {
+            let fm = cm.new_source_file(
+                FileName::Real(PathBuf::from("/project/a.ts")),
+                "// @ts-ignore\nlet a = 1;\n// @ts-expect-error\nlet b = 1;\n// @ts-expect-error\nlet c = 1;\nlet d = 1;\n".into(),
+            );
+            let comment = |line: usize| Comment {
+                kind: CommentKind::Line,
+                span: Span::new(fm.lines[line], fm.lines[line] + BytePos(3), Default::default()),
+                text: if line == 0 {
+                    " @ts-ignore".into()
+                } else {
+                    " @ts-expect-error".into()
+                },
+            };
+            let comments = vec![comment(0), comment(2), comment(4)];
+            let directives = CommentDirectives::new(&cm, &comments);
+
+            let error_at = |line: usize| -> Error {
+                ErrorKind::TS2365 {
+                    span: Span::new(fm.lines[line], fm.lines[line] + BytePos(1), Default::default()),
+                }
+                .into()
+            };
+
+            assert!(directives.apply(error_at(1)).is_none());
+            assert!(directives.apply(error_at(3)).is_none());
+            assert!(directives.apply(error_at(6)).is_some());
+
+            let unused = directives.unused_errors();
+            assert_eq!(unused.len(), 1);
+            assert_eq!(unused[0].span().lo, fm.lines[4]);
+
+            let mut directives = CommentDirectives::new(&cm, &comments);
+            directives.add_unchecked_range(fm.start_pos, fm.end_pos);
+            assert!(directives.apply(error_at(6)).is_none());
+            assert!(directives.unused_errors().is_empty());
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn sort_and_dedup() {
+        testing::run_test(false, |cm, _| {
+            let b = cm.new_source_file(FileName::Real(PathBuf::from("/project/b.ts")), "let a = 1;".into());
+            let a = cm.new_source_file(FileName::Real(PathBuf::from("/project/a.ts")), "let a = 1;".into());
+
+            let error_at = |lo: BytePos| -> Error {
+                ErrorKind::TS2365 {
+                    span: Span::new(lo, lo + BytePos(1), Default::default()),
+                }
+                .into()
+            };
+
+            let errors = sort_and_dedup_errors(
+                &cm,
+                vec![
+                    error_at(b.start_pos),
+                    error_at(a.start_pos + BytePos(4)),
+                    error_at(a.start_pos),
+                    error_at(b.start_pos),
+                ],
+            );
+
+            let positions = errors.iter().map(|err| err.span().lo).collect::<Vec<_>>();
+            assert_eq!(positions, vec![a.start_pos, a.start_pos + BytePos(4), b.start_pos]);
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn query() {
+        testing::run_test(false, |cm, _| {
+            let a = cm.new_source_file(FileName::Real(PathBuf::from("/project/a.ts")), "let a = 1;".into());
+            let b = cm.new_source_file(FileName::Real(PathBuf::from("/project/b.ts")), "let b = 1;".into());
+
+            let span_at = |lo: BytePos| Span::new(lo, lo + BytePos(1), Default::default());
+
+            let mut info = Info::default();
+            info.errors.push(
+                ErrorKind::TS2365 {
+                    span: span_at(a.start_pos),
+                }
+                .into(),
+            );
+            info.errors.push(
+                ErrorKind::TS2370 {
+                    span: span_at(a.start_pos + BytePos(4)),
+                }
+                .into(),
+            );
+            info.errors.push(
+                ErrorKind::TS2365 {
+                    span: span_at(b.start_pos),
+                }
+                .into(),
+            );
+
+            assert_eq!(info.errors_matching(&DiagnosticQuery::default()).count(), 3);
+            assert_eq!(info.errors_matching(&DiagnosticQuery::default().code(2365)).count(), 2);
+            assert_eq!(
+                info.errors_matching(&DiagnosticQuery::default().span(span_at(a.start_pos + BytePos(4))))
+                    .count(),
+                1
+            );
+
+            let taken = info.take_errors_matching(&DiagnosticQuery::default().file(&a));
+            assert_eq!(taken.len(), 2);
+            assert_eq!(info.errors.len(), 1);
+
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn baseline() {
+        testing::run_test(false, |cm, _| {
+            let old = cm.new_source_file(
+                FileName::Real(PathBuf::from("/project/old/a.ts")),
+                "let a = 1;\nlet b = 2;\n".into(),
+            );
+            let new = cm.new_source_file(
+                FileName::Real(PathBuf::from("/project/new/a.ts")),
+                "// moved\nlet a = 1;\nlet b = 2;\nlet c = 3;\n".into(),
+            );
+
+            let error_at = |file: &SourceFile, line: usize| -> Error {
+                let lo = file.lines[line];
+                ErrorKind::TS2365 {
+                    span: Span::new(lo, lo + BytePos(10), Default::default()),
+                }
+                .into()
+            };
+
+            let recorded = Baseline::record(PathBuf::from("/project/old"), &cm, &[error_at(&old, 0), error_at(&old, 1)]);
+            assert_eq!(recorded.to_string(), "TS2365\ta.ts\t1\tlet a = 1;\nTS2365\ta.ts\t2\tlet b = 2;\n");
+
+            let baseline = Baseline::parse(PathBuf::from("/project/new"), &recorded.to_string()).unwrap();
+            assert_eq!(baseline.len(), 2);
+
+            let errors = baseline.filter_new(&cm, vec![error_at(&new, 1), error_at(&new, 2), error_at(&new, 3)]);
+            assert_eq!(errors, vec![error_at(&new, 3)]);
+
+            assert!(Baseline::parse(PathBuf::new(), "TS2365\ta.ts").is_err());
+
+            Ok(())
+        })
+        .unwrap();
+    }
 }
@@ -0,0 +1,41 @@
+use std::cmp::Ordering;
+
+use stc_ts_errors::{Error, ErrorKind};
+use swc_common::{SourceMap, Spanned};
+
+/// Sorts errors by file and offset, and removes errors reported multiple times
+/// at the same span with the same code.
+///
+/// This makes the output independent of the order of traversal.
+pub fn sort_and_dedup_errors(cm: &SourceMap, errors: Vec<Error>) -> Vec<Error> {
+    let mut errors = ErrorKind::flatten(errors)
+        .into_iter()
+        .map(|err| {
+            let span = err.span();
+            let key = if span.is_dummy() {
+                None
+            } else {
+                let file = cm.lookup_char_pos(span.lo).file;
+                Some((file.name.to_string(), span.lo - file.start_pos, span.hi - file.start_pos))
+            };
+            let code = ErrorKind::normalize_error_code(err.code());
+
+            (key, code, err)
+        })
+        .collect::<Vec<_>>();
+
+    // Errors without a span come first.
+    errors.sort_by(|(lk, lc, le), (rk, rc, re)| {
+        lk.cmp(rk)
+            .then(lc.cmp(rc))
+            // Prefer errors over warnings.
+            .then(match (le.is_warning(), re.is_warning()) {
+                (false, true) => Ordering::Less,
+                (true, false) => Ordering::Greater,
+                _ => Ordering::Equal,
+            })
+    });
+    errors.dedup_by(|(rk, rc, re), (lk, lc, le)| lk.is_some() && lk == rk && lc == rc && le.span() == re.span());
+
+    errors.into_iter().map(|(_, _, err)| err).collect()
+}
@@ -0,0 +1,59 @@
+use stc_ts_errors::{Error, ErrorKind};
+use swc_common::{BytePos, SourceFile, Span, Spanned};
+
+/// Selects diagnostics by file, span and error code, so a language server can
+/// publish diagnostics of each document.
+///
+/// All conditions should match, and a query without conditions matches all
+/// diagnostics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DiagnosticQuery {
+    /// Start and end of a file.
+    file: Option<(BytePos, BytePos)>,
+    /// Diagnostics overlapping this span match.
+    span: Option<Span>,
+    /// Normalized error code.
+    code: Option<usize>,
+}
+
+impl DiagnosticQuery {
+    pub fn file(mut self, file: &SourceFile) -> Self {
+        self.file = Some((file.start_pos, file.end_pos));
+        self
+    }
+
+    pub fn span(mut self, span: Span) -> Self {
+        self.span = Some(span);
+        self
+    }
+
+    /// `code` is the numeric part of a code like `TS2345`.
+    pub fn code(mut self, code: usize) -> Self {
+        self.code = Some(ErrorKind::normalize_error_code(code));
+        self
+    }
+
+    pub fn matches(&self, err: &Error) -> bool {
+        if let Some(code) = self.code {
+            if ErrorKind::normalize_error_code(err.code()) != code {
+                return false;
+            }
+        }
+
+        let span = err.span();
+        if span.is_dummy() {
+            return self.file.is_none() && self.span.is_none();
+        }
+
+        if let Some((lo, hi)) = self.file {
+            if span.lo < lo || hi < span.lo {
+                return false;
+            }
+        }
+
+        match self.span {
+            Some(range) => span.lo <= range.hi && range.lo <= span.hi,
+            None => true,
+        }
+    }
+}
@@ -0,0 +1,126 @@
+use std::str::FromStr;
+
+use fxhash::FxHashMap;
+use stc_ts_errors::{Error, ErrorKind};
+
+/// Severity of errors selected by [SeverityOverrides].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    /// Drop the error.
+    Off,
+}
+
+impl FromStr for Severity {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "error" => Ok(Severity::Error),
+            "warning" | "warn" => Ok(Severity::Warning),
+            "off" => Ok(Severity::Off),
+            _ => Err(format!("unknown severity `{}`; expected `error`, `warning` or `off`", s)),
+        }
+    }
+}
+
+/// Groups of [ErrorKind]s which can be configured at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorGroup {
+    /// `assign`: TS2322 and errors reported as it.
+    Assign,
+    /// `property-not-found`
+    PropertyNotFound,
+    /// `var-not-found`
+    VarNotFound,
+    /// `type-not-found`
+    TypeNotFound,
+}
+
+impl ErrorGroup {
+    pub fn contains(self, err: &ErrorKind) -> bool {
+        match self {
+            ErrorGroup::Assign => err.is_assign_failure(),
+            ErrorGroup::PropertyNotFound => err.is_property_not_found(),
+            ErrorGroup::VarNotFound => err.is_var_not_found(),
+            ErrorGroup::TypeNotFound => err.is_type_not_found(),
+        }
+    }
+}
+
+impl FromStr for ErrorGroup {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "assign" => Ok(ErrorGroup::Assign),
+            "property-not-found" => Ok(ErrorGroup::PropertyNotFound),
+            "var-not-found" => Ok(ErrorGroup::VarNotFound),
+            "type-not-found" => Ok(ErrorGroup::TypeNotFound),
+            _ => Err(format!("unknown error group `{}`", s)),
+        }
+    }
+}
+
+/// Project-wide severities of errors, configured by error code or by
+/// [ErrorGroup].
+///
+/// An override for an error code takes precedence over groups, and the first
+/// matching group wins.
+#[derive(Debug, Default)]
+pub struct SeverityOverrides {
+    codes: FxHashMap<usize, Severity>,
+    groups: Vec<(ErrorGroup, Severity)>,
+}
+
+impl SeverityOverrides {
+    /// `code` is the numeric part of a code like `TS2345`.
+    pub fn set_code(&mut self, code: usize, severity: Severity) {
+        self.codes.insert(ErrorKind::normalize_error_code(code), severity);
+    }
+
+    pub fn set_group(&mut self, group: ErrorGroup, severity: Severity) {
+        self.groups.push((group, severity));
+    }
+
+    /// Parses an entry like `TS2322=warning` or `property-not-found=off`.
+    pub fn add_entry(&mut self, entry: &str) -> Result<(), String> {
+        let (selector, severity) = entry
+            .split_once('=')
+            .ok_or_else(|| format!("expected `<code or group>=<severity>`, got `{}`", entry))?;
+        let severity = severity.trim().parse()?;
+        let selector = selector.trim();
+
+        match selector.trim_start_matches("TS").parse::<usize>() {
+            Ok(code) => self.set_code(code, severity),
+            Err(_) => self.set_group(selector.parse()?, severity),
+        }
+
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.codes.is_empty() && self.groups.is_empty()
+    }
+
+    pub fn severity(&self, err: &ErrorKind) -> Option<Severity> {
+        if let Some(&severity) = self.codes.get(&ErrorKind::normalize_error_code(err.code())) {
+            return Some(severity);
+        }
+
+        self.groups
+            .iter()
+            .find(|(group, _)| group.contains(err))
+            .map(|&(_, severity)| severity)
+    }
+
+    /// Returns [None] if `err` is suppressed.
+    pub fn apply(&self, err: Error) -> Option<Error> {
+        match self.severity(&err) {
+            Some(Severity::Off) => None,
+            Some(Severity::Warning) => Some(err.into_warning()),
+            Some(Severity::Error) | None => Some(err),
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use std::path::Component;
+
+use stc_ts_storage::FilePragma;
+use swc_common::FileName;
+
+/// Used to decide whether errors in a file are reported, for `skipLibCheck`
+/// and `skipDefaultLibCheck`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    /// A declaration file marked with `/// <reference no-default-lib="true"/>`.
+    DefaultLib,
+    /// A declaration file in `node_modules`.
+    ExternalDts,
+    /// Other declaration files.
+    ProjectDts,
+    /// A TypeScript or JavaScript file which is not a declaration file.
+    Source,
+}
+
+impl FileKind {
+    pub fn of(name: &FileName, pragmas: &[FilePragma]) -> Self {
+        let path = match name {
+            FileName::Real(path) => path,
+            _ => return FileKind::Source,
+        };
+
+        let is_dts = path.file_name().and_then(|name| name.to_str()).map_or(false, |name| {
+            name.ends_with(".d.ts") || name.ends_with(".d.mts") || name.ends_with(".d.cts")
+        });
+        if !is_dts {
+            return FileKind::Source;
+        }
+
+        if pragmas.contains(&FilePragma::NoDefaultLib) {
+            FileKind::DefaultLib
+        } else if path.components().any(|c| c == Component::Normal("node_modules".as_ref())) {
+            FileKind::ExternalDts
+        } else {
+            FileKind::ProjectDts
+        }
+    }
+}
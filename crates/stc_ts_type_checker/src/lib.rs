@@ -1,7 +1,7 @@
 //! Full type checker with dependency support.
 #![feature(box_syntax)]
 
-use std::{mem::take, sync::Arc, time::Instant};
+use std::{mem::take, path::PathBuf, sync::Arc, time::Instant};
 
 use dashmap::{DashMap, DashSet, SharedValue};
 use fxhash::{FxBuildHasher, FxHashMap};
@@ -11,10 +11,16 @@ use rnode::{NodeIdGenerator, RNode, VisitWith};
 use stc_ts_ast_rnode::{RModule, RStr, RTsModuleName};
 use stc_ts_dts::{apply_mutations, cleanup_module_for_dts};
 use stc_ts_env::Env;
-use stc_ts_errors::{debug::debugger::Debugger, Error};
+use stc_ts_errors::{debug::debugger::Debugger, pretty, Error, ErrorKind, Errors, ExplainOpts, Fix, MessageCatalog};
 use stc_ts_file_analyzer::{analyzer::Analyzer, loader::Load, validator::ValidateWith, ModuleTypeData, VResult};
-use stc_ts_module_loader::ModuleGraph;
-use stc_ts_storage::{ErrorStore, File, Group, Single};
+use stc_ts_module_loader::{
+    fs::{FileSystem, RealFileSystem},
+    resolvers::paths::PathMappings,
+    ModuleGraph,
+};
+use stc_ts_storage::{
+    sort_and_dedup_errors, Baseline, CommentDirectives, DiagnosticFilter, DiagnosticQuery, ErrorStore, File, FilePragma, Group, Single,
+};
 use stc_ts_types::{ModuleId, Type};
 use stc_ts_utils::StcComments;
 use stc_utils::{cache::Freeze, early_error, panic_ctx};
@@ -25,8 +31,11 @@ use swc_ecma_loader::resolve::Resolve;
 use swc_ecma_parser::TsConfig;
 use swc_ecma_transforms::resolver;
 use swc_ecma_visit::FoldWith;
-use tracing::{info, warn};
+use tracing::{info, span, warn, Level};
 
+pub use self::file_kind::FileKind;
+
+mod file_kind;
 mod typings;
 
 /// Onc instance per swc::Compiler
@@ -48,7 +57,25 @@ pub struct Checker {
 
     errors: Mutex<Vec<Error>>,
 
+    /// Applied to errors of all modules.
+    diagnostic_filter: Option<Arc<DiagnosticFilter>>,
+
+    /// Errors in this are omitted by [Checker::take_errors].
+    baseline: Option<Baseline>,
+
+    /// Errors beyond this limit are omitted by [Checker::take_errors].
+    /// Warnings and errors in the baseline are not limited.
+    max_errors: Option<usize>,
+    /// If `true`, entries are not checked once `max_errors` is reached.
+    fail_fast: bool,
+
+    /// Used by [Checker::render_error].
+    messages: MessageCatalog,
+
     env: Env,
+    /// Used instead of `env` for matching files. See
+    /// [Checker::add_env_override].
+    env_overrides: Vec<(Box<dyn Fn(&FileName) -> bool + Send + Sync>, Env)>,
 
     debugger: Option<Debugger>,
 }
@@ -61,23 +88,113 @@ impl Checker {
         parser_config: TsConfig,
         debugger: Option<Debugger>,
         resolver: Arc<dyn Resolve>,
+    ) -> Self {
+        Self::new_with_fs(cm, handler, env, parser_config, debugger, resolver, Arc::new(RealFileSystem))
+    }
+
+    /// Creates a checker which reads files using `fs`, which allows checking
+    /// projects which do not exist on the disk.
+    ///
+    /// Note that `resolver` should use the same file system.
+    pub fn new_with_fs(
+        cm: Arc<SourceMap>,
+        handler: Arc<Handler>,
+        env: Env,
+        parser_config: TsConfig,
+        debugger: Option<Debugger>,
+        resolver: Arc<dyn Resolve>,
+        fs: Arc<dyn FileSystem>,
     ) -> Self {
         cm.new_source_file(FileName::Anon, "".into());
 
+        let mut module_graph = ModuleGraph::new_with_fs(cm.clone(), Default::default(), resolver, parser_config, env.target(), fs);
+        if env.jsx().is_automatic() {
+            module_graph.set_jsx_runtime(env.jsx().runtime_module());
+        }
+
         Checker {
-            env: env.clone(),
-            cm: cm.clone(),
+            env,
+            cm,
             handler,
             module_types: Default::default(),
             dts_modules: Default::default(),
-            module_graph: Arc::new(ModuleGraph::new(cm, Default::default(), resolver, parser_config, env.target())),
+            module_graph: Arc::new(module_graph),
             started: Default::default(),
             errors: Default::default(),
+            diagnostic_filter: None,
+            baseline: None,
+            max_errors: None,
+            fail_fast: false,
+            messages: Default::default(),
+            env_overrides: Default::default(),
             debugger,
             declared_modules: Default::default(),
         }
     }
 
+    /// Suppresses or downgrades errors using `filter`.
+    ///
+    /// This should be called before checking modules.
+    pub fn set_diagnostic_filter(&mut self, filter: DiagnosticFilter) {
+        self.diagnostic_filter = Some(Arc::new(filter));
+    }
+
+    /// Omits errors recorded in `baseline`, so that only new errors count
+    /// toward the error limit.
+    pub fn set_baseline(&mut self, baseline: Baseline) {
+        self.baseline = Some(baseline);
+    }
+
+    /// Stores at most `max_errors` errors, and reports
+    /// [ErrorKind::TooManyErrors] instead of the others.
+    ///
+    /// If `fail_fast` is `true`, [Checker::check] does nothing once the limit
+    /// is reached. Dependencies of a module being checked are still analyzed.
+    pub fn set_error_limit(&mut self, max_errors: usize, fail_fast: bool) {
+        self.max_errors = Some(max_errors);
+        self.fail_fast = fail_fast;
+    }
+
+    /// Resolves non-relative imports using `paths` of `tsconfig.json`.
+    ///
+    /// This should be called before checking modules.
+    pub fn set_path_mappings(&mut self, paths: PathMappings) {
+        Arc::get_mut(&mut self.module_graph)
+            .expect("path mappings should be set before checking modules")
+            .set_path_mappings(paths);
+    }
+
+    /// Resolves `/// <reference types="..." />` using `typeRoots` of
+    /// `tsconfig.json`.
+    ///
+    /// This should be called before checking modules.
+    pub fn set_type_roots(&mut self, type_roots: Vec<PathBuf>) {
+        Arc::get_mut(&mut self.module_graph)
+            .expect("type roots should be set before checking modules")
+            .set_type_roots(type_roots);
+    }
+
+    /// Analyzes files matching `matches` using `env`, which is usually created
+    /// by `EnvFactory::with_libs` to check worker entries against `webworker`
+    /// instead of `dom`. The first matching override is used.
+    ///
+    /// This should be called before checking modules.
+    pub fn add_env_override(&mut self, matches: impl Fn(&FileName) -> bool + Send + Sync + 'static, env: Env) {
+        self.env_overrides.push((Box::new(matches), env));
+    }
+
+    fn env_of(&self, path: &FileName) -> &Env {
+        self.env_overrides
+            .iter()
+            .find(|(matches, _)| matches(path))
+            .map_or(&self.env, |(_, env)| env)
+    }
+
+    /// Overrides or localizes the messages of [Checker::render_error].
+    pub fn set_message_catalog(&mut self, messages: MessageCatalog) {
+        self.messages = messages;
+    }
+
     pub fn run<F, R>(&self, op: F) -> R
     where
         F: FnOnce() -> R,
@@ -108,27 +225,215 @@ impl Checker {
 
     /// After calling this method, you can get errors using `.take_errors()`
     pub fn check(&self, entry: Arc<FileName>) -> ModuleId {
+        self.check_all(&[entry])[0]
+    }
+
+    /// Checks all of `entries`. Modules shared by entries are analyzed only
+    /// once.
+    ///
+    /// After calling this method, you can get errors using `.take_errors()`
+    pub fn check_all(&self, entries: &[Arc<FileName>]) -> Vec<ModuleId> {
         self.run(|| {
-            let start = Instant::now();
+            let ids = entries
+                .iter()
+                .map(|entry| {
+                    let _tracing = span!(Level::ERROR, "load", entry = %entry).entered();
 
-            let id = self.module_graph.load_all(&entry);
+                    self.module_graph.load_all(entry).unwrap_or_else(|(id, _)| id)
+                })
+                .collect();
 
-            let end = Instant::now();
-            log::debug!("Loading of `{}` and dependencies took {:?}", entry, end - start);
+            self.analyze_global_augmentations();
 
-            let start = Instant::now();
+            for entry in entries {
+                if self.fail_fast && self.is_error_limit_reached() {
+                    break;
+                }
 
-            self.analyze_module(None, entry.clone());
+                let _tracing = span!(Level::ERROR, "project", entry = %entry).entered();
 
-            let end = Instant::now();
-            log::debug!("Analysis of `{}` and dependencies took {:?}", entry, end - start);
+                self.analyze_module(None, entry.clone());
+            }
 
-            id.unwrap_or_else(|(id, _)| id)
+            ids
         })
     }
 
+    /// Analyzes modules containing `declare global` before others, so that
+    /// global augmentations are visible to all modules, regardless of the
+    /// order of modules.
+    fn analyze_global_augmentations(&self) {
+        let _tracing = span!(Level::ERROR, "globals").entered();
+
+        for id in self.module_graph.modules_with_global_augmentations() {
+            if self.fail_fast && self.is_error_limit_reached() {
+                return;
+            }
+
+            self.analyze_module(None, self.module_graph.path(id));
+        }
+    }
+
+    /// Returns errors sorted by their position, without duplicates and errors
+    /// in the baseline.
+    ///
+    /// If the error limit is reached, [ErrorKind::TooManyErrors] comes last.
     pub fn take_errors(&mut self) -> Vec<Error> {
-        take(self.errors.get_mut())
+        let mut errors = sort_and_dedup_errors(&self.cm, take(self.errors.get_mut()));
+        if let Some(baseline) = &self.baseline {
+            errors = baseline.filter_new(&self.cm, errors);
+        }
+
+        let max = match self.max_errors {
+            Some(max) => max,
+            None => return errors,
+        };
+
+        // The limit is applied after sorting, so the kept errors don't depend on the
+        // order in which modules are analyzed.
+        let mut count = 0;
+        let mut omitted = 0;
+        errors.retain(|err| {
+            if err.is_warning() {
+                return true;
+            }
+            if count >= max {
+                omitted += 1;
+                return false;
+            }
+            count += 1;
+            true
+        });
+
+        if omitted > 0 {
+            errors.push(
+                ErrorKind::TooManyErrors {
+                    span: DUMMY_SP,
+                    limit: max,
+                    omitted,
+                }
+                .into(),
+            );
+        }
+
+        errors
+    }
+
+    /// Takes errors in the file named `name`, leaving errors of other files.
+    ///
+    /// Returns errors sorted by their position, without duplicates.
+    pub fn take_errors_of(&mut self, name: &FileName) -> Vec<Error> {
+        let file = match self.cm.get_source_file(name) {
+            Some(file) => file,
+            None => return vec![],
+        };
+        let query = DiagnosticQuery::default().file(&file);
+
+        let errors = self.errors.get_mut();
+        let (taken, rest) = ErrorKind::flatten(take(errors)).into_iter().partition(|err| query.matches(err));
+        *errors = rest;
+
+        sort_and_dedup_errors(&self.cm, taken)
+    }
+
+    /// Renders `err` with the source code it points to, like `tsc --pretty`.
+    pub fn render_error(&self, err: &Error, colored: bool) -> String {
+        pretty::render(&self.cm, err, &ExplainOpts::from_env(), &self.messages, colored)
+    }
+
+    /// Returns fixes of `err` which can be offered as code actions.
+    pub fn fixes(&self, err: &Error) -> Vec<Fix> {
+        err.fixes(&self.cm)
+    }
+
+    /// Collects comment directives in the files of `modules`.
+    ///
+    /// Files marked with `@ts-nocheck` and JavaScript files which are not
+    /// marked with `@ts-check` are not checked, unless `checkJs` is enabled.
+    /// Declaration files are not checked if `skipLibCheck` or
+    /// `skipDefaultLibCheck` applies to them.
+    fn comment_directives(&self, modules: &[&RModule]) -> Option<Arc<CommentDirectives>> {
+        let files = modules
+            .iter()
+            .filter(|m| !m.span.is_dummy())
+            .map(|m| {
+                let file = self.cm.lookup_char_pos(m.span.lo).file;
+                // Pragmas should come before the code.
+                let code_start = m.body.first().map_or(file.end_pos, |item| item.span().lo);
+                (file, code_start)
+            })
+            .collect::<Vec<_>>();
+
+        let comments = self.module_graph.comments();
+        let comments = comments
+            .leading
+            .iter()
+            .chain(comments.trailing.iter())
+            .flat_map(|entry| entry.value().clone())
+            .filter(|c| {
+                files
+                    .iter()
+                    .any(|(file, _)| file.start_pos <= c.span.lo && c.span.hi <= file.end_pos)
+            })
+            .collect::<Vec<_>>();
+
+        let mut directives = CommentDirectives::new(&self.cm, &comments);
+
+        for (file, code_start) in &files {
+            let pragmas = comments
+                .iter()
+                .filter(|c| file.start_pos <= c.span.lo && c.span.hi <= *code_start)
+                .filter_map(FilePragma::parse)
+                .collect::<Vec<_>>();
+
+            let is_js = match &file.name {
+                FileName::Real(path) => matches!(path.extension().and_then(|ext| ext.to_str()), Some("js" | "jsx" | "mjs" | "cjs")),
+                _ => false,
+            };
+
+            let rule = self.env.rule();
+            let checked = if pragmas.contains(&FilePragma::NoCheck) {
+                false
+            } else {
+                match FileKind::of(&file.name, &pragmas) {
+                    FileKind::DefaultLib => !rule.skip_lib_check && !rule.skip_default_lib_check,
+                    FileKind::ExternalDts | FileKind::ProjectDts => !rule.skip_lib_check,
+                    FileKind::Source => !is_js || rule.check_js || pragmas.contains(&FilePragma::Check),
+                }
+            };
+
+            if !checked {
+                directives.add_unchecked_range(file.start_pos, file.end_pos);
+            }
+        }
+
+        if directives.is_empty() {
+            None
+        } else {
+            Some(Arc::new(directives))
+        }
+    }
+
+    fn is_error_limit_reached(&self) -> bool {
+        match self.max_errors {
+            Some(max) => {
+                let errors = self.errors.lock();
+                let count = match &self.baseline {
+                    Some(baseline) => baseline
+                        .filter_new(&self.cm, errors.clone())
+                        .iter()
+                        .filter(|err| !err.is_warning())
+                        .count(),
+                    None => errors.iter().filter(|err| !err.is_warning()).count(),
+                };
+                count >= max
+            }
+            None => false,
+        }
+    }
+
+    fn store_errors(&self, errors: Errors) {
+        self.errors.lock().extend(ErrorKind::flatten(errors.into()));
     }
 
     /// Analyzes one module.
@@ -163,6 +468,9 @@ impl Checker {
                     }
 
                     {
+                        let _tracing = span!(Level::ERROR, "module", path = %path, circular = set.len()).entered();
+
+                        let start = Instant::now();
                         let mut node_id_gen = NodeIdGenerator::default();
                         let mut storage = Group {
                             parent: None,
@@ -178,8 +486,11 @@ impl Checker {
                             ),
                             errors: Default::default(),
                             info: Default::default(),
+                            filter: self.diagnostic_filter.clone(),
+                            directives: None,
                         };
                         let ids = set.to_vec();
+                        let resolve_span = span!(Level::ERROR, "resolve").entered();
                         let modules = ids
                             .iter()
                             .map(|&id| (id, self.module_graph.clone_module(id)))
@@ -195,10 +506,13 @@ impl Checker {
                                 )
                             })
                             .collect::<Vec<_>>();
+                        resolve_span.exit();
+                        storage.directives = self.comment_directives(&modules.iter().collect::<Vec<_>>());
                         let mut mutations;
                         {
+                            let _tracing = span!(Level::ERROR, "analyze").entered();
                             let mut a = Analyzer::root(
-                                self.env.clone(),
+                                self.env_of(&path).clone(),
                                 self.cm.clone(),
                                 self.module_graph.comments().clone(),
                                 box &mut storage,
@@ -209,6 +523,13 @@ impl Checker {
                             mutations = a.mutations.unwrap();
                         }
 
+                        if let Some(directives) = storage.directives.clone() {
+                            for err in directives.unused_errors() {
+                                storage.report(err);
+                            }
+                        }
+
+                        let dts_span = span!(Level::ERROR, "dts").entered();
                         for (id, mut dts_module) in ids.iter().zip(modules) {
                             let type_data = storage.info.entry(*id).or_default();
 
@@ -222,11 +543,9 @@ impl Checker {
                                 warn!("Duplicated work: `{}`: (.d.ts already computed)", path);
                             }
                         }
+                        dts_span.exit();
 
-                        {
-                            let mut lock = self.errors.lock();
-                            lock.extend(storage.take_errors());
-                        }
+                        self.store_errors(storage.take_errors());
                         {
                             let mut lock = self.module_types.write();
                             for (module_id, data) in storage.info {
@@ -251,6 +570,12 @@ impl Checker {
                                 }
                             }
                         }
+
+                        // Modules in a circular group are analyzed at once, so the time is recorded
+                        // for the module which triggered the analysis.
+                        if let Some(perf) = self.env.perf() {
+                            perf.record_module(path.clone(), Instant::now() - start);
+                        }
                     }
 
                     let lock = self.module_types.read();
@@ -291,6 +616,7 @@ impl Checker {
     fn analyze_non_circular_module(&self, module_id: ModuleId, path: Arc<FileName>) -> Type {
         self.run(|| {
             let _panic = panic_ctx!(format!("analyze_non_circular_module({})", path));
+            let _tracing = span!(Level::ERROR, "module", path = %path).entered();
 
             let start = Instant::now();
 
@@ -304,28 +630,34 @@ impl Checker {
                 .module_graph
                 .clone_module(module_id)
                 .unwrap_or_else(|| unreachable!("Module graph does not contains {:?}: {}", module_id, path));
-            module = module.fold_with(&mut resolver(
-                self.env.shared().marks().unresolved_mark(),
-                self.module_graph.top_level_mark(module_id),
-                true,
-            ));
+            module = span!(Level::ERROR, "resolve").in_scope(|| {
+                module.fold_with(&mut resolver(
+                    self.env.shared().marks().unresolved_mark(),
+                    self.module_graph.top_level_mark(module_id),
+                    true,
+                ))
+            });
 
             let _panic = panic_ctx!(format!("Span of module = ({:?})", module.span));
 
             let mut module = RModule::from_orig(&mut node_id_gen, module);
 
+            let directives = self.comment_directives(&[&module]);
+
             let mut storage = Single {
                 parent: None,
                 id: module_id,
                 path: path.clone(),
                 info: Default::default(),
                 is_dts,
+                filter: self.diagnostic_filter.clone(),
+                directives: directives.clone(),
             };
             let mut mutations;
             {
-                let start = Instant::now();
+                let _tracing = span!(Level::ERROR, "analyze").entered();
                 let mut a = Analyzer::root(
-                    self.env.clone(),
+                    self.env_of(&path).clone(),
                     self.cm.clone(),
                     self.module_graph.comments().clone(),
                     box &mut storage,
@@ -335,15 +667,18 @@ impl Checker {
 
                 module.visit_with(&mut a);
 
-                let end = Instant::now();
-                let dur = end - start;
-                log::debug!("[Timing] Analysis of {} took {:?}", path, dur);
-
                 mutations = a.mutations.unwrap();
             }
 
+            if let Some(directives) = &directives {
+                for err in directives.unused_errors() {
+                    storage.report(err);
+                }
+            }
+
             {
                 // Get .d.ts file
+                let _tracing = span!(Level::ERROR, "dts").entered();
                 apply_mutations(&mut mutations, &mut module);
                 cleanup_module_for_dts(&mut module.body, &storage.info.exports);
             }
@@ -353,8 +688,7 @@ impl Checker {
                     self.handler.struct_span_err(err.span(), &format!("{:?}", err)).emit();
                 }
             } else {
-                let mut errors = self.errors.lock();
-                errors.extend(storage.info.errors);
+                self.store_errors(storage.info.errors);
             }
 
             let type_info = Type::Module(stc_ts_types::Module {
@@ -372,7 +706,9 @@ impl Checker {
             self.dts_modules.insert(module_id, module);
 
             let dur = Instant::now() - start;
-            log::trace!("[Timing] Full analysis of {} took {:?}", path, dur);
+            if let Some(perf) = self.env.perf() {
+                perf.record_module(path.clone(), dur);
+            }
 
             type_info
         })
@@ -386,6 +722,10 @@ impl Load for Checker {
         Some(id)
     }
 
+    fn path_mapping_of(&self, src: &JsWord) -> Option<JsWord> {
+        self.module_graph.path_mapping_of(src).map(JsWord::from)
+    }
+
     fn is_in_same_circular_group(&self, base: ModuleId, dep: ModuleId) -> bool {
         let circular_set = self.module_graph.get_circular(base);
 
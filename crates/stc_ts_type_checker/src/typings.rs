@@ -1,30 +1,33 @@
 use std::{
-    fs::read_dir,
     path::{Path, PathBuf},
     sync::Arc,
     time::Instant,
 };
 
+use fxhash::FxHashSet;
 use rayon::prelude::*;
-use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_module_loader::{fs::FileSystem, resolvers::node::NodeResolver};
 use swc_common::FileName;
 
 use crate::Checker;
 
 impl Checker {
     fn try_loading_typing_of_one_package(&self, dir: &Path) {
-        if !dir.is_dir() {
+        let fs = self.module_graph.fs();
+        if !fs.is_dir(dir) {
             return;
         }
 
-        let result = NodeResolver
-            .resolve_as_file(dir)
-            .or_else(|_| NodeResolver.resolve_as_directory(dir));
+        let resolver = NodeResolver::with_fs(fs.clone());
+        let result = resolver.resolve_as_file(dir).or_else(|_| resolver.resolve_as_directory(dir));
 
         if let Ok(entry) = result {
             let entry = Arc::new(FileName::Real(entry));
             let start = Instant::now();
-            self.module_graph.load_all(&entry).unwrap();
+            if let Err((_, err)) = self.module_graph.load_all(&entry) {
+                log::warn!("Failed to load typings at `{}`: {:?}", dir.display(), err);
+                return;
+            }
 
             self.analyze_module(None, entry);
 
@@ -33,43 +36,66 @@ impl Checker {
         }
     }
 
-    fn load_typings_from_dir(&self, dir: &Path, types: Option<&[String]>) {
-        let types_dir = dir.join("node_modules").join("@types");
-
-        if !types_dir.is_dir() {
-            return Default::default();
-        }
-
-        let dirs = types.map(|s| s.iter().map(|s| PathBuf::from(s.clone())).collect()).or_else(|| {
-            let pkgs = read_dir(&types_dir).ok()?;
-
-            let f = pkgs.into_iter().filter_map(Result::ok).map(|e| e.path()).collect::<Vec<_>>();
-
-            Some(f)
-        });
-
-        if let Some(dirs) = dirs {
-            dirs.into_par_iter().for_each(|dir| {
-                self.try_loading_typing_of_one_package(&types_dir.join(dir));
-            });
-        }
-    }
-
     /// Load typings from node_modules.
     ///
+    /// If `type_roots` is [None], `node_modules/@types` of `base` and its
+    /// ancestors are used. If `types` is [None], all packages in the type roots
+    /// are loaded. A package is loaded only from the first type root which
+    /// contains it, so packages installed at multiple levels are loaded once.
+    ///
+    /// Returns the names in `types` which are not found.
+    ///
     /// - https://www.typescriptlang.org/tsconfig#typeRoots
     /// - https://www.typescriptlang.org/tsconfig#types
-    pub fn load_typings(&self, base: &Path, _type_roots: Option<&[PathBuf]>, types: Option<&[String]>) {
-        let mut dirs = vec![];
-
-        let mut cur = Some(base);
-        while let Some(c) = cur {
-            dirs.push(c.to_path_buf());
-            cur = c.parent();
+    pub fn load_typings(&self, base: &Path, type_roots: Option<&[PathBuf]>, types: Option<&[String]>) -> Vec<String> {
+        let fs = self.module_graph.fs();
+
+        let roots = match type_roots {
+            Some(roots) => roots.to_vec(),
+            None => base.ancestors().map(|dir| dir.join("node_modules").join("@types")).collect(),
+        };
+        let roots = roots.into_iter().filter(|root| fs.is_dir(root)).collect::<Vec<_>>();
+
+        let mut packages = vec![];
+        let mut not_found = vec![];
+
+        match types {
+            Some(types) => {
+                for name in types {
+                    match roots.iter().map(|root| root.join(name)).find(|dir| fs.is_dir(dir)) {
+                        Some(dir) => packages.push(dir),
+                        None => not_found.push(name.clone()),
+                    }
+                }
+            }
+            None => {
+                let mut seen = FxHashSet::default();
+
+                for root in &roots {
+                    let mut dirs = fs.read_dir(root).unwrap_or_default();
+                    dirs.sort();
+
+                    for dir in dirs {
+                        let name = match dir.file_name() {
+                            Some(name) => name.to_os_string(),
+                            None => continue,
+                        };
+                        if name.to_string_lossy().starts_with('.') || !fs.is_dir(&dir) {
+                            continue;
+                        }
+
+                        if seen.insert(name) {
+                            packages.push(dir);
+                        }
+                    }
+                }
+            }
         }
 
-        dirs.into_par_iter().for_each(|dir| {
-            self.load_typings_from_dir(&dir, types);
+        packages.into_par_iter().for_each(|dir| {
+            self.try_loading_typing_of_one_package(&dir);
         });
+
+        not_found
     }
 }
@@ -80,7 +80,7 @@ fn do_test(file_name: &Path) -> Result<(), StdErr> {
                 ..Default::default()
             },
             None,
-            Arc::new(NodeResolver),
+            Arc::new(NodeResolver::new()),
         );
 
         let id = checker.check(Arc::new(file_name.clone().into()));
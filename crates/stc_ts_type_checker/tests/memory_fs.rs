@@ -0,0 +1,478 @@
+//! Checks projects which exist only in memory.
+
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use stc_ts_builtin_types::Lib;
+use stc_ts_env::{Env, JsxConfig, JsxMode, ModuleConfig, Rule};
+use stc_ts_errors::{Error, ErrorKind, MessageCatalog};
+use stc_ts_file_analyzer::env::EnvFactory;
+use stc_ts_module_loader::{
+    fs::{FileSystem, MemoryFileSystem},
+    resolvers::node::NodeResolver,
+};
+use stc_ts_storage::Baseline;
+use stc_ts_type_checker::Checker;
+use stc_ts_types::{KeywordType, Type};
+use swc_common::{FileName, DUMMY_SP};
+use swc_ecma_ast::{EsVersion, TsKeywordTypeKind};
+
+fn check(fs: Arc<MemoryFileSystem>, entries: &[&str]) -> usize {
+    check_with(fs, entries, |_| {}).len()
+}
+
+fn check_with(fs: Arc<MemoryFileSystem>, entries: &[&str], configure: impl FnOnce(&mut Checker)) -> Vec<Error> {
+    check_with_rule(fs, entries, Default::default(), configure)
+}
+
+fn check_with_rule(fs: Arc<MemoryFileSystem>, entries: &[&str], rule: Rule, configure: impl FnOnce(&mut Checker)) -> Vec<Error> {
+    check_with_env(
+        fs,
+        entries,
+        Env::simple(rule, EsVersion::Es5, ModuleConfig::None, &[Lib::Es5]),
+        configure,
+    )
+}
+
+fn check_with_env(fs: Arc<MemoryFileSystem>, entries: &[&str], env: Env, configure: impl FnOnce(&mut Checker)) -> Vec<Error> {
+    ::testing::run_test2(false, |cm, handler| {
+        let handler = Arc::new(handler);
+        let fs: Arc<dyn FileSystem> = fs;
+
+        let mut checker = Checker::new_with_fs(
+            cm,
+            handler,
+            env,
+            Default::default(),
+            None,
+            Arc::new(NodeResolver::with_fs(fs.clone())),
+            fs,
+        );
+        configure(&mut checker);
+
+        let entries = entries
+            .iter()
+            .map(|path| Arc::new(FileName::Real(Path::new(path).to_path_buf())))
+            .collect::<Vec<_>>();
+        checker.check_all(&entries);
+
+        Ok(checker.take_errors())
+    })
+    .unwrap()
+}
+
+#[test]
+fn resolves_imports_in_memory() {
+    let fs = Arc::new(MemoryFileSystem::new());
+    fs.add_file("/project/a.ts", "export const a: number = 1;");
+    fs.add_file("/project/index.ts", "import { a } from './a';\nexport const b: number = a;");
+
+    assert_eq!(check(fs, &["/project/index.ts"]), 0);
+}
+
+#[test]
+fn reports_errors_of_all_entries() {
+    let fs = Arc::new(MemoryFileSystem::new());
+    fs.add_file("/project/a.ts", "export const a: string = 1;");
+    fs.add_file("/project/b.ts", "export const b: number = '';");
+
+    assert_ne!(check(fs, &["/project/a.ts", "/project/b.ts"]), 0);
+}
+
+#[test]
+fn stops_at_error_limit() {
+    let fs = Arc::new(MemoryFileSystem::new());
+    fs.add_file(
+        "/project/a.ts",
+        "export const a: string = 1;\nexport const b: string = 2;\nexport const c: string = 3;",
+    );
+    fs.add_file("/project/b.ts", "export const d: number = '';");
+
+    let errors = check_with(fs, &["/project/a.ts", "/project/b.ts"], |checker| checker.set_error_limit(2, true));
+
+    assert_eq!(errors.len(), 3);
+    assert!(matches!(
+        &**errors.last().unwrap(),
+        ErrorKind::TooManyErrors { limit: 2, omitted: 1, .. }
+    ));
+}
+
+#[test]
+fn keeps_first_errors_by_position() {
+    let fs = Arc::new(MemoryFileSystem::new());
+    fs.add_file("/project/a.ts", "export const a: string = 1;");
+    fs.add_file("/project/b.ts", "foo;\nbar;\nexport {};");
+
+    let errors = check_with(fs, &["/project/b.ts", "/project/a.ts"], |checker| checker.set_error_limit(1, false));
+
+    assert_eq!(errors.len(), 2);
+    assert_eq!(ErrorKind::normalize_error_code(errors[0].code()), 2322);
+    assert!(matches!(
+        &**errors.last().unwrap(),
+        ErrorKind::TooManyErrors { limit: 1, omitted: 2, .. }
+    ));
+}
+
+#[test]
+fn limits_errors_not_in_baseline() {
+    let fs = Arc::new(MemoryFileSystem::new());
+    fs.add_file("/project/a.ts", "export const a: string = 1;\nexport const b: string = 2;");
+    fs.add_file("/project/b.ts", "export const d: number = '';");
+
+    let baseline = Baseline::parse(
+        PathBuf::from("/project"),
+        "TS2322\ta.ts\t1\texport const a: string = 1;\nTS2322\ta.ts\t2\texport const b: string = 2;",
+    )
+    .unwrap();
+    let errors = check_with(fs, &["/project/a.ts", "/project/b.ts"], |checker| {
+        checker.set_baseline(baseline);
+        checker.set_error_limit(1, false);
+    });
+
+    // Errors in the baseline don't count toward the limit.
+    assert_eq!(errors.len(), 1);
+    assert_eq!(ErrorKind::normalize_error_code(errors[0].code()), 2322);
+    assert!(!matches!(&**errors[0], ErrorKind::TooManyErrors { .. }));
+}
+
+#[test]
+fn skips_comments_after_directives() {
+    let fs = Arc::new(MemoryFileSystem::new());
+    fs.add_file(
+        "/project/index.ts",
+        "// @ts-expect-error\n\n// The next line is wrong.\nexport const a: string = 1;\n// @ts-expect-error\n// @ts-expect-error\nexport \
+         const b: string = 2;",
+    );
+
+    let errors = check_with(fs, &["/project/index.ts"], |_| {});
+
+    // Only the first of the two directives for `b` is unused.
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&**errors[0], ErrorKind::UnusedTsExpectError { .. }));
+}
+
+#[test]
+fn formats_messages_by_exact_code() {
+    let fs = Arc::new(MemoryFileSystem::new());
+    fs.add_file("/project/index.ts", "export const m = new Map();");
+
+    let errors = check_with(fs, &["/project/index.ts"], |_| {});
+    let err = errors.iter().find(|err| err.code() == 2583).expect("should report TS2583");
+
+    // TS2583 is normalized to TS2304, but it has its own message.
+    let mut messages = MessageCatalog::default();
+    messages.set(2304, "'{0}' is not found.");
+    assert_eq!(
+        messages.format(err).as_deref(),
+        Some(
+            "Cannot find name 'Map'. Do you need to change your target library? Try changing the 'lib' compiler option to 'es2015' or \
+             later."
+        )
+    );
+
+    messages.set(2583, "'{0}' is declared by '{1}'.");
+    assert_eq!(messages.format(err).as_deref(), Some("'Map' is declared by 'es2015'."));
+
+    // Codes without their own message fall back to the normalized one.
+    assert_eq!(messages.get(2580), Some("'{0}' is not found."));
+}
+
+#[test]
+fn formats_assignability_and_call_messages() {
+    let fs = Arc::new(MemoryFileSystem::new());
+    fs.add_file(
+        "/project/index.ts",
+        "declare function f(a: number): void;\nexport const a: number = '';\nf('');\nf(1, 2);",
+    );
+
+    let errors = check_with(fs, &["/project/index.ts"], |_| {});
+    let messages = MessageCatalog::default();
+    let message = |code: usize| {
+        let err = errors
+            .iter()
+            .find(|err| err.code() == code)
+            .unwrap_or_else(|| panic!("should report TS{}", code));
+        messages.format(err)
+    };
+
+    assert_eq!(message(2322).as_deref(), Some("Type is not assignable to the target type."));
+    assert_eq!(message(2345).as_deref(), Some("Argument is not assignable to the parameter."));
+    assert_eq!(message(2554).as_deref(), Some("Expected 1 arguments."));
+}
+
+#[test]
+fn loads_nearest_typings_once() {
+    let fs = Arc::new(MemoryFileSystem::new());
+    fs.add_file(
+        "/node_modules/@types/foo/index.d.ts",
+        "export {};\ndeclare global { var fooFromRoot: number; }",
+    );
+    fs.add_file(
+        "/node_modules/@types/bar/index.d.ts",
+        "export {};\ndeclare global { var bar: number; }",
+    );
+    fs.add_file(
+        "/project/node_modules/@types/foo/index.d.ts",
+        "export {};\ndeclare global { var foo: number; }",
+    );
+    fs.add_file("/project/a.ts", "export const a: number = foo + bar;");
+    fs.add_file("/project/b.ts", "export const b: number = fooFromRoot;");
+
+    let mut not_found = vec![];
+    let errors = check_with(fs, &["/project/a.ts", "/project/b.ts"], |checker| {
+        not_found = checker.load_typings(Path::new("/project"), None, None);
+    });
+
+    assert_eq!(not_found, Vec::<String>::new());
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&**errors[0], ErrorKind::NoSuchVar { .. }));
+}
+
+#[test]
+fn loads_types_from_type_roots() {
+    let fs = Arc::new(MemoryFileSystem::new());
+    fs.add_file("/project/typings/foo/index.d.ts", "export {};\ndeclare global { var foo: number; }");
+    fs.add_file("/project/a.ts", "export const a: number = foo;");
+
+    let roots = [PathBuf::from("/project/typings")];
+    let types = ["foo".to_string(), "missing".to_string()];
+    let mut not_found = vec![];
+    let errors = check_with(fs, &["/project/a.ts"], |checker| {
+        not_found = checker.load_typings(Path::new("/project"), Some(&roots), Some(&types));
+    });
+
+    assert_eq!(not_found, vec!["missing".to_string()]);
+    assert_eq!(errors.len(), 0);
+}
+
+#[test]
+fn resolves_types_references_using_type_roots() {
+    let check_types = |type_roots: Option<Vec<PathBuf>>| {
+        let fs = Arc::new(MemoryFileSystem::new());
+        fs.add_file(
+            "/project/node_modules/node/index.d.ts",
+            "export {};\ndeclare global { var fromPackage: number; }",
+        );
+        fs.add_file(
+            "/project/node_modules/@types/node/index.d.ts",
+            "export {};\ndeclare global { var fromTypes: number; }",
+        );
+        fs.add_file(
+            "/project/typings/node/index.d.ts",
+            "export {};\ndeclare global { var fromRoot: number; }",
+        );
+        fs.add_file(
+            "/project/index.ts",
+            "/// <reference types=\"node\" />\nexport const a: number = fromTypes;\nexport const b: number = fromRoot;",
+        );
+
+        check_with(fs, &["/project/index.ts"], |checker| {
+            if let Some(type_roots) = type_roots {
+                checker.set_type_roots(type_roots);
+            }
+        })
+    };
+
+    // `node_modules/@types/node` is preferred over `node_modules/node`.
+    let errors = check_types(None);
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&**errors[0], ErrorKind::NoSuchVar { .. }));
+
+    let errors = check_types(Some(vec![PathBuf::from("/project/typings")]));
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&**errors[0], ErrorKind::NoSuchVar { .. }));
+
+    let errors = check_types(Some(vec![PathBuf::from("/project/missing")]));
+    assert_eq!(errors.len(), 2);
+}
+
+#[test]
+fn skips_checking_declaration_files() {
+    let count = |rule: Rule| {
+        let fs = Arc::new(MemoryFileSystem::new());
+        fs.add_file(
+            "/project/lib.d.ts",
+            "/// <reference no-default-lib=\"true\"/>\ndeclare var fromLib: MissingInLib;",
+        );
+        fs.add_file(
+            "/project/node_modules/foo/index.d.ts",
+            "export declare const foo: MissingInExternal;",
+        );
+        fs.add_file("/project/types.d.ts", "export declare const bar: MissingInProject;");
+        fs.add_file(
+            "/project/a.ts",
+            "import { foo } from './node_modules/foo/index';\nimport { bar } from './types';\nexport const a = [foo, bar];",
+        );
+
+        check_with_rule(fs, &["/project/lib.d.ts", "/project/a.ts"], rule, |_| {}).len()
+    };
+
+    let all = count(Default::default());
+    let skip_default_lib = count(Rule {
+        skip_default_lib_check: true,
+        ..Default::default()
+    });
+    let skip_lib = count(Rule {
+        skip_lib_check: true,
+        ..Default::default()
+    });
+
+    assert!(all > skip_default_lib);
+    assert!(skip_default_lib > skip_lib);
+    assert_eq!(skip_lib, 0);
+}
+
+#[test]
+fn merges_global_augmentations_of_all_modules() {
+    let fs = Arc::new(MemoryFileSystem::new());
+    fs.add_file(
+        "/project/index.ts",
+        "declare const c: Config;\nexport const a: number = c.a;\nexport const b: string = c.b;\nexport const d: boolean = configured;",
+    );
+    fs.add_file("/project/a.ts", "export {};\ndeclare global { interface Config { a: number; } }");
+    fs.add_file(
+        "/project/b.ts",
+        "export {};\ndeclare global { interface Config { b: string; }\nvar configured: boolean; }",
+    );
+
+    assert_eq!(check(fs, &["/project/index.ts", "/project/a.ts", "/project/b.ts"]), 0);
+}
+
+#[test]
+fn checks_matched_files_against_other_libs() {
+    let count = |override_worker: bool| {
+        let fs = Arc::new(MemoryFileSystem::new());
+        fs.add_file("/project/index.ts", "import './worker/index';\nexport const a: number = 1;");
+        fs.add_file(
+            "/project/worker/index.ts",
+            "export const scope: WorkerGlobalScope = self;\npostMessage(scope, []);",
+        );
+
+        check_with(fs, &["/project/index.ts"], |checker| {
+            if override_worker {
+                let base = Env::simple(Default::default(), EsVersion::Es5, ModuleConfig::None, &[Lib::Es5]);
+                let worker = Env::with_libs(&base, &[Lib::load("es5"), Lib::load("webworker")].concat());
+                checker.add_env_override(
+                    |path| matches!(path, FileName::Real(path) if path.starts_with("/project/worker")),
+                    worker,
+                );
+            }
+        })
+        .len()
+    };
+
+    assert_ne!(count(false), 0);
+    assert_eq!(count(true), 0);
+}
+
+#[test]
+fn uses_globals_registered_by_embedder() {
+    let keyword = |kind| {
+        Type::Keyword(KeywordType {
+            span: DUMMY_SP,
+            kind,
+            metadata: Default::default(),
+        })
+    };
+    let count = |register: bool| {
+        let fs = Arc::new(MemoryFileSystem::new());
+        fs.add_file(
+            "/project/index.ts",
+            "export const dev: boolean = __DEV__;\nexport const timeout: Seconds = 1;",
+        );
+
+        let mut env = Env::simple(Default::default(), EsVersion::Es5, ModuleConfig::None, &[Lib::Es5]);
+        if register {
+            env = env
+                .with_global_var("__DEV__", keyword(TsKeywordTypeKind::TsBooleanKeyword))
+                .with_global_type("Seconds", keyword(TsKeywordTypeKind::TsNumberKeyword));
+        }
+
+        check_with_env(fs, &["/project/index.ts"], env, |_| {}).len()
+    };
+
+    assert_ne!(count(false), 0);
+    assert_eq!(count(true), 0);
+}
+
+#[test]
+fn types_jsx_using_automatic_runtime() {
+    let check_runtime = |runtime: Option<&str>| {
+        let fs = Arc::new(MemoryFileSystem::new());
+        if let Some(runtime) = runtime {
+            fs.add_file("/project/node_modules/react/jsx-runtime.d.ts", runtime);
+        }
+        fs.add_file(
+            "/project/index.tsx",
+            "export const a = <div id=\"a\">text</div>;\nexport const b = <>text</>;",
+        );
+
+        let env = Env::simple(Default::default(), EsVersion::Es5, ModuleConfig::None, &[Lib::Es5]).with_jsx(JsxConfig {
+            mode: Some(JsxMode::ReactJsx),
+            ..Default::default()
+        });
+
+        check_with_env(fs, &["/project/index.tsx"], env, |_| {})
+    };
+    let runtime = |id: &str, exports: &str| {
+        format!(
+            "{}\nexport namespace JSX {{ interface Element {{}} interface IntrinsicElements {{ div: {{ id: {} }}; }} }}",
+            exports, id
+        )
+    };
+    let jsx = "export declare function jsx(type: any, props: any): JSX.Element;";
+    let exports = format!("{}\nexport declare const Fragment: any;", jsx);
+
+    assert_eq!(check_runtime(Some(&runtime("string", &exports))).len(), 0);
+
+    let errors = check_runtime(Some(&runtime("number", &exports)));
+    assert_eq!(errors.len(), 1);
+
+    let errors = check_runtime(Some(&runtime("string", jsx)));
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(&**errors[0], ErrorKind::ImportFailed { .. }));
+
+    let errors = check_runtime(None);
+    assert!(errors.iter().any(|err| matches!(&**err, ErrorKind::JsxRuntimeNotFound { .. })));
+}
+
+#[test]
+fn types_jsx_using_namespace_of_imported_factory() {
+    let check_factory = |module: &str, import: &str, factory: &str| {
+        let fs = Arc::new(MemoryFileSystem::new());
+        fs.add_file("/project/node_modules/lib/index.d.ts", module);
+        fs.add_file(
+            "/project/index.tsx",
+            &format!("{}\nexport const a = <div id=\"a\" />;\nexport const b = <div id={{1}} />;", import),
+        );
+
+        let env = Env::simple(Default::default(), EsVersion::Es5, ModuleConfig::None, &[Lib::Es5]).with_jsx(JsxConfig {
+            mode: Some(JsxMode::React),
+            factory: Some(factory.into()),
+            ..Default::default()
+        });
+
+        check_with_env(fs, &["/project/index.tsx"], env, |_| {})
+    };
+    let jsx = "namespace JSX { interface Element {} interface IntrinsicElements { div: { id: string }; } }";
+
+    // `h` of preact, which is merged with a namespace.
+    let errors = check_factory(
+        &format!(
+            "export declare function h(type: any, props: any): any;\nexport namespace h {{ {} }}",
+            jsx
+        ),
+        "import { h } from \"lib\";",
+        "h",
+    );
+    assert_eq!(errors.len(), 1);
+
+    let errors = check_factory(
+        &format!("export declare function createElement(type: any, props: any): any;\nexport {}", jsx),
+        "import * as React from \"lib\";",
+        "React.createElement",
+    );
+    assert_eq!(errors.len(), 1);
+}
@@ -0,0 +1,9 @@
+export interface Shape {
+    area(): number;
+}
+
+export const unit = 1;
+
+export default function make(): Shape {
+    return { area: () => unit };
+}
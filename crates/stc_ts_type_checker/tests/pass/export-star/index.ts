@@ -0,0 +1,5 @@
+import { base, extra, Shape, unit } from "./middle";
+
+const s: Shape = base.default();
+const n: number = unit + s.area();
+const e: string = extra;
@@ -0,0 +1,4 @@
+export * from "./base";
+export * as base from "./base";
+
+export const extra = "extra";
@@ -0,0 +1,7 @@
+import Model = require("./model");
+import * as ModelNs from "./model";
+
+const model: Model = new Model();
+const id: number = model.id;
+const version: number = Model.version;
+const other: Model = new ModelNs();
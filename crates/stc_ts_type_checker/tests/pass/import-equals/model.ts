@@ -0,0 +1,9 @@
+class Model {
+    id: number = 0;
+}
+
+namespace Model {
+    export const version = 1;
+}
+
+export = Model;
@@ -0,0 +1,8 @@
+/// <reference path="./index.ts" />
+
+interface Point {
+    x: number;
+    y: number;
+}
+
+declare const origin: Point;
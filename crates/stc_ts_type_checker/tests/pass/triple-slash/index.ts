@@ -0,0 +1,3 @@
+/// <reference path="./globals.ts" />
+
+const p: Point = { x: origin.x, y: 1 };
@@ -0,0 +1,6 @@
+import type { Model } from "./model";
+import { type Point, origin } from "./reexport";
+
+const p: Point = origin;
+let m: Model | undefined;
+let o: typeof origin = p;
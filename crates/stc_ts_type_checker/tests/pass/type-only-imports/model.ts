@@ -0,0 +1,9 @@
+export interface Point {
+    x: number;
+}
+
+export class Model {
+    point: Point = { x: 1 };
+}
+
+export const origin: Point = { x: 0 };
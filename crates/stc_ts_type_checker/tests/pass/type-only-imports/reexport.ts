@@ -0,0 +1,2 @@
+export type { Point } from "./model";
+export { type Model, origin } from "./model";
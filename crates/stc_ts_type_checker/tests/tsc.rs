@@ -26,7 +26,7 @@ use once_cell::sync::Lazy;
 use parking_lot::Mutex;
 use serde::Deserialize;
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_env::{Env, JsxConfig, ModuleConfig, Rule};
 use stc_ts_file_analyzer::env::EnvFactory;
 use stc_ts_module_loader::resolvers::node::NodeResolver;
 use stc_ts_type_checker::Checker;
@@ -300,6 +300,7 @@ struct TestSpec {
     ts_config: TsConfig,
     target: EsVersion,
     module_config: ModuleConfig,
+    jsx: JsxConfig,
 }
 
 fn parse_targets(s: &str) -> Vec<EsVersion> {
@@ -378,6 +379,7 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
             ..Default::default()
         };
         let mut module_config = ModuleConfig::None;
+        let mut jsx = JsxConfig::default();
         let ts_config = TsConfig::default();
 
         let mut had_comment = false;
@@ -418,6 +420,7 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
                     rule.always_strict = strict;
                     rule.strict_null_checks = strict;
                     rule.strict_function_types = strict;
+                    rule.strict_property_initialization = strict;
                 } else if s.starts_with("noLib:") {
                     let v = s["noLib:".len()..].trim().parse().unwrap();
                     if v {
@@ -426,6 +429,9 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
                 } else if s.to_lowercase().starts_with("noimplicitany:") {
                     let v = s["noImplicitAny:".len()..].trim().parse().unwrap();
                     rule.no_implicit_any = v;
+                } else if s.starts_with("noImplicitOverride:") {
+                    let v = s["noImplicitOverride:".len()..].trim().parse().unwrap();
+                    rule.no_implicit_override = v;
                 } else if s.starts_with("noImplicitReturns:") {
                     let v = s["noImplicitReturns:".len()..].trim().parse().unwrap();
                     rule.no_implicit_returns = v;
@@ -439,12 +445,23 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
                     rule.allow_unused_labels = v;
                 } else if s.starts_with("noEmitHelpers") {
                     // TODO
-                } else if s.starts_with("downlevelIteration:") {
-                    // TODO
+                } else if s.to_lowercase().starts_with("downleveliteration:") {
+                    let v = s["downlevelIteration:".len()..].trim().parse().unwrap();
+                    rule.downlevel_iteration = v;
                 } else if s.starts_with("sourceMap:") || s.starts_with("sourcemap:") {
                     // TODO
+                } else if s.to_lowercase().starts_with("checkjs:") {
+                    let v = s["checkJs:".len()..].trim().parse().unwrap();
+                    rule.check_js = v;
                 } else if s.starts_with("isolatedModules:") {
-                    // TODO
+                    let v = s["isolatedModules:".len()..].trim().parse().unwrap();
+                    rule.isolated_modules = v;
+                } else if s.to_lowercase().starts_with("esmoduleinterop:") {
+                    let v = s["esModuleInterop:".len()..].trim().parse::<bool>().unwrap();
+                    rule.allow_synthetic_default_imports |= v;
+                } else if s.to_lowercase().starts_with("allowsyntheticdefaultimports:") {
+                    let v = s["allowSyntheticDefaultImports:".len()..].trim().parse().unwrap();
+                    rule.allow_synthetic_default_imports = v;
                 } else if s.starts_with("lib:") {
                     let s = s["lib:".len()..].trim();
                     let mut ls = HashSet::<_>::default();
@@ -458,6 +475,12 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
                 } else if s.starts_with("strictNullChecks:") {
                     let v = s["strictNullChecks:".len()..].trim().parse().unwrap();
                     rule.strict_null_checks = v;
+                } else if s.starts_with("strictPropertyInitialization:") {
+                    let v = s["strictPropertyInitialization:".len()..].trim().parse().unwrap();
+                    rule.strict_property_initialization = v;
+                } else if s.starts_with("exactOptionalPropertyTypes:") {
+                    let v = s["exactOptionalPropertyTypes:".len()..].trim().parse().unwrap();
+                    rule.exact_optional_property_types = v;
                 } else if s.starts_with("noImplicitThis:") {
                     let v = s["noImplicitThis:".len()..].trim().parse().unwrap();
                     rule.no_implicit_this = v;
@@ -470,14 +493,35 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
                 } else if s.starts_with("module:") {
                     let v = s["module:".len()..].trim().parse().unwrap();
                     module_config = v;
+                } else if s.starts_with("moduleDetection:") {
+                    // TODO: Check all of the values, like `legacy,auto,force`.
+                    let v = s["moduleDetection:".len()..]
+                        .split(',')
+                        .next()
+                        .unwrap()
+                        .trim()
+                        .to_lowercase()
+                        .parse()
+                        .unwrap();
+                    rule.module_detection = v;
                 } else if s.to_lowercase().starts_with("notypesandsymbols") {
                     // Ignored as we don't generate them.
                 } else if s.to_lowercase().starts_with("usedefineforclassfields") {
                     rule.use_define_property_for_class_fields = true;
-                } else if s.to_lowercase().starts_with("noemit")
-                    || s.to_lowercase().starts_with("jsx")
-                    || s.to_lowercase().starts_with("preserveconstenums")
-                {
+                } else if s.to_lowercase().starts_with("experimentaldecorators:") {
+                    let v = s["experimentalDecorators:".len()..].trim().parse().unwrap();
+                    rule.experimental_decorators = v;
+                } else if s.to_lowercase().starts_with("jsx:") {
+                    // TODO: Check all of the values, like `react,react-jsx`.
+                    let v = s["jsx:".len()..].split(',').next().unwrap().trim().to_lowercase().parse().unwrap();
+                    jsx.mode = Some(v);
+                } else if s.to_lowercase().starts_with("jsxfactory:") {
+                    jsx.factory = Some(s["jsxFactory:".len()..].trim().into());
+                } else if s.to_lowercase().starts_with("jsxfragmentfactory:") {
+                    jsx.fragment_factory = Some(s["jsxFragmentFactory:".len()..].trim().into());
+                } else if s.to_lowercase().starts_with("jsximportsource:") {
+                    jsx.import_source = Some(s["jsxImportSource:".len()..].trim().into());
+                } else if s.to_lowercase().starts_with("noemit") || s.to_lowercase().starts_with("preserveconstenums") {
                     // Ignored as we only checks type.
                 } else if s.starts_with("strict") {
                     let strict = true;
@@ -486,6 +530,7 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
                     rule.always_strict = strict;
                     rule.strict_null_checks = strict;
                     rule.strict_function_types = strict;
+                    rule.strict_property_initialization = strict;
                 } else {
                     panic!("Comment is not handled: {}", s);
                 }
@@ -525,6 +570,7 @@ fn parse_test(file_name: &Path) -> Vec<TestSpec> {
                     ts_config,
                     target,
                     module_config,
+                    jsx: jsx.clone(),
                 }
             })
             .collect())
@@ -547,6 +593,7 @@ fn do_test(file_name: &Path) -> Result<(), StdErr> {
         ts_config,
         target,
         module_config,
+        jsx,
     } in specs
     {
         let mut time_of_check = Duration::new(0, 0);
@@ -583,13 +630,13 @@ fn do_test(file_name: &Path) -> Result<(), StdErr> {
                 let mut checker = Checker::new(
                     cm,
                     handler.clone(),
-                    Env::simple(rule, target, module_config, &libs),
+                    Env::simple(rule, target, module_config, &libs).with_jsx(jsx.clone()),
                     TsConfig {
                         tsx: fname.contains("tsx"),
                         ..ts_config
                     },
                     None,
-                    Arc::new(NodeResolver),
+                    Arc::new(NodeResolver::new()),
                 );
 
                 // Install a logger
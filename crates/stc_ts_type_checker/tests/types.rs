@@ -220,8 +220,9 @@ fn do_test(path: &Path) -> Result<(), StdErr> {
                 Some(Debugger {
                     cm,
                     handler: type_info_handler,
+                    records: None,
                 }),
-                Arc::new(NodeResolver),
+                Arc::new(NodeResolver::new()),
             );
 
             checker.check(Arc::new(FileName::Real(path.into())));
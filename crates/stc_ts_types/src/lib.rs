@@ -809,6 +809,9 @@ pub struct ClassProperty {
     pub definite: bool,
 
     pub accessor: Accessor,
+    /// The parameter type of the setter, if it's different from the type of
+    /// the getter.
+    pub write_ty: Option<Box<Type>>,
 }
 
 #[derive(Debug, Clone, PartialEq, Spanned, EqIgnoreSpan, TypeEq, Visit, Serialize, Deserialize)]
@@ -2116,6 +2119,20 @@ impl Type {
         )
     }
 
+    /// Returns true if `self` is a `bigint` or a bigint literal.
+    pub fn is_bigint(&self) -> bool {
+        matches!(
+            self.normalize(),
+            Type::Keyword(KeywordType {
+                kind: TsKeywordTypeKind::TsBigIntKeyword,
+                ..
+            }) | Type::Lit(LitType {
+                lit: RTsLit::BigInt(..),
+                ..
+            })
+        )
+    }
+
     pub fn is_num_lit(&self) -> bool {
         matches!(
             self.normalize(),
@@ -2511,6 +2528,13 @@ pub struct Freezed {
     ty: Arc<Type>,
 }
 
+impl Freezed {
+    /// Returns the address of the shared type, which identifies it.
+    pub fn as_ptr(&self) -> *const Type {
+        Arc::as_ptr(&self.ty)
+    }
+}
+
 impl Spanned for Freezed {
     fn span(&self) -> Span {
         self.ty.span()
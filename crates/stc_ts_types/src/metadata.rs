@@ -231,6 +231,10 @@ impl_traits!(RefMetadata);
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ConditionalMetadata {
     pub common: CommonTypeMetadata,
+
+    /// `true` if the check type is a naked type parameter.
+    #[serde(default)]
+    pub distributive: bool,
 }
 
 impl_traits!(ConditionalMetadata);
@@ -4,23 +4,52 @@ use swc_common::{
     BytePos, Span,
 };
 
+/// Prefix of the specifiers of `/// <reference types="..." />`.
+pub const TYPES_PREFIX: &str = "@types/";
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ImportRef {
     /// path="foo"
     Path(JsWord),
     /// type="foo"
     Types(JsWord),
+    /// lib="foo"
+    Lib(JsWord),
 
     /// ES6 import.
     Normal(JsWord),
 }
 
 impl ImportRef {
-    pub fn to_path(self) -> JsWord {
+    /// Returns the module specifier to load, or [None] for `lib` references
+    /// which do not refer to a module.
+    ///
+    /// `types` references to packages are converted to `@types/name`, which
+    /// is resolved using the type roots.
+    pub fn to_path(self) -> Option<JsWord> {
         match self {
-            ImportRef::Path(s) => format!("./{}", s).into(),
-            ImportRef::Types(s) => s,
-            ImportRef::Normal(s) => s,
+            ImportRef::Path(s) => {
+                if s.starts_with('.') || s.starts_with('/') {
+                    Some(s)
+                } else {
+                    Some(format!("./{}", s).into())
+                }
+            }
+            ImportRef::Types(s) => {
+                if s.starts_with('.') || s.starts_with('/') {
+                    Some(s)
+                } else {
+                    // `@babel/core` is `@types/babel__core`.
+                    let name = s
+                        .strip_prefix('@')
+                        .map(|s| s.replacen('/', "__", 1))
+                        .unwrap_or_else(|| s.to_string());
+
+                    Some(format!("{}{}", TYPES_PREFIX, name).into())
+                }
+            }
+            ImportRef::Lib(..) => None,
+            ImportRef::Normal(s) => Some(s),
         }
     }
 }
@@ -49,15 +78,15 @@ where
                 .strip_prefix('/')
                 .map(|s| s.trim())
                 .and_then(|s| s.strip_prefix("<reference"))
-                .and_then(|s| s.strip_suffix("\" />"))
+                .and_then(|s| s.strip_suffix("/>"))
                 .map(|s| s.trim())
             {
-                if let Some(path) = cmt_text.strip_prefix("path=\"") {
+                if let Some(path) = attr_value(cmt_text, "path") {
                     deps.push(ImportRef::Path(path.into()));
-                } else if let Some(path) = cmt_text.strip_prefix("types=\"") {
+                } else if let Some(path) = attr_value(cmt_text, "types") {
                     deps.push(ImportRef::Types(path.into()));
-                } else {
-                    // TODO: Handle lib, types
+                } else if let Some(lib) = attr_value(cmt_text, "lib") {
+                    deps.push(ImportRef::Lib(lib.to_ascii_lowercase().into()));
                 }
             }
         }
@@ -65,3 +94,12 @@ where
 
     deps
 }
+
+/// Extracts `value` from `name="value"` or `name='value'`.
+fn attr_value<'a>(s: &'a str, name: &str) -> Option<&'a str> {
+    let s = s.strip_prefix(name)?.trim_start().strip_prefix('=')?.trim_start();
+    let quote = s.chars().next().filter(|&c| c == '"' || c == '\'')?;
+    let s = &s[1..];
+
+    s.find(quote).map(|end| &s[..end])
+}
@@ -0,0 +1,98 @@
+//! Spelling suggestions, like `Did you mean 'foo'?` of tsc.
+
+/// Returns the candidate which is most similar to `name`, if it's similar
+/// enough.
+///
+/// This follows `getSpellingSuggestion` of tsc. A candidate which differs only
+/// in case is preferred, and otherwise the edit distance is used.
+pub fn spelling_suggestion<'a, I>(name: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let name_len = name.chars().count();
+    let max_len_diff = (name_len as f64 * 0.34).floor().max(2.0) as usize;
+    // Candidates with a distance greater than or equal to this are ignored.
+    let mut best_distance = (name_len as f64 * 0.4).floor() + 1.0;
+    let mut best = None;
+
+    let name_lower = name.to_lowercase();
+
+    for candidate in candidates {
+        let len = candidate.chars().count();
+        if candidate == name || len.abs_diff(name_len) > max_len_diff {
+            continue;
+        }
+
+        let candidate_lower = candidate.to_lowercase();
+        if candidate_lower == name_lower {
+            return Some(candidate);
+        }
+        // Short names are too likely to be similar by accident.
+        if len < 3 {
+            continue;
+        }
+
+        if let Some(distance) = levenshtein_with_max(name, candidate, best_distance - 0.1) {
+            best_distance = distance;
+            best = Some(candidate);
+        }
+    }
+
+    best
+}
+
+/// Edit distance where changing only the case of a character costs `0.1`.
+///
+/// Returns [None] if the distance is greater than `max`.
+fn levenshtein_with_max(s1: &str, s2: &str, max: f64) -> Option<f64> {
+    let s1 = s1.chars().collect::<Vec<_>>();
+    let s2 = s2.chars().collect::<Vec<_>>();
+
+    let mut prev = (0..=s2.len()).map(|i| i as f64).collect::<Vec<_>>();
+    let mut cur = vec![0.0; s2.len() + 1];
+
+    for (i, c1) in s1.iter().enumerate() {
+        cur[0] = (i + 1) as f64;
+        let mut row_min = cur[0];
+
+        for (j, c2) in s2.iter().enumerate() {
+            let substitution = if c1 == c2 {
+                0.0
+            } else if c1.to_lowercase().eq(c2.to_lowercase()) {
+                0.1
+            } else {
+                2.0
+            };
+
+            cur[j + 1] = (prev[j] + substitution).min(prev[j + 1] + 1.0).min(cur[j] + 1.0);
+            row_min = row_min.min(cur[j + 1]);
+        }
+
+        if row_min > max {
+            return None;
+        }
+
+        std::mem::swap(&mut prev, &mut cur);
+    }
+
+    let distance = prev[s2.len()];
+    if distance > max {
+        None
+    } else {
+        Some(distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::spelling_suggestion;
+
+    #[test]
+    fn suggestions() {
+        assert_eq!(spelling_suggestion("consol", ["console", "Math", "JSON"]), Some("console"));
+        assert_eq!(spelling_suggestion("math", ["console", "Math"]), Some("Math"));
+        assert_eq!(spelling_suggestion("lenght", ["length", "push", "pop"]), Some("length"));
+        assert_eq!(spelling_suggestion("foo", ["bar", "baz"]), None);
+        assert_eq!(spelling_suggestion("x", ["y"]), None);
+    }
+}
@@ -1,12 +1,27 @@
+use std::path::PathBuf;
+
 use clap::Args;
+use stc_ts_errors::debug::debugger::TypeDumpFormat;
 
 /// Perform type checking, but this command is not public api and is only used
 /// for testing.
 #[derive(Debug, Args)]
 #[clap(rename_all = "camel-case")]
 pub struct TestCommand {
-    #[clap(name = "file")]
-    pub file: String,
+    /// The file to check. If omitted, the files of `--project` are checked.
+    #[clap(name = "file", required_unless_present = "project")]
+    pub file: Option<String>,
+
+    /// Path to a `tsconfig.json`, or a directory containing it. Compiler
+    /// options are read from it, and are overridden by the other options.
+    #[clap(short = 'p', long = "project")]
+    pub project: Option<PathBuf>,
+
+    /// Check the projects referenced by `--project` before it, in dependency
+    /// order. Projects whose inputs are not modified since the last
+    /// successful check are skipped.
+    #[clap(short = 'b', long = "build", requires = "project", conflicts_with = "file")]
+    pub build: bool,
 
     /// The builtin libraries to load. Defaults to `es5`.
     #[clap(long)]
@@ -15,4 +30,45 @@ pub struct TestCommand {
     /// Directory name of typings to load.
     #[clap(long)]
     pub types: Option<Vec<String>>,
+
+    /// Print the slowest modules and the most expensive operations after
+    /// checking. The value is the number of entries to print for each
+    /// category.
+    #[clap(long = "diagnose-slow", num_args = 0..=1, default_missing_value = "10")]
+    pub diagnose_slow: Option<usize>,
+
+    /// Overrides the severity of errors, like `TS2322=warning` or
+    /// `property-not-found=off`. Severities are `error`, `warning` and `off`.
+    #[clap(long = "severity")]
+    pub severity: Vec<String>,
+
+    /// Stop reporting errors after this many errors. Errors in `--baseline`
+    /// are not counted.
+    #[clap(long = "max-errors")]
+    pub max_errors: Option<usize>,
+
+    /// Stop checking once `--max-errors` is reached.
+    #[clap(long = "fail-fast", requires = "max_errors")]
+    pub fail_fast: bool,
+
+    /// Report only errors which are not recorded in this file.
+    #[clap(long = "baseline")]
+    pub baseline: Option<PathBuf>,
+
+    /// Record all errors into the file given by `--baseline` instead of
+    /// reporting them.
+    #[clap(long = "update-baseline", requires = "baseline")]
+    pub update_baseline: bool,
+
+    /// Print the inferred types of expressions and declarations in the file,
+    /// as `text` or `json`.
+    #[clap(long = "dump-types", num_args = 0..=1, default_missing_value = "text")]
+    pub dump_types: Option<TypeDumpFormat>,
+
+    /// Write the spans of modules, analysis phases and statements to this
+    /// file, which can be opened with `chrome://tracing`. Spans of
+    /// expressions are only included in debug builds, and release builds
+    /// require disabling the `release_max_level_off` feature.
+    #[clap(long = "trace-chrome")]
+    pub trace_chrome: Option<PathBuf>,
 }
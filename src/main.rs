@@ -5,10 +5,16 @@ use std::{path::PathBuf, sync::Arc, time::Instant};
 use anyhow::Error;
 use clap::Parser;
 use stc_ts_builtin_types::Lib;
-use stc_ts_env::{Env, ModuleConfig, Rule};
+use stc_ts_config::{BuildInfo, ProjectConfig, ProjectGraph};
+use stc_ts_env::{perf::PerfRecorder, Env, ModuleConfig, Rule};
+use stc_ts_errors::{
+    debug::debugger::{format_type_dump, Debugger},
+    MessageCatalog,
+};
 use stc_ts_file_analyzer::env::EnvFactory;
 use stc_ts_lang_server::LspCommand;
-use stc_ts_module_loader::resolvers::node::NodeResolver;
+use stc_ts_module_loader::resolvers::{node::NodeResolver, paths::PathMappings};
+use stc_ts_storage::{Baseline, DiagnosticFilter, SeverityOverrides};
 use stc_ts_type_checker::Checker;
 use swc_common::{
     errors::{ColorConfig, EmitterWriter, Handler},
@@ -16,11 +22,13 @@ use swc_common::{
 };
 use swc_ecma_ast::EsVersion;
 use swc_ecma_parser::TsConfig;
-use tracing_subscriber::EnvFilter;
+use tracing::level_filters::{LevelFilter, STATIC_MAX_LEVEL};
+use tracing_subscriber::{fmt::format::FmtSpan, layer::SubscriberExt, EnvFilter, Registry};
 
-use crate::check::TestCommand;
+use crate::{check::TestCommand, trace::ChromeTraceLayer};
 
 mod check;
+mod trace;
 
 #[derive(Debug, Parser)]
 #[command(name = "stc", about = "Super fast type checker for typescript", author, rename_all = "camel")]
@@ -35,18 +43,38 @@ async fn main() -> Result<(), Error> {
 
     env_logger::init();
 
-    let sub = tracing_subscriber::FmtSubscriber::builder()
-        .with_target(false)
-        .with_ansi(true)
-        .without_time()
-        .with_env_filter(EnvFilter::new("STC_LOG"))
-        .pretty()
-        .finish();
-
-    tracing::subscriber::set_global_default(sub).unwrap();
-
     let command = Command::parse();
 
+    let trace_chrome = match &command {
+        Command::Test(cmd) => cmd.trace_chrome.as_deref(),
+        _ => None,
+    };
+    let trace_guard = match trace_chrome {
+        Some(_) if STATIC_MAX_LEVEL == LevelFilter::OFF => {
+            return Err(Error::msg(
+                "tracing spans are compiled out; build with `--no-default-features` to use `--trace-chrome`",
+            ));
+        }
+        Some(path) => {
+            let (layer, guard) = ChromeTraceLayer::new(path)?;
+            let sub = Registry::default().with(EnvFilter::new("error")).with(layer);
+            tracing::subscriber::set_global_default(sub).unwrap();
+            Some(guard)
+        }
+        None => {
+            let sub = tracing_subscriber::FmtSubscriber::builder()
+                .with_target(false)
+                .with_ansi(true)
+                .without_time()
+                .with_span_events(FmtSpan::CLOSE)
+                .with_env_filter(EnvFilter::new("STC_LOG"))
+                .pretty()
+                .finish();
+            tracing::subscriber::set_global_default(sub).unwrap();
+            None
+        }
+    };
+
     let cm = Arc::new(SourceMap::default());
     let handler = {
         let emitter = Box::new(EmitterWriter::stderr(ColorConfig::Always, Some(cm.clone()), false, false));
@@ -63,77 +91,134 @@ async fn main() -> Result<(), Error> {
 
     match command {
         Command::Test(cmd) => {
-            let libs = {
-                let start = Instant::now();
-
-                let mut libs = match cmd.libs {
-                    Some(libs) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
-                    None => Lib::load("es5"),
-                };
-                libs.sort();
-                libs.dedup();
-
-                let end = Instant::now();
+            let perf = cmd.diagnose_slow.map(|top_n| Arc::new(PerfRecorder::new(top_n)));
+            let debugger = cmd.dump_types.map(|_| Debugger::recording(cm.clone(), handler.clone()));
 
-                log::info!("Loading builtin libraries took {:?}", end - start);
+            let mut entries = vec![];
+            let mut errors = vec![];
 
-                libs
+            let root = std::env::current_dir()?;
+            let baseline = match &cmd.baseline {
+                Some(path) if !cmd.update_baseline && path.exists() => {
+                    Some(Baseline::parse(root.clone(), &std::fs::read_to_string(path)?).map_err(Error::msg)?)
+                }
+                _ => None,
             };
 
-            let env = Env::simple(Rule { ..Default::default() }, EsVersion::latest(), ModuleConfig::None, &libs);
-
-            let path = PathBuf::from(cmd.file);
-
-            {
-                let start = Instant::now();
-
-                let checker = Checker::new(
-                    cm.clone(),
-                    handler.clone(),
-                    env.clone(),
-                    TsConfig { ..Default::default() },
-                    None,
-                    Arc::new(NodeResolver),
-                );
+            let start = Instant::now();
+            if cmd.build {
+                let graph = ProjectGraph::load(cmd.project.as_deref().expect("clap requires `--project`"))?;
+                // Whether each project has errors.
+                let mut failed = vec![false; graph.projects().len()];
+
+                for (idx, project) in graph.projects().iter().enumerate() {
+                    if graph.deps_of(idx).iter().any(|&dep| failed[dep]) {
+                        eprintln!("Skipping `{}` because its dependency has errors", project.path.display());
+                        failed[idx] = true;
+                        continue;
+                    }
+
+                    let build_info_path = project.build_info_path();
+                    let build_info = BuildInfo::new(&graph.inputs_of(idx)?, 0)?;
+                    if BuildInfo::read(&build_info_path).map_or(false, |prev| prev.is_up_to_date(&build_info)) {
+                        eprintln!("Project `{}` is up to date", project.path.display());
+                        continue;
+                    }
+
+                    let project_entries = project.root_files()?;
+                    let project_errors = check_files(
+                        &cmd,
+                        &cm,
+                        &handler,
+                        Some(project),
+                        &project_entries,
+                        baseline.as_ref(),
+                        debugger.as_ref(),
+                        perf.as_ref(),
+                    )?;
+
+                    let error_count = project_errors.iter().filter(|err| !err.is_warning()).count();
+                    failed[idx] = error_count != 0;
+
+                    let options = &project.compiler_options;
+                    if options.composite == Some(true) || options.incremental == Some(true) {
+                        BuildInfo {
+                            errors: error_count,
+                            ..build_info
+                        }
+                        .write(&build_info_path)?;
+                    }
+
+                    entries.extend(project_entries);
+                    errors.extend(project_errors);
+                }
+            } else {
+                let project = cmd.project.as_deref().map(ProjectConfig::load).transpose()?;
 
-                checker.load_typings(&path, None, cmd.types.as_deref());
+                entries = match (&cmd.file, &project) {
+                    (Some(file), _) => vec![PathBuf::from(file)],
+                    (None, Some(project)) => project.root_files()?,
+                    (None, None) => unreachable!("clap requires either `file` or `--project`"),
+                };
+                errors = check_files(
+                    &cmd,
+                    &cm,
+                    &handler,
+                    project.as_ref(),
+                    &entries,
+                    baseline.as_ref(),
+                    debugger.as_ref(),
+                    perf.as_ref(),
+                )?;
+            }
+            let end = Instant::now();
 
-                let end = Instant::now();
+            log::info!("Checking took {:?}", end - start);
 
-                log::info!("Loading typing libraries took {:?}", end - start);
+            if let (Some(debugger), Some(format)) = (&debugger, cmd.dump_types) {
+                let files = entries
+                    .iter()
+                    .filter_map(|path| cm.get_source_file(&FileName::Real(path.clone())))
+                    .collect::<Vec<_>>();
+                let records = debugger
+                    .take_records()
+                    .into_iter()
+                    .filter(|r| files.iter().any(|file| file.start_pos <= r.span.lo && r.span.lo < file.end_pos))
+                    .collect::<Vec<_>>();
+                print!("{}", format_type_dump(&cm, &records, format));
             }
 
-            let mut errors = vec![];
-
-            let start = Instant::now();
-            {
-                let mut checker = Checker::new(
-                    cm.clone(),
-                    handler.clone(),
-                    env,
-                    TsConfig { ..Default::default() },
-                    None,
-                    Arc::new(NodeResolver),
-                );
-
-                checker.check(Arc::new(FileName::Real(path)));
-
-                errors.extend(checker.take_errors());
+            if cmd.update_baseline {
+                let baseline_path = cmd.baseline.as_ref().expect("clap requires `--baseline`");
+                let baseline = Baseline::record(root, &cm, &errors);
+                std::fs::write(baseline_path, baseline.to_string())?;
+                eprintln!("Recorded {} errors to {}", baseline.len(), baseline_path.display());
+                return Ok(());
             }
-            let end = Instant::now();
 
-            log::info!("Checking took {:?}", end - start);
+            // Warnings do not fail the check.
+            let has_errors = errors.iter().any(|err| !err.is_warning());
 
             {
                 let start = Instant::now();
+                let messages = MessageCatalog::default();
                 for err in errors {
-                    err.emit(&handler);
+                    err.emit_with_catalog(&handler, &messages);
                 }
 
                 let end = Instant::now();
 
                 log::info!("Error reporting took {:?}", end - start);
             }
+
+            if let Some(perf) = &perf {
+                eprintln!("{}", perf.report(&cm));
+            }
+
+            if has_errors {
+                drop(trace_guard);
+                std::process::exit(1);
+            }
         }
         Command::Lsp(cmd) => {
             cmd.run().await?;
@@ -146,3 +231,136 @@ async fn main() -> Result<(), Error> {
 
     Ok(())
 }
+
+/// Checks `entries` using the options of `project`, which are overridden by
+/// the options of `cmd`.
+fn check_files(
+    cmd: &TestCommand,
+    cm: &Arc<SourceMap>,
+    handler: &Arc<Handler>,
+    project: Option<&ProjectConfig>,
+    entries: &[PathBuf],
+    baseline: Option<&Baseline>,
+    debugger: Option<&Debugger>,
+    perf: Option<&Arc<PerfRecorder>>,
+) -> Result<Vec<stc_ts_errors::Error>, Error> {
+    let libs = {
+        let start = Instant::now();
+
+        let mut libs = match (&cmd.libs, project) {
+            (Some(libs), _) => libs.iter().flat_map(|s| Lib::load(s)).collect::<Vec<_>>(),
+            (None, Some(project)) => project.compiler_options.libs()?,
+            (None, None) => Lib::load("es5"),
+        };
+        libs.sort();
+        libs.dedup();
+
+        let end = Instant::now();
+
+        log::info!("Loading builtin libraries took {:?}", end - start);
+
+        libs
+    };
+
+    let lib_dir = match (&cmd.libs, project) {
+        (None, Some(project)) => project.load_lib_dir()?,
+        _ => None,
+    };
+    let mut env = match project {
+        Some(project) => {
+            let options = &project.compiler_options;
+            let env = match &lib_dir {
+                Some(lib_dir) => Env::with_provider(options.rule(), options.target()?, options.module()?, lib_dir)?,
+                None => Env::simple(options.rule(), options.target()?, options.module()?, &libs),
+            };
+            env.with_jsx(options.jsx()?)
+        }
+        None => Env::simple(Rule { ..Default::default() }, EsVersion::latest(), ModuleConfig::None, &libs),
+    };
+    if let Some(perf) = perf {
+        env = env.with_perf(perf.clone());
+    }
+
+    let mut overrides = SeverityOverrides::default();
+    for entry in &cmd.severity {
+        overrides.add_entry(entry).map_err(Error::msg)?;
+    }
+
+    let base = match (&cmd.file, project) {
+        (None, Some(project)) => project.dir().to_path_buf(),
+        _ => entries[0].clone(),
+    };
+    let types = cmd.types.clone().or_else(|| project.and_then(|p| p.compiler_options.types.clone()));
+
+    {
+        let start = Instant::now();
+
+        let checker = Checker::new(
+            cm.clone(),
+            handler.clone(),
+            env.clone(),
+            TsConfig { ..Default::default() },
+            None,
+            Arc::new(NodeResolver::new()),
+        );
+
+        let type_roots = project.and_then(|p| p.compiler_options.type_roots.clone());
+        for name in checker.load_typings(&base, type_roots.as_deref(), types.as_deref()) {
+            eprintln!("warning: cannot find type definition file for `{}`", name);
+        }
+
+        let end = Instant::now();
+
+        log::info!("Loading typing libraries took {:?}", end - start);
+    }
+
+    let mut checker = Checker::new(
+        cm.clone(),
+        handler.clone(),
+        env.clone(),
+        TsConfig { ..Default::default() },
+        debugger.cloned(),
+        Arc::new(NodeResolver::new()),
+    );
+
+    if !overrides.is_empty() {
+        let mut filter = DiagnosticFilter::new(cm.clone());
+        filter.set_severity_overrides(overrides);
+        checker.set_diagnostic_filter(filter);
+    }
+    // Errors in the baseline don't count toward the limit.
+    if let Some(baseline) = baseline {
+        checker.set_baseline(baseline.clone());
+    }
+    // All errors are recorded while updating the baseline.
+    if let Some(max_errors) = cmd.max_errors.filter(|_| !cmd.update_baseline) {
+        checker.set_error_limit(max_errors, cmd.fail_fast);
+    }
+    if let Some(project) = project {
+        let options = &project.compiler_options;
+        let entries = options.paths.clone().unwrap_or_default().into_iter().collect();
+        let mut paths = PathMappings::new(project.paths_base().unwrap_or(project.dir()).to_path_buf(), entries);
+        if let Some(base_url) = &options.base_url {
+            paths = paths.with_base_url(base_url.clone());
+        }
+        checker.set_path_mappings(paths);
+
+        for lib_override in &project.lib_overrides {
+            let override_env = Env::with_libs(&env, &lib_override.libs());
+            let lib_override = lib_override.clone();
+            checker.add_env_override(
+                move |path| matches!(path, FileName::Real(path) if lib_override.matches(path)),
+                override_env,
+            );
+        }
+    }
+
+    checker.check_all(
+        &entries
+            .iter()
+            .map(|path| Arc::new(FileName::Real(path.clone())))
+            .collect::<Vec<_>>(),
+    );
+
+    Ok(checker.take_errors())
+}
@@ -0,0 +1,161 @@
+//! Writes tracing spans as a trace of `chrome://tracing`.
+//!
+//! Only spans are written. Events like `debug!` are still printed by the
+//! logger selected by `STC_LOG`, as they are not converted to spans.
+
+use std::{
+    fmt::{self, Write as _},
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::Instant,
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id},
+    Subscriber,
+};
+use tracing_subscriber::{layer::Context, registry::LookupSpan, Layer};
+
+type Output = Arc<Mutex<TraceFile>>;
+
+struct TraceFile {
+    w: BufWriter<File>,
+    has_events: bool,
+}
+
+/// Emits a complete event each time a span is exited, with the fields of the
+/// span as the arguments.
+pub struct ChromeTraceLayer {
+    out: Output,
+    start: Instant,
+}
+
+/// Finishes the trace file on drop.
+pub struct FlushGuard {
+    out: Output,
+}
+
+/// Fields of a span, formatted as JSON object members.
+struct SpanArgs(String);
+
+/// Time when the span is entered.
+struct EnteredAt(Instant);
+
+impl ChromeTraceLayer {
+    pub fn new(path: &Path) -> io::Result<(Self, FlushGuard)> {
+        let mut w = BufWriter::new(File::create(path)?);
+        w.write_all(b"[")?;
+
+        let out = Arc::new(Mutex::new(TraceFile { w, has_events: false }));
+        Ok((
+            ChromeTraceLayer {
+                out: out.clone(),
+                start: Instant::now(),
+            },
+            FlushGuard { out },
+        ))
+    }
+}
+
+impl Drop for FlushGuard {
+    fn drop(&mut self) {
+        let mut out = self.out.lock().unwrap();
+        let _ = out.w.write_all(b"\n]\n");
+        let _ = out.w.flush();
+    }
+}
+
+impl<S> Layer<S> for ChromeTraceLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut args = SpanArgs(String::new());
+        attrs.record(&mut args);
+
+        if let Some(span) = ctx.span(id) {
+            span.extensions_mut().insert(args);
+        }
+    }
+
+    fn on_enter(&self, id: &Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut ext = span.extensions_mut();
+            ext.remove::<EnteredAt>();
+            ext.insert(EnteredAt(Instant::now()));
+        }
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let span = match ctx.span(id) {
+            Some(span) => span,
+            None => return,
+        };
+        let ext = span.extensions();
+        let entered_at = match ext.get::<EnteredAt>() {
+            Some(v) => v.0,
+            None => return,
+        };
+
+        let ts = entered_at.duration_since(self.start).as_secs_f64() * 1e6;
+        let dur = entered_at.elapsed().as_secs_f64() * 1e6;
+        let args = ext.get::<SpanArgs>().map(|args| &*args.0).unwrap_or_default();
+
+        let mut out = self.out.lock().unwrap();
+        let sep = if out.has_events { "," } else { "" };
+        out.has_events = true;
+        let _ = write!(
+            out.w,
+            "{}\n{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":{},\"args\":{{{}}}}}",
+            sep,
+            span.name(),
+            span.metadata().target(),
+            ts,
+            dur,
+            thread_id(),
+            args
+        );
+    }
+}
+
+impl Visit for SpanArgs {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record_str(field, &format!("{:?}", value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if !self.0.is_empty() {
+            self.0.push(',');
+        }
+
+        let _ = write!(self.0, "\"{}\":\"", field.name());
+        for c in value.chars() {
+            match c {
+                '"' => self.0.push_str("\\\""),
+                '\\' => self.0.push_str("\\\\"),
+                c if (c as u32) < 0x20 => {
+                    let _ = write!(self.0, "\\u{:04x}", c as u32);
+                }
+                c => self.0.push(c),
+            }
+        }
+        self.0.push('"');
+    }
+}
+
+/// Small numbers, unlike [std::thread::ThreadId].
+fn thread_id() -> usize {
+    static NEXT: AtomicUsize = AtomicUsize::new(1);
+
+    thread_local! {
+        static ID: usize = NEXT.fetch_add(1, Ordering::Relaxed);
+    }
+
+    ID.with(|id| *id)
+}